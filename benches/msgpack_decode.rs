@@ -0,0 +1,46 @@
+//! Record a baseline before a change with
+//! `cargo bench --bench msgpack_decode -- --save-baseline before`, then
+//! compare against it with `-- --baseline before`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use titan_swap_api_client::{decode_price_only_quotes, decode_swap_quotes};
+
+// One single-hop route, ~1KB on the wire.
+const SINGLE_HOP: &[u8] = include_bytes!("../tests/fixtures/single_hop.msgpack");
+// A two-hop route split across pools, ~2KB.
+const MULTI_HOP_SPLIT: &[u8] = include_bytes!("../tests/fixtures/multi_hop_split.msgpack");
+// Two 4-hop routes with 30 instructions each, ~54KB on the wire.
+const SWAP_QUOTES_LARGE: &[u8] = include_bytes!("../tests/fixtures/swap_quotes_large.msgpack");
+
+const PAYLOADS: [(&str, &[u8]); 3] = [
+    ("single_hop", SINGLE_HOP),
+    ("multi_hop_split", MULTI_HOP_SPLIT),
+    ("swap_quotes_large", SWAP_QUOTES_LARGE),
+];
+
+fn bench_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("msgpack_decode");
+    for (name, payload) in PAYLOADS {
+        group.throughput(Throughput::Bytes(payload.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| black_box(decode_swap_quotes(black_box(payload)).unwrap()))
+        });
+    }
+    group.finish();
+}
+
+// The same payloads with instructions and lookup tables skipped, for
+// comparison with `msgpack_decode`.
+fn bench_decode_price_only(c: &mut Criterion) {
+    let mut group = c.benchmark_group("msgpack_decode_price_only");
+    for (name, payload) in PAYLOADS {
+        group.throughput(Throughput::Bytes(payload.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| black_box(decode_price_only_quotes(black_box(payload)).unwrap()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_decode, bench_decode_price_only);
+criterion_main!(benches);
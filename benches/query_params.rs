@@ -0,0 +1,70 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    account_limits::AccountLimits,
+    constants::{SOL_MINT, USDC_MINT},
+    query::QuoteRequestTemplate,
+    quote::{Provider, QuoteRequest, SizeConstraint, SwapMode},
+};
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn bench_query_params(c: &mut Criterion) {
+    let minimal = QuoteRequest {
+        input_mint: SOL_MINT,
+        output_mint: USDC_MINT,
+        amount: 1_000_000_000,
+        ..Default::default()
+    };
+    let full = QuoteRequest {
+        user_pubkey: Some(USER),
+        account_limits: AccountLimits {
+            total: Some(40),
+            writable: Some(20),
+        },
+        swap_mode: Some(SwapMode::ExactIn),
+        slippage_bps: 50,
+        only_direct_routes: Some(false),
+        excluded_dexes: Some("Raydium CLMM, Phoenix, Whirlpool".to_string()),
+        size_constraint: Some(SizeConstraint::reserving_bytes(200).unwrap()),
+        providers: Some(Provider::Titan),
+        ..minimal.clone()
+    };
+
+    let mut group = c.benchmark_group("query_params");
+    for (name, request) in [("minimal", &minimal), ("full", &full)] {
+        group.bench_function(name, |b| b.iter(|| black_box(request.query_params())));
+        group.bench_function(format!("{name}_string"), |b| {
+            b.iter(|| black_box(request.to_query_string()))
+        });
+    }
+    group.finish();
+
+    // A hot loop re-quoting one pair at varying amounts.
+    let template = QuoteRequestTemplate::new(full.clone());
+    let mut group = c.benchmark_group("query_params_per_amount");
+    group.bench_function("rebuilt", |b| {
+        let mut amount = 0u64;
+        b.iter(|| {
+            amount += 1;
+            black_box(
+                QuoteRequest {
+                    amount,
+                    ..full.clone()
+                }
+                .query_params(),
+            )
+        })
+    });
+    group.bench_function("template", |b| {
+        let mut amount = 0u64;
+        b.iter(|| {
+            amount += 1;
+            black_box(template.query_params_with_amount(amount))
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_query_params);
+criterion_main!(benches);
@@ -0,0 +1,121 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use serde_json::json;
+use titan_swap_api_client::{
+    bench::quote_from_swap_quotes,
+    decode_swap_quotes,
+    quote::{QuoteRequest, QuoteResponse, SwapRoute},
+    TitanClient,
+};
+
+const SWAP_QUOTES_LARGE: &[u8] = include_bytes!("../tests/fixtures/swap_quotes_large.msgpack");
+
+const HOPS: usize = 4;
+const INSTRUCTIONS: usize = 30;
+const ACCOUNTS_PER_INSTRUCTION: usize = 16;
+const DATA_LEN: usize = 128;
+
+// A realistic 4-hop route: 30 instructions with 16 accounts and 128 bytes of data each.
+fn route() -> SwapRoute {
+    let pubkey = |seed: usize| vec![seed as u8; 32];
+    let steps: Vec<_> = (0..HOPS)
+        .map(|i| {
+            json!({
+                "ammKey": pubkey(i),
+                "label": "Whirlpool",
+                "inputMint": pubkey(100 + i),
+                "outputMint": pubkey(101 + i),
+                "inAmount": 1_000_000_000u64,
+                "outAmount": 999_000_000u64,
+                "allocPpb": 1_000_000_000u32,
+            })
+        })
+        .collect();
+    let instructions: Vec<_> = (0..INSTRUCTIONS)
+        .map(|i| {
+            let accounts: Vec<_> = (0..ACCOUNTS_PER_INSTRUCTION)
+                .map(|j| json!({ "p": pubkey(i + j), "s": j == 0, "w": j % 2 == 0 }))
+                .collect();
+            json!({ "p": pubkey(i), "a": accounts, "d": vec![i as u8; DATA_LEN] })
+        })
+        .collect();
+
+    let route = json!({
+        "inAmount": 1_000_000_000u64,
+        "outAmount": 996_000_000u64,
+        "slippageBps": 50,
+        "steps": steps,
+        "instructions": instructions,
+        "addressLookupTables": [pubkey(200), pubkey(201)],
+    });
+
+    rmp_serde::from_slice(&rmp_serde::to_vec_named(&route).unwrap()).unwrap()
+}
+
+fn bench_from_route(c: &mut Criterion) {
+    let request = QuoteRequest::default();
+    let route = route();
+
+    let mut group = c.benchmark_group("quote_from_route");
+    group.bench_function("move", |b| {
+        b.iter_batched(
+            || route.clone(),
+            |route| black_box(QuoteResponse::from_route(&request, route)),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("clone", |b| {
+        b.iter_batched(
+            || route.clone(),
+            |route| black_box(QuoteResponse::from_route(&request, route.clone())),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+// Fanning a quote out to subscribers: the route data is shared rather than
+// copied, and a summary drops it altogether.
+fn bench_clone(c: &mut Criterion) {
+    let quote = QuoteResponse::from_route(&QuoteRequest::default(), route());
+    let route = quote.route_data().unwrap().as_ref().clone();
+    let summary = quote.summary();
+
+    let mut group = c.benchmark_group("quote_clone");
+    group.bench_function("route_data", |b| b.iter(|| black_box(route.clone())));
+    group.bench_function("quote", |b| b.iter(|| black_box(quote.clone())));
+    group.bench_function("summary", |b| b.iter(|| black_box(summary.clone())));
+    group.finish();
+}
+
+// `quote` after the response is decoded, with the fetch left out.
+fn bench_select(c: &mut Criterion) {
+    let client = TitanClient::new(String::new(), None);
+    let request = QuoteRequest::default();
+
+    c.bench_function("quote_from_swap_quotes", |b| {
+        b.iter_batched(
+            || decode_swap_quotes(SWAP_QUOTES_LARGE).unwrap(),
+            |quotes| black_box(quote_from_swap_quotes(&client, &request, quotes).unwrap()),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+// Converting the 30 instructions of a route into a `SwapResponse`.
+fn bench_swap(c: &mut Criterion) {
+    let client = TitanClient::new(String::new(), None);
+    let quote = QuoteResponse::from_route(&QuoteRequest::default(), route());
+
+    c.bench_function("swap", |b| {
+        b.iter(|| black_box(client.swap(&quote).unwrap()))
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_from_route,
+    bench_clone,
+    bench_select,
+    bench_swap
+);
+criterion_main!(benches);
@@ -0,0 +1,55 @@
+//! Captures a raw msgpack quote response from the Titan API into a fixture file.
+//!
+//! cargo run --package titan-swap-test --bin capture -- <input_mint> <output_mint> <amount> <out_file> [extra query params...]
+
+const TITAN_API_URL: &str = "https://api.titan.exchange";
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenv::from_path("examples/.env").ok();
+
+    let auth_token = std::env::var("TITAN_AUTH_TOKEN")
+        .expect("TITAN_AUTH_TOKEN environment variable must be set");
+    let base_url = std::env::var("TITAN_BASE_URL")
+        .ok()
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| TITAN_API_URL.to_string());
+    let user_pubkey =
+        std::env::var("USER_PUBKEY").expect("USER_PUBKEY environment variable must be set");
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() < 4 {
+        anyhow::bail!(
+            "usage: capture <input_mint> <output_mint> <amount> <out_file> [key=value...]"
+        );
+    }
+
+    let mut params = vec![
+        ("inputMint".to_string(), args[0].clone()),
+        ("outputMint".to_string(), args[1].clone()),
+        ("amount".to_string(), args[2].clone()),
+        ("userPublicKey".to_string(), user_pubkey),
+    ];
+    for extra in &args[4..] {
+        let (key, value) = extra
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("expected key=value, got {}", extra))?;
+        params.push((key.to_string(), value.to_string()));
+    }
+
+    let response = reqwest::Client::new()
+        .get(format!("{}/api/v1/quote/swap", base_url))
+        .query(&params)
+        .header("Accept", "application/vnd.msgpack")
+        .header("Authorization", format!("Bearer {}", auth_token))
+        .send()
+        .await?;
+
+    let status = response.status();
+    let body = response.bytes().await?;
+    std::fs::write(&args[3], &body)?;
+
+    println!("Wrote {} bytes ({}) to {}", body.len(), status, args[3]);
+
+    Ok(())
+}
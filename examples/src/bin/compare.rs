@@ -0,0 +1,75 @@
+//! Quotes the same swap on Titan and Jupiter through `AggregatorClient` and
+//! prints the spread.
+//!
+//! cargo run --package titan-swap-test --bin compare -- [amount]
+
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use titan_swap_api_client::{
+    aggregator::AggregatorClient,
+    constants::{SOL_MINT, USDC_MINT},
+    jupiter_client::JupiterClient,
+    quote::{QuoteRequest, SwapMode},
+    TitanClient,
+};
+
+const SLIPPAGE_BPS: u16 = 50;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenv::from_path("examples/.env").ok();
+
+    let auth_token = std::env::var("TITAN_AUTH_TOKEN")
+        .expect("TITAN_AUTH_TOKEN environment variable must be set");
+    let base_url = std::env::var("TITAN_BASE_URL")
+        .ok()
+        .filter(|s| !s.is_empty());
+    let user_pubkey =
+        std::env::var("USER_PUBKEY").expect("USER_PUBKEY environment variable must be set");
+    let jupiter_api_key = std::env::var("JUPITER_API_KEY")
+        .ok()
+        .filter(|s| !s.is_empty());
+    let amount = match std::env::args().nth(1) {
+        Some(amount) => amount.parse()?,
+        None => 100_000_000,
+    };
+
+    let request = QuoteRequest {
+        input_mint: SOL_MINT,
+        output_mint: USDC_MINT,
+        amount,
+        user_pubkey: Some(Pubkey::from_str(&user_pubkey)?),
+        swap_mode: Some(SwapMode::ExactIn),
+        slippage_bps: SLIPPAGE_BPS,
+        ..Default::default()
+    };
+
+    let clients: Vec<Box<dyn AggregatorClient>> = vec![
+        Box::new(TitanClient::new(auth_token, base_url)),
+        Box::new(JupiterClient::new(jupiter_api_key, None)),
+    ];
+
+    let mut results = Vec::new();
+    for client in &clients {
+        match client.quote(&request).await {
+            Ok(quote) => {
+                println!(
+                    "{:<8} out={:>14} hops={}",
+                    client.name(),
+                    quote.out_amount,
+                    quote.route_plan.len()
+                );
+                results.push((client.name(), quote.out_amount));
+            }
+            Err(err) => println!("{:<8} failed: {}", client.name(), err),
+        }
+    }
+
+    if let [(titan, titan_out), (jupiter, jupiter_out)] = results[..] {
+        let spread_bps =
+            (titan_out as f64 - jupiter_out as f64) / jupiter_out.max(1) as f64 * 10_000.0;
+        println!("{} vs {}: {:+.2} bps", titan, jupiter, spread_bps);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+//! Measures the latency of a quote on a cold client (fresh connection pool,
+//! so DNS, TCP and TLS are all paid on the request) against a client kept
+//! warm with `keep_warm`, after the same idle period.
+//!
+//! cargo run --release --package titan-swap-test --bin keep_warm -- [samples] [idle_secs]
+
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{QuoteRequest, SwapMode},
+    TitanClient,
+};
+
+const KEEP_WARM_INTERVAL: Duration = Duration::from_secs(5);
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenv::from_path("examples/.env").ok();
+
+    let auth_token = std::env::var("TITAN_AUTH_TOKEN")
+        .expect("TITAN_AUTH_TOKEN environment variable must be set");
+    let base_url = std::env::var("TITAN_BASE_URL")
+        .ok()
+        .filter(|s| !s.is_empty());
+    let user_pubkey =
+        std::env::var("USER_PUBKEY").expect("USER_PUBKEY environment variable must be set");
+    let mut args = std::env::args().skip(1);
+    let samples: usize = match args.next() {
+        Some(samples) => samples.parse()?,
+        None => 10,
+    };
+    let idle = Duration::from_secs(match args.next() {
+        Some(secs) => secs.parse()?,
+        None => 15,
+    });
+
+    let request = QuoteRequest {
+        input_mint: SOL_MINT,
+        output_mint: USDC_MINT,
+        amount: 100_000_000,
+        user_pubkey: Some(Pubkey::from_str(&user_pubkey)?),
+        swap_mode: Some(SwapMode::ExactIn),
+        slippage_bps: 50,
+        ..Default::default()
+    };
+    let builder = || {
+        let builder = TitanClient::builder(auth_token.clone());
+        match &base_url {
+            Some(base_url) => builder.base_path(base_url.clone()),
+            None => builder,
+        }
+    };
+
+    let warm = builder().keep_warm(KEEP_WARM_INTERVAL).build()?;
+    warm.quote(&request).await?;
+
+    let mut cold_ms = Vec::with_capacity(samples);
+    let mut warm_ms = Vec::with_capacity(samples);
+    for sample in 1..=samples {
+        tokio::time::sleep(idle).await;
+
+        cold_ms.push(timed_quote(&builder().build()?, &request).await?);
+        warm_ms.push(timed_quote(&warm, &request).await?);
+        println!(
+            "sample {sample}/{samples}: cold {:.1} ms, warm {:.1} ms",
+            cold_ms[sample - 1],
+            warm_ms[sample - 1]
+        );
+    }
+
+    let (cold, warm) = (median(&mut cold_ms), median(&mut warm_ms));
+    println!(
+        "median over {samples} samples after {}s idle: cold {cold:.1} ms, warm {warm:.1} ms, saved {:.1} ms",
+        idle.as_secs(),
+        cold - warm
+    );
+    Ok(())
+}
+
+async fn timed_quote(client: &TitanClient, request: &QuoteRequest) -> anyhow::Result<f64> {
+    let started = Instant::now();
+    client.quote(request).await?;
+    Ok(started.elapsed().as_secs_f64() * 1000.0)
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    values[values.len() / 2]
+}
@@ -9,14 +9,17 @@ use solana_sdk::{
 };
 use std::str::FromStr;
 use titan_swap_api_client::{
-    quote::{Provider, QuoteRequest, SwapMode},
+    account_limits::AccountLimits,
+    constants::{SOL_MINT, TITAN_PROGRAM_ID, USDC_MINT},
+    quote::{QuoteRequest, SwapMode},
+    security::default_program_allowlist,
     TitanClient,
 };
 
-const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
-const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
 const SWAP_AMOUNT: u64 = 100_000_000;
 const SLIPPAGE_BPS: u16 = 50;
+/// The most the route may tip a Jito validator.
+const MAX_TIP_LAMPORTS: u64 = 100_000;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -39,8 +42,8 @@ async fn main() -> anyhow::Result<()> {
         .unwrap_or(false);
 
     let user_pubkey = Pubkey::from_str(&user_pubkey)?;
-    let input_mint = Pubkey::from_str(SOL_MINT)?;
-    let output_mint = Pubkey::from_str(USDC_MINT)?;
+    let input_mint = SOL_MINT;
+    let output_mint = USDC_MINT;
 
     let keypair = Keypair::from_base58_string(&private_key);
     if keypair.pubkey() != user_pubkey {
@@ -53,8 +56,8 @@ async fn main() -> anyhow::Result<()> {
         input_mint,
         output_mint,
         amount: SWAP_AMOUNT,
-        user_pubkey,
-        max_accounts: Some(50),
+        user_pubkey: Some(user_pubkey),
+        account_limits: AccountLimits::with_total(50),
         swap_mode: Some(SwapMode::ExactIn),
         slippage_bps: SLIPPAGE_BPS,
         // providers: Some(Provider::Titan),
@@ -76,12 +79,13 @@ async fn main() -> anyhow::Result<()> {
     let is_titan_swap = swap
         .instructions
         .iter()
-        .any(|ix| ix.program_id.to_string() == "T1TANpTeScyeqVzzgNViGDNrkQ6qHz9KrSBS4aNXvGT");
+        .any(|ix| ix.program_id == TITAN_PROGRAM_ID);
 
     println!(
         "Swap: {} instructions, {} CU limit, {} ALT{} [{}]",
         swap.instructions.len(),
-        swap.compute_unit_limit,
+        swap.compute_unit_limit
+            .map_or_else(|| "no".to_string(), |cu| cu.to_string()),
         swap.address_lookup_table_addresses.len(),
         if swap.address_lookup_table_addresses.len() == 1 {
             ""
@@ -100,6 +104,12 @@ async fn main() -> anyhow::Result<()> {
         return Ok(());
     }
 
+    swap.verify_programs(
+        &default_program_allowlist(),
+        keypair.pubkey(),
+        MAX_TIP_LAMPORTS,
+    )?;
+
     let rpc_client = RpcClient::new(rpc_url);
     let tx = build_and_sign_transaction(
         &rpc_client,
@@ -0,0 +1,95 @@
+//! Limits on the accounts a route may use, sent as `accountsLimitTotal` and
+//! `accountsLimitWritable`.
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "native")]
+use solana_sdk::instruction::Instruction;
+#[cfg(feature = "native")]
+use std::collections::BTreeSet;
+use thiserror::Error;
+
+/// Accounts a Solana transaction may lock, read-only and writable alike.
+pub const ACCOUNT_LOCK_LIMIT: usize = 64;
+
+/// Field names match [`QuoteRequest`](crate::quote::QuoteRequest)'s, into
+/// which this is flattened.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountLimits {
+    /// The most accounts the route may use in total.
+    #[serde(rename = "maxAccounts", default)]
+    pub total: Option<usize>,
+    /// The most of those accounts that may be writable.
+    #[serde(rename = "accountsLimitWritable", default)]
+    pub writable: Option<usize>,
+}
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum AccountLimitsError {
+    #[error("Writable account limit {writable} exceeds the total limit {total}")]
+    WritableExceedsTotal { writable: usize, total: usize },
+    #[error("Account limit {0} exceeds the transaction lock limit of {ACCOUNT_LOCK_LIMIT}")]
+    OverLockLimit(usize),
+    #[error("Account limit is zero")]
+    Zero,
+}
+
+impl AccountLimits {
+    pub fn with_total(total: usize) -> Self {
+        Self {
+            total: Some(total),
+            writable: None,
+        }
+    }
+
+    /// Checks each limit is between 1 and [`ACCOUNT_LOCK_LIMIT`] and that
+    /// `writable` is at most `total`.
+    pub fn validate(&self) -> Result<(), AccountLimitsError> {
+        for limit in [self.total, self.writable].into_iter().flatten() {
+            if limit == 0 {
+                return Err(AccountLimitsError::Zero);
+            }
+            if limit > ACCOUNT_LOCK_LIMIT {
+                return Err(AccountLimitsError::OverLockLimit(limit));
+            }
+        }
+        match (self.writable, self.total) {
+            (Some(writable), Some(total)) if writable > total => {
+                Err(AccountLimitsError::WritableExceedsTotal { writable, total })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Limits leaving room in the transaction for `existing` instructions,
+    /// such as a tip, a memo or ATA creation, sent alongside the swap.
+    ///
+    /// Every distinct account and program `existing` uses counts against
+    /// [`ACCOUNT_LOCK_LIMIT`], except signers, which are taken to be the
+    /// user's wallet that the swap signs with too. The lock limit counts
+    /// writable and read-only accounts alike, so only `total` is set. Fails
+    /// with `AccountLimitsError::Zero` when nothing is left for the swap.
+    #[cfg(feature = "native")]
+    pub fn for_transaction_with(existing: &[Instruction]) -> Result<Self, AccountLimitsError> {
+        let metas = || {
+            existing
+                .iter()
+                .flat_map(|instruction| &instruction.accounts)
+        };
+        let signers: BTreeSet<_> = metas()
+            .filter(|meta| meta.is_signer)
+            .map(|meta| meta.pubkey)
+            .collect();
+        let used = existing
+            .iter()
+            .map(|instruction| instruction.program_id)
+            .chain(metas().map(|meta| meta.pubkey))
+            .filter(|account| !signers.contains(account))
+            .collect::<BTreeSet<_>>()
+            .len();
+
+        let limits = Self::with_total(ACCOUNT_LOCK_LIMIT.saturating_sub(used));
+        limits.validate()?;
+        Ok(limits)
+    }
+}
@@ -0,0 +1,49 @@
+//! A provider-agnostic interface so Titan and other aggregators can be
+//! driven through the same code path, e.g. as `Box<dyn AggregatorClient>`.
+
+use crate::quote::{QuoteRequest, QuoteResponse};
+use crate::swap::SwapResponse;
+use crate::{ClientError, TitanClient};
+use solana_sdk::pubkey::Pubkey;
+use std::future::Future;
+use std::pin::Pin;
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+pub trait AggregatorClient: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn quote<'a>(
+        &'a self,
+        request: &'a QuoteRequest,
+    ) -> BoxFuture<'a, Result<QuoteResponse, ClientError>>;
+
+    /// `user_pubkey` is the swap signer. Aggregators that bake it into the
+    /// quote (Titan) ignore it.
+    fn swap_instructions<'a>(
+        &'a self,
+        quote: &'a QuoteResponse,
+        user_pubkey: &'a Pubkey,
+    ) -> BoxFuture<'a, Result<SwapResponse, ClientError>>;
+}
+
+impl AggregatorClient for TitanClient {
+    fn name(&self) -> &str {
+        "Titan"
+    }
+
+    fn quote<'a>(
+        &'a self,
+        request: &'a QuoteRequest,
+    ) -> BoxFuture<'a, Result<QuoteResponse, ClientError>> {
+        Box::pin(TitanClient::quote(self, request))
+    }
+
+    fn swap_instructions<'a>(
+        &'a self,
+        quote: &'a QuoteResponse,
+        _user_pubkey: &'a Pubkey,
+    ) -> BoxFuture<'a, Result<SwapResponse, ClientError>> {
+        Box::pin(async move { self.swap(quote) })
+    }
+}
@@ -0,0 +1,110 @@
+//! Amount arithmetic shared by the crate. Products are taken in `u128` and
+//! narrowed with a check, so nothing wraps even at `u64::MAX` with extreme
+//! bps.
+
+use thiserror::Error;
+
+const BPS_DENOMINATOR: u128 = 10_000;
+const PPB_PER_PERCENT: u64 = 10_000_000;
+#[cfg(feature = "native")]
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("Amount arithmetic overflowed")]
+pub struct AmountOverflow;
+
+fn narrow(value: u128) -> Result<u64, AmountOverflow> {
+    u64::try_from(value).map_err(|_| AmountOverflow)
+}
+
+/// `out_amount` less `slippage_bps`, rounded down. Slippage above 100%
+/// leaves nothing.
+pub(crate) fn min_out_after_slippage(out_amount: u64, slippage_bps: u16) -> u64 {
+    let kept = BPS_DENOMINATOR.saturating_sub(u128::from(slippage_bps));
+    // kept <= 10_000, so the quotient is at most `out_amount`.
+    (u128::from(out_amount) * kept / BPS_DENOMINATOR) as u64
+}
+
+/// `in_amount` plus `slippage_bps`, rounded up.
+pub(crate) fn max_in_after_slippage(
+    in_amount: u64,
+    slippage_bps: u16,
+) -> Result<u64, AmountOverflow> {
+    let scaled = u128::from(in_amount) * (BPS_DENOMINATOR + u128::from(slippage_bps));
+    narrow(scaled.div_ceil(BPS_DENOMINATOR))
+}
+
+#[cfg(feature = "native")]
+/// A fee of `fee_bps` on `amount`, rounded up and capped at `max_fee`.
+pub(crate) fn capped_bps_fee(amount: u64, fee_bps: u16, max_fee: u64) -> u64 {
+    let fee = (u128::from(amount) * u128::from(fee_bps)).div_ceil(BPS_DENOMINATOR);
+    // Capping first makes the narrowing lossless.
+    fee.min(u128::from(max_fee)) as u64
+}
+
+#[cfg(feature = "native")]
+/// `amount` less a fee of `fee_bps`, the fee rounded up. A fee above 100%
+/// leaves nothing.
+pub(crate) fn after_bps_fee(amount: u64, fee_bps: u16) -> u64 {
+    amount.saturating_sub(capped_bps_fee(amount, fee_bps, u64::MAX))
+}
+
+#[cfg(feature = "native")]
+pub(crate) fn checked_add(a: u64, b: u64) -> Result<u64, AmountOverflow> {
+    a.checked_add(b).ok_or(AmountOverflow)
+}
+
+#[cfg(feature = "native")]
+/// The prioritization fee for `compute_units` at `cu_price_micro_lamports`,
+/// rounded up to a whole lamport as the runtime does.
+pub(crate) fn priority_fee_lamports(
+    compute_units: u64,
+    cu_price_micro_lamports: u64,
+) -> Result<u64, AmountOverflow> {
+    let micro_lamports = u128::from(compute_units) * u128::from(cu_price_micro_lamports);
+    narrow(micro_lamports.div_ceil(MICRO_LAMPORTS_PER_LAMPORT))
+}
+
+/// `a - b` as a signed value.
+pub(crate) fn signed_delta(a: u64, b: u64) -> Result<i64, AmountOverflow> {
+    i64::try_from(i128::from(a) - i128::from(b)).map_err(|_| AmountOverflow)
+}
+
+/// A parts-per-billion allocation as a whole percent, rounded half up and
+/// capped at 100.
+pub(crate) fn percent_from_ppb(alloc_ppb: u64) -> u8 {
+    bucketed_percent_from_ppb(alloc_ppb, 1)
+}
+
+/// A parts-per-billion allocation rounded half up to a multiple of
+/// `bucket` percent, capped at 100.
+pub(crate) fn bucketed_percent_from_ppb(alloc_ppb: u64, bucket: u8) -> u8 {
+    let ppb_per_bucket = PPB_PER_PERCENT * u64::from(bucket.max(1));
+    let buckets =
+        alloc_ppb / ppb_per_bucket + u64::from(alloc_ppb % ppb_per_bucket >= ppb_per_bucket / 2);
+    buckets.saturating_mul(u64::from(bucket.max(1))).min(100) as u8
+}
+
+pub(crate) fn ppb_from_percent(percent: u8) -> u32 {
+    // 255 * 10^7 fits in a u32.
+    u32::from(percent) * PPB_PER_PERCENT as u32
+}
+
+pub(crate) fn ns_to_secs(ns: u64) -> f64 {
+    ns as f64 / 1e9
+}
+
+/// `None` for negative, non-finite or out-of-range durations.
+pub(crate) fn secs_to_ns(secs: f64) -> Option<u64> {
+    let ns = (secs * 1e9).round();
+    // u64::MAX as f64 rounds up to 2^64, which is itself out of range.
+    (0.0..u64::MAX as f64).contains(&ns).then_some(ns as u64)
+}
+
+#[cfg(feature = "native")]
+/// `ui_amount` whole tokens in raw units, rounded down. `None` for negative,
+/// non-finite or out-of-range amounts.
+pub(crate) fn raw_from_ui(ui_amount: f64, decimals: u8) -> Option<u64> {
+    let raw = (ui_amount * 10f64.powi(i32::from(decimals))).floor();
+    (0.0..u64::MAX as f64).contains(&raw).then_some(raw as u64)
+}
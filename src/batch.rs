@@ -0,0 +1,161 @@
+use crate::options::QuoteRequestOptions;
+use crate::quote::{QuoteRequest, QuoteResponse};
+use crate::{ClientError, TitanClient};
+use serde::ser::SerializeSeq;
+use serde::{Serialize, Serializer};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+
+/// The outcome of [`TitanClient::quote_batch`], with successes and failures
+/// each in request order.
+///
+/// Serializes with each failure's error as its message plus whether it was
+/// retryable and rate limited, and `elapsed` in milliseconds.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchReport {
+    pub successes: Vec<(QuoteRequest, QuoteResponse)>,
+    #[serde(serialize_with = "serialize_failures")]
+    pub failures: Vec<(QuoteRequest, ClientError)>,
+    #[serde(rename = "elapsedMs", serialize_with = "serialize_millis")]
+    pub elapsed: Duration,
+    /// How many failures were rate limits, either the client's own or a 429
+    /// from the server.
+    pub rate_limited: usize,
+}
+
+impl BatchReport {
+    pub fn total(&self) -> usize {
+        self.successes.len() + self.failures.len()
+    }
+
+    /// Failures as a fraction of all requests, or 0 for an empty batch.
+    pub fn failure_rate(&self) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => self.failures.len() as f64 / total as f64,
+        }
+    }
+
+    /// Failures worth retrying later: transient errors (see
+    /// `ClientError::is_retryable`) and rate limits.
+    pub fn retryable_failures(&self) -> impl Iterator<Item = &(QuoteRequest, ClientError)> {
+        self.failures
+            .iter()
+            .filter(|(_, err)| err.is_retryable() || is_rate_limited(err))
+    }
+
+    /// The requests of [`retryable_failures`](Self::retryable_failures), to
+    /// pass to another `quote_batch`.
+    pub fn retry_requests(&self) -> Vec<QuoteRequest> {
+        self.retryable_failures()
+            .map(|(request, _)| request.clone())
+            .collect()
+    }
+}
+
+impl TitanClient {
+    /// Quotes every request concurrently, subject to the client's rate
+    /// limiter, and reports each outcome instead of stopping at the first
+    /// error. Must be called within a Tokio runtime.
+    pub async fn quote_batch(&self, requests: Vec<QuoteRequest>) -> BatchReport {
+        self.quote_batch_with_options(requests, &QuoteRequestOptions::default())
+            .await
+    }
+
+    /// Like [`quote_batch`](Self::quote_batch), quoting each request with
+    /// [`quote_with_options`](Self::quote_with_options). The options' timeout
+    /// runs from the start of the batch, so it bounds the whole batch, rate
+    /// limiter waits included, rather than each quote.
+    pub async fn quote_batch_with_options(
+        &self,
+        requests: Vec<QuoteRequest>,
+        options: &QuoteRequestOptions,
+    ) -> BatchReport {
+        let started = self.clock.instant();
+        let options = Arc::new(QuoteRequestOptions {
+            timeout: None,
+            deadline: options.effective_deadline(Instant::now()),
+            ..options.clone()
+        });
+        let mut tasks = JoinSet::new();
+        for (index, request) in requests.into_iter().enumerate() {
+            let client = self.clone();
+            let options = options.clone();
+            tasks.spawn(async move {
+                let result = client.quote_with_options(&request, &options).await;
+                (index, request, result)
+            });
+        }
+
+        let mut outcomes = Vec::with_capacity(tasks.len());
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(outcome) => outcomes.push(outcome),
+                // Quoting doesn't panic, and the set is never aborted.
+                Err(err) => std::panic::resume_unwind(err.into_panic()),
+            }
+        }
+        outcomes.sort_by_key(|(index, _, _)| *index);
+
+        let mut report = BatchReport {
+            successes: Vec::new(),
+            failures: Vec::new(),
+            elapsed: self.clock.elapsed(started),
+            rate_limited: 0,
+        };
+        for (_, request, result) in outcomes {
+            match result {
+                Ok(quote) => report.successes.push((request, quote)),
+                Err(err) => {
+                    if is_rate_limited(&err) {
+                        report.rate_limited += 1;
+                    }
+                    report.failures.push((request, err));
+                }
+            }
+        }
+        report
+    }
+}
+
+fn is_rate_limited(err: &ClientError) -> bool {
+    match err {
+        ClientError::RateLimited { .. } => true,
+        ClientError::RequestFailed { status, .. } => {
+            *status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        }
+        _ => false,
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FailureRecord<'a> {
+    request: &'a QuoteRequest,
+    error: String,
+    retryable: bool,
+    rate_limited: bool,
+}
+
+fn serialize_failures<S: Serializer>(
+    failures: &[(QuoteRequest, ClientError)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut seq = serializer.serialize_seq(Some(failures.len()))?;
+    for (request, err) in failures {
+        let rate_limited = is_rate_limited(err);
+        seq.serialize_element(&FailureRecord {
+            request,
+            error: err.to_string(),
+            retryable: err.is_retryable() || rate_limited,
+            rate_limited,
+        })?;
+    }
+    seq.end()
+}
+
+fn serialize_millis<S: Serializer>(elapsed: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX))
+}
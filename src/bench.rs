@@ -0,0 +1,15 @@
+//! Network-free entry points into the client's internals for the criterion
+//! benchmarks in `benches/`. Not a stable API.
+
+use crate::quote::{QuoteRequest, QuoteResponse, SwapQuotes};
+use crate::{ClientError, TitanClient};
+
+/// What `quote` does with a decoded response: route filtering, selection,
+/// the ExactOut check and conversion into a [`QuoteResponse`].
+pub fn quote_from_swap_quotes(
+    client: &TitanClient,
+    request: &QuoteRequest,
+    quotes: SwapQuotes,
+) -> Result<QuoteResponse, ClientError> {
+    client.quote_from_swap_quotes(request, None, quotes)
+}
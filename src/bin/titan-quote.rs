@@ -0,0 +1,186 @@
+//! Quotes a swap from the command line.
+//!
+//! ```text
+//! titan-quote --in SOL --out USDC --amount 1.5 --slippage-bps 50 [--json]
+//! ```
+//!
+//! Tokens are symbols from Titan's token list or mint addresses, and the
+//! amount is in UI units. The token is read from `TITAN_API_TOKEN` and the
+//! base URL from `TITAN_API_URL`, unless given as flags.
+//!
+//! Exits with 0 on success, 2 for bad arguments or unknown tokens, 3 when no
+//! route was found, 4 when the token was rejected, 5 when the API couldn't
+//! be reached, and 1 for any other failure.
+
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use solana_sdk::pubkey::Pubkey;
+use std::process::ExitCode;
+use std::time::Duration;
+use thiserror::Error;
+use titan_swap_api_client::{
+    quote::{QuoteRequest, SwapMode},
+    quote_builder::QuoteRequestError,
+    tokens::TokenRegistry,
+    ClientError, TitanClient,
+};
+
+const FAILURE: u8 = 1;
+const USAGE: u8 = 2;
+const NO_ROUTE: u8 = 3;
+const AUTH: u8 = 4;
+const NETWORK: u8 = 5;
+
+fn command() -> Command {
+    Command::new("titan-quote")
+        .about("Quote a swap with the Titan API")
+        .arg(
+            Arg::new("in")
+                .long("in")
+                .value_name("TOKEN")
+                .required(true)
+                .help("Token to sell, as a symbol or mint address"),
+        )
+        .arg(
+            Arg::new("out")
+                .long("out")
+                .value_name("TOKEN")
+                .required(true)
+                .help("Token to buy, as a symbol or mint address"),
+        )
+        .arg(
+            Arg::new("amount")
+                .long("amount")
+                .value_name("AMOUNT")
+                .required(true)
+                .value_parser(value_parser!(f64))
+                .help("Amount to sell, or to buy with --exact-out, in UI units"),
+        )
+        .arg(
+            Arg::new("slippage-bps")
+                .long("slippage-bps")
+                .value_name("BPS")
+                .default_value("50")
+                .value_parser(value_parser!(u16))
+                .help("Slippage tolerance in basis points"),
+        )
+        .arg(
+            Arg::new("exact-out")
+                .long("exact-out")
+                .action(ArgAction::SetTrue)
+                .help("Quote for receiving exactly --amount"),
+        )
+        .arg(
+            Arg::new("user")
+                .long("user")
+                .value_name("PUBKEY")
+                .value_parser(value_parser!(Pubkey))
+                .help("Wallet to quote for; indicative quote if unset"),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .action(ArgAction::SetTrue)
+                .help("Print the quote as JSON instead of its route"),
+        )
+        .arg(
+            Arg::new("url")
+                .long("url")
+                .value_name("URL")
+                .env("TITAN_API_URL")
+                .help("API base URL; Titan's by default"),
+        )
+        .arg(
+            Arg::new("token")
+                .long("token")
+                .value_name("API_TOKEN")
+                .env("TITAN_API_TOKEN")
+                .required(true)
+                .hide_env_values(true)
+                .help("API token"),
+        )
+}
+
+#[derive(Debug, Error)]
+enum CliError {
+    #[error(transparent)]
+    Request(#[from] QuoteRequestError),
+    #[error(transparent)]
+    Client(#[from] ClientError),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl CliError {
+    fn exit_code(&self) -> u8 {
+        match self {
+            Self::Request(_) => USAGE,
+            Self::Client(ClientError::NoRoutesAvailable | ClientError::ProviderUnavailable(_)) => {
+                NO_ROUTE
+            }
+            Self::Client(ClientError::RequestFailed { status, .. })
+                if status.as_u16() == 401 || status.as_u16() == 403 =>
+            {
+                AUTH
+            }
+            Self::Client(ClientError::HttpError(err))
+                if err.is_connect() || err.is_timeout() || err.is_request() =>
+            {
+                NETWORK
+            }
+            Self::Client(_) | Self::Json(_) => FAILURE,
+        }
+    }
+}
+
+fn request(args: &ArgMatches, registry: &TokenRegistry) -> Result<QuoteRequest, CliError> {
+    let token = |name| args.get_one::<String>(name).expect("required");
+    let mut builder = QuoteRequest::builder()
+        .registry(registry)
+        .ui_amount(*args.get_one::<f64>("amount").expect("required"))
+        .slippage_bps(*args.get_one::<u16>("slippage-bps").expect("defaulted"));
+    builder = match token("in").parse::<Pubkey>() {
+        Ok(mint) => builder.input_mint(mint),
+        Err(_) => builder.input_symbol(token("in")),
+    };
+    builder = match token("out").parse::<Pubkey>() {
+        Ok(mint) => builder.output_mint(mint),
+        Err(_) => builder.output_symbol(token("out")),
+    };
+    if args.get_flag("exact-out") {
+        builder = builder.swap_mode(SwapMode::ExactOut);
+    }
+    if let Some(user) = args.get_one::<Pubkey>("user") {
+        builder = builder.user_pubkey(*user);
+    }
+    Ok(builder.build()?)
+}
+
+async fn run(args: &ArgMatches) -> Result<(), CliError> {
+    let mut builder = TitanClient::builder(args.get_one::<String>("token").expect("required"));
+    if let Some(url) = args.get_one::<String>("url") {
+        builder = builder.base_path(url);
+    }
+    let client = builder.build()?;
+    let registry = TokenRegistry::new(client.clone(), Duration::MAX);
+    registry.refresh().await?;
+
+    let quote = client.quote(&request(args, &registry)?).await?;
+    if args.get_flag("json") {
+        println!("{}", serde_json::to_string_pretty(&quote)?);
+    } else {
+        println!("{}", quote.display().registry(&registry));
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = command().get_matches();
+    match run(&args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(err.exit_code())
+        }
+    }
+}
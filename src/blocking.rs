@@ -0,0 +1,105 @@
+//! A synchronous client built on `reqwest::blocking`.
+//!
+//! `reqwest::blocking` panics when used from inside an async runtime, so calls
+//! made from a tokio context are transparently moved onto a scoped OS thread.
+//! Prefer the async [`crate::TitanClient`] there.
+
+use crate::api_version::ApiVersion;
+use crate::client::build_swap;
+use crate::quote::{QuoteRequest, QuoteResponse, SwapQuotes};
+use crate::swap::SwapResponse;
+use crate::transport::{
+    decode_quote_response, prepare_request, quote_from_response, quote_headers, quote_url,
+    quotes_from_response, read_limited_blocking, HttpResponse, DEFAULT_MAX_RESPONSE_BYTES,
+};
+use crate::{ClientError, TITAN_API_URL};
+use std::collections::BTreeMap;
+
+pub struct TitanClient {
+    client: reqwest::blocking::Client,
+    base_path: String,
+    api_version: ApiVersion,
+    headers: Vec<(&'static str, String)>,
+    max_response_bytes: usize,
+}
+
+impl TitanClient {
+    pub fn new(auth_token: String, base_path: Option<String>) -> Self {
+        Self {
+            client: off_runtime(|| {
+                reqwest::blocking::Client::builder()
+                    .user_agent(crate::DEFAULT_USER_AGENT)
+                    .build()
+                    .expect("HTTP client configuration is valid")
+            }),
+            base_path: base_path.unwrap_or_else(|| TITAN_API_URL.to_string()),
+            api_version: ApiVersion::default(),
+            headers: quote_headers(&auth_token),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+        }
+    }
+
+    /// Sends requests to `api_version`'s endpoints. Defaults to
+    /// `ApiVersion::V1`.
+    pub fn with_api_version(mut self, api_version: ApiVersion) -> Self {
+        self.api_version = api_version;
+        self
+    }
+
+    /// Largest response body to accept, `DEFAULT_MAX_RESPONSE_BYTES` by
+    /// default. The download is abandoned with `ResponseTooLarge` as soon as
+    /// it goes over.
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    fn fetch_swap_quotes(&self, params: &[(&str, String)]) -> Result<SwapQuotes, ClientError> {
+        off_runtime(|| {
+            let mut request = self
+                .client
+                .get(quote_url(&self.base_path, self.api_version))
+                .query(params);
+            for (name, value) in &self.headers {
+                request = request.header(*name, value);
+            }
+            let response = request.send()?;
+
+            decode_quote_response(&HttpResponse {
+                status: response.status(),
+                headers: response.headers().clone(),
+                body: read_limited_blocking(response, self.max_response_bytes)?,
+            })
+        })
+    }
+
+    pub fn quote(&self, request: &QuoteRequest) -> Result<QuoteResponse, ClientError> {
+        let quotes = self.fetch_swap_quotes(&prepare_request(request)?)?;
+        quote_from_response(request, quotes)
+    }
+
+    pub fn quote_all(
+        &self,
+        request: &QuoteRequest,
+    ) -> Result<BTreeMap<String, QuoteResponse>, ClientError> {
+        let quotes = self.fetch_swap_quotes(&prepare_request(request)?)?;
+        quotes_from_response(request, quotes)
+    }
+
+    pub fn swap(&self, quote: &QuoteResponse) -> Result<SwapResponse, ClientError> {
+        build_swap(quote)
+    }
+}
+
+fn off_runtime<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    if tokio::runtime::Handle::try_current().is_err() {
+        return f();
+    }
+
+    std::thread::scope(|scope| {
+        scope
+            .spawn(f)
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
@@ -0,0 +1,60 @@
+use crate::quote::{QuoteRequest, QuoteResponse};
+use crate::TitanClient;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+impl TitanClient {
+    /// Keeps the latest quote for `request` in a watch channel, re-quoting
+    /// every `interval`. A failed refresh leaves the previous quote published
+    /// until it expires, after which `None` is published instead.
+    ///
+    /// The task stops once every receiver is dropped, or when the handle is
+    /// aborted. Must be called within a Tokio runtime.
+    pub fn quote_broadcast(
+        &self,
+        request: &QuoteRequest,
+        interval: Duration,
+    ) -> (watch::Receiver<Option<Arc<QuoteResponse>>>, JoinHandle<()>) {
+        let (sender, receiver) = watch::channel(None);
+        let client = self.clone();
+        let request = request.clone();
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                let refreshed = tokio::select! {
+                    _ = sender.closed() => return,
+                    result = async {
+                        ticker.tick().await;
+                        client.quote(&request).await
+                    } => result,
+                };
+
+                match refreshed {
+                    Ok(quote) => {
+                        if sender.send(Some(Arc::new(quote))).is_err() {
+                            return;
+                        }
+                    }
+                    Err(_) => {
+                        sender.send_if_modified(|latest| {
+                            let expired = latest
+                                .as_deref()
+                                .is_some_and(|quote| client.expires_within(quote, Duration::ZERO));
+                            if expired {
+                                *latest = None;
+                            }
+                            expired
+                        });
+                    }
+                }
+            }
+        });
+
+        (receiver, handle)
+    }
+}
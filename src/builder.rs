@@ -0,0 +1,634 @@
+use crate::api_version::ApiVersion;
+use crate::circuit_breaker::CircuitBreaker;
+use crate::client::RequestMiddleware;
+use crate::clock::{Clock, SystemClock};
+use crate::execution_budget::{BudgetSource, ExecutionBudget};
+use crate::failover::{Endpoints, FailoverPolicy};
+use crate::format::ResponseFormat;
+use crate::hedge::{Hedging, DEFAULT_MAX_HEDGE_RATIO};
+#[cfg(feature = "journal")]
+use crate::journal::QuoteJournal;
+use crate::keep_warm;
+use crate::observer::{Observer, RawResponseHook};
+use crate::quota::QuotaHeaders;
+use crate::quote::RouteField;
+use crate::quote_defaults::QuoteDefaults;
+use crate::rate_limit::{RateLimitStrategy, RateLimiter};
+use crate::transform::RouteChecks;
+use crate::{
+    ClientError, TitanClient, CLIENT_ID_HEADER, DEFAULT_ERROR_BODY_LIMIT, DEFAULT_USER_AGENT,
+    TITAN_API_URL,
+};
+use bytes::Bytes;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Certificate, Proxy};
+use std::sync::Arc;
+use std::time::Duration;
+
+const DEFAULT_POST_THRESHOLD: usize = 2048;
+pub use crate::transform::DEFAULT_EXACT_OUT_TOLERANCE_BPS;
+pub use crate::transport::DEFAULT_MAX_RESPONSE_BYTES;
+
+pub struct TitanClientBuilder {
+    auth_token: String,
+    base_urls: Vec<String>,
+    failover_policy: FailoverPolicy,
+    failover_cooldown: Option<Duration>,
+    observer: Option<Arc<dyn Observer>>,
+    #[cfg(feature = "journal")]
+    journal: Option<Arc<dyn QuoteJournal>>,
+    hedge_after: Option<Duration>,
+    max_hedge_ratio: f64,
+    circuit_breaker: Option<(u32, Duration)>,
+    rate_limit: Option<(u32, Duration)>,
+    rate_limit_strategy: RateLimitStrategy,
+    price_check_bps: Option<u16>,
+    quote_defaults: Option<QuoteDefaults>,
+    execution_budget: Option<BudgetSource>,
+    clock: Arc<dyn Clock>,
+    prefer_post: bool,
+    post_threshold: usize,
+    api_version: ApiVersion,
+    path_prefix: Option<String>,
+    lenient_decode: bool,
+    response_format: ResponseFormat,
+    max_response_bytes: usize,
+    error_body_limit: usize,
+    quota_headers: QuotaHeaders,
+    verbose_errors: bool,
+    enforce_direct_routes: bool,
+    verify_responses: bool,
+    exact_out_tolerance_bps: u16,
+    require_onchain_min_out: bool,
+    required_route_fields: Vec<RouteField>,
+    #[cfg(feature = "tracing")]
+    log_requests: bool,
+    #[cfg(feature = "tracing")]
+    redact_user_pubkey: bool,
+    on_raw_response: Option<RawResponseHook>,
+    default_headers: HeaderMap,
+    user_agent: Option<String>,
+    client_id: Option<String>,
+    request_middleware: Option<RequestMiddleware>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxies: Vec<Proxy>,
+    root_certificates: Vec<Certificate>,
+    accept_invalid_certs: bool,
+    tls_backend: Option<TlsBackend>,
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    tcp_keepalive: Option<Duration>,
+    keep_warm: Option<Duration>,
+}
+
+#[derive(Clone, Copy)]
+enum TlsBackend {
+    #[cfg(feature = "rustls")]
+    Rustls,
+    #[cfg(feature = "native-tls")]
+    NativeTls,
+}
+
+impl TlsBackend {
+    // With neither TLS feature enabled there are no variants to match.
+    #[cfg_attr(
+        not(any(feature = "rustls", feature = "native-tls")),
+        allow(unused_variables)
+    )]
+    fn apply(self, http: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        match self {
+            #[cfg(feature = "rustls")]
+            Self::Rustls => http.use_rustls_tls(),
+            #[cfg(feature = "native-tls")]
+            Self::NativeTls => http.use_native_tls(),
+        }
+    }
+}
+
+impl TitanClientBuilder {
+    pub fn new(auth_token: impl Into<String>) -> Self {
+        Self {
+            auth_token: auth_token.into(),
+            base_urls: vec![],
+            failover_policy: FailoverPolicy::default(),
+            failover_cooldown: None,
+            observer: None,
+            #[cfg(feature = "journal")]
+            journal: None,
+            hedge_after: None,
+            max_hedge_ratio: DEFAULT_MAX_HEDGE_RATIO,
+            circuit_breaker: None,
+            rate_limit: None,
+            rate_limit_strategy: RateLimitStrategy::default(),
+            price_check_bps: None,
+            quote_defaults: None,
+            execution_budget: None,
+            clock: Arc::new(SystemClock),
+            prefer_post: false,
+            post_threshold: DEFAULT_POST_THRESHOLD,
+            api_version: ApiVersion::default(),
+            path_prefix: None,
+            lenient_decode: false,
+            response_format: ResponseFormat::default(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            error_body_limit: DEFAULT_ERROR_BODY_LIMIT,
+            quota_headers: QuotaHeaders::default(),
+            verbose_errors: false,
+            enforce_direct_routes: true,
+            verify_responses: true,
+            exact_out_tolerance_bps: DEFAULT_EXACT_OUT_TOLERANCE_BPS,
+            require_onchain_min_out: false,
+            required_route_fields: Vec::new(),
+            #[cfg(feature = "tracing")]
+            log_requests: false,
+            #[cfg(feature = "tracing")]
+            redact_user_pubkey: false,
+            on_raw_response: None,
+            default_headers: HeaderMap::new(),
+            user_agent: None,
+            client_id: None,
+            request_middleware: None,
+            timeout: None,
+            connect_timeout: None,
+            proxies: vec![],
+            root_certificates: vec![],
+            accept_invalid_certs: false,
+            tls_backend: None,
+            pool_idle_timeout: None,
+            pool_max_idle_per_host: None,
+            tcp_keepalive: None,
+            keep_warm: None,
+        }
+    }
+
+    pub fn base_path(mut self, base_path: impl Into<String>) -> Self {
+        self.base_urls = vec![base_path.into()];
+        self
+    }
+
+    /// Endpoints to fail over between, primary first.
+    pub fn base_urls(mut self, base_urls: Vec<String>) -> Self {
+        self.base_urls = base_urls;
+        self
+    }
+
+    pub fn failover_policy(mut self, policy: FailoverPolicy) -> Self {
+        self.failover_policy = policy;
+        self
+    }
+
+    /// How long a failed endpoint is skipped before being retried. Defaults to 30s.
+    pub fn failover_cooldown(mut self, cooldown: Duration) -> Self {
+        self.failover_cooldown = Some(cooldown);
+        self
+    }
+
+    pub fn observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Records every quote and swap this client produces.
+    #[cfg(feature = "journal")]
+    pub fn journal(mut self, journal: Arc<dyn QuoteJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Send a second identical quote request if the first hasn't completed after `delay`.
+    pub fn hedge_after(mut self, delay: Duration) -> Self {
+        self.hedge_after = Some(delay);
+        self
+    }
+
+    /// Upper bound on hedged requests as a fraction of all requests. Defaults to 0.1.
+    pub fn max_hedge_ratio(mut self, ratio: f64) -> Self {
+        self.max_hedge_ratio = ratio;
+        self
+    }
+
+    /// Fail fast with `ClientError::CircuitOpen` for `open_duration` after
+    /// `failure_threshold` consecutive timeouts, connection errors, or 5xx responses.
+    pub fn circuit_breaker(mut self, failure_threshold: u32, open_duration: Duration) -> Self {
+        self.circuit_breaker = Some((failure_threshold, open_duration));
+        self
+    }
+
+    /// Allow at most `requests` HTTP requests per `per`, shared across clones of the client.
+    pub fn rate_limit(mut self, requests: u32, per: Duration) -> Self {
+        self.rate_limit = Some((requests, per));
+        self
+    }
+
+    pub fn rate_limit_strategy(mut self, strategy: RateLimitStrategy) -> Self {
+        self.rate_limit_strategy = strategy;
+        self
+    }
+
+    /// Check each quote against the spot price endpoint and set
+    /// `QuoteResponse::price_check`, flagged when it deviates by more than
+    /// `max_deviation_bps`. Costs one extra request per quote.
+    pub fn price_sanity_check(mut self, max_deviation_bps: u16) -> Self {
+        self.price_check_bps = Some(max_deviation_bps);
+        self
+    }
+
+    /// Parameters merged into every quote request that leaves them unset,
+    /// unless it was made with `QuoteRequest::without_defaults`. See
+    /// `QuoteDefaults::merge` for how each one is merged.
+    pub fn default_quote_options(mut self, defaults: QuoteDefaults) -> Self {
+        self.quote_defaults = Some(defaults);
+        self
+    }
+
+    /// Reject quotes in `swap` and `swap_instructions` with
+    /// `ClientError::QuoteExpired` when they have less validity left than
+    /// `budget`, and requote them in `swap_fresh`. Off by default, when only
+    /// `swap_fresh` checks expiry, with a fixed 2 second margin.
+    pub fn execution_budget(mut self, budget: ExecutionBudget) -> Self {
+        self.execution_budget = Some(BudgetSource::Fixed(budget));
+        self
+    }
+
+    /// Like [`execution_budget`](Self::execution_budget), estimated from the
+    /// client's recent swap build times and the send times reported with
+    /// `TitanClient::record_send_latency`, starting from `initial`.
+    pub fn estimated_execution_budget(mut self, initial: ExecutionBudget) -> Self {
+        self.execution_budget = Some(BudgetSource::Estimated { initial });
+        self
+    }
+
+    /// Where the client reads the time: quote expiry, cache and token list
+    /// TTLs, rate limits, cooldowns and measured latencies. Defaults to
+    /// [`SystemClock`]; tests can use `test_utils::MockClock`.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Always send quote requests as a POST with a JSON body.
+    pub fn prefer_post(mut self, prefer_post: bool) -> Self {
+        self.prefer_post = prefer_post;
+        self
+    }
+
+    /// Switch quote requests to POST when the encoded query string would
+    /// exceed `bytes`, e.g. with long DEX exclusion lists. Defaults to 2048.
+    pub fn post_threshold(mut self, bytes: usize) -> Self {
+        self.post_threshold = bytes;
+        self
+    }
+
+    /// API version to request and decode. Defaults to `ApiVersion::V1`.
+    pub fn api_version(mut self, version: ApiVersion) -> Self {
+        self.api_version = version;
+        self
+    }
+
+    /// Replace the version's `/api/vN` prefix, e.g. for a staging deployment
+    /// mounted at `/staging/api/v1`. Responses are still decoded per `api_version`.
+    pub fn path_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.path_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Keep quotes whose optional route fields fail to decode, leaving those
+    /// fields unset and listing them in `QuoteResponse::warnings`.
+    pub fn lenient_decode(mut self, lenient: bool) -> Self {
+        self.lenient_decode = lenient;
+        self
+    }
+
+    /// Format to request quote responses in. Defaults to msgpack.
+    pub fn response_format(mut self, format: ResponseFormat) -> Self {
+        self.response_format = format;
+        self
+    }
+
+    /// Largest response body to accept, 8 MiB by default. The download is
+    /// abandoned with `ResponseTooLarge` as soon as it goes over.
+    pub fn max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    /// How many bytes of an error response's body to keep in
+    /// `RequestFailed`. Defaults to `DEFAULT_ERROR_BODY_LIMIT`.
+    pub fn error_body_limit(mut self, limit: usize) -> Self {
+        self.error_body_limit = limit;
+        self
+    }
+
+    /// The response headers to read the server's quota from. Defaults to
+    /// `x-ratelimit-remaining`, `x-ratelimit-limit` and `x-ratelimit-reset`.
+    pub fn quota_headers(mut self, headers: QuotaHeaders) -> Self {
+        self.quota_headers = headers;
+        self
+    }
+
+    /// Keep query strings in the URLs of HTTP errors. They carry the user's
+    /// pubkey, so they are redacted by default.
+    pub fn verbose_errors(mut self, verbose: bool) -> Self {
+        self.verbose_errors = verbose;
+        self
+    }
+
+    /// Drop routes through an intermediate mint when a request sets
+    /// `only_direct_routes`, in case the server returns them anyway. On by
+    /// default; pass `false` to take the server's routes as they are.
+    pub fn enforce_direct_routes(mut self, enforce: bool) -> Self {
+        self.enforce_direct_routes = enforce;
+        self
+    }
+
+    /// Fail quotes with `ClientError::ResponseMismatch` when the response is
+    /// for other mints, another swap mode or another amount than requested,
+    /// as [`verify_swap_quotes`](crate::transform::verify_swap_quotes)
+    /// checks. On by default; pass `false` to skip the check.
+    pub fn verify_responses(mut self, verify: bool) -> Self {
+        self.verify_responses = verify;
+        self
+    }
+
+    /// How far above the requested amount an ExactOut route's `out_amount`
+    /// may be before `quote` fails with `ClientError::QuoteMismatch`. A
+    /// route delivering less than requested always fails.
+    pub fn exact_out_tolerance_bps(mut self, tolerance_bps: u16) -> Self {
+        self.exact_out_tolerance_bps = tolerance_bps;
+        self
+    }
+
+    /// Strict mode for building swaps: `swap`, `swap_fresh` and
+    /// `swap_instructions` fail with `ClientError::NoOnchainMinOut` unless
+    /// [`SwapResponse::has_onchain_min_out`](crate::swap::SwapResponse::has_onchain_min_out)
+    /// is `Some(true)`, so a route that can't be shown to check its output is
+    /// never signed. Off by default.
+    pub fn require_onchain_min_out(mut self, require: bool) -> Self {
+        self.require_onchain_min_out = require;
+        self
+    }
+
+    /// Discards routes missing any of `fields` rather than quoting them with
+    /// the field unset. When no route has them all, `quote` and `quote_all`
+    /// fail with `ClientError::MissingRouteField`. None are required by
+    /// default.
+    pub fn required_route_fields(mut self, fields: impl IntoIterator<Item = RouteField>) -> Self {
+        self.required_route_fields = fields.into_iter().collect();
+        self
+    }
+
+    /// Log every quote request attempt at debug level through `tracing`: its
+    /// method, URL and parameters, attempt number, status and latency. The
+    /// `Authorization` header is never logged.
+    #[cfg(feature = "tracing")]
+    pub fn log_requests(mut self, log: bool) -> Self {
+        self.log_requests = log;
+        self
+    }
+
+    /// Log a digest of `userPublicKey` in place of the key itself.
+    #[cfg(feature = "tracing")]
+    pub fn redact_user_pubkey(mut self, redact: bool) -> Self {
+        self.redact_user_pubkey = redact;
+        self
+    }
+
+    /// Called with `(url, status, body)` for every quote response, including
+    /// errors and bodies that later fail to decode, e.g. for archival.
+    pub fn on_raw_response(
+        mut self,
+        hook: impl Fn(&str, u16, &Bytes) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_raw_response = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sent on every request. Setting `Authorization` here replaces the
+    /// bearer token header.
+    pub fn default_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    /// Replaces [`DEFAULT_USER_AGENT`], and any `User-Agent` set with
+    /// `default_header`. `build` fails with `InvalidHeader` if it isn't a
+    /// valid header value.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Identifies the integration to Titan, sent in [`CLIENT_ID_HEADER`] on
+    /// every request. `build` fails with `InvalidHeader` if it isn't a valid
+    /// header value.
+    pub fn client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.client_id = Some(client_id.into());
+        self
+    }
+
+    /// Called with each request just before it is sent, after all other
+    /// headers are set, for per-request headers such as signatures.
+    pub fn request_middleware(
+        mut self,
+        middleware: impl Fn(&mut reqwest::Request) + Send + Sync + 'static,
+    ) -> Self {
+        self.request_middleware = Some(Arc::new(middleware));
+        self
+    }
+
+    /// Timeout for each HTTP request, from connecting to reading the body.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Route requests through `proxy`. May be called more than once.
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Trust `certificate` in addition to the system roots, e.g. a private CA.
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Skip TLS certificate validation entirely. Anyone on the network path
+    /// can then impersonate the API; only use this against local test servers.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    #[cfg(feature = "rustls")]
+    pub fn use_rustls(mut self) -> Self {
+        self.tls_backend = Some(TlsBackend::Rustls);
+        self
+    }
+
+    #[cfg(feature = "native-tls")]
+    pub fn use_native_tls(mut self) -> Self {
+        self.tls_backend = Some(TlsBackend::NativeTls);
+        self
+    }
+
+    /// How long an idle pooled connection is kept open. reqwest defaults to 90s.
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Ping the health endpoint every `interval` from a background task, so
+    /// the first quote after an idle period skips the TLS handshake. Keep
+    /// `interval` below the pool idle timeout. The task stops when the last
+    /// clone of the client is dropped; `build` must then be called within a
+    /// Tokio runtime.
+    pub fn keep_warm(mut self, interval: Duration) -> Self {
+        self.keep_warm = Some(interval);
+        self
+    }
+
+    /// Fails with `HttpError` if the HTTP client can't be built, e.g. when
+    /// the TLS backend fails to initialize, and with `InvalidHeader` if the
+    /// user agent or client id isn't a valid header value.
+    pub fn build(self) -> Result<TitanClient, ClientError> {
+        let base_urls = if self.base_urls.is_empty() {
+            vec![TITAN_API_URL.to_string()]
+        } else {
+            self.base_urls
+        };
+
+        let mut http = reqwest::Client::builder();
+        for proxy in self.proxies {
+            http = http.proxy(proxy);
+        }
+        for certificate in self.root_certificates {
+            http = http.add_root_certificate(certificate);
+        }
+        if self.accept_invalid_certs {
+            http = http.danger_accept_invalid_certs(true);
+        }
+        if let Some(timeout) = self.timeout {
+            http = http.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            http = http.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            http = http.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            http = http.pool_max_idle_per_host(max);
+        }
+        if let Some(interval) = self.tcp_keepalive {
+            http = http.tcp_keepalive(interval);
+        }
+        if let Some(backend) = self.tls_backend {
+            http = backend.apply(http);
+        }
+
+        let mut default_headers = self.default_headers;
+        match self.user_agent {
+            Some(user_agent) => {
+                default_headers.insert(
+                    reqwest::header::USER_AGENT,
+                    header_value("user-agent", user_agent)?,
+                );
+            }
+            None => {
+                default_headers
+                    .entry(reqwest::header::USER_AGENT)
+                    .or_insert(HeaderValue::from_static(DEFAULT_USER_AGENT));
+            }
+        }
+        if let Some(client_id) = self.client_id {
+            default_headers.insert(CLIENT_ID_HEADER, header_value(CLIENT_ID_HEADER, client_id)?);
+        }
+
+        let mut client = TitanClient {
+            client: http.build()?,
+            endpoints: Arc::new(Endpoints::new(
+                base_urls,
+                self.failover_policy,
+                self.failover_cooldown,
+                self.clock.clone(),
+            )),
+            auth_header: format!("Bearer {}", self.auth_token),
+            observer: self.observer,
+            #[cfg(feature = "journal")]
+            journal: self.journal,
+            hedging: self
+                .hedge_after
+                .map(|after| Arc::new(Hedging::new(after, self.max_hedge_ratio))),
+            circuit_breaker: self.circuit_breaker.map(|(threshold, open)| {
+                Arc::new(CircuitBreaker::new(threshold, open, self.clock.clone()))
+            }),
+            rate_limiter: self.rate_limit.map(|(requests, per)| {
+                Arc::new(RateLimiter::new(
+                    requests,
+                    per,
+                    self.rate_limit_strategy,
+                    self.clock.clone(),
+                ))
+            }),
+            price_check_bps: self.price_check_bps,
+            quote_defaults: self.quote_defaults.map(Arc::new),
+            execution_budget: self.execution_budget,
+            latencies: Arc::default(),
+            clock: self.clock,
+            prefer_post: self.prefer_post,
+            post_threshold: self.post_threshold,
+            api_version: self.api_version,
+            api_prefix: self
+                .path_prefix
+                .unwrap_or_else(|| self.api_version.prefix().to_string()),
+            lenient_decode: self.lenient_decode,
+            response_format: self.response_format,
+            max_response_bytes: self.max_response_bytes,
+            error_body_limit: self.error_body_limit,
+            quota_headers: Arc::new(self.quota_headers),
+            last_quota: Arc::default(),
+            verbose_errors: self.verbose_errors,
+            route_checks: RouteChecks {
+                enforce_direct_routes: self.enforce_direct_routes,
+                required_route_fields: self.required_route_fields,
+                exact_out_tolerance_bps: self.exact_out_tolerance_bps,
+            },
+            verify_responses: self.verify_responses,
+            require_onchain_min_out: self.require_onchain_min_out,
+            #[cfg(feature = "tracing")]
+            log_requests: self.log_requests,
+            #[cfg(feature = "tracing")]
+            redact_user_pubkey: self.redact_user_pubkey,
+            on_raw_response: self.on_raw_response,
+            default_headers,
+            request_middleware: self.request_middleware,
+            keep_warm: None,
+        };
+        if let Some(interval) = self.keep_warm {
+            client.keep_warm = Some(keep_warm::start(&client, interval));
+        }
+        Ok(client)
+    }
+}
+
+fn header_value(name: &'static str, value: String) -> Result<HeaderValue, ClientError> {
+    HeaderValue::try_from(value.as_str()).map_err(|_| ClientError::InvalidHeader { name, value })
+}
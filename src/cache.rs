@@ -0,0 +1,253 @@
+//! A quote cache in front of a [`TitanClient`], keyed by each request's
+//! [`cache_key`](QuoteRequest::cache_key).
+//!
+//! An entry is fresh for the cache's TTL. Past that it is refetched, or,
+//! with [`QuoteCache::stale_while_revalidate`], served as is while a
+//! background task refetches it for the next caller. A quote is never served
+//! past its route's `expires_at_ms`, and one without an expiry is never
+//! served stale. Entries that can't be served again are dropped whenever a
+//! quote is stored.
+
+use crate::query::QuoteRequestTemplate;
+use crate::quote::{QuoteRequest, QuoteResponse};
+use crate::{ClientError, TitanClient};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+use tokio::task::AbortHandle;
+
+type Entries = Mutex<HashMap<String, Entry>>;
+
+struct Entry {
+    quote: Arc<QuoteResponse>,
+    fetched_at: Instant,
+    /// The background refresh in flight, if any.
+    refresh: Option<AbortHandle>,
+}
+
+impl Entry {
+    fn new(quote: Arc<QuoteResponse>, fetched_at: Instant) -> Self {
+        Self {
+            quote,
+            fetched_at,
+            refresh: None,
+        }
+    }
+
+    /// Whether this entry may be served now or later: it hasn't expired,
+    /// and it's within `ttl` or may be served stale.
+    fn servable(&self, client: &TitanClient, ttl: Duration, stale_while_revalidate: bool) -> bool {
+        if client.expires_within(&self.quote, Duration::ZERO) {
+            return false;
+        }
+        client.clock.elapsed(self.fetched_at) <= ttl
+            || (stale_while_revalidate && self.has_expiry())
+    }
+
+    fn has_expiry(&self) -> bool {
+        self.quote
+            .route_data()
+            .is_some_and(|route| route.expires_at_ms.is_some())
+    }
+}
+
+/// Drops the entries that can't be served again, aborting their refreshes.
+fn prune(
+    entries: &mut HashMap<String, Entry>,
+    client: &TitanClient,
+    ttl: Duration,
+    stale_while_revalidate: bool,
+) {
+    entries.retain(|_, entry| {
+        let servable = entry.servable(client, ttl, stale_while_revalidate);
+        if let (false, Some(refresh)) = (servable, &entry.refresh) {
+            refresh.abort();
+        }
+        servable
+    });
+}
+
+#[derive(Debug, Clone)]
+pub struct CachedQuote {
+    pub quote: Arc<QuoteResponse>,
+    /// Whether the quote was past the cache's TTL, with a refresh started
+    /// in the background.
+    pub stale: bool,
+    /// How long ago the quote was fetched; zero for a quote fetched by this
+    /// call.
+    pub age: Duration,
+}
+
+/// Dropping the cache aborts its background refreshes.
+pub struct QuoteCache {
+    client: TitanClient,
+    ttl: Duration,
+    stale_while_revalidate: bool,
+    entries: Arc<Entries>,
+}
+
+impl QuoteCache {
+    pub fn new(client: TitanClient, ttl: Duration) -> Self {
+        Self {
+            client,
+            ttl,
+            stale_while_revalidate: false,
+            entries: Arc::default(),
+        }
+    }
+
+    /// Serve entries past the TTL, but not past their expiry, immediately
+    /// and refresh them in the background. Off by default.
+    pub fn stale_while_revalidate(mut self, enabled: bool) -> Self {
+        self.stale_while_revalidate = enabled;
+        self
+    }
+
+    /// The cached quote for `request` if it may be served, otherwise a
+    /// fresh one. Concurrent misses each fetch their own quote. Must be
+    /// called within a Tokio runtime.
+    pub async fn quote(&self, request: &QuoteRequest) -> Result<CachedQuote, ClientError> {
+        self.quote_keyed(self.key(request), request, self.client.quote(request))
+            .await
+    }
+
+    /// Like [`quote`](Self::quote) for `template.request_with_amount(amount)`,
+    /// sharing its entries, with the request encoded by the template.
+    pub async fn quote_with_amount(
+        &self,
+        template: &QuoteRequestTemplate,
+        amount: u64,
+    ) -> Result<CachedQuote, ClientError> {
+        let request = template.request_with_amount(amount);
+        let key = match self.client.request_with_defaults(&request) {
+            Cow::Borrowed(_) => template.cache_key_with_amount(amount),
+            Cow::Owned(merged) => merged.cache_key(),
+        };
+        self.quote_keyed(
+            key,
+            &request,
+            template.quote_with_amount(&self.client, amount),
+        )
+        .await
+    }
+
+    /// Keyed by what is sent, with the client's default quote options
+    /// merged in.
+    fn key(&self, request: &QuoteRequest) -> String {
+        self.client.request_with_defaults(request).cache_key()
+    }
+
+    async fn quote_keyed(
+        &self,
+        key: String,
+        request: &QuoteRequest,
+        fetch: impl Future<Output = Result<QuoteResponse, ClientError>>,
+    ) -> Result<CachedQuote, ClientError> {
+        if let Some(cached) = self.lookup(&key, request) {
+            return Ok(cached);
+        }
+
+        let quote = Arc::new(fetch.await?);
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(refresh) = entries
+            .insert(key, Entry::new(quote.clone(), self.client.clock.instant()))
+            .and_then(|entry| entry.refresh)
+        {
+            refresh.abort();
+        }
+        prune(
+            &mut entries,
+            &self.client,
+            self.ttl,
+            self.stale_while_revalidate,
+        );
+        Ok(CachedQuote {
+            quote,
+            stale: false,
+            age: Duration::ZERO,
+        })
+    }
+
+    /// Entries held, including any that can't be served again but haven't
+    /// been dropped yet.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn invalidate(&self, request: &QuoteRequest) {
+        let removed = self.entries.lock().unwrap().remove(&self.key(request));
+        if let Some(refresh) = removed.and_then(|entry| entry.refresh) {
+            refresh.abort();
+        }
+    }
+
+    fn lookup(&self, key: &str, request: &QuoteRequest) -> Option<CachedQuote> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get_mut(key)?;
+        if self.client.expires_within(&entry.quote, Duration::ZERO) {
+            return None;
+        }
+
+        let age = self.client.clock.elapsed(entry.fetched_at);
+        let stale = age > self.ttl;
+        if stale {
+            if !self.stale_while_revalidate || !entry.has_expiry() {
+                return None;
+            }
+            if entry.refresh.is_none() {
+                entry.refresh = Some(self.spawn_refresh(key.to_string(), request.clone()));
+            }
+        }
+
+        Some(CachedQuote {
+            quote: entry.quote.clone(),
+            stale,
+            age,
+        })
+    }
+
+    // The task only holds the entries weakly, so a dropped cache isn't kept
+    // alive by it. It can't store its result before `lookup` releases the
+    // lock and records the handle.
+    fn spawn_refresh(&self, key: String, request: QuoteRequest) -> AbortHandle {
+        let client = self.client.clone();
+        let (ttl, stale_while_revalidate) = (self.ttl, self.stale_while_revalidate);
+        let entries: Weak<Entries> = Arc::downgrade(&self.entries);
+        tokio::spawn(async move {
+            let refreshed = client.quote(&request).await;
+            let Some(entries) = entries.upgrade() else {
+                return;
+            };
+            let mut entries = entries.lock().unwrap();
+            match refreshed {
+                Ok(quote) => {
+                    entries.insert(key, Entry::new(Arc::new(quote), client.clock.instant()));
+                    prune(&mut entries, &client, ttl, stale_while_revalidate);
+                }
+                // The stale quote stays until it expires; the next caller
+                // to be served it starts another refresh.
+                Err(_) => {
+                    if let Some(entry) = entries.get_mut(&key) {
+                        entry.refresh = None;
+                    }
+                }
+            }
+        })
+        .abort_handle()
+    }
+}
+
+impl Drop for QuoteCache {
+    fn drop(&mut self) {
+        let entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        for refresh in entries.values().filter_map(|entry| entry.refresh.as_ref()) {
+            refresh.abort();
+        }
+    }
+}
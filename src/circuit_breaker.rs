@@ -0,0 +1,134 @@
+use crate::clock::Clock;
+use crate::ClientError;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+/// Trips after `failure_threshold` consecutive retryable failures and fails
+/// fast for `open_duration`, then lets a single probe through to decide
+/// whether to close again.
+pub(crate) struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    state: Mutex<BreakerState>,
+    clock: Arc<dyn Clock>,
+}
+
+enum BreakerState {
+    Closed { consecutive_failures: u32 },
+    Open { until: Instant },
+    HalfOpen { until: Instant },
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(
+        failure_threshold: u32,
+        open_duration: Duration,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            open_duration,
+            state: Mutex::new(BreakerState::Closed {
+                consecutive_failures: 0,
+            }),
+            clock,
+        }
+    }
+
+    pub(crate) fn state(&self) -> CircuitState {
+        match *self.state.lock().unwrap() {
+            BreakerState::Closed { .. } => CircuitState::Closed,
+            BreakerState::Open { .. } => CircuitState::Open,
+            BreakerState::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Lets a call through, or fails fast while the circuit is open. The
+    /// call's result goes to [`Permit::record`].
+    pub(crate) fn acquire(&self) -> Result<Permit<'_>, ClientError> {
+        let mut state = self.state.lock().unwrap();
+        match *state {
+            BreakerState::Closed { .. } => Ok(Permit {
+                breaker: self,
+                probe: false,
+            }),
+            BreakerState::Open { until } if self.clock.instant() >= until => {
+                *state = BreakerState::HalfOpen { until };
+                Ok(Permit {
+                    breaker: self,
+                    probe: true,
+                })
+            }
+            BreakerState::Open { until } | BreakerState::HalfOpen { until } => {
+                Err(ClientError::CircuitOpen { retry_at: until })
+            }
+        }
+    }
+
+    fn record<T>(&self, result: &Result<T, ClientError>, probe: bool) {
+        let mut state = self.state.lock().unwrap();
+        let failed = matches!(result, Err(err) if err.is_retryable());
+
+        *state = match (&*state, probe, failed) {
+            // Calls let through before the circuit opened can finish late;
+            // only the probe decides whether it closes again.
+            (BreakerState::Open { .. } | BreakerState::HalfOpen { .. }, false, _) => return,
+            (_, _, false) => BreakerState::Closed {
+                consecutive_failures: 0,
+            },
+            (
+                BreakerState::Closed {
+                    consecutive_failures,
+                },
+                _,
+                true,
+            ) if consecutive_failures + 1 < self.failure_threshold => BreakerState::Closed {
+                consecutive_failures: consecutive_failures + 1,
+            },
+            (_, _, true) => BreakerState::Open {
+                until: self.clock.instant() + self.open_duration,
+            },
+        };
+    }
+}
+
+/// A call let through by [`CircuitBreaker::acquire`]. A half-open probe
+/// dropped before its result is recorded, e.g. by a timeout or a losing
+/// hedge, reopens the circuit for another `open_duration`, so the next probe
+/// isn't shut out for good.
+pub(crate) struct Permit<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+}
+
+impl Permit<'_> {
+    pub(crate) fn record<T>(mut self, result: &Result<T, ClientError>) {
+        self.breaker.record(result, self.probe);
+        self.probe = false;
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if !self.probe {
+            return;
+        }
+        let mut state = self
+            .breaker
+            .state
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        if let BreakerState::HalfOpen { .. } = *state {
+            *state = BreakerState::Open {
+                until: self.breaker.clock.instant() + self.breaker.open_duration,
+            };
+        }
+    }
+}
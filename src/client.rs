@@ -0,0 +1,1092 @@
+use crate::api_version::ApiVersion;
+use crate::builder::TitanClientBuilder;
+use crate::circuit_breaker::{CircuitBreaker, CircuitState};
+use crate::clock::Clock;
+use crate::composite::CompositeQuote;
+use crate::execution_budget::{BudgetSource, ExecutionBudget, LatencyTracker};
+use crate::failover::Endpoints;
+use crate::fallback::{FallbackQuote, QuoteRelaxation};
+use crate::format::ResponseFormat;
+use crate::hedge::Hedging;
+#[cfg(feature = "journal")]
+use crate::journal::{ExecutionStatus, QuoteJournal};
+use crate::keep_warm::KeepWarm;
+use crate::observer::{HttpAttempt, Observer, QuoteResultKind, RawResponseHook};
+use crate::options::QuoteRequestOptions;
+use crate::quota::{QuotaHeaders, QuotaInfo};
+use crate::quote::{
+    QuoteDetail, QuoteRequest, QuoteResponse, QuoteTiming, ReferralError, SwapMode, SwapQuotes,
+    SwapRoute,
+};
+use crate::quote_defaults::QuoteDefaults;
+use crate::rate_limit::RateLimiter;
+use crate::swap::{FreshSwap, SwapInstructionsOptions, SwapResponse, SwapWarning};
+use crate::transform::{
+    filter_routes, quote_from_swap_quotes, route_to_swap_response, verify_swap_quotes, RouteChecks,
+};
+use crate::transport::{
+    endpoint_url, prepare_request, read_limited, transport_error, HttpResponse, HttpTransport,
+    TransportError, QUOTE_PATH,
+};
+use crate::{
+    content_type_of, decode_body, decode_swap_quotes_for, error_from_response, quotes_by_provider,
+    validate_request, ClientError,
+};
+use bytes::Bytes;
+use reqwest::header::HeaderMap;
+use reqwest::{RequestBuilder, Response};
+use serde::{de::DeserializeOwned, Serialize};
+use solana_sdk::pubkey::Pubkey;
+#[cfg(feature = "journal")]
+use solana_sdk::signature::Signature;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// A quote response body as received, before decoding.
+struct RawQuotes {
+    format: ResponseFormat,
+    body: Bytes,
+    http_ms: u64,
+    quota: Option<QuotaInfo>,
+}
+
+/// The client's quote GETs as an [`HttpTransport`]: sent with its headers
+/// and middleware, and read within its `max_response_bytes`.
+struct ClientTransport<'a>(&'a TitanClient);
+
+impl HttpTransport for ClientTransport<'_> {
+    async fn get(
+        &self,
+        url: &str,
+        headers: &[(&str, String)],
+        query: &[(&str, String)],
+    ) -> Result<HttpResponse, TransportError> {
+        let mut request = self.0.client.get(url).query(query);
+        for (name, value) in headers {
+            request = request.header(*name, value);
+        }
+        let response = self.0.send(request).await.map_err(ClientError::from)?;
+        Ok(self.0.http_response(response).await?)
+    }
+}
+
+#[derive(Clone)]
+pub struct TitanClient {
+    pub(crate) client: reqwest::Client,
+    pub(crate) endpoints: Arc<Endpoints>,
+    pub(crate) auth_header: String,
+    pub(crate) observer: Option<Arc<dyn Observer>>,
+    #[cfg(feature = "journal")]
+    pub(crate) journal: Option<Arc<dyn QuoteJournal>>,
+    pub(crate) hedging: Option<Arc<Hedging>>,
+    pub(crate) circuit_breaker: Option<Arc<CircuitBreaker>>,
+    pub(crate) rate_limiter: Option<Arc<RateLimiter>>,
+    pub(crate) price_check_bps: Option<u16>,
+    pub(crate) quote_defaults: Option<Arc<QuoteDefaults>>,
+    pub(crate) execution_budget: Option<BudgetSource>,
+    pub(crate) latencies: Arc<LatencyTracker>,
+    pub(crate) clock: Arc<dyn Clock>,
+    pub(crate) prefer_post: bool,
+    pub(crate) post_threshold: usize,
+    pub(crate) api_version: ApiVersion,
+    pub(crate) api_prefix: String,
+    pub(crate) lenient_decode: bool,
+    pub(crate) response_format: ResponseFormat,
+    pub(crate) max_response_bytes: usize,
+    pub(crate) error_body_limit: usize,
+    pub(crate) quota_headers: Arc<QuotaHeaders>,
+    pub(crate) last_quota: Arc<Mutex<Option<QuotaInfo>>>,
+    pub(crate) verbose_errors: bool,
+    pub(crate) route_checks: RouteChecks,
+    pub(crate) verify_responses: bool,
+    pub(crate) require_onchain_min_out: bool,
+    #[cfg(feature = "tracing")]
+    pub(crate) log_requests: bool,
+    #[cfg(feature = "tracing")]
+    pub(crate) redact_user_pubkey: bool,
+    pub(crate) on_raw_response: Option<RawResponseHook>,
+    pub(crate) default_headers: HeaderMap,
+    pub(crate) request_middleware: Option<RequestMiddleware>,
+    pub(crate) keep_warm: Option<Arc<KeepWarm>>,
+}
+
+/// Runs on every request just before it is sent, e.g. to sign it.
+pub type RequestMiddleware = Arc<dyn Fn(&mut reqwest::Request) + Send + Sync>;
+
+impl TitanClient {
+    /// Panics if the HTTP client can't be built, as `reqwest::Client::new`
+    /// does. Use [`builder`](Self::builder) to handle that instead.
+    pub fn new(auth_token: String, base_path: Option<String>) -> Self {
+        let builder = TitanClientBuilder::new(auth_token);
+        let builder = match base_path {
+            Some(base_path) => builder.base_path(base_path),
+            None => builder,
+        };
+        builder.build().expect("HTTP client configuration is valid")
+    }
+
+    pub fn builder(auth_token: impl Into<String>) -> TitanClientBuilder {
+        TitanClientBuilder::new(auth_token)
+    }
+
+    pub fn current_endpoint(&self) -> &str {
+        self.endpoints.current()
+    }
+
+    pub fn api_version(&self) -> ApiVersion {
+        self.api_version
+    }
+
+    /// Full URL of `path` (e.g. `/quote/swap`) on the current endpoint.
+    pub fn endpoint(&self, path: &str) -> String {
+        self.endpoint_at(self.endpoints.current(), path)
+    }
+
+    pub(crate) fn endpoint_at(&self, base_path: &str, path: &str) -> String {
+        endpoint_url(base_path, &self.api_prefix, path)
+    }
+
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.circuit_breaker.as_ref().map(|breaker| breaker.state())
+    }
+
+    /// `request` as it will be sent, with the builder's
+    /// `default_quote_options` merged in. Borrowed when they change nothing.
+    pub fn request_with_defaults<'a>(&self, request: &'a QuoteRequest) -> Cow<'a, QuoteRequest> {
+        match &self.quote_defaults {
+            Some(defaults) => {
+                let merged = defaults.merge(request);
+                if merged == *request {
+                    Cow::Borrowed(request)
+                } else {
+                    Cow::Owned(merged)
+                }
+            }
+            None => Cow::Borrowed(request),
+        }
+    }
+
+    /// The quota reported by the most recent response that carried quota
+    /// headers, shared between clones.
+    pub fn last_quota(&self) -> Option<QuotaInfo> {
+        *self
+            .last_quota
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Reads the quota from `headers`, keeping it as the latest and passing
+    /// it to the rate limiter.
+    fn record_quota(&self, headers: &HeaderMap) -> Option<QuotaInfo> {
+        let quota = self
+            .quota_headers
+            .parse_at(headers, self.clock.system_time())?;
+        *self
+            .last_quota
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(quota);
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.observe_quota(&quota);
+        }
+        Some(quota)
+    }
+
+    async fn fetch_raw_quotes(&self, params: &[(&str, String)]) -> Result<RawQuotes, ClientError> {
+        let Some(breaker) = &self.circuit_breaker else {
+            return self.fetch_raw_quotes_failover(params).await;
+        };
+
+        let permit = breaker.acquire()?;
+        let result = self.fetch_raw_quotes_failover(params).await;
+        permit.record(&result);
+        result
+    }
+
+    async fn fetch_raw_quotes_failover(
+        &self,
+        params: &[(&str, String)],
+    ) -> Result<RawQuotes, ClientError> {
+        let mut last_err = None;
+        for (attempt, index) in (1..).zip(self.endpoints.attempt_order()) {
+            let started = self.clock.instant();
+            match self
+                .fetch_raw_quotes_hedged(self.endpoints.url(index), params, attempt)
+                .await
+            {
+                Ok(result) => {
+                    self.endpoints
+                        .report_success(index, self.clock.elapsed(started));
+                    return Ok(result);
+                }
+                Err(err) if err.is_retryable() => {
+                    self.endpoints.report_failure(index);
+                    last_err = Some(err);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(last_err.expect("at least one endpoint is configured"))
+    }
+
+    // Fires a second identical request if the first hasn't completed within
+    // `hedge_after`; whichever finishes first wins and the other is dropped.
+    async fn fetch_raw_quotes_hedged(
+        &self,
+        base_path: &str,
+        params: &[(&str, String)],
+        attempt: u32,
+    ) -> Result<RawQuotes, ClientError> {
+        let Some(hedging) = &self.hedging else {
+            return self
+                .fetch_raw_quotes_from(base_path, params, attempt, false)
+                .await;
+        };
+        hedging.record_request();
+
+        let primary = self.fetch_raw_quotes_from(base_path, params, attempt, false);
+        tokio::pin!(primary);
+        tokio::select! {
+            result = &mut primary => return result,
+            _ = tokio::time::sleep(hedging.after()) => {}
+        }
+
+        if !hedging.try_acquire() {
+            return primary.await;
+        }
+
+        let secondary = self.fetch_raw_quotes_from(base_path, params, attempt, true);
+        tokio::pin!(secondary);
+        tokio::select! {
+            result = &mut primary => result,
+            result = &mut secondary => result,
+        }
+    }
+
+    /// GETs one of the auxiliary endpoints (tokens, prices) from the current
+    /// endpoint. These aren't latency critical, so there is no failover or
+    /// hedging, but the rate limiter still applies.
+    pub(crate) async fn get_decoded<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> Result<T, ClientError> {
+        let request = self.client.get(self.endpoint(path)).query(params);
+        self.send_decoded(request).await
+    }
+
+    /// Like [`get_decoded`](Self::get_decoded), with a JSON request body.
+    pub(crate) async fn post_decoded<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &impl Serialize,
+    ) -> Result<T, ClientError> {
+        let body = serde_json::to_vec(body).map_err(|err| ClientError::InvalidResponse {
+            field: "request body",
+            value: err.to_string(),
+        })?;
+        let request = self
+            .client
+            .post(self.endpoint(path))
+            .header("Content-Type", "application/json")
+            .body(body);
+        self.send_decoded(request).await
+    }
+
+    async fn send_decoded<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T, ClientError> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await?;
+        }
+
+        let response = self
+            .send(request.header("Accept", "application/vnd.msgpack, application/json"))
+            .await?;
+        let response = self.check_response(response).await?;
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+        let buffer = self.read_body(response).await?;
+        decode_body(content_type.as_deref(), &buffer)
+    }
+
+    /// Sends `request` with the client's headers: `Authorization`, then the
+    /// builder's default headers (which replace it if they set it too), then
+    /// the request middleware.
+    pub(crate) async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let mut request = request.header("Authorization", &self.auth_header).build()?;
+        for (name, value) in self.default_headers.iter() {
+            request.headers_mut().insert(name, value.clone());
+        }
+        if let Some(middleware) = &self.request_middleware {
+            middleware(&mut request);
+        }
+        self.client
+            .execute(request)
+            .await
+            .map_err(|err| self.redact(err))
+    }
+
+    /// Drops the query string from `err`'s URL unless `verbose_errors` is set.
+    pub(crate) fn redact(&self, mut err: reqwest::Error) -> reqwest::Error {
+        if !self.verbose_errors {
+            if let Some(url) = err.url_mut() {
+                url.set_query(None);
+            }
+        }
+        err
+    }
+
+    /// Reads `response`'s body a chunk at a time, abandoning it once it passes
+    /// `max_response_bytes` instead of buffering whatever the server sends.
+    async fn read_body(&self, response: Response) -> Result<Bytes, ClientError> {
+        read_limited(response, self.max_response_bytes, |err| {
+            self.redact(err).into()
+        })
+        .await
+    }
+
+    /// Reads the whole of `response` within `max_response_bytes`.
+    async fn http_response(&self, response: Response) -> Result<HttpResponse, ClientError> {
+        Ok(HttpResponse {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: self.read_body(response).await?,
+        })
+    }
+
+    async fn check_response(&self, response: Response) -> Result<Response, ClientError> {
+        let status = response.status();
+        let quota = self.record_quota(response.headers());
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let content_type = content_type_of(response.headers());
+        let body = self.read_body(response).await?;
+        Err(error_from_response(
+            status,
+            content_type,
+            String::from_utf8_lossy(&body).into_owned(),
+            self.error_body_limit,
+            quota,
+        ))
+    }
+
+    fn should_post(&self, params: &[(&str, String)]) -> bool {
+        self.prefer_post || encoded_query_len(params) > self.post_threshold
+    }
+
+    /// `attempt` counts endpoints tried, from 1; `hedge` marks the second
+    /// of a hedged pair.
+    async fn fetch_raw_quotes_from(
+        &self,
+        base_path: &str,
+        params: &[(&str, String)],
+        attempt: u32,
+        hedge: bool,
+    ) -> Result<RawQuotes, ClientError> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await?;
+        }
+
+        let started = self.clock.instant();
+        let http_attempt = HttpAttempt::start(&self.observer, &*self.clock);
+        let url = self.endpoint_at(base_path, QUOTE_PATH);
+        let post = self.should_post(params);
+        let mut status = None;
+        let result = async {
+            let accept = self.response_format.content_type();
+            let response = if post {
+                let request = self
+                    .client
+                    .post(&url)
+                    .header("Content-Type", "application/json")
+                    .header("Accept", accept)
+                    .body(params_as_json(params));
+                self.http_response(self.send(request).await?).await?
+            } else {
+                ClientTransport(self)
+                    .get(&url, &[("Accept", accept.to_string())], params)
+                    .await
+                    .map_err(transport_error)?
+            };
+            let response_status = response.status;
+            status = Some(response_status.as_u16());
+
+            // Go by what was served, in case a proxy ignored `Accept`.
+            let format = response
+                .headers
+                .get(http::header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(ResponseFormat::from_content_type)
+                .unwrap_or(self.response_format);
+            let content_type = content_type_of(&response.headers);
+            let quota = self.record_quota(&response.headers);
+            let body = response.body;
+            if let Some(hook) = &self.on_raw_response {
+                hook(&url, response_status.as_u16(), &body);
+            }
+            if !response_status.is_success() {
+                let body = String::from_utf8_lossy(&body).into_owned();
+                return Err(error_from_response(
+                    response_status,
+                    content_type,
+                    body,
+                    self.error_body_limit,
+                    quota,
+                ));
+            }
+            Ok::<_, ClientError>((format, body, quota))
+        }
+        .await;
+
+        http_attempt.finish(status, result.as_ref().map_or(0, |(_, body, _)| body.len()));
+        self.log_request(&RequestLog {
+            method: if post { "POST" } else { "GET" },
+            url: &url,
+            params,
+            attempt,
+            hedge,
+            status,
+            latency: self.clock.elapsed(started),
+        });
+        let (format, body, quota) = result?;
+
+        Ok(RawQuotes {
+            format,
+            body,
+            http_ms: elapsed_ms(self.clock.elapsed(started)),
+            quota,
+        })
+    }
+
+    /// The undecoded quote response for `request`, as served: msgpack unless
+    /// the client asks for another [`ResponseFormat`]. Decode msgpack with
+    /// [`crate::decode_swap_quotes`].
+    pub async fn fetch_swap_quotes_raw(
+        &self,
+        request: &QuoteRequest,
+    ) -> Result<Bytes, ClientError> {
+        let request = self.request_with_defaults(request);
+        let raw = self.fetch_raw_quotes(&request.query_params()).await?;
+        Ok(raw.body)
+    }
+
+    /// Every provider's route for `request`, decoded but not converted into
+    /// a [`QuoteResponse`].
+    pub async fn fetch_swap_quotes(
+        &self,
+        request: &QuoteRequest,
+    ) -> Result<SwapQuotes, ClientError> {
+        let request = self.request_with_defaults(request);
+        let (quotes, _, _) = self
+            .fetch_decoded_quotes(&request.query_params(), QuoteDetail::Full)
+            .await?;
+        Ok(quotes)
+    }
+
+    async fn fetch_decoded_quotes(
+        &self,
+        params: &[(&str, String)],
+        detail: QuoteDetail,
+    ) -> Result<(SwapQuotes, QuoteTiming, Option<QuotaInfo>), ClientError> {
+        let raw = self.fetch_raw_quotes(params).await?;
+
+        let decode_started = self.clock.instant();
+        let quotes = decode_swap_quotes_for(
+            self.api_version,
+            raw.format,
+            self.lenient_decode,
+            detail,
+            &raw.body,
+        )?;
+        let timing = QuoteTiming {
+            http_ms: raw.http_ms,
+            decode_ms: elapsed_ms(self.clock.elapsed(decode_started)),
+            ..Default::default()
+        };
+
+        Ok((quotes, timing, raw.quota))
+    }
+
+    /// Like [`quote`](Self::quote), requesting the response in `format`
+    /// instead of the client's default.
+    pub async fn quote_with_format(
+        &self,
+        request: &QuoteRequest,
+        format: ResponseFormat,
+    ) -> Result<QuoteResponse, ClientError> {
+        let mut client = self.clone();
+        client.response_format = format;
+        client.quote(request).await
+    }
+
+    pub async fn quote(&self, request: &QuoteRequest) -> Result<QuoteResponse, ClientError> {
+        self.quote_with_options(request, &QuoteRequestOptions::default())
+            .await
+    }
+
+    /// Like [`quote`](Self::quote), failing with `ClientError::DeadlineExceeded`
+    /// once the options' timeout or deadline passes, or `ClientError::Cancelled`
+    /// once their cancellation token fires.
+    pub async fn quote_with_options(
+        &self,
+        request: &QuoteRequest,
+        options: &QuoteRequestOptions,
+    ) -> Result<QuoteResponse, ClientError> {
+        self.quote_instrumented(request, &request.query_params(), None, options)
+            .await
+    }
+
+    /// Like [`quote_with_options`](Self::quote_with_options), sending
+    /// `params` instead of `request.query_params()`. `params` must encode
+    /// `request`.
+    pub(crate) async fn quote_with_params(
+        &self,
+        request: &QuoteRequest,
+        params: &[(&'static str, String)],
+        options: &QuoteRequestOptions,
+    ) -> Result<QuoteResponse, ClientError> {
+        self.quote_instrumented(request, params, None, options)
+            .await
+    }
+
+    /// Quotes `request` using only `provider`'s route (matched
+    /// case-insensitively against each key's provider segment), failing with `ClientError::ProviderUnavailable`
+    /// when other providers returned routes but it did not.
+    pub async fn quote_from(
+        &self,
+        request: &QuoteRequest,
+        provider: &str,
+    ) -> Result<QuoteResponse, ClientError> {
+        self.quote_instrumented(
+            request,
+            &request.query_params(),
+            Some(provider),
+            &QuoteRequestOptions::default(),
+        )
+        .await
+    }
+
+    async fn quote_instrumented(
+        &self,
+        request: &QuoteRequest,
+        params: &[(&'static str, String)],
+        provider: Option<&str>,
+        options: &QuoteRequestOptions,
+    ) -> Result<QuoteResponse, ClientError> {
+        let started = self.clock.instant();
+        // `params` still encodes `request` when the defaults change nothing.
+        let merged = self.request_with_defaults(request);
+        let merged_params;
+        let (request, params) = match &merged {
+            Cow::Borrowed(_) => (request, params),
+            Cow::Owned(merged) => {
+                merged_params = merged.query_params();
+                (merged, merged_params.as_slice())
+            }
+        };
+        let quote = async {
+            // Timers run on Tokio's time, not the client's clock, which may
+            // be a mock that never moves.
+            match options.effective_deadline(Instant::now()) {
+                Some(deadline) => tokio::time::timeout_at(
+                    deadline.into(),
+                    self.select_quote(request, params, provider, options.detail, started),
+                )
+                .await
+                .unwrap_or(Err(ClientError::DeadlineExceeded)),
+                None => {
+                    self.select_quote(request, params, provider, options.detail, started)
+                        .await
+                }
+            }
+        };
+        // Dropping the losing future aborts its HTTP request.
+        let result = match &options.cancel {
+            Some(cancel) => tokio::select! {
+                biased;
+                _ = cancel.cancelled() => Err(ClientError::Cancelled),
+                result = quote => result,
+            },
+            None => quote.await,
+        };
+        let result = match &options.guard {
+            Some(guard) => result.and_then(|quote| guard.check(&quote).map(|()| quote)),
+            None => result,
+        };
+
+        let (kind, provider, route_hops) = match &result {
+            Ok(quote) => (
+                QuoteResultKind::Success,
+                quote.provider.clone(),
+                quote.route_plan.len(),
+            ),
+            Err(ClientError::NoRoutesAvailable) => (QuoteResultKind::NoRoutes, None, 0),
+            Err(_) => (QuoteResultKind::Error, None, 0),
+        };
+        crate::observer::notify_quote(
+            &self.observer,
+            kind,
+            self.clock.elapsed(started),
+            provider,
+            route_hops,
+        );
+        #[cfg(feature = "journal")]
+        if let Some(journal) = &self.journal {
+            journal.record_quote(request, result.as_ref());
+        }
+
+        result
+    }
+
+    async fn select_quote(
+        &self,
+        request: &QuoteRequest,
+        params: &[(&'static str, String)],
+        provider: Option<&str>,
+        detail: QuoteDetail,
+        started: Instant,
+    ) -> Result<QuoteResponse, ClientError> {
+        validate_request(request)?;
+        let (quotes, mut timing, quota) = self.fetch_decoded_quotes(params, detail).await?;
+        self.verify_response(request, &quotes)?;
+        let mut quote = self.quote_from_swap_quotes(request, provider, quotes)?;
+        quote.quota = quota;
+        timing.server_ns = quote.route_data().and_then(|route| route.time_taken_ns);
+        // A failed price lookup leaves `price_check` unset rather than
+        // failing the quote.
+        if let Some(max_deviation_bps) = self.price_check_bps {
+            quote.price_check = self.check_price(&quote, max_deviation_bps).await.ok();
+        }
+
+        timing.total_ms = elapsed_ms(self.clock.elapsed(started));
+        record_timing(&timing);
+        quote.timing = Some(timing);
+
+        Ok(quote)
+    }
+
+    /// The network-free part of `quote`: filters the decoded routes, picks
+    /// one and converts it.
+    pub(crate) fn quote_from_swap_quotes(
+        &self,
+        request: &QuoteRequest,
+        provider: Option<&str>,
+        quotes: SwapQuotes,
+    ) -> Result<QuoteResponse, ClientError> {
+        quote_from_swap_quotes(request, quotes, provider, &self.route_checks)
+    }
+
+    /// Quotes `request`, and on `NoRoutesAvailable` retries with each
+    /// relaxation applied on top of the previous ones until a route is found.
+    /// If the chain runs out, the original `NoRoutesAvailable` is returned.
+    pub async fn quote_with_fallback(
+        &self,
+        request: &QuoteRequest,
+        fallback_chain: Vec<QuoteRelaxation>,
+    ) -> Result<FallbackQuote, ClientError> {
+        let original_err = match self.quote(request).await {
+            Ok(quote) => {
+                return Ok(FallbackQuote {
+                    quote,
+                    applied: Vec::new(),
+                })
+            }
+            Err(err @ ClientError::NoRoutesAvailable) => err,
+            Err(err) => return Err(err),
+        };
+
+        // Relax what was sent, and don't let the defaults undo it.
+        let mut relaxed = self
+            .request_with_defaults(request)
+            .into_owned()
+            .without_defaults();
+        let mut applied = Vec::with_capacity(fallback_chain.len());
+        for relaxation in fallback_chain {
+            relaxation.apply(&mut relaxed);
+            applied.push(relaxation);
+
+            match self.quote(&relaxed).await {
+                Ok(quote) => return Ok(FallbackQuote { quote, applied }),
+                Err(ClientError::NoRoutesAvailable) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(original_err)
+    }
+
+    /// Quotes `request` in two legs through the `via` mint, for pairs with
+    /// no direct route: input to `via`, then the first leg's
+    /// `min_out_amount()` of `via` to the output, which it delivers even at
+    /// its worst. Only ExactIn is supported; ExactOut fails with
+    /// `ClientError::UnsupportedSwapMode`.
+    pub async fn quote_via(
+        &self,
+        request: &QuoteRequest,
+        via: Pubkey,
+    ) -> Result<CompositeQuote, ClientError> {
+        let swap_mode = request.swap_mode.clone().unwrap_or_default();
+        if swap_mode != SwapMode::ExactIn {
+            return Err(ClientError::UnsupportedSwapMode(swap_mode));
+        }
+
+        let first = self
+            .quote(&QuoteRequest {
+                output_mint: via,
+                ..request.clone()
+            })
+            .await?;
+        let second = self
+            .quote(&QuoteRequest {
+                input_mint: via,
+                amount: first.min_out_amount(),
+                ..request.clone()
+            })
+            .await?;
+
+        Ok(CompositeQuote { first, second })
+    }
+
+    /// One quote per route in the response, keyed by its full `QuoteKey` as
+    /// a string and ordered by provider name.
+    pub async fn quote_all(
+        &self,
+        request: &QuoteRequest,
+    ) -> Result<BTreeMap<String, QuoteResponse>, ClientError> {
+        let request = self.request_with_defaults(request);
+        let request = request.as_ref();
+        let params = prepare_request(request)?;
+        let (mut quotes, _, quota) = self
+            .fetch_decoded_quotes(&params, QuoteDetail::Full)
+            .await?;
+        self.verify_response(request, &quotes)?;
+        filter_routes(request, &mut quotes, &self.route_checks)?;
+        let mut quotes = quotes_by_provider(request, quotes)?;
+        for quote in quotes.values_mut() {
+            quote.quota = quota;
+        }
+        Ok(quotes)
+    }
+
+    fn verify_response(
+        &self,
+        request: &QuoteRequest,
+        quotes: &SwapQuotes,
+    ) -> Result<(), ClientError> {
+        if self.verify_responses {
+            verify_swap_quotes(request, quotes)?;
+        }
+        Ok(())
+    }
+
+    pub fn swap(&self, quote: &QuoteResponse) -> Result<SwapResponse, ClientError> {
+        let started = self.clock.instant();
+        let result = self.check_expiry(quote).and_then(|()| build_swap(quote));
+        if let Ok(swap) = &result {
+            self.latencies.record_build(self.clock.elapsed(started));
+            self.report_swap_warning(swap);
+        }
+        self.journaled_swap(quote, result)
+    }
+
+    /// Tells the observer and the log about duplicate or missing lookup
+    /// tables in `swap`.
+    fn report_swap_warning(&self, swap: &SwapResponse) {
+        let listed = swap.address_lookup_table_addresses.len();
+        let unique = swap.lookup_table_addresses().len();
+        let warning = if listed > unique {
+            SwapWarning::DuplicateLookupTables { listed, unique }
+        } else {
+            match swap.lookup_table_warning() {
+                Some(warning) => warning,
+                None => return,
+            }
+        };
+        #[cfg(feature = "tracing")]
+        tracing::warn!(%warning, "suspect route");
+        crate::observer::notify_swap_warning(&self.observer, warning);
+    }
+
+    /// The budget set with the builder's `execution_budget` or
+    /// `estimated_execution_budget`, as it stands now.
+    pub fn execution_budget(&self) -> Option<ExecutionBudget> {
+        self.execution_budget
+            .as_ref()
+            .map(|source| self.latencies.budget(source))
+    }
+
+    /// Reports how long signing and sending a swap took, for the estimated
+    /// execution budget. Shared between clones.
+    pub fn record_send_latency(&self, latency: Duration) {
+        self.latencies.record_send(latency);
+    }
+
+    fn check_expiry(&self, quote: &QuoteResponse) -> Result<(), ClientError> {
+        self.execution_budget()
+            .map_or(Ok(()), |budget| budget.check_at(quote, self.clock.now_ms()))
+    }
+
+    /// Records a sent swap transaction in the client's journal, if it has
+    /// one.
+    #[cfg(feature = "journal")]
+    pub fn record_execution(&self, signature: &Signature, status: &ExecutionStatus) {
+        if let Some(journal) = &self.journal {
+            journal.record_execution(signature, status);
+        }
+    }
+
+    fn journaled_swap(
+        &self,
+        quote: &QuoteResponse,
+        result: Result<SwapResponse, ClientError>,
+    ) -> Result<SwapResponse, ClientError> {
+        let result = result.and_then(|swap| self.check_onchain_min_out(swap));
+        #[cfg(feature = "journal")]
+        if let Some(journal) = &self.journal {
+            journal.record_swap(quote, result.as_ref());
+        }
+        #[cfg(not(feature = "journal"))]
+        let _ = quote;
+        result
+    }
+
+    fn check_onchain_min_out(&self, swap: SwapResponse) -> Result<SwapResponse, ClientError> {
+        match swap.has_onchain_min_out() {
+            Some(false) | None if self.require_onchain_min_out => Err(ClientError::NoOnchainMinOut),
+            _ => Ok(swap),
+        }
+    }
+
+    /// Asks the server for finalized swap instructions, which may use fresher
+    /// state than the quote and handle ATA creation server-side. The quote is
+    /// sent by reference id when it has one, otherwise as its raw route.
+    /// [`swap`](Self::swap) remains the offline alternative.
+    pub async fn swap_instructions(
+        &self,
+        quote: &QuoteResponse,
+        options: SwapInstructionsOptions,
+    ) -> Result<SwapResponse, ClientError> {
+        let route = executable_route(quote)?;
+        if quote.referral_fee.is_some() && options.fee_account.is_some() {
+            return self.journaled_swap(quote, Err(ReferralError::FeeAccountConflict.into()));
+        }
+        if let Err(err) = self.check_expiry(quote) {
+            return self.journaled_swap(quote, Err(err));
+        }
+        let reference_id = route.reference_id.as_deref();
+        let body = SwapInstructionsBody {
+            reference_id,
+            route: reference_id.is_none().then_some(&**route),
+            options,
+        };
+        let started = self.clock.instant();
+        let result = self.post_decoded(SWAP_INSTRUCTIONS_PATH, &body).await;
+        if result.is_ok() {
+            self.latencies.record_build(self.clock.elapsed(started));
+        }
+        self.journaled_swap(quote, result)
+    }
+
+    /// Builds the swap for `quote`, re-quoting first if it has expired (or is
+    /// about to, by the client's execution budget). The fresh quote is used
+    /// only if it is at most `max_divergence_bps` worse than the original.
+    pub async fn swap_fresh(
+        &self,
+        request: &QuoteRequest,
+        quote: &QuoteResponse,
+        max_divergence_bps: u16,
+    ) -> Result<FreshSwap, ClientError> {
+        let expiring = match self.execution_budget() {
+            Some(budget) => budget.check_at(quote, self.clock.now_ms()).is_err(),
+            None => self.expires_within(quote, EXPIRY_MARGIN),
+        };
+        if !expiring {
+            return Ok(FreshSwap {
+                swap: self.swap(quote)?,
+                quote: quote.clone(),
+                requoted: false,
+            });
+        }
+
+        let fresh = self.quote(request).await?;
+        let delta = quote
+            .compare(&fresh)
+            .map_err(|err| ClientError::InvalidResponse {
+                field: "quote",
+                value: err.to_string(),
+            })?;
+        if !delta.within_bps(f64::from(max_divergence_bps)) {
+            return Err(ClientError::PriceMoved {
+                divergence_bps: -delta.bps,
+            });
+        }
+
+        Ok(FreshSwap {
+            swap: self.swap(&fresh)?,
+            quote: fresh,
+            requoted: true,
+        })
+    }
+}
+
+/// Length of `params` once percent-encoded into a query string.
+fn encoded_query_len(params: &[(&str, String)]) -> usize {
+    let encoded_len = |s: &str| {
+        s.bytes()
+            .map(|b| match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'*' => 1,
+                _ => 3,
+            })
+            .sum::<usize>()
+    };
+    params
+        .iter()
+        .map(|(key, value)| encoded_len(key) + encoded_len(value) + 2)
+        .sum()
+}
+
+/// The same parameters as the query string, as a flat JSON object.
+fn params_as_json(params: &[(&str, String)]) -> Vec<u8> {
+    let body: serde_json::Map<String, serde_json::Value> = params
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.clone().into()))
+        .collect();
+    serde_json::to_vec(&body).expect("string map serializes")
+}
+
+const SWAP_INSTRUCTIONS_PATH: &str = "/swap/instructions";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SwapInstructionsBody<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reference_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    route: Option<&'a SwapRoute>,
+    #[serde(flatten)]
+    options: SwapInstructionsOptions,
+}
+
+/// Without an execution budget, quotes this close to expiry are treated as
+/// expired by `swap_fresh`, leaving time to sign and land the transaction.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(2);
+
+impl TitanClient {
+    pub(crate) fn expires_within(&self, quote: &QuoteResponse, margin: Duration) -> bool {
+        let Some(expires_at_ms) = quote.route_data().and_then(|route| route.expires_at_ms) else {
+            return false;
+        };
+        u128::from(self.clock.now_ms()) + margin.as_millis() >= u128::from(expires_at_ms)
+    }
+
+    /// The clock set with the builder's `clock`, the system clock by default.
+    pub fn clock(&self) -> &dyn Clock {
+        &*self.clock
+    }
+}
+
+pub(crate) fn build_swap(quote: &QuoteResponse) -> Result<SwapResponse, ClientError> {
+    route_to_swap_response(executable_route(quote)?)
+}
+
+/// The route data of a quote that can be swapped.
+fn executable_route(quote: &QuoteResponse) -> Result<&Arc<SwapRoute>, ClientError> {
+    if quote.indicative {
+        return Err(ClientError::MissingUserContext);
+    }
+    if quote.detail == QuoteDetail::PriceOnly {
+        return Err(ClientError::PriceOnlyQuote);
+    }
+    quote.route_data().ok_or(ClientError::MissingRouteData)
+}
+
+fn elapsed_ms(elapsed: Duration) -> u64 {
+    u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(feature = "tracing")]
+fn record_timing(timing: &QuoteTiming) {
+    let span = tracing::debug_span!(
+        "titan_quote",
+        server_ns = timing.server_ns,
+        http_ms = timing.http_ms,
+        decode_ms = timing.decode_ms,
+        total_ms = timing.total_ms,
+    );
+    let _entered = span.enter();
+    tracing::debug!("quote completed");
+}
+
+#[cfg(not(feature = "tracing"))]
+fn record_timing(_timing: &QuoteTiming) {}
+
+/// One quote request attempt, as logged by `log_requests`.
+#[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+struct RequestLog<'a> {
+    method: &'static str,
+    url: &'a str,
+    params: &'a [(&'a str, String)],
+    attempt: u32,
+    hedge: bool,
+    status: Option<u16>,
+    latency: Duration,
+}
+
+impl TitanClient {
+    /// Logs `log` at debug level if `log_requests` is set. Only the URL and
+    /// parameters are logged, never headers, so the auth token can't leak.
+    #[cfg(feature = "tracing")]
+    fn log_request(&self, log: &RequestLog<'_>) {
+        if !self.log_requests {
+            return;
+        }
+        let query = log
+            .params
+            .iter()
+            .map(|(name, value)| {
+                let value = if *name == "userPublicKey" && self.redact_user_pubkey {
+                    redacted_pubkey(value)
+                } else {
+                    crate::query::encode(value)
+                };
+                format!("{}={}", name, value)
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+        tracing::debug!(
+            method = log.method,
+            url = log.url,
+            query = query.as_str(),
+            attempt = log.attempt,
+            hedge = log.hedge,
+            status = log.status,
+            latency_ms = u64::try_from(log.latency.as_millis()).unwrap_or(u64::MAX),
+            "quote request"
+        );
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    fn log_request(&self, _log: &RequestLog<'_>) {}
+}
+
+/// Stands in for a user pubkey in logs: a short SHA-256 digest, so requests
+/// from the same user can still be correlated.
+#[cfg(feature = "tracing")]
+fn redacted_pubkey(pubkey: &str) -> String {
+    let digest = solana_sdk::hash::hashv(&[pubkey.as_bytes()]);
+    let hex: String = digest.as_ref()[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("sha256:{}", hex)
+}
@@ -0,0 +1,40 @@
+//! The time source behind expiry checks, TTLs, cooldowns and latencies.
+//!
+//! A client reads time only through its [`Clock`], set with
+//! `TitanClientBuilder::clock`, so tests can drive it with
+//! `test_utils::MockClock`. Waiting (rate limiting, hedging, timeouts) still
+//! uses Tokio's timer.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+pub trait Clock: Send + Sync + 'static {
+    /// Wall-clock time in Unix milliseconds, as quote expiries are given.
+    fn now_ms(&self) -> u64;
+
+    /// Monotonic time, for measuring intervals.
+    fn instant(&self) -> Instant;
+
+    fn elapsed(&self, since: Instant) -> Duration {
+        self.instant().saturating_duration_since(since)
+    }
+
+    fn system_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.now_ms())
+    }
+}
+
+/// The operating system's clock. The default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| u64::try_from(now.as_millis()).unwrap_or(u64::MAX))
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
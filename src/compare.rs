@@ -0,0 +1,112 @@
+use crate::amount_math::{self, AmountOverflow};
+use crate::pubkey::Pubkey;
+use crate::quote::{QuoteResponse, SwapMode};
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CompareError {
+    #[error("Quotes are for different pairs: {0} -> {1} vs {2} -> {3}")]
+    MintMismatch(Pubkey, Pubkey, Pubkey, Pubkey),
+    #[error("Quotes use different swap modes: {0:?} vs {1:?}")]
+    SwapModeMismatch(SwapMode, SwapMode),
+    #[error(transparent)]
+    AmountOverflow(#[from] AmountOverflow),
+}
+
+/// How `other` differs from `self` in [`QuoteResponse::compare`]. Positive
+/// amounts mean `other` is better: more output for ExactIn, less input for
+/// ExactOut.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuoteDelta {
+    pub amount: i128,
+    pub bps: f64,
+    pub fee: i128,
+    pub hops: i64,
+    /// `None` unless both routes report compute units.
+    pub compute_units: Option<i64>,
+}
+
+impl QuoteDelta {
+    /// Whether `other` is at most `max_bps` worse than `self`.
+    pub fn within_bps(&self, max_bps: f64) -> bool {
+        self.bps >= -max_bps
+    }
+}
+
+impl QuoteResponse {
+    // Errors are rare and carry both pairs, so the size isn't worth a box.
+    #[allow(clippy::result_large_err)]
+    pub fn compare(&self, other: &QuoteResponse) -> Result<QuoteDelta, CompareError> {
+        if self.input_mint != other.input_mint || self.output_mint != other.output_mint {
+            return Err(CompareError::MintMismatch(
+                self.input_mint,
+                self.output_mint,
+                other.input_mint,
+                other.output_mint,
+            ));
+        }
+        if self.swap_mode != other.swap_mode {
+            return Err(CompareError::SwapModeMismatch(
+                self.swap_mode.clone(),
+                other.swap_mode.clone(),
+            ));
+        }
+
+        // `in_amount` echoes the request for ExactOut; the route has the cost.
+        let (base, amount) = match self.swap_mode {
+            SwapMode::ExactOut => (
+                self.input_amount(),
+                i128::from(self.input_amount()) - i128::from(other.input_amount()),
+            ),
+            SwapMode::ExactIn | SwapMode::Other(_) => (
+                self.out_amount,
+                i128::from(other.out_amount) - i128::from(self.out_amount),
+            ),
+        };
+        let bps = if base == 0 {
+            0.0
+        } else {
+            amount as f64 / base as f64 * 10_000.0
+        };
+
+        let fee = |quote: &QuoteResponse| {
+            i128::from(quote.platform_fee.as_ref().map_or(0, |fee| fee.amount))
+        };
+        let compute_units = |quote: &QuoteResponse| {
+            quote
+                .raw_route
+                .as_ref()
+                .and_then(|route| route.compute_units)
+        };
+        let compute_units = match (compute_units(self), compute_units(other)) {
+            (Some(ours), Some(theirs)) => Some(amount_math::signed_delta(theirs, ours)?),
+            _ => None,
+        };
+
+        Ok(QuoteDelta {
+            amount,
+            bps,
+            fee: fee(other) - fee(self),
+            hops: other.route_plan.len() as i64 - self.route_plan.len() as i64,
+            compute_units,
+        })
+    }
+}
+
+impl fmt::Display for QuoteDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:+.1} bps, {:+} {}",
+            self.bps,
+            self.hops,
+            if self.hops.abs() == 1 { "hop" } else { "hops" }
+        )?;
+        match self.compute_units {
+            Some(cu) if cu.abs() >= 1_000 => write!(f, ", {:+.0}k CU", cu as f64 / 1_000.0),
+            Some(cu) => write!(f, ", {:+} CU", cu),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Combining several swaps into one atomic transaction.
+
+use crate::constants::COMPUTE_BUDGET_PROGRAM_ID;
+use crate::inspect::{classify_instruction, InstructionKind};
+use crate::quote::MAX_TRANSACTION_BYTES;
+use crate::swap::SwapResponse;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, pubkey::Pubkey,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ops::Range;
+use thiserror::Error;
+
+/// Accounts a transaction may lock.
+pub const MAX_TRANSACTION_ACCOUNTS: usize = 64;
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ComposeError {
+    #[error("No swaps to compose")]
+    Empty,
+    #[error("Account {account} is writable in both swap {first} and swap {second}")]
+    WritableConflict {
+        account: Pubkey,
+        first: usize,
+        second: usize,
+    },
+    #[error("Composed swap locks {count} accounts, over the limit of {limit}")]
+    TooManyAccounts { count: usize, limit: usize },
+    #[error("Composed swap needs at least {size} bytes, over the limit of {limit}")]
+    TransactionTooLarge { size: usize, limit: usize },
+    #[error(
+        "Composed swap needs {units} compute units, over the limit of {}",
+        MAX_COMPUTE_UNIT_LIMIT
+    )]
+    ComputeUnitsExceeded { units: u64 },
+}
+
+#[derive(Debug, Clone)]
+pub struct ComposeOptions {
+    /// Non-signer accounts that several swaps may write, typically the
+    /// user's own token accounts (e.g. a shared USDC destination). Any other
+    /// account written by two swaps is a `WritableConflict`.
+    pub shared_writable: HashSet<Pubkey>,
+    pub max_accounts: usize,
+    pub max_transaction_size: usize,
+}
+
+impl Default for ComposeOptions {
+    fn default() -> Self {
+        Self {
+            shared_writable: HashSet::new(),
+            max_accounts: MAX_TRANSACTION_ACCOUNTS,
+            max_transaction_size: usize::from(MAX_TRANSACTION_BYTES),
+        }
+    }
+}
+
+/// Several swaps as one. `swap` can be used anywhere a single
+/// `SwapResponse` is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComposedSwap {
+    pub swap: SwapResponse,
+    /// Where each input swap's instructions ended up in `swap.instructions`.
+    pub instruction_ranges: Vec<Range<usize>>,
+    pub account_count: usize,
+    /// A lower bound on the serialized transaction size, assuming every
+    /// account that can be is resolved through the lookup tables.
+    pub estimated_size: usize,
+}
+
+pub fn compose_swaps(swaps: &[SwapResponse]) -> Result<ComposedSwap, ComposeError> {
+    compose_swaps_with(swaps, &ComposeOptions::default())
+}
+
+/// Concatenates `swaps` in order. Each swap's compute budget instructions are
+/// replaced by a single budget for the whole transaction: the summed unit
+/// limit (when every swap reports one or sets one in its instructions) and
+/// the highest unit price. Lookup tables are merged and deduplicated;
+/// expiries and context slots take the earliest.
+pub fn compose_swaps_with(
+    swaps: &[SwapResponse],
+    options: &ComposeOptions,
+) -> Result<ComposedSwap, ComposeError> {
+    if swaps.is_empty() {
+        return Err(ComposeError::Empty);
+    }
+    check_writable_conflicts(swaps, &options.shared_writable)?;
+
+    let compute_unit_limit = sum_all(swaps.iter().map(|swap| {
+        swap.compute_unit_limit
+            .or_else(|| swap.existing_compute_budget()?.unit_limit)
+            .map(u64::from)
+    }))
+    .map(|units| {
+        u32::try_from(units)
+            .ok()
+            .filter(|units| *units <= MAX_COMPUTE_UNIT_LIMIT)
+            .ok_or(ComposeError::ComputeUnitsExceeded { units })
+    })
+    .transpose()?;
+    let compute_unit_price = swaps
+        .iter()
+        .flat_map(|swap| &swap.instructions)
+        .filter_map(compute_unit_price)
+        .max();
+
+    let mut instructions = Vec::new();
+    if let Some(units) = compute_unit_limit {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+    }
+    if let Some(price) = compute_unit_price {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+    }
+    let mut instruction_ranges = Vec::with_capacity(swaps.len());
+    for swap in swaps {
+        let start = instructions.len();
+        instructions.extend(
+            swap.instructions
+                .iter()
+                .filter(|ix| ix.program_id != COMPUTE_BUDGET_PROGRAM_ID)
+                .cloned(),
+        );
+        instruction_ranges.push(start..instructions.len());
+    }
+
+    let mut address_lookup_table_addresses = Vec::new();
+    let mut seen_tables = HashSet::new();
+    for address in swaps
+        .iter()
+        .flat_map(|swap| &swap.address_lookup_table_addresses)
+    {
+        if seen_tables.insert(*address) {
+            address_lookup_table_addresses.push(*address);
+        }
+    }
+
+    let account_count = instructions
+        .iter()
+        .flat_map(|ix| {
+            std::iter::once(&ix.program_id).chain(ix.accounts.iter().map(|meta| &meta.pubkey))
+        })
+        .collect::<HashSet<_>>()
+        .len();
+    if account_count > options.max_accounts {
+        return Err(ComposeError::TooManyAccounts {
+            count: account_count,
+            limit: options.max_accounts,
+        });
+    }
+    let estimated_size = estimate_size(&instructions, address_lookup_table_addresses.len());
+    if estimated_size > options.max_transaction_size {
+        return Err(ComposeError::TransactionTooLarge {
+            size: estimated_size,
+            limit: options.max_transaction_size,
+        });
+    }
+
+    Ok(ComposedSwap {
+        swap: SwapResponse {
+            instructions,
+            address_lookup_table_addresses,
+            compute_unit_limit,
+            compute_units_safe: sum_all(swaps.iter().map(|swap| swap.compute_units_safe)),
+            context_slot: swaps.iter().filter_map(|swap| swap.context_slot).min(),
+            expires_at_ms: swaps.iter().filter_map(|swap| swap.expires_at_ms).min(),
+            expires_after_slot: swaps
+                .iter()
+                .filter_map(|swap| swap.expires_after_slot)
+                .min(),
+        },
+        instruction_ranges,
+        account_count,
+        estimated_size,
+    })
+}
+
+fn check_writable_conflicts(
+    swaps: &[SwapResponse],
+    shared: &HashSet<Pubkey>,
+) -> Result<(), ComposeError> {
+    let mut writers: HashMap<Pubkey, usize> = HashMap::new();
+    for (index, swap) in swaps.iter().enumerate() {
+        let writable: BTreeSet<Pubkey> = swap
+            .instructions
+            .iter()
+            .flat_map(|ix| &ix.accounts)
+            .filter(|meta| meta.is_writable && !meta.is_signer && !shared.contains(&meta.pubkey))
+            .map(|meta| meta.pubkey)
+            .collect();
+        for account in writable {
+            if let Some(&first) = writers.get(&account) {
+                return Err(ComposeError::WritableConflict {
+                    account,
+                    first,
+                    second: index,
+                });
+            }
+            writers.insert(account, index);
+        }
+    }
+    Ok(())
+}
+
+/// `None` unless every value is known; saturates rather than wraps.
+fn sum_all(mut values: impl Iterator<Item = Option<u64>>) -> Option<u64> {
+    values.try_fold(0u64, |sum, value| Some(sum.saturating_add(value?)))
+}
+
+fn compute_unit_price(ix: &Instruction) -> Option<u64> {
+    match classify_instruction(ix) {
+        InstructionKind::SetComputeUnitPrice { micro_lamports } => Some(micro_lamports),
+        _ => None,
+    }
+}
+
+fn compact_len(len: usize) -> usize {
+    match len {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        _ => 3,
+    }
+}
+
+/// Signers and programs must be static keys; everything else is counted at
+/// one byte, as if found in a lookup table.
+fn estimate_size(instructions: &[Instruction], lookup_tables: usize) -> usize {
+    let signers: HashSet<&Pubkey> = instructions
+        .iter()
+        .flat_map(|ix| &ix.accounts)
+        .filter(|meta| meta.is_signer)
+        .map(|meta| &meta.pubkey)
+        .collect();
+    let mut static_keys = signers.clone();
+    static_keys.extend(instructions.iter().map(|ix| &ix.program_id));
+    let looked_up = instructions
+        .iter()
+        .flat_map(|ix| &ix.accounts)
+        .map(|meta| &meta.pubkey)
+        .filter(|pubkey| !static_keys.contains(pubkey))
+        .collect::<HashSet<_>>()
+        .len();
+
+    // Per table: address and two index-list lengths; then one index per
+    // looked-up account.
+    let lookups = compact_len(lookup_tables) + lookup_tables * (32 + 2) + looked_up;
+
+    signed_message_size(signers.len(), static_keys.len(), instructions) + lookups
+}
+
+/// [`estimate_size`] for a transaction without lookup tables, where every
+/// account is a static key.
+pub(crate) fn estimate_size_without_lookup_tables(instructions: &[Instruction]) -> usize {
+    let signers = instructions
+        .iter()
+        .flat_map(|ix| &ix.accounts)
+        .filter(|meta| meta.is_signer)
+        .map(|meta| &meta.pubkey)
+        .collect::<HashSet<_>>()
+        .len();
+    let keys = instructions
+        .iter()
+        .flat_map(|ix| {
+            std::iter::once(&ix.program_id).chain(ix.accounts.iter().map(|meta| &meta.pubkey))
+        })
+        .collect::<HashSet<_>>()
+        .len();
+
+    signed_message_size(signers, keys, instructions) + compact_len(0)
+}
+
+/// Everything but the lookup tables: signatures, preamble, static keys and
+/// instructions.
+fn signed_message_size(signers: usize, static_keys: usize, instructions: &[Instruction]) -> usize {
+    let signatures = compact_len(signers) + 64 * signers;
+    // Version prefix, header and recent blockhash.
+    let preamble = 1 + 3 + 32;
+    let keys = compact_len(static_keys) + 32 * static_keys;
+    let instructions_len = compact_len(instructions.len())
+        + instructions
+            .iter()
+            .map(|ix| {
+                1 + compact_len(ix.accounts.len())
+                    + ix.accounts.len()
+                    + compact_len(ix.data.len())
+                    + ix.data.len()
+            })
+            .sum::<usize>();
+
+    signatures + preamble + keys + instructions_len
+}
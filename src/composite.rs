@@ -0,0 +1,88 @@
+use crate::amount_math::{self, AmountOverflow};
+use crate::client::build_swap;
+use crate::quote::QuoteResponse;
+use crate::swap::SwapResponse;
+use crate::ClientError;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+
+/// Two quotes chained through an intermediate mint by
+/// [`crate::TitanClient::quote_via`]: `first` swaps the input into the
+/// intermediate, `second` swaps `first.min_out_amount()` of it into the
+/// output, so the second leg can't spend more than the first delivers.
+/// Whatever the first leg delivers above its minimum stays in the
+/// intermediate token account.
+#[derive(Debug, Clone)]
+pub struct CompositeQuote {
+    pub first: QuoteResponse,
+    pub second: QuoteResponse,
+}
+
+impl CompositeQuote {
+    pub fn via(&self) -> Pubkey {
+        self.first.output_mint
+    }
+
+    pub fn in_amount(&self) -> u64 {
+        self.first.input_amount()
+    }
+
+    pub fn out_amount(&self) -> u64 {
+        self.second.out_amount
+    }
+
+    /// The second leg's: it spends only what the first leg is sure to
+    /// deliver, so the first leg's slippage is already in `out_amount`.
+    pub fn slippage_bps(&self) -> u16 {
+        self.second.slippage_bps
+    }
+
+    /// The least the output can be with both legs at their worst.
+    pub fn min_out_amount(&self) -> u64 {
+        self.second.min_out_amount()
+    }
+
+    /// Route step and platform fees across both legs, summed per fee mint.
+    pub fn fees_by_mint(&self) -> Result<BTreeMap<Pubkey, u64>, AmountOverflow> {
+        let steps = self
+            .first
+            .route_plan
+            .iter()
+            .chain(&self.second.route_plan)
+            .filter_map(|step| Some((step.swap_info.fee_mint?, step.swap_info.fee_amount?)));
+        let platform = [&self.first, &self.second]
+            .into_iter()
+            .filter_map(|leg| Some((leg.platform_fee_mint()?, leg.platform_fee?.amount)));
+
+        let mut fees = BTreeMap::new();
+        for (mint, amount) in steps.chain(platform) {
+            if amount == 0 {
+                continue;
+            }
+            let total = fees.entry(mint).or_insert(0);
+            *total = amount_math::checked_add(*total, amount)?;
+        }
+        Ok(fees)
+    }
+
+    /// One swap per leg, in order, ready for [`crate::compose::compose_swaps`].
+    /// Both legs write the intermediate token account, so pass it in
+    /// `ComposeOptions::shared_writable`.
+    ///
+    /// Fails with `ClientError::UnchainedLegs` if the second leg spends
+    /// another mint than the first outputs, or more of it than the first's
+    /// `min_out_amount()`, as legs not made by `quote_via` may.
+    pub fn swap(&self) -> Result<[SwapResponse; 2], ClientError> {
+        let guaranteed = self.first.min_out_amount();
+        let spent = self.second.max_in_amount();
+        if self.second.input_mint != self.via() || spent > guaranteed {
+            return Err(ClientError::UnchainedLegs {
+                via: self.via(),
+                guaranteed,
+                spent_mint: self.second.input_mint,
+                spent,
+            });
+        }
+        Ok([build_swap(&self.first)?, build_swap(&self.second)?])
+    }
+}
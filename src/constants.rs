@@ -0,0 +1,65 @@
+//! Well-known mints and program ids.
+
+use solana_sdk::{pubkey, pubkey::Pubkey};
+
+/// Wrapped SOL. Native SOL is quoted and swapped through this mint.
+pub const WSOL_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+pub const SOL_MINT: Pubkey = WSOL_MINT;
+pub const USDC_MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+pub const USDT_MINT: Pubkey = pubkey!("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB");
+
+pub const SYSTEM_PROGRAM_ID: Pubkey = pubkey!("11111111111111111111111111111111");
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    pubkey!("ComputeBudget111111111111111111111111111111");
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+pub const TITAN_PROGRAM_ID: Pubkey = pubkey!("T1TANpTeScyeqVzzgNViGDNrkQ6qHz9KrSBS4aNXvGT");
+pub const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+
+/// Jito's tip payment accounts. A transfer to one of these is a bundle tip.
+pub const JITO_TIP_ACCOUNTS: &[Pubkey] = &[
+    pubkey!("96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"),
+    pubkey!("HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe"),
+    pubkey!("Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY"),
+    pubkey!("ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49"),
+    pubkey!("DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh"),
+    pubkey!("ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt"),
+    pubkey!("DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL"),
+    pubkey!("3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT"),
+];
+
+/// The base fee charged per transaction signature.
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+pub fn is_native_sol(mint: &Pubkey) -> bool {
+    *mint == WSOL_MINT
+}
+
+/// The token program that owns `mint`: `TOKEN_PROGRAM_ID` or
+/// `TOKEN_2022_PROGRAM_ID`. Fails with `InvalidResponse` if the account is
+/// owned by anything else.
+#[cfg(feature = "solana-client")]
+pub async fn token_program_for(
+    mint: &Pubkey,
+    rpc: &solana_client::nonblocking::rpc_client::RpcClient,
+) -> Result<Pubkey, crate::ClientError> {
+    if is_native_sol(mint) {
+        return Ok(TOKEN_PROGRAM_ID);
+    }
+
+    let account = rpc
+        .get_account(mint)
+        .await
+        .map_err(|err| crate::ClientError::Rpc(Box::new(err)))?;
+    match account.owner {
+        owner @ (TOKEN_PROGRAM_ID | TOKEN_2022_PROGRAM_ID) => Ok(owner),
+        owner => Err(crate::ClientError::InvalidResponse {
+            field: "mint owner",
+            value: owner.to_string(),
+        }),
+    }
+}
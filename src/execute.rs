@@ -0,0 +1,350 @@
+//! Signing and sending a swap over a pluggable RPC, with the compute unit
+//! limit chosen by a [`CuStrategy`].
+
+use crate::compose::MAX_COMPUTE_UNIT_LIMIT;
+#[cfg(feature = "journal")]
+use crate::journal::ExecutionStatus;
+use crate::quote::QuoteResponse;
+use crate::security::SecurityError;
+use crate::swap::SwapResponse;
+use crate::{ClientError, TitanClient};
+use solana_sdk::{
+    hash::Hash,
+    message::{v0, AddressLookupTableAccount, CompileError, VersionedMessage},
+    pubkey::Pubkey,
+    signature::{Signature, Signer, SignerError},
+    transaction::VersionedTransaction,
+};
+use std::collections::HashSet;
+use std::future::Future;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum RpcError {
+    /// The transaction's blockhash has expired, or the node hasn't seen it
+    /// yet. Worth rebuilding with a fresh one.
+    #[error("Blockhash not found")]
+    BlockhashNotFound,
+    #[error(transparent)]
+    Other(Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[derive(Debug, Error)]
+pub enum ExecuteError {
+    #[error("RPC request failed: {0}")]
+    Rpc(#[from] RpcError),
+    #[error("Failed to compile the swap transaction: {0}")]
+    Compile(#[from] CompileError),
+    #[error("Failed to sign the swap transaction: {0}")]
+    Sign(#[from] SignerError),
+    #[error("Swap failed the program check: {0}")]
+    Security(#[from] SecurityError),
+}
+
+/// What a simulation reported. `err` is set when the transaction would
+/// fail, in which case `units_consumed` isn't trusted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Simulation {
+    pub units_consumed: Option<u64>,
+    pub err: Option<String>,
+}
+
+/// The RPC calls [`TitanClient::execute_swap`] makes. Implemented for
+/// solana-client's nonblocking `RpcClient` with the `solana-client` feature.
+pub trait SwapRpc: Send + Sync {
+    fn latest_blockhash(&self) -> impl Future<Output = Result<Hash, RpcError>> + Send;
+
+    /// The tables at `addresses`, in the same order.
+    fn lookup_tables(
+        &self,
+        addresses: &[Pubkey],
+    ) -> impl Future<Output = Result<Vec<AddressLookupTableAccount>, RpcError>> + Send;
+
+    fn simulate(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> impl Future<Output = Result<Simulation, RpcError>> + Send;
+
+    fn send(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> impl Future<Output = Result<Signature, RpcError>> + Send;
+}
+
+/// Where the swap's compute unit limit comes from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CuStrategy {
+    /// The route's `computeUnits`.
+    #[default]
+    ServerReported,
+    /// The route's `computeUnitsSafe`, falling back to `computeUnits`.
+    ServerSafe,
+    /// The units a simulation consumed plus `margin_pct` percent. Falls back
+    /// to `ServerSafe` when the simulation fails.
+    Simulated { margin_pct: u16 },
+}
+
+/// Which source the limit a swap was sent with came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CuSource {
+    ServerReported,
+    ServerSafe,
+    Simulated,
+    /// The route reported no units; its instructions were left as they are.
+    Unset,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecuteOptions {
+    pub cu_strategy: CuStrategy,
+    /// How many times a send failing with `BlockhashNotFound` is re-signed
+    /// with a fresh blockhash and resent. None by default.
+    pub max_blockhash_rebuilds: u32,
+    /// When set, the swap is checked with
+    /// [`verify_programs`](SwapResponse::verify_programs) for the signer
+    /// before anything is signed, e.g. against
+    /// [`default_program_allowlist`](crate::security::default_program_allowlist).
+    pub program_allowlist: Option<HashSet<Pubkey>>,
+    /// The most the program check lets Jito tips add up to. Zero by default,
+    /// so a route that tips fails the check unless this is raised.
+    pub max_tip_lamports: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubmittedSwap {
+    pub signature: Signature,
+    pub compute_unit_limit: Option<u32>,
+    pub cu_source: CuSource,
+    /// The blockhash of each send, the last being the one that was accepted.
+    pub blockhashes: Vec<Hash>,
+}
+
+impl SubmittedSwap {
+    /// How many sends it took, rebuilds included.
+    pub fn attempts(&self) -> usize {
+        self.blockhashes.len()
+    }
+}
+
+impl TitanClient {
+    /// Builds the swap for `quote` as [`swap`](Self::swap) does, sets its
+    /// compute unit limit by `options.cu_strategy`, then signs it with
+    /// `signer` and sends it through `rpc`.
+    ///
+    /// A send failing with `BlockhashNotFound` is retried with a fresh
+    /// blockhash up to `options.max_blockhash_rebuilds` times. The quote's
+    /// expiry is checked before each send, the first included, failing with
+    /// `ClientError::QuoteExpired` rather than sending a stale route. Without
+    /// a configured execution budget only an expired quote fails.
+    ///
+    /// A successful send's signing and sending time feeds the estimated
+    /// execution budget. With the `journal` feature, the send is recorded
+    /// as `ExecutionStatus::Sent`, or as `Failed` when it finally fails.
+    pub async fn execute_swap<R, S>(
+        &self,
+        quote: &QuoteResponse,
+        rpc: &R,
+        signer: &S,
+        options: &ExecuteOptions,
+    ) -> Result<SubmittedSwap, ClientError>
+    where
+        R: SwapRpc,
+        S: Signer + Sync + ?Sized,
+    {
+        let mut swap = self.swap(quote)?;
+        if let Some(allowlist) = &options.program_allowlist {
+            swap.verify_programs(allowlist, signer.pubkey(), options.max_tip_lamports)
+                .map_err(ExecuteError::from)?;
+        }
+        let tables = rpc
+            .lookup_tables(&swap.lookup_table_addresses())
+            .await
+            .map_err(ExecuteError::from)?;
+        let mut blockhash = rpc.latest_blockhash().await.map_err(ExecuteError::from)?;
+
+        let (limit, cu_source) =
+            compute_unit_limit(&swap, options.cu_strategy, rpc, signer, &tables, blockhash).await?;
+        if let Some(limit) = limit {
+            swap.set_compute_unit_limit(limit);
+        }
+
+        let mut blockhashes = Vec::new();
+        loop {
+            // Sends happen after RPC round trips, so the quote may have
+            // expired since `swap` checked it. Checked even without a
+            // configured budget.
+            self.execution_budget()
+                .unwrap_or_default()
+                .check_at(quote, self.clock.now_ms())?;
+            blockhashes.push(blockhash);
+            let started = self.clock.instant();
+            let transaction = sign(&swap, signer, &tables, blockhash)?;
+            match rpc.send(&transaction).await {
+                Ok(signature) => {
+                    self.record_send_latency(self.clock.elapsed(started));
+                    #[cfg(feature = "journal")]
+                    self.record_execution(&signature, &ExecutionStatus::Sent);
+                    return Ok(SubmittedSwap {
+                        signature,
+                        compute_unit_limit: limit,
+                        cu_source,
+                        blockhashes,
+                    })
+                }
+                Err(RpcError::BlockhashNotFound)
+                    if blockhashes.len() <= options.max_blockhash_rebuilds as usize =>
+                {
+                    blockhash = rpc.latest_blockhash().await.map_err(ExecuteError::from)?;
+                }
+                Err(err) => {
+                    #[cfg(feature = "journal")]
+                    self.record_execution(
+                        &transaction.signatures[0],
+                        &ExecutionStatus::Failed {
+                            reason: err.to_string(),
+                        },
+                    );
+                    return Err(ExecuteError::from(err).into());
+                }
+            }
+        }
+    }
+}
+
+async fn compute_unit_limit<R, S>(
+    swap: &SwapResponse,
+    strategy: CuStrategy,
+    rpc: &R,
+    signer: &S,
+    tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> Result<(Option<u32>, CuSource), ExecuteError>
+where
+    R: SwapRpc,
+    S: Signer + Sync + ?Sized,
+{
+    let margin_pct = match strategy {
+        CuStrategy::ServerReported => return Ok(server_reported(swap)),
+        CuStrategy::ServerSafe => return Ok(server_safe(swap)),
+        CuStrategy::Simulated { margin_pct } => margin_pct,
+    };
+
+    // Simulated at the maximum limit, so the limit can't be what fails it.
+    let mut unbounded = swap.clone();
+    unbounded.set_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT);
+    let transaction = sign(&unbounded, signer, tables, blockhash)?;
+    match rpc.simulate(&transaction).await {
+        Ok(Simulation {
+            units_consumed: Some(units),
+            err: None,
+        }) => Ok((Some(with_margin(units, margin_pct)), CuSource::Simulated)),
+        _ => Ok(server_safe(swap)),
+    }
+}
+
+fn server_reported(swap: &SwapResponse) -> (Option<u32>, CuSource) {
+    match swap.compute_unit_limit {
+        Some(limit) => (Some(limit), CuSource::ServerReported),
+        None => (None, CuSource::Unset),
+    }
+}
+
+fn server_safe(swap: &SwapResponse) -> (Option<u32>, CuSource) {
+    match swap
+        .compute_units_safe
+        .and_then(|units| u32::try_from(units).ok())
+    {
+        Some(limit) => (
+            Some(limit.min(MAX_COMPUTE_UNIT_LIMIT)),
+            CuSource::ServerSafe,
+        ),
+        None => server_reported(swap),
+    }
+}
+
+/// `units` plus `margin_pct` percent, capped at the largest limit Solana
+/// allows.
+fn with_margin(units: u64, margin_pct: u16) -> u32 {
+    let limit = u128::from(units) * (100 + u128::from(margin_pct)) / 100;
+    u32::try_from(limit).map_or(MAX_COMPUTE_UNIT_LIMIT, |limit| {
+        limit.min(MAX_COMPUTE_UNIT_LIMIT)
+    })
+}
+
+fn sign<S: Signer + ?Sized>(
+    swap: &SwapResponse,
+    signer: &S,
+    tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> Result<VersionedTransaction, ExecuteError> {
+    let message =
+        v0::Message::try_compile(&signer.pubkey(), &swap.instructions, tables, blockhash)?;
+    Ok(VersionedTransaction::try_new(
+        VersionedMessage::V0(message),
+        &[signer],
+    )?)
+}
+
+#[cfg(feature = "solana-client")]
+impl SwapRpc for solana_client::nonblocking::rpc_client::RpcClient {
+    async fn latest_blockhash(&self) -> Result<Hash, RpcError> {
+        self.get_latest_blockhash().await.map_err(rpc_error)
+    }
+
+    async fn lookup_tables(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<AddressLookupTableAccount>, RpcError> {
+        use solana_sdk::address_lookup_table::state::AddressLookupTable;
+
+        if addresses.is_empty() {
+            return Ok(Vec::new());
+        }
+        let accounts = self
+            .get_multiple_accounts(addresses)
+            .await
+            .map_err(rpc_error)?;
+        addresses
+            .iter()
+            .zip(accounts)
+            .map(|(address, account)| {
+                let account = account.ok_or_else(|| {
+                    RpcError::Other(format!("Lookup table {} not found", address).into())
+                })?;
+                let table = AddressLookupTable::deserialize(&account.data)
+                    .map_err(|err| RpcError::Other(Box::new(err)))?;
+                Ok(AddressLookupTableAccount {
+                    key: *address,
+                    addresses: table.addresses.to_vec(),
+                })
+            })
+            .collect()
+    }
+
+    async fn simulate(&self, transaction: &VersionedTransaction) -> Result<Simulation, RpcError> {
+        let result = self
+            .simulate_transaction(transaction)
+            .await
+            .map_err(rpc_error)?
+            .value;
+        Ok(Simulation {
+            units_consumed: result.units_consumed,
+            err: result.err.map(|err| err.to_string()),
+        })
+    }
+
+    async fn send(&self, transaction: &VersionedTransaction) -> Result<Signature, RpcError> {
+        self.send_transaction(transaction).await.map_err(rpc_error)
+    }
+}
+
+#[cfg(feature = "solana-client")]
+fn rpc_error(err: solana_client::client_error::ClientError) -> RpcError {
+    use solana_sdk::transaction::TransactionError;
+
+    match err.get_transaction_error() {
+        Some(TransactionError::BlockhashNotFound) => RpcError::BlockhashNotFound,
+        _ => RpcError::Other(Box::new(err)),
+    }
+}
@@ -0,0 +1,149 @@
+//! How long executing a quote takes, for rejecting quotes that would expire
+//! before their transaction lands.
+
+use crate::clock::{Clock, SystemClock};
+use crate::quote::QuoteResponse;
+use crate::ClientError;
+#[cfg(feature = "native")]
+use std::collections::VecDeque;
+use std::fmt;
+#[cfg(feature = "native")]
+use std::sync::{Mutex, PoisonError};
+#[cfg(feature = "native")]
+use std::time::Duration;
+
+/// The time between deciding to execute a quote and its transaction
+/// landing. A quote with less validity left than `total_ms` is treated as
+/// expired.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecutionBudget {
+    /// Building the swap, including any `/swap/instructions` round trip.
+    pub build_ms: u64,
+    /// Signing and sending the transaction.
+    pub send_ms: u64,
+    pub safety_margin_ms: u64,
+}
+
+/// Why [`ExecutionBudget::check`] rejected a quote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpiryReason {
+    /// `expires_at_ms` has passed.
+    Expired,
+    /// The quote is still valid, but for less than the budget.
+    InsufficientMargin,
+}
+
+impl fmt::Display for ExpiryReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Expired => "has expired",
+            Self::InsufficientMargin => "expires before it can execute",
+        })
+    }
+}
+
+impl ExecutionBudget {
+    pub fn total_ms(&self) -> u64 {
+        self.build_ms
+            .saturating_add(self.send_ms)
+            .saturating_add(self.safety_margin_ms)
+    }
+
+    /// Like [`check_at`](Self::check_at), at the wall-clock time. This
+    /// ignores any clock a client was built with, so clients check with
+    /// `check_at` and their own clock.
+    pub fn check(&self, quote: &QuoteResponse) -> Result<(), ClientError> {
+        self.check_at(quote, SystemClock.now_ms())
+    }
+
+    /// Fails with `ClientError::QuoteExpired` when `quote` has less than
+    /// `total_ms` of validity left at `now_ms`, in Unix milliseconds. Quotes
+    /// without an expiry always pass.
+    pub fn check_at(&self, quote: &QuoteResponse, now_ms: u64) -> Result<(), ClientError> {
+        let Some(expires_at_ms) = quote.route_data().and_then(|route| route.expires_at_ms) else {
+            return Ok(());
+        };
+        let remaining_ms = expires_at_ms.saturating_sub(now_ms);
+        let reason = if remaining_ms == 0 {
+            ExpiryReason::Expired
+        } else if remaining_ms < self.total_ms() {
+            ExpiryReason::InsufficientMargin
+        } else {
+            return Ok(());
+        };
+        Err(ClientError::QuoteExpired {
+            reason,
+            remaining_ms,
+            budget_ms: self.total_ms(),
+        })
+    }
+}
+
+/// Where a client's execution budget comes from.
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetSource {
+    Fixed(ExecutionBudget),
+    /// The average of the client's recent build and send latencies, each
+    /// falling back to `initial` until it has been measured, plus
+    /// `initial.safety_margin_ms`.
+    Estimated {
+        initial: ExecutionBudget,
+    },
+}
+
+/// How many recent latencies the estimate averages.
+#[cfg(feature = "native")]
+const WINDOW: usize = 32;
+
+/// Recent build and send latencies, shared between clones of a client.
+#[cfg(feature = "native")]
+#[derive(Debug, Default)]
+pub(crate) struct LatencyTracker {
+    samples: Mutex<Samples>,
+}
+
+#[cfg(feature = "native")]
+#[derive(Debug, Default)]
+struct Samples {
+    build_ms: VecDeque<u64>,
+    send_ms: VecDeque<u64>,
+}
+
+#[cfg(feature = "native")]
+impl LatencyTracker {
+    pub(crate) fn record_build(&self, latency: Duration) {
+        self.record(latency, |samples| &mut samples.build_ms);
+    }
+
+    pub(crate) fn record_send(&self, latency: Duration) {
+        self.record(latency, |samples| &mut samples.send_ms);
+    }
+
+    fn record(&self, latency: Duration, window: fn(&mut Samples) -> &mut VecDeque<u64>) {
+        let mut samples = self.samples.lock().unwrap_or_else(PoisonError::into_inner);
+        let window = window(&mut samples);
+        if window.len() == WINDOW {
+            window.pop_front();
+        }
+        window.push_back(u64::try_from(latency.as_millis()).unwrap_or(u64::MAX));
+    }
+
+    pub(crate) fn budget(&self, source: &BudgetSource) -> ExecutionBudget {
+        let initial = match source {
+            BudgetSource::Fixed(budget) => return *budget,
+            BudgetSource::Estimated { initial } => initial,
+        };
+        let samples = self.samples.lock().unwrap_or_else(PoisonError::into_inner);
+        // Summed in u128, so the average of any samples fits a u64.
+        let average = |window: &VecDeque<u64>, initial: u64| match window.len() {
+            0 => initial,
+            len => (window.iter().map(|&ms| u128::from(ms)).sum::<u128>() / len as u128) as u64,
+        };
+        ExecutionBudget {
+            build_ms: average(&samples.build_ms, initial.build_ms),
+            send_ms: average(&samples.send_ms, initial.send_ms),
+            safety_margin_ms: initial.safety_margin_ms,
+        }
+    }
+}
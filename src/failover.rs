@@ -0,0 +1,141 @@
+use crate::clock::Clock;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailoverPolicy {
+    /// Stay on the current endpoint until it fails, then move to the next one in order.
+    #[default]
+    Sequential,
+    /// Stick with the fastest known endpoint, trying one of the others every
+    /// `reprobe_interval` to refresh its latency.
+    LatencySticky { reprobe_interval: Duration },
+}
+
+pub(crate) struct Endpoints {
+    urls: Vec<String>,
+    policy: FailoverPolicy,
+    cooldown: Duration,
+    state: Mutex<EndpointState>,
+    clock: Arc<dyn Clock>,
+}
+
+struct EndpointState {
+    current: usize,
+    failed_until: Vec<Option<Instant>>,
+    latencies: Vec<Option<Duration>>,
+    last_probe: Instant,
+    next_probe: usize,
+}
+
+impl Endpoints {
+    pub(crate) fn new(
+        urls: Vec<String>,
+        policy: FailoverPolicy,
+        cooldown: Option<Duration>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        assert!(!urls.is_empty(), "at least one base URL is required");
+        let len = urls.len();
+        Self {
+            urls,
+            policy,
+            cooldown: cooldown.unwrap_or(DEFAULT_COOLDOWN),
+            state: Mutex::new(EndpointState {
+                current: 0,
+                failed_until: vec![None; len],
+                latencies: vec![None; len],
+                last_probe: clock.instant(),
+                next_probe: 0,
+            }),
+            clock,
+        }
+    }
+
+    pub(crate) fn url(&self, index: usize) -> &str {
+        &self.urls[index]
+    }
+
+    pub(crate) fn urls(&self) -> &[String] {
+        &self.urls
+    }
+
+    pub(crate) fn current(&self) -> &str {
+        let current = self.state.lock().unwrap().current;
+        &self.urls[current]
+    }
+
+    /// Endpoints to try for the next request, best first. Endpoints still in
+    /// cooldown go last so a request is always attempted somewhere.
+    pub(crate) fn attempt_order(&self) -> Vec<usize> {
+        let mut state = self.state.lock().unwrap();
+        let now = self.clock.instant();
+        let len = self.urls.len();
+
+        let mut order: Vec<usize> = (0..len).map(|i| (state.current + i) % len).collect();
+
+        if let FailoverPolicy::LatencySticky { reprobe_interval } = self.policy {
+            if len > 1 && now.duration_since(state.last_probe) >= reprobe_interval {
+                state.last_probe = now;
+                state.next_probe = (state.next_probe + 1) % len;
+                if state.next_probe == state.current {
+                    state.next_probe = (state.next_probe + 1) % len;
+                }
+                let probe = state.next_probe;
+                order.retain(|&i| i != probe);
+                order.insert(0, probe);
+            }
+        }
+
+        let in_cooldown = |i: &usize| state.failed_until[*i].is_some_and(|until| until > now);
+        let (mut ready, cooling): (Vec<_>, Vec<_>) =
+            order.into_iter().partition(|i| !in_cooldown(i));
+        ready.extend(cooling);
+        ready
+    }
+
+    pub(crate) fn report_success(&self, index: usize, latency: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.failed_until[index] = None;
+        state.latencies[index] = Some(latency);
+
+        state.current = match self.policy {
+            FailoverPolicy::Sequential => index,
+            FailoverPolicy::LatencySticky { .. } => {
+                let now = self.clock.instant();
+                (0..self.urls.len())
+                    .filter(|&i| state.failed_until[i].is_none_or(|until| until <= now))
+                    .filter_map(|i| state.latencies[i].map(|latency| (latency, i)))
+                    .min()
+                    .map_or(index, |(_, i)| i)
+            }
+        };
+    }
+
+    /// Records a health probe. Unlike a served request, a successful probe
+    /// doesn't move a `Sequential` client onto the probed endpoint.
+    pub(crate) fn report_probe(&self, index: usize, latency: Option<Duration>) {
+        match (latency, self.policy) {
+            (None, _) => self.report_failure(index),
+            (Some(latency), FailoverPolicy::LatencySticky { .. }) => {
+                self.report_success(index, latency)
+            }
+            (Some(latency), FailoverPolicy::Sequential) => {
+                let mut state = self.state.lock().unwrap();
+                state.failed_until[index] = None;
+                state.latencies[index] = Some(latency);
+            }
+        }
+    }
+
+    pub(crate) fn report_failure(&self, index: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.failed_until[index] = Some(self.clock.instant() + self.cooldown);
+        state.latencies[index] = None;
+        if state.current == index {
+            state.current = (index + 1) % self.urls.len();
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::quote::{QuoteRequest, QuoteResponse};
+
+/// A loosening of a [`QuoteRequest`] tried by
+/// [`crate::TitanClient::quote_with_fallback`] when no route is found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuoteRelaxation {
+    /// Adds to the total account limit; no-op when it is unset.
+    IncreaseMaxAccounts(usize),
+    AllowIndirectRoutes,
+    RemoveDexExclusions,
+}
+
+impl QuoteRelaxation {
+    pub fn apply(&self, request: &mut QuoteRequest) {
+        match self {
+            Self::IncreaseMaxAccounts(extra) => {
+                if let Some(total) = request.account_limits.total.as_mut() {
+                    *total = total.saturating_add(*extra);
+                }
+            }
+            Self::AllowIndirectRoutes => request.only_direct_routes = Some(false),
+            Self::RemoveDexExclusions => request.excluded_dexes = None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FallbackQuote {
+    pub quote: QuoteResponse,
+    /// Relaxations in effect for `quote`, in the order applied. Empty when
+    /// the original request succeeded.
+    pub applied: Vec<QuoteRelaxation>,
+}
@@ -0,0 +1,205 @@
+use crate::quote::{
+    AccountMetaData, InstructionData, PlatformFeeData, QuoteKey, RoutePlanStepData, SwapMode,
+    SwapQuotes, SwapRoute,
+};
+use crate::serde_helpers::{bytes_as_base64, field_as_string, vec_field_as_string};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+
+/// Wire format requested for quote responses. Msgpack is smaller and faster
+/// to decode; JSON is easier to inspect and caches better in some proxies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ResponseFormat {
+    #[default]
+    Msgpack,
+    Json,
+}
+
+impl ResponseFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Msgpack => "application/vnd.msgpack",
+            Self::Json => "application/json",
+        }
+    }
+
+    /// The format a response was actually served in, if its `Content-Type`
+    /// says.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        if content_type.contains("json") {
+            Some(Self::Json)
+        } else if content_type.contains("msgpack") {
+            Some(Self::Msgpack)
+        } else {
+            None
+        }
+    }
+}
+
+// The JSON shape of `SwapQuotes`: pubkeys are base58 strings and byte
+// payloads base64, where msgpack carries raw bytes for both.
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct JsonSwapQuotes {
+    id: String,
+    #[serde(with = "field_as_string")]
+    input_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    output_mint: Pubkey,
+    swap_mode: SwapMode,
+    amount: u64,
+    quotes: BTreeMap<String, JsonSwapRoute>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonSwapRoute {
+    in_amount: u64,
+    out_amount: u64,
+    slippage_bps: u16,
+    #[serde(default)]
+    platform_fee: Option<PlatformFeeData>,
+    #[serde(default)]
+    referral_fee: Option<PlatformFeeData>,
+    steps: Vec<JsonRoutePlanStep>,
+    instructions: Vec<JsonInstruction>,
+    #[serde(with = "vec_field_as_string")]
+    address_lookup_tables: Vec<Pubkey>,
+    #[serde(default)]
+    context_slot: Option<u64>,
+    #[serde(default)]
+    time_taken_ns: Option<u64>,
+    #[serde(default)]
+    expires_at_ms: Option<u64>,
+    #[serde(default)]
+    expires_after_slot: Option<u64>,
+    #[serde(default)]
+    compute_units: Option<u64>,
+    #[serde(default)]
+    compute_units_safe: Option<u64>,
+    #[serde(with = "bytes_as_base64::option", default)]
+    transaction: Option<Vec<u8>>,
+    #[serde(default)]
+    reference_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonRoutePlanStep {
+    #[serde(with = "field_as_string")]
+    amm_key: Pubkey,
+    label: String,
+    #[serde(with = "field_as_string")]
+    input_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    output_mint: Pubkey,
+    in_amount: u64,
+    out_amount: u64,
+    alloc_ppb: u32,
+    #[serde(with = "field_as_string::option", default)]
+    fee_mint: Option<Pubkey>,
+    #[serde(default)]
+    fee_amount: Option<u64>,
+    #[serde(default)]
+    context_slot: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct JsonInstruction {
+    #[serde(with = "field_as_string")]
+    p: Pubkey,
+    a: Vec<JsonAccountMeta>,
+    #[serde(with = "bytes_as_base64")]
+    d: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct JsonAccountMeta {
+    #[serde(with = "field_as_string")]
+    p: Pubkey,
+    s: bool,
+    w: bool,
+}
+
+impl From<JsonSwapQuotes> for SwapQuotes {
+    fn from(quotes: JsonSwapQuotes) -> Self {
+        SwapQuotes {
+            id: quotes.id,
+            input_mint: quotes.input_mint.to_bytes(),
+            output_mint: quotes.output_mint.to_bytes(),
+            swap_mode: quotes.swap_mode,
+            amount: quotes.amount,
+            quotes: quotes
+                .quotes
+                .into_iter()
+                .map(|(key, route)| (QuoteKey::from(key), route.into()))
+                .collect(),
+        }
+    }
+}
+
+impl From<JsonSwapRoute> for SwapRoute {
+    fn from(route: JsonSwapRoute) -> Self {
+        SwapRoute {
+            in_amount: route.in_amount,
+            out_amount: route.out_amount,
+            slippage_bps: route.slippage_bps,
+            platform_fee: route.platform_fee,
+            referral_fee: route.referral_fee,
+            steps: route.steps.into_iter().map(Into::into).collect(),
+            instructions: route.instructions.into_iter().map(Into::into).collect(),
+            address_lookup_tables: route
+                .address_lookup_tables
+                .iter()
+                .map(|address| address.to_bytes())
+                .collect(),
+            context_slot: route.context_slot,
+            time_taken_ns: route.time_taken_ns,
+            expires_at_ms: route.expires_at_ms,
+            expires_after_slot: route.expires_after_slot,
+            compute_units: route.compute_units,
+            compute_units_safe: route.compute_units_safe,
+            transaction: route.transaction,
+            reference_id: route.reference_id,
+            warnings: Vec::new(),
+            price_only: false,
+        }
+    }
+}
+
+impl From<JsonRoutePlanStep> for RoutePlanStepData {
+    fn from(step: JsonRoutePlanStep) -> Self {
+        RoutePlanStepData {
+            amm_key: step.amm_key.to_bytes(),
+            label: step.label,
+            input_mint: step.input_mint.to_bytes(),
+            output_mint: step.output_mint.to_bytes(),
+            in_amount: step.in_amount,
+            out_amount: step.out_amount,
+            alloc_ppb: step.alloc_ppb,
+            fee_mint: step.fee_mint.map(|mint| mint.to_bytes()),
+            fee_amount: step.fee_amount,
+            context_slot: step.context_slot,
+        }
+    }
+}
+
+impl From<JsonInstruction> for InstructionData {
+    fn from(ix: JsonInstruction) -> Self {
+        InstructionData {
+            p: ix.p.to_bytes(),
+            a: ix
+                .a
+                .into_iter()
+                .map(|meta| AccountMetaData {
+                    p: meta.p.to_bytes(),
+                    s: meta.s,
+                    w: meta.w,
+                })
+                .collect(),
+            d: ix.d,
+        }
+    }
+}
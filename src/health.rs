@@ -0,0 +1,73 @@
+use crate::{ClientError, TitanClient};
+use std::time::Duration;
+
+const HEALTH_PATH: &str = "/health";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthState {
+    Healthy,
+    /// Reachable but not serving normally, e.g. a 5xx or 429.
+    Degraded(String),
+    /// The auth token was rejected.
+    Unauthorized,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthStatus {
+    pub state: HealthState,
+    pub latency: Duration,
+}
+
+impl HealthStatus {
+    pub fn is_healthy(&self) -> bool {
+        self.state == HealthState::Healthy
+    }
+}
+
+impl TitanClient {
+    /// Checks that the current endpoint is reachable and accepts the auth
+    /// token. Cheap enough for a periodic readiness probe; it bypasses the
+    /// rate limiter and circuit breaker. Transport failures are returned as
+    /// errors.
+    pub async fn health(&self) -> Result<HealthStatus, ClientError> {
+        self.health_of(self.endpoints.current()).await
+    }
+
+    /// Probes every configured endpoint and feeds the results to failover:
+    /// unhealthy endpoints are put in cooldown and latencies are refreshed.
+    pub async fn probe_endpoints(&self) -> Vec<(String, Result<HealthStatus, ClientError>)> {
+        let mut results = Vec::with_capacity(self.endpoints.urls().len());
+        for (index, url) in self.endpoints.urls().iter().enumerate() {
+            let result = self.health_of(url).await;
+            let latency = match &result {
+                Ok(status) if status.is_healthy() => Some(status.latency),
+                _ => None,
+            };
+            self.endpoints.report_probe(index, latency);
+            results.push((url.clone(), result));
+        }
+        results
+    }
+
+    async fn health_of(&self, base_path: &str) -> Result<HealthStatus, ClientError> {
+        let started = self.clock.instant();
+        let response = self
+            .send(self.client.get(self.endpoint_at(base_path, HEALTH_PATH)))
+            .await?;
+        let latency = self.clock.elapsed(started);
+
+        let status = response.status();
+        let state = if status.is_success() {
+            HealthState::Healthy
+        } else if status == reqwest::StatusCode::UNAUTHORIZED
+            || status == reqwest::StatusCode::FORBIDDEN
+        {
+            HealthState::Unauthorized
+        } else {
+            let body = response.text().await.unwrap_or_default();
+            HealthState::Degraded(format!("{}: {}", status, body.trim()))
+        };
+
+        Ok(HealthStatus { state, latency })
+    }
+}
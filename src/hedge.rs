@@ -0,0 +1,43 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+pub(crate) const DEFAULT_MAX_HEDGE_RATIO: f64 = 0.1;
+
+/// Hedging configuration plus a budget that caps hedges to a fraction of all
+/// requests, so sustained slowness can't double the load on the API.
+pub(crate) struct Hedging {
+    after: Duration,
+    max_ratio: f64,
+    requests: AtomicU64,
+    hedges: AtomicU64,
+}
+
+impl Hedging {
+    pub(crate) fn new(after: Duration, max_ratio: f64) -> Self {
+        Self {
+            after,
+            max_ratio: max_ratio.clamp(0.0, 1.0),
+            requests: AtomicU64::new(0),
+            hedges: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn after(&self) -> Duration {
+        self.after
+    }
+
+    pub(crate) fn record_request(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn try_acquire(&self) -> bool {
+        let requests = self.requests.load(Ordering::Relaxed) as f64;
+        // Always allow the first hedge so the budget isn't zero on a cold client.
+        let budget = (requests * self.max_ratio).max(1.0) as u64;
+        self.hedges
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |hedges| {
+                (hedges < budget).then_some(hedges + 1)
+            })
+            .is_ok()
+    }
+}
@@ -0,0 +1,341 @@
+//! Offline classification of swap instructions into the operations they
+//! perform, by program id and, for common programs, instruction
+//! discriminator.
+
+use crate::amount_math;
+use crate::constants::{
+    ASSOCIATED_TOKEN_PROGRAM_ID, COMPUTE_BUDGET_PROGRAM_ID, JITO_TIP_ACCOUNTS, JUPITER_PROGRAM_ID,
+    MEMO_PROGRAM_ID, SYSTEM_PROGRAM_ID, TITAN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+use crate::swap::SwapResponse;
+use crate::venues::Dex;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use std::fmt;
+
+// Anchor discriminators: the first 8 bytes of sha256("global:<name>").
+const ANCHOR_SWAP: [u8; 8] = [0xf8, 0xc6, 0x9e, 0x91, 0xe1, 0x75, 0x87, 0xc8];
+const ANCHOR_SWAP_V2: [u8; 8] = [0x2b, 0x04, 0xed, 0x0b, 0x1a, 0xc9, 0x1e, 0x62];
+const ANCHOR_SWAP2: [u8; 8] = [0x41, 0x4b, 0x3f, 0x4c, 0xeb, 0x5b, 0x5b, 0x88];
+const ANCHOR_SWAP_BASE_INPUT: [u8; 8] = [0x8f, 0xbe, 0x5a, 0xda, 0xc4, 0x1e, 0x33, 0xde];
+const ANCHOR_SWAP_BASE_OUTPUT: [u8; 8] = [0x37, 0xd9, 0x62, 0x56, 0xa3, 0x4a, 0xb4, 0xad];
+const ANCHOR_BUY: [u8; 8] = [0x66, 0x06, 0x3d, 0x12, 0x01, 0xda, 0xeb, 0xea];
+const ANCHOR_SELL: [u8; 8] = [0x33, 0xe6, 0x85, 0xa4, 0x01, 0x7f, 0x83, 0xad];
+
+// Jupiter v6 route instructions.
+const JUPITER_ROUTE: [u8; 8] = [0xe5, 0x17, 0xcb, 0x97, 0x7a, 0xe3, 0xad, 0x2a];
+const JUPITER_ROUTE_WITH_TOKEN_LEDGER: [u8; 8] = [0x96, 0x56, 0x47, 0x74, 0xa7, 0x5d, 0x0e, 0x68];
+const JUPITER_SHARED_ACCOUNTS_ROUTE: [u8; 8] = [0xc1, 0x20, 0x9b, 0x33, 0x41, 0xd6, 0x9c, 0x81];
+const JUPITER_SHARED_ACCOUNTS_ROUTE_WITH_TOKEN_LEDGER: [u8; 8] =
+    [0xe6, 0x79, 0x8f, 0x50, 0x77, 0x9f, 0x6a, 0xaa];
+const JUPITER_EXACT_OUT_ROUTE: [u8; 8] = [0xd0, 0x33, 0xef, 0x97, 0x7b, 0x2b, 0xed, 0x5c];
+const JUPITER_SHARED_ACCOUNTS_EXACT_OUT_ROUTE: [u8; 8] =
+    [0xb0, 0xd1, 0x69, 0xa8, 0x9a, 0x7d, 0x45, 0x3e];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Router {
+    Titan,
+    Jupiter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DexOperation {
+    Swap,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstructionKind {
+    SetComputeUnitLimit {
+        units: u32,
+    },
+    SetComputeUnitPrice {
+        micro_lamports: u64,
+    },
+    CreateAssociatedTokenAccount {
+        idempotent: bool,
+    },
+    Transfer {
+        lamports: u64,
+        to: Pubkey,
+    },
+    /// A transfer to one of `JITO_TIP_ACCOUNTS`.
+    Tip {
+        lamports: u64,
+        to: Pubkey,
+    },
+    TokenTransfer {
+        program_id: Pubkey,
+        amount: u64,
+    },
+    SyncNative {
+        program_id: Pubkey,
+    },
+    CloseAccount {
+        program_id: Pubkey,
+    },
+    Memo {
+        text: String,
+    },
+    Route {
+        router: Router,
+    },
+    Dex {
+        dex: Dex,
+        operation: DexOperation,
+    },
+    /// A known program whose instruction wasn't recognized.
+    Program {
+        name: &'static str,
+        program_id: Pubkey,
+    },
+    Unknown {
+        program_id: Pubkey,
+    },
+}
+
+impl fmt::Display for InstructionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SetComputeUnitLimit { units } => write!(f, "set compute unit limit {}", units),
+            Self::SetComputeUnitPrice { micro_lamports } => {
+                write!(
+                    f,
+                    "set compute unit price {} micro-lamports",
+                    micro_lamports
+                )
+            }
+            Self::CreateAssociatedTokenAccount { idempotent: false } => f.write_str("create ATA"),
+            Self::CreateAssociatedTokenAccount { idempotent: true } => {
+                f.write_str("create ATA (idempotent)")
+            }
+            Self::Transfer { lamports, to } => {
+                write!(f, "transfer {} lamports to {}", lamports, to)
+            }
+            Self::Tip { lamports, to } => write!(f, "transfer tip {} lamports to {}", lamports, to),
+            Self::TokenTransfer { amount, .. } => write!(f, "token transfer {}", amount),
+            Self::SyncNative { .. } => f.write_str("sync native SOL"),
+            Self::CloseAccount { .. } => f.write_str("close token account"),
+            Self::Memo { text } => write!(f, "memo {:?}", text),
+            Self::Route { router } => write!(f, "{:?} route", router),
+            Self::Dex {
+                dex,
+                operation: DexOperation::Swap,
+            } => write!(f, "{} swap", dex.name()),
+            Self::Dex {
+                dex,
+                operation: DexOperation::Other,
+            } => write!(f, "{} instruction", dex.name()),
+            Self::Program { name, .. } => write!(f, "{} instruction", name),
+            Self::Unknown { program_id } => write!(f, "unknown program {}", program_id),
+        }
+    }
+}
+
+pub fn classify_instructions(swap: &SwapResponse) -> Vec<InstructionKind> {
+    swap.instructions.iter().map(classify_instruction).collect()
+}
+
+pub fn classify_instruction(ix: &Instruction) -> InstructionKind {
+    let program_id = ix.program_id;
+    let data = ix.data.as_slice();
+
+    if program_id == COMPUTE_BUDGET_PROGRAM_ID {
+        match data {
+            [2, rest @ ..] if rest.len() >= 4 => {
+                return InstructionKind::SetComputeUnitLimit {
+                    units: u32::from_le_bytes(rest[..4].try_into().unwrap()),
+                }
+            }
+            [3, rest @ ..] if rest.len() >= 8 => {
+                return InstructionKind::SetComputeUnitPrice {
+                    micro_lamports: u64::from_le_bytes(rest[..8].try_into().unwrap()),
+                }
+            }
+            _ => {}
+        }
+    } else if program_id == SYSTEM_PROGRAM_ID {
+        // Transfer is tag 2 as a little-endian u32, with the recipient second.
+        if let ([2, 0, 0, 0, rest @ ..], Some(to)) = (data, ix.accounts.get(1)) {
+            if rest.len() >= 8 {
+                let lamports = u64::from_le_bytes(rest[..8].try_into().unwrap());
+                let to = to.pubkey;
+                return if JITO_TIP_ACCOUNTS.contains(&to) {
+                    InstructionKind::Tip { lamports, to }
+                } else {
+                    InstructionKind::Transfer { lamports, to }
+                };
+            }
+        }
+    } else if program_id == ASSOCIATED_TOKEN_PROGRAM_ID {
+        match data {
+            [] | [0] => return InstructionKind::CreateAssociatedTokenAccount { idempotent: false },
+            [1] => return InstructionKind::CreateAssociatedTokenAccount { idempotent: true },
+            _ => {}
+        }
+    } else if program_id == TOKEN_PROGRAM_ID || program_id == TOKEN_2022_PROGRAM_ID {
+        match data {
+            // Transfer and TransferChecked both lead with the amount.
+            [3 | 12, rest @ ..] if rest.len() >= 8 => {
+                return InstructionKind::TokenTransfer {
+                    program_id,
+                    amount: u64::from_le_bytes(rest[..8].try_into().unwrap()),
+                }
+            }
+            [9] => return InstructionKind::CloseAccount { program_id },
+            [17] => return InstructionKind::SyncNative { program_id },
+            _ => {}
+        }
+    } else if program_id == MEMO_PROGRAM_ID {
+        return InstructionKind::Memo {
+            text: String::from_utf8_lossy(data).into_owned(),
+        };
+    } else if program_id == TITAN_PROGRAM_ID {
+        return InstructionKind::Route {
+            router: Router::Titan,
+        };
+    } else if program_id == JUPITER_PROGRAM_ID {
+        return InstructionKind::Route {
+            router: Router::Jupiter,
+        };
+    } else if let Some(dex) = Dex::from_program_id(&program_id) {
+        let operation = if is_swap(dex, data) {
+            DexOperation::Swap
+        } else {
+            DexOperation::Other
+        };
+        return InstructionKind::Dex { dex, operation };
+    }
+
+    match program_name(&program_id) {
+        Some(name) => InstructionKind::Program { name, program_id },
+        None => InstructionKind::Unknown { program_id },
+    }
+}
+
+fn is_swap(dex: Dex, data: &[u8]) -> bool {
+    let discriminator = data.get(..8);
+    let anchor = |names: &[[u8; 8]]| discriminator.is_some_and(|d| names.iter().any(|n| d == n));
+    match dex {
+        // Raydium AMM v4's swap_base_in and swap_base_out.
+        Dex::Raydium => matches!(data.first(), Some(9 | 11)),
+        Dex::Phoenix => data.first() == Some(&0),
+        Dex::Whirlpool | Dex::RaydiumClmm => anchor(&[ANCHOR_SWAP, ANCHOR_SWAP_V2]),
+        Dex::MeteoraDlmm => anchor(&[ANCHOR_SWAP, ANCHOR_SWAP2]),
+        Dex::MeteoraDamm | Dex::Lifinity => anchor(&[ANCHOR_SWAP]),
+        Dex::RaydiumCpmm => anchor(&[ANCHOR_SWAP_BASE_INPUT, ANCHOR_SWAP_BASE_OUTPUT]),
+        Dex::PumpSwap => anchor(&[ANCHOR_BUY, ANCHOR_SELL]),
+        Dex::OpenBookV2 => false,
+    }
+}
+
+fn program_name(program_id: &Pubkey) -> Option<&'static str> {
+    [
+        (COMPUTE_BUDGET_PROGRAM_ID, "Compute Budget"),
+        (SYSTEM_PROGRAM_ID, "System Program"),
+        (ASSOCIATED_TOKEN_PROGRAM_ID, "Associated Token Account"),
+        (TOKEN_PROGRAM_ID, "Token Program"),
+        (TOKEN_2022_PROGRAM_ID, "Token-2022"),
+    ]
+    .into_iter()
+    .find(|(id, _)| id == program_id)
+    .map(|(_, name)| name)
+}
+
+/// Whether the swap's final output is checked on-chain: the last swapping
+/// instruction, a router's or a DEX's, either enforces a non-zero minimum
+/// output or fixes the output amount exactly. A sandwiched intermediate hop
+/// then fails the whole transaction rather than passing on a worse price.
+///
+/// `None` when there is no swapping instruction or its parameter layout
+/// isn't known, which includes Titan's router.
+pub fn onchain_min_out(instructions: &[Instruction]) -> Option<bool> {
+    let last_swap = instructions.iter().rev().find(|ix| {
+        matches!(
+            classify_instruction(ix),
+            InstructionKind::Route { .. }
+                | InstructionKind::Dex {
+                    operation: DexOperation::Swap,
+                    ..
+                }
+        )
+    })?;
+    match output_limit(last_swap)? {
+        OutputLimit::MinOut(min_out) => Some(min_out > 0),
+        OutputLimit::Exact => Some(true),
+    }
+}
+
+enum OutputLimit {
+    MinOut(u64),
+    /// The instruction names the output amount; its threshold bounds the
+    /// input instead.
+    Exact,
+}
+
+fn output_limit(ix: &Instruction) -> Option<OutputLimit> {
+    let data = ix.data.as_slice();
+    if ix.program_id == JUPITER_PROGRAM_ID {
+        return jupiter_output_limit(data);
+    }
+
+    let u64_at = |offset: usize| {
+        data.get(offset..offset + 8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+    };
+    // The threshold second, after the amount, and whether that amount is
+    // the input after a u128 price limit.
+    let threshold_or_exact = |is_input_at: usize| match data.get(is_input_at)? {
+        0 => Some(OutputLimit::Exact),
+        _ => u64_at(16).map(OutputLimit::MinOut),
+    };
+    let discriminator = data.get(..8);
+    match Dex::from_program_id(&ix.program_id)? {
+        Dex::Raydium => match data.first()? {
+            9 => u64_at(9).map(OutputLimit::MinOut),
+            11 => Some(OutputLimit::Exact),
+            _ => None,
+        },
+        Dex::Whirlpool | Dex::RaydiumClmm => threshold_or_exact(40),
+        Dex::RaydiumCpmm if discriminator == Some(&ANCHOR_SWAP_BASE_OUTPUT) => {
+            Some(OutputLimit::Exact)
+        }
+        Dex::PumpSwap if discriminator == Some(&ANCHOR_BUY) => Some(OutputLimit::Exact),
+        Dex::RaydiumCpmm | Dex::PumpSwap | Dex::MeteoraDlmm | Dex::MeteoraDamm | Dex::Lifinity => {
+            u64_at(16).map(OutputLimit::MinOut)
+        }
+        Dex::Phoenix | Dex::OpenBookV2 => None,
+    }
+}
+
+// Every route instruction ends with the quoted amount, `slippage_bps: u16`
+// and `platform_fee_bps: u8`, after a route plan of variable length. The
+// minimum out the program enforces is the quoted amount less slippage.
+fn jupiter_output_limit(data: &[u8]) -> Option<OutputLimit> {
+    let discriminator: [u8; 8] = data.get(..8)?.try_into().unwrap();
+    if [
+        JUPITER_EXACT_OUT_ROUTE,
+        JUPITER_SHARED_ACCOUNTS_EXACT_OUT_ROUTE,
+    ]
+    .contains(&discriminator)
+    {
+        return Some(OutputLimit::Exact);
+    }
+    if ![
+        JUPITER_ROUTE,
+        JUPITER_ROUTE_WITH_TOKEN_LEDGER,
+        JUPITER_SHARED_ACCOUNTS_ROUTE,
+        JUPITER_SHARED_ACCOUNTS_ROUTE_WITH_TOKEN_LEDGER,
+    ]
+    .contains(&discriminator)
+    {
+        return None;
+    }
+
+    let tail = data.len().checked_sub(11).filter(|&tail| tail >= 8)?;
+    let quoted_out_amount = u64::from_le_bytes(data[tail..tail + 8].try_into().unwrap());
+    let slippage_bps = u16::from_le_bytes(data[tail + 8..tail + 10].try_into().unwrap());
+    Some(OutputLimit::MinOut(amount_math::min_out_after_slippage(
+        quoted_out_amount,
+        slippage_bps,
+    )))
+}
@@ -0,0 +1,219 @@
+//! A record of every quote and swap a client produced, for retention and
+//! replay. Attach one with `TitanClientBuilder::journal`.
+
+use crate::clock::{Clock, SystemClock};
+use crate::quote::{QuoteRequest, QuoteResponse};
+use crate::serde_helpers::field_as_string;
+use crate::swap::SwapResponse;
+use crate::ClientError;
+use serde::{Deserialize, Serialize};
+use solana_sdk::signature::Signature;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{mpsc, Arc};
+use std::thread::JoinHandle;
+
+/// Buffer size of [`JsonlFileJournal::open`].
+pub const DEFAULT_JOURNAL_BUFFER_BYTES: usize = 64 * 1024;
+
+/// Receives every quote and swap result of the client it is attached to.
+///
+/// Calls are made inline on the quote path, so implementations must hand
+/// records off rather than write them synchronously.
+pub trait QuoteJournal: Send + Sync + 'static {
+    fn record_quote(&self, request: &QuoteRequest, result: Result<&QuoteResponse, &ClientError>);
+
+    fn record_swap(&self, quote: &QuoteResponse, result: Result<&SwapResponse, &ClientError>);
+
+    /// Called by `TitanClient::execute_swap` when its send succeeds or
+    /// finally fails, and through `TitanClient::record_execution` for
+    /// outcomes learned later.
+    fn record_execution(&self, signature: &Signature, status: &ExecutionStatus);
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum ExecutionStatus {
+    /// Accepted by the RPC node; whether it landed is not yet known.
+    Sent,
+    Landed,
+    Failed { reason: String },
+}
+
+/// One line of a [`JsonlFileJournal`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum JournalRecord {
+    #[serde(rename_all = "camelCase")]
+    Quote {
+        timestamp_ms: u64,
+        request: QuoteRequest,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        quote: Option<QuoteResponse>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Swap {
+        timestamp_ms: u64,
+        quote: QuoteResponse,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        swap: Option<SwapResponse>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Execution {
+        timestamp_ms: u64,
+        #[serde(with = "field_as_string")]
+        signature: Signature,
+        #[serde(flatten)]
+        status: ExecutionStatus,
+    },
+}
+
+impl JournalRecord {
+    pub fn timestamp_ms(&self) -> u64 {
+        match self {
+            Self::Quote { timestamp_ms, .. }
+            | Self::Swap { timestamp_ms, .. }
+            | Self::Execution { timestamp_ms, .. } => *timestamp_ms,
+        }
+    }
+}
+
+/// Appends records to a file as newline-delimited JSON.
+///
+/// Records are queued to a writer thread, which flushes whenever the queue
+/// drains and whenever its buffer fills. Dropping the journal writes out
+/// everything queued before returning. After a crash the file holds every
+/// record flushed so far and possibly a truncated last line, which
+/// [`read_journal`] skips.
+pub struct JsonlFileJournal {
+    sender: Option<mpsc::Sender<JournalRecord>>,
+    writer: Option<JoinHandle<io::Result<()>>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl JsonlFileJournal {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::with_buffer_size(path, DEFAULT_JOURNAL_BUFFER_BYTES)
+    }
+
+    pub fn with_buffer_size(path: impl AsRef<Path>, buffer_bytes: usize) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let (sender, receiver) = mpsc::channel();
+        let writer = std::thread::Builder::new()
+            .name("titan-journal".to_string())
+            .spawn(move || write_records(BufWriter::with_capacity(buffer_bytes, file), receiver))?;
+
+        Ok(Self {
+            sender: Some(sender),
+            writer: Some(writer),
+            clock: Arc::new(SystemClock),
+        })
+    }
+
+    /// Timestamps records with `clock` instead of the system clock.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Queues `record` without waiting for it to be written.
+    pub fn append(&self, record: JournalRecord) {
+        if let Some(sender) = &self.sender {
+            // The writer only stops early on an I/O error, which `close`
+            // reports.
+            let _ = sender.send(record);
+        }
+    }
+
+    /// Writes out everything queued, returning the first write error if
+    /// there was one. Dropping the journal does the same but discards the
+    /// error.
+    pub fn close(mut self) -> io::Result<()> {
+        self.finish()
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        // Closing the channel lets the writer drain and flush it.
+        self.sender.take();
+        match self.writer.take() {
+            Some(writer) => writer
+                .join()
+                .unwrap_or_else(|_| Err(io::Error::other("journal writer panicked"))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for JsonlFileJournal {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+impl QuoteJournal for JsonlFileJournal {
+    fn record_quote(&self, request: &QuoteRequest, result: Result<&QuoteResponse, &ClientError>) {
+        self.append(JournalRecord::Quote {
+            timestamp_ms: self.clock.now_ms(),
+            request: request.clone(),
+            quote: result.ok().cloned(),
+            error: result.err().map(ToString::to_string),
+        });
+    }
+
+    fn record_swap(&self, quote: &QuoteResponse, result: Result<&SwapResponse, &ClientError>) {
+        self.append(JournalRecord::Swap {
+            timestamp_ms: self.clock.now_ms(),
+            quote: quote.clone(),
+            swap: result.ok().cloned(),
+            error: result.err().map(ToString::to_string),
+        });
+    }
+
+    fn record_execution(&self, signature: &Signature, status: &ExecutionStatus) {
+        self.append(JournalRecord::Execution {
+            timestamp_ms: self.clock.now_ms(),
+            signature: *signature,
+            status: status.clone(),
+        });
+    }
+}
+
+fn write_records(
+    mut file: BufWriter<File>,
+    receiver: mpsc::Receiver<JournalRecord>,
+) -> io::Result<()> {
+    while let Ok(record) = receiver.recv() {
+        write_record(&mut file, &record)?;
+        while let Ok(record) = receiver.try_recv() {
+            write_record(&mut file, &record)?;
+        }
+        file.flush()?;
+    }
+    Ok(())
+}
+
+fn write_record(file: &mut BufWriter<File>, record: &JournalRecord) -> io::Result<()> {
+    serde_json::to_writer(&mut *file, record)?;
+    file.write_all(b"\n")
+}
+
+/// Reads back a journal written by [`JsonlFileJournal`], skipping a last
+/// line left incomplete by a crash.
+pub fn read_journal(path: impl AsRef<Path>) -> io::Result<Vec<JournalRecord>> {
+    let mut records = Vec::new();
+    let mut lines = BufReader::new(File::open(path)?).lines().peekable();
+    while let Some(line) = lines.next() {
+        let line = line?;
+        match serde_json::from_str(&line) {
+            Ok(record) => records.push(record),
+            Err(_) if lines.peek().is_none() => break,
+            Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+        }
+    }
+    Ok(records)
+}
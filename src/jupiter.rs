@@ -0,0 +1,211 @@
+//! Jupiter `/quote` schema compatibility, for tooling that already speaks it.
+
+use crate::amount_math;
+use crate::pubkey::Pubkey;
+use crate::quote::{
+    PlatformFee, PlatformFeeData, QuoteResponse, RoutePlanStep, RoutePlanStepData, SwapMode,
+    SwapRoute,
+};
+use crate::serde_helpers::field_as_string;
+use serde::{Deserialize, Serialize};
+
+/// Mirrors `jupiter_swap_api_client::quote::QuoteResponse` field for field.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterQuote {
+    #[serde(with = "field_as_string")]
+    pub input_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub in_amount: u64,
+    #[serde(with = "field_as_string")]
+    pub output_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub out_amount: u64,
+    #[serde(with = "field_as_string")]
+    pub other_amount_threshold: u64,
+    pub swap_mode: SwapMode,
+    pub slippage_bps: u16,
+    pub platform_fee: Option<JupiterPlatformFee>,
+    pub price_impact_pct: String,
+    pub route_plan: Vec<JupiterRoutePlanStep>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_slot: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_taken: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterPlatformFee {
+    #[serde(with = "field_as_string")]
+    pub amount: u64,
+    pub fee_bps: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterRoutePlanStep {
+    pub swap_info: JupiterSwapInfo,
+    pub percent: u8,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterSwapInfo {
+    #[serde(with = "field_as_string")]
+    pub amm_key: Pubkey,
+    pub label: String,
+    #[serde(with = "field_as_string")]
+    pub input_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub output_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub in_amount: u64,
+    #[serde(with = "field_as_string")]
+    pub out_amount: u64,
+    #[serde(with = "field_as_string")]
+    pub fee_amount: u64,
+    #[serde(with = "field_as_string")]
+    pub fee_mint: Pubkey,
+}
+
+impl JupiterQuote {
+    /// A Titan-shaped route carrying this quote's amounts and steps, for use
+    /// with [`QuoteResponse::from_route`]. It has no instructions: Jupiter
+    /// builds those in a separate `/swap-instructions` call.
+    pub fn into_swap_route(self) -> SwapRoute {
+        SwapRoute {
+            in_amount: self.in_amount,
+            out_amount: self.out_amount,
+            slippage_bps: self.slippage_bps,
+            platform_fee: self.platform_fee.map(|fee| PlatformFeeData {
+                amount: fee.amount,
+                fee_bps: fee.fee_bps,
+            }),
+            referral_fee: None,
+            steps: self
+                .route_plan
+                .into_iter()
+                .map(|step| RoutePlanStepData {
+                    amm_key: step.swap_info.amm_key.to_bytes(),
+                    label: step.swap_info.label,
+                    input_mint: step.swap_info.input_mint.to_bytes(),
+                    output_mint: step.swap_info.output_mint.to_bytes(),
+                    in_amount: step.swap_info.in_amount,
+                    out_amount: step.swap_info.out_amount,
+                    alloc_ppb: amount_math::ppb_from_percent(step.percent),
+                    fee_mint: Some(step.swap_info.fee_mint.to_bytes()),
+                    fee_amount: Some(step.swap_info.fee_amount),
+                    context_slot: self.context_slot,
+                })
+                .collect(),
+            instructions: Vec::new(),
+            address_lookup_tables: Vec::new(),
+            context_slot: self.context_slot,
+            time_taken_ns: self.time_taken.and_then(amount_math::secs_to_ns),
+            expires_at_ms: None,
+            expires_after_slot: None,
+            compute_units: None,
+            compute_units_safe: None,
+            transaction: None,
+            reference_id: None,
+            warnings: Vec::new(),
+            price_only: false,
+        }
+    }
+}
+
+impl QuoteResponse {
+    pub fn to_jupiter(&self) -> JupiterQuote {
+        JupiterQuote::from(self)
+    }
+
+    pub fn to_jupiter_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(&self.to_jupiter())
+    }
+}
+
+impl From<QuoteResponse> for JupiterQuote {
+    fn from(quote: QuoteResponse) -> Self {
+        JupiterQuote::from(&quote)
+    }
+}
+
+impl From<&QuoteResponse> for JupiterQuote {
+    #[allow(deprecated)]
+    fn from(quote: &QuoteResponse) -> Self {
+        // `in_amount` echoes the requested amount, which is the output side
+        // for ExactOut; the route carries the actual input.
+        let in_amount = match quote.swap_mode {
+            SwapMode::ExactOut => quote.input_amount(),
+            SwapMode::ExactIn | SwapMode::Other(_) => quote.in_amount,
+        };
+
+        JupiterQuote {
+            input_mint: quote.input_mint,
+            in_amount,
+            output_mint: quote.output_mint,
+            out_amount: quote.out_amount,
+            other_amount_threshold: match quote.swap_mode {
+                SwapMode::ExactOut => quote.max_in_amount(),
+                SwapMode::ExactIn | SwapMode::Other(_) => quote.min_out_amount(),
+            },
+            swap_mode: quote.swap_mode.clone(),
+            slippage_bps: quote.slippage_bps,
+            platform_fee: quote.platform_fee.as_ref().map(JupiterPlatformFee::from),
+            // Titan doesn't report price impact.
+            price_impact_pct: "0".to_string(),
+            route_plan: quote
+                .route_plan
+                .iter()
+                .map(JupiterRoutePlanStep::from)
+                .collect(),
+            context_slot: quote.context_slot,
+            time_taken: quote
+                .timing
+                .and_then(|timing| timing.server_ns)
+                .map(amount_math::ns_to_secs)
+                .or(quote.time_taken),
+        }
+    }
+}
+
+impl From<&PlatformFee> for JupiterPlatformFee {
+    fn from(fee: &PlatformFee) -> Self {
+        JupiterPlatformFee {
+            amount: fee.amount,
+            fee_bps: fee.fee_bps,
+        }
+    }
+}
+
+impl From<&RoutePlanStep> for JupiterRoutePlanStep {
+    fn from(step: &RoutePlanStep) -> Self {
+        let info = &step.swap_info;
+        JupiterRoutePlanStep {
+            swap_info: JupiterSwapInfo {
+                amm_key: info.amm_key,
+                label: info.label.clone(),
+                input_mint: info.input_mint,
+                output_mint: info.output_mint,
+                in_amount: info.in_amount,
+                out_amount: info.out_amount,
+                // Jupiter's format requires both; a step without a
+                // reported fee is sent as a zero fee.
+                fee_amount: info.fee_amount.unwrap_or(0),
+                fee_mint: info.fee_mint.unwrap_or_default(),
+            },
+            percent: percent_from_alloc_ppb(info.alloc_ppb),
+        }
+    }
+}
+
+/// Jupiter's `percent` is the share of the input routed through a step,
+/// which Titan reports in parts per billion. A missing allocation (older
+/// serialized quotes) is treated as the whole amount.
+fn percent_from_alloc_ppb(alloc_ppb: u64) -> u8 {
+    if alloc_ppb == 0 {
+        return 100;
+    }
+    amount_math::percent_from_ppb(alloc_ppb)
+}
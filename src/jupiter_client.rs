@@ -0,0 +1,202 @@
+//! An [`AggregatorClient`] over Jupiter's public swap API, so flow can be
+//! A/B routed between aggregators without a second code path.
+
+use crate::aggregator::{AggregatorClient, BoxFuture};
+use crate::jupiter::JupiterQuote;
+use crate::quote::{QuoteRequest, QuoteResponse};
+use crate::serde_helpers::{field_as_string, instructions::InstructionRepr};
+use crate::swap::SwapResponse;
+use crate::ClientError;
+use crate::{content_type_of, error_from_response, DEFAULT_ERROR_BODY_LIMIT};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+const JUPITER_API_URL: &str = "https://lite-api.jup.ag/swap/v1";
+
+#[derive(Clone)]
+pub struct JupiterClient {
+    client: reqwest::Client,
+    base_path: String,
+    api_key: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SwapInstructionsRequest<'a> {
+    quote_response: &'a JupiterQuote,
+    #[serde(with = "field_as_string")]
+    user_public_key: Pubkey,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SwapInstructionsResponse {
+    #[serde(default)]
+    token_ledger_instruction: Option<InstructionRepr>,
+    #[serde(default)]
+    setup_instructions: Vec<InstructionRepr>,
+    swap_instruction: InstructionRepr,
+    #[serde(default)]
+    cleanup_instruction: Option<InstructionRepr>,
+    #[serde(default)]
+    other_instructions: Vec<InstructionRepr>,
+    #[serde(default)]
+    address_lookup_table_addresses: Vec<String>,
+    #[serde(default)]
+    compute_unit_limit: Option<u32>,
+}
+
+impl JupiterClient {
+    pub fn new(api_key: Option<String>, base_path: Option<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_path: base_path.unwrap_or_else(|| JUPITER_API_URL.to_string()),
+            api_key,
+        }
+    }
+
+    pub async fn quote(&self, request: &QuoteRequest) -> Result<QuoteResponse, ClientError> {
+        let response = self
+            .with_api_key(self.client.get(format!("{}/quote", self.base_path)))
+            .query(&build_query_params(request))
+            .send()
+            .await?;
+        let quote: JupiterQuote = parse_json(response).await?;
+
+        Ok(QuoteResponse::from_route(request, quote.into_swap_route()))
+    }
+
+    pub async fn swap_instructions(
+        &self,
+        quote: &QuoteResponse,
+        user_pubkey: &Pubkey,
+    ) -> Result<SwapResponse, ClientError> {
+        let body = serde_json::to_vec(&SwapInstructionsRequest {
+            quote_response: &quote.to_jupiter(),
+            user_public_key: *user_pubkey,
+        })
+        .map_err(|err| ClientError::InvalidResponse {
+            field: "quoteResponse",
+            value: err.to_string(),
+        })?;
+
+        let response = self
+            .with_api_key(
+                self.client
+                    .post(format!("{}/swap-instructions", self.base_path)),
+            )
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await?;
+        let response: SwapInstructionsResponse = parse_json(response).await?;
+
+        // Compute budget instructions are left out to match Titan, which
+        // reports the limit separately.
+        let instructions = response
+            .token_ledger_instruction
+            .into_iter()
+            .chain(response.setup_instructions)
+            .chain(Some(response.swap_instruction))
+            .chain(response.cleanup_instruction)
+            .chain(response.other_instructions)
+            .map(Instruction::from)
+            .collect();
+
+        let address_lookup_table_addresses = response
+            .address_lookup_table_addresses
+            .iter()
+            .map(|address| {
+                address.parse().map_err(|_| ClientError::InvalidResponse {
+                    field: "addressLookupTableAddresses",
+                    value: address.clone(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(SwapResponse {
+            instructions,
+            address_lookup_table_addresses,
+            compute_unit_limit: response.compute_unit_limit,
+            compute_units_safe: None,
+            context_slot: quote.context_slot,
+            expires_at_ms: None,
+            expires_after_slot: None,
+        })
+    }
+
+    fn with_api_key(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.api_key {
+            Some(api_key) => builder.header("x-api-key", api_key),
+            None => builder,
+        }
+    }
+}
+
+impl AggregatorClient for JupiterClient {
+    fn name(&self) -> &str {
+        "Jupiter"
+    }
+
+    fn quote<'a>(
+        &'a self,
+        request: &'a QuoteRequest,
+    ) -> BoxFuture<'a, Result<QuoteResponse, ClientError>> {
+        Box::pin(JupiterClient::quote(self, request))
+    }
+
+    fn swap_instructions<'a>(
+        &'a self,
+        quote: &'a QuoteResponse,
+        user_pubkey: &'a Pubkey,
+    ) -> BoxFuture<'a, Result<SwapResponse, ClientError>> {
+        Box::pin(JupiterClient::swap_instructions(self, quote, user_pubkey))
+    }
+}
+
+fn build_query_params(request: &QuoteRequest) -> Vec<(&'static str, String)> {
+    let mut params = vec![
+        ("inputMint", request.input_mint.to_string()),
+        ("outputMint", request.output_mint.to_string()),
+        ("amount", request.amount.to_string()),
+        ("slippageBps", request.slippage_bps.to_string()),
+    ];
+
+    if let Some(swap_mode) = &request.swap_mode {
+        params.push(("swapMode", swap_mode.to_string()));
+    }
+    if let Some(only_direct_routes) = request.only_direct_routes {
+        params.push(("onlyDirectRoutes", only_direct_routes.to_string()));
+    }
+    if let Some(ref excluded_dexes) = request.excluded_dexes {
+        params.push(("excludeDexes", excluded_dexes.clone()));
+    }
+    if let Some(max_accounts) = request.account_limits.total {
+        params.push(("maxAccounts", max_accounts.to_string()));
+    }
+
+    params
+}
+
+async fn parse_json<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+) -> Result<T, ClientError> {
+    let status = response.status();
+    if !status.is_success() {
+        let content_type = content_type_of(response.headers());
+        let body = response.text().await.unwrap_or_default();
+        return Err(error_from_response(
+            status,
+            content_type,
+            body,
+            DEFAULT_ERROR_BODY_LIMIT,
+            None,
+        ));
+    }
+
+    let bytes = response.bytes().await?;
+    serde_json::from_slice(&bytes).map_err(|err| ClientError::InvalidResponse {
+        field: "body",
+        value: err.to_string(),
+    })
+}
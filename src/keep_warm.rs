@@ -0,0 +1,43 @@
+use crate::TitanClient;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+
+/// Shared by every clone of a client with keep-warm enabled. Dropping the
+/// last clone drops this, which stops the background task.
+pub(crate) struct KeepWarm {
+    cancel: CancellationToken,
+}
+
+impl Drop for KeepWarm {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+/// Pings the health endpoint every `interval` so an idle client keeps a warm
+/// connection in its pool. Must be called within a Tokio runtime.
+pub(crate) fn start(client: &TitanClient, interval: Duration) -> Arc<KeepWarm> {
+    let cancel = CancellationToken::new();
+    // The task's own clone must not hold `KeepWarm`, or it would keep itself alive.
+    let mut pinger = client.clone();
+    pinger.keep_warm = None;
+
+    let stopped = cancel.clone();
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = stopped.cancelled() => return,
+                _ = async {
+                    ticker.tick().await;
+                    let _ = pinger.health().await;
+                } => {}
+            }
+        }
+    });
+
+    Arc::new(KeepWarm { cancel })
+}
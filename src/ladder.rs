@@ -0,0 +1,177 @@
+//! One pair quoted at several sizes, for sizing orders against price
+//! impact.
+
+use crate::query::QuoteRequestTemplate;
+use crate::quote::{QuoteRequest, QuoteResponse};
+use crate::serde_helpers::field_as_string;
+use crate::summary::QuoteSummary;
+use crate::{ClientError, TitanClient};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+/// Prices are raw output units per raw input unit, whatever the swap mode.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteLadder {
+    /// One level per distinct requested amount, smallest first.
+    pub levels: Vec<LadderLevel>,
+    /// Between each pair of consecutive successful levels.
+    pub marginal_prices: Vec<MarginalPrice>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LadderLevel {
+    /// The requested amount.
+    #[serde(with = "field_as_string")]
+    pub amount: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote: Option<QuoteSummary>,
+    /// Why the level failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_price: Option<f64>,
+    /// How far `effective_price` is below that of the smallest successful
+    /// level.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impact_bps: Option<f64>,
+}
+
+/// The price of the extra size from one level to the next.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginalPrice {
+    #[serde(with = "field_as_string")]
+    pub from_amount: u64,
+    #[serde(with = "field_as_string")]
+    pub to_amount: u64,
+    /// The extra output over the extra input; `None` when the input didn't
+    /// grow.
+    pub price: Option<f64>,
+}
+
+impl QuoteLadder {
+    /// Builds a ladder from each amount's quote result. Amounts are sorted,
+    /// and only the first result of a repeated amount is kept.
+    pub fn from_results(
+        results: impl IntoIterator<Item = (u64, Result<QuoteResponse, ClientError>)>,
+    ) -> Self {
+        let mut results: Vec<_> = results.into_iter().collect();
+        results.sort_by_key(|(amount, _)| *amount);
+        results.dedup_by_key(|(amount, _)| *amount);
+
+        let mut levels: Vec<LadderLevel> = results
+            .into_iter()
+            .map(|(amount, result)| match result {
+                Ok(quote) => {
+                    let quote = quote.summary();
+                    LadderLevel {
+                        amount,
+                        effective_price: price(quote.out_amount, quote.in_amount),
+                        quote: Some(quote),
+                        error: None,
+                        impact_bps: None,
+                    }
+                }
+                Err(err) => LadderLevel {
+                    amount,
+                    quote: None,
+                    error: Some(err.to_string()),
+                    effective_price: None,
+                    impact_bps: None,
+                },
+            })
+            .collect();
+
+        let reference = levels.iter().find_map(|level| level.effective_price);
+        if let Some(reference) = reference.filter(|price| *price > 0.0) {
+            for level in &mut levels {
+                level.impact_bps = level
+                    .effective_price
+                    .map(|price| (reference - price) / reference * 10_000.0);
+            }
+        }
+
+        let quoted: Vec<(u64, &QuoteSummary)> = levels
+            .iter()
+            .filter_map(|level| Some((level.amount, level.quote.as_ref()?)))
+            .collect();
+        let marginal_prices = quoted
+            .windows(2)
+            .map(|pair| {
+                let [(from_amount, from), (to_amount, to)] = pair else {
+                    unreachable!()
+                };
+                MarginalPrice {
+                    from_amount: *from_amount,
+                    to_amount: *to_amount,
+                    price: match to.in_amount.checked_sub(from.in_amount) {
+                        Some(0) | None => None,
+                        Some(extra_in) => {
+                            Some((to.out_amount as f64 - from.out_amount as f64) / extra_in as f64)
+                        }
+                    },
+                }
+            })
+            .collect();
+
+        Self {
+            levels,
+            marginal_prices,
+        }
+    }
+
+    /// The largest successful level whose impact is at most `impact_bps`.
+    pub fn worst_level_within(&self, impact_bps: f64) -> Option<&LadderLevel> {
+        self.levels
+            .iter()
+            .rev()
+            .find(|level| level.impact_bps.is_some_and(|impact| impact <= impact_bps))
+    }
+
+    pub fn failed_levels(&self) -> impl Iterator<Item = &LadderLevel> {
+        self.levels.iter().filter(|level| level.error.is_some())
+    }
+}
+
+fn price(out_amount: u64, in_amount: u64) -> Option<f64> {
+    (in_amount > 0).then(|| out_amount as f64 / in_amount as f64)
+}
+
+impl TitanClient {
+    /// Quotes `base_request` at each of `amounts` concurrently, subject to
+    /// the client's rate limiter. A level that fails is recorded in the
+    /// ladder; only an invalid `base_request` fails the call. Must be
+    /// called within a Tokio runtime.
+    pub async fn quote_ladder(
+        &self,
+        base_request: &QuoteRequest,
+        amounts: &[u64],
+    ) -> Result<QuoteLadder, ClientError> {
+        base_request.account_limits.validate()?;
+        let template = Arc::new(QuoteRequestTemplate::new(base_request.clone()));
+
+        let mut tasks = JoinSet::new();
+        for amount in amounts.iter().copied().collect::<BTreeSet<_>>() {
+            let client = self.clone();
+            let template = template.clone();
+            tasks.spawn(async move {
+                let result = template.quote_with_amount(&client, amount).await;
+                (amount, result)
+            });
+        }
+
+        let mut results = Vec::with_capacity(tasks.len());
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(result) => results.push(result),
+                // Quoting doesn't panic, and the set is never aborted.
+                Err(err) => std::panic::resume_unwind(err.into_panic()),
+            }
+        }
+        Ok(QuoteLadder::from_results(results))
+    }
+}
@@ -1,224 +1,487 @@
-use crate::quote::{QuoteRequest, QuoteResponse, SwapMode, SwapQuotes};
-use reqwest::Response;
-use solana_sdk::pubkey::Pubkey;
+use crate::pubkey::Pubkey;
+use crate::quote::{QuoteRequest, QuoteResponse, SwapQuotes};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
+pub mod account_limits;
+#[cfg(feature = "native")]
+pub mod aggregator;
+mod amount_math;
+pub mod api_version;
+#[cfg(feature = "native")]
+pub mod batch;
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "native")]
+mod broadcast;
+#[cfg(feature = "native")]
+pub mod builder;
+#[cfg(feature = "native")]
+pub mod cache;
+#[cfg(feature = "native")]
+pub mod circuit_breaker;
+#[cfg(feature = "native")]
+mod client;
+pub mod clock;
+pub mod compare;
+#[cfg(feature = "native")]
+pub mod compose;
+#[cfg(feature = "native")]
+pub mod composite;
+#[cfg(feature = "native")]
+pub mod constants;
+#[cfg(feature = "native")]
+pub mod execute;
+pub mod execution_budget;
+#[cfg(feature = "native")]
+pub mod failover;
+#[cfg(feature = "native")]
+pub mod fallback;
+#[cfg(feature = "native")]
+pub mod format;
+#[cfg(feature = "native")]
+pub mod health;
+#[cfg(feature = "native")]
+mod hedge;
+#[cfg(feature = "native")]
+pub mod inspect;
+#[cfg(feature = "journal")]
+pub mod journal;
+pub mod jupiter;
+#[cfg(feature = "jupiter-compat")]
+pub mod jupiter_client;
+#[cfg(feature = "native")]
+mod keep_warm;
+#[cfg(feature = "native")]
+pub mod ladder;
+#[cfg(feature = "native")]
+pub mod observer;
+#[cfg(feature = "native")]
+pub mod options;
+#[cfg(feature = "native")]
+pub mod price;
+pub mod pubkey;
+pub mod query;
+pub mod quota;
 pub mod quote;
+#[cfg(feature = "native")]
+pub mod quote_builder;
+pub mod quote_defaults;
+#[cfg(feature = "native")]
+pub mod rate_limit;
+pub mod raw;
+#[cfg(feature = "native")]
+pub mod receipt;
+pub mod route_display;
+pub mod route_shape;
+#[cfg(feature = "native")]
+pub mod security;
 pub mod serde_helpers;
+#[cfg(feature = "native")]
+pub mod split;
+#[cfg(feature = "solana-client")]
+pub mod staleness;
+#[cfg(feature = "stream")]
+pub mod stream;
+pub mod summary;
+#[cfg(feature = "native")]
 pub mod swap;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+#[cfg(feature = "native")]
+pub mod token_2022;
+#[cfg(feature = "native")]
+pub mod tokens;
+pub mod transform;
+pub mod transport;
+pub mod ui;
+pub mod venues;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use amount_math::AmountOverflow;
+#[cfg(feature = "native")]
+pub use builder::TitanClientBuilder;
+#[cfg(feature = "native")]
+pub use client::{RequestMiddleware, TitanClient};
+/// The solana-sdk this crate is built against. Use it, or the `to_raw()`
+/// conversions, when your own sdk version differs.
+#[cfg(feature = "native")]
+pub use solana_sdk;
 
 const TITAN_API_URL: &str = "https://api.titan.exchange";
 
+/// Sent as `User-Agent` unless the builder's `user_agent` replaces it.
+pub const DEFAULT_USER_AGENT: &str = concat!("titan-swap-api-client/", env!("CARGO_PKG_VERSION"));
+
+/// The header the builder's `client_id` is sent in.
+pub const CLIENT_ID_HEADER: &str = "x-client-id";
+
+/// How much of an error response's body `RequestFailed` keeps by default.
+pub const DEFAULT_ERROR_BODY_LIMIT: usize = 512;
+
+/// New variants may be added in minor releases.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ClientError {
-    #[error("Request failed with status {status}: {body}")]
+    /// `body` is cut to the client's error body limit; `body_len` is the
+    /// length of the whole body. `quota` is read from the response headers.
+    #[error(
+        "Request failed with status {status}{}: {body}{}",
+        content_type_note(.content_type),
+        truncation_note(.body, *.body_len)
+    )]
     RequestFailed {
-        status: reqwest::StatusCode,
+        status: http::StatusCode,
         body: String,
+        content_type: Option<String>,
+        body_len: usize,
+        quota: Option<quota::QuotaInfo>,
     },
+    #[cfg(feature = "reqwest-transport")]
     #[error("HTTP client error: {0}")]
     HttpError(#[from] reqwest::Error),
+    /// A failure that isn't a `reqwest::Error`, such as one from a custom
+    /// `transport::HttpTransport`.
+    #[error("HTTP transport error: {0}")]
+    Transport(transport::TransportError),
     #[error("No routes available")]
     NoRoutesAvailable,
+    #[error("Quote has no route data to build a swap from")]
+    MissingRouteData,
+    #[error("Quote is indicative: request it with a user public key to swap")]
+    MissingUserContext,
+    #[error("Quote was decoded price-only and has no instructions to swap")]
+    PriceOnlyQuote,
+    #[error("Provider {0} did not return a route")]
+    ProviderUnavailable(String),
+    #[error("Swap mode {0} is not supported here")]
+    UnsupportedSwapMode(quote::SwapMode),
     #[error("Failed to decode msgpack: {0}")]
     MsgpackError(#[from] rmp_serde::decode::Error),
+    #[error(transparent)]
+    InvalidAccountLimits(#[from] account_limits::AccountLimitsError),
+    #[error(transparent)]
+    InvalidReferral(#[from] quote::ReferralError),
+    /// A header value set on the builder, such as the user agent, that
+    /// can't be sent.
+    #[error("Invalid {name} header value {value:?}")]
+    InvalidHeader { name: &'static str, value: String },
+    #[error("Invalid response field {field}: {value}")]
+    InvalidResponse { field: &'static str, value: String },
+    /// The response answers a different request, e.g. another pair. `field`
+    /// is its name in the response.
+    #[error("Response {field} is {actual}, but {expected} was requested")]
+    ResponseMismatch {
+        field: &'static str,
+        expected: String,
+        actual: String,
+    },
+    #[error("ExactOut route delivers {out_amount}, but {requested} was requested")]
+    QuoteMismatch { requested: u64, out_amount: u64 },
+    /// The second leg of a `CompositeQuote` spends `spent` of `spent_mint`,
+    /// but the first leg is only sure to deliver `guaranteed` of `via`.
+    #[error("Second leg spends {spent} of {spent_mint}, but the first leg guarantees {guaranteed} of {via}")]
+    UnchainedLegs {
+        via: Pubkey,
+        guaranteed: u64,
+        spent_mint: Pubkey,
+        spent: u64,
+    },
+    #[error("Circuit breaker open, retry in {:?}", .retry_at.saturating_duration_since(Instant::now()))]
+    CircuitOpen { retry_at: Instant },
+    #[error("Client-side rate limit exceeded, retry in {retry_after:?}")]
+    RateLimited { retry_after: Duration },
+    #[error("Price moved {divergence_bps:.1} bps against the original quote")]
+    PriceMoved { divergence_bps: f64 },
+    /// `deviation_bps` is negative when the quote is worse than the
+    /// reference, and NaN when it couldn't be priced.
+    #[error("Quote price is {deviation_bps:.1} bps from the guard's reference")]
+    QuoteRejected { deviation_bps: f64 },
+    #[error("Swap doesn't enforce a minimum output on-chain")]
+    NoOnchainMinOut,
+    #[error("Route is missing required field {0}")]
+    MissingRouteField(quote::RouteField),
+    #[error("Deadline exceeded")]
+    DeadlineExceeded,
+    /// `remaining_ms` is how long the quote was still valid for, and
+    /// `budget_ms` how long executing it was expected to take.
+    #[error("Quote {reason}: {remaining_ms} ms left for a {budget_ms} ms execution budget")]
+    QuoteExpired {
+        reason: execution_budget::ExpiryReason,
+        remaining_ms: u64,
+        budget_ms: u64,
+    },
+    #[error("Cancelled")]
+    Cancelled,
+    #[error("Response body exceeded {limit} bytes (received at least {received_at_least})")]
+    ResponseTooLarge {
+        limit: usize,
+        received_at_least: usize,
+    },
+    #[error(transparent)]
+    AmountOverflow(#[from] AmountOverflow),
+    #[cfg(feature = "solana-client")]
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<solana_client::client_error::ClientError>),
+    #[cfg(feature = "native")]
+    #[error(transparent)]
+    Execution(#[from] execute::ExecuteError),
+    #[cfg(feature = "stream")]
+    #[error(transparent)]
+    Stream(#[from] stream::StreamError),
 }
 
-pub struct TitanClient {
-    client: reqwest::Client,
-    base_path: String,
-    auth_header: String,
-}
-
-impl TitanClient {
-    pub fn new(auth_token: String, base_path: Option<String>) -> Self {
-        Self {
-            client: reqwest::Client::new(),
-            base_path: base_path.unwrap_or_else(|| TITAN_API_URL.to_string()),
-            auth_header: format!("Bearer {}", auth_token),
+#[cfg(feature = "native")]
+impl ClientError {
+    /// Whether the failure is transient and worth retrying, possibly elsewhere:
+    /// timeouts, connection errors, and 5xx responses.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::HttpError(err) => err.is_timeout() || err.is_connect() || err.is_request(),
+            Self::RequestFailed { status, .. } => status.is_server_error(),
+            _ => false,
         }
     }
 
-    async fn fetch_swap_quotes(
-        &self,
-        params: &[(&str, String)],
-    ) -> Result<SwapQuotes, ClientError> {
-        let response = self
-            .client
-            .get(format!("{}/api/v1/quote/swap", self.base_path))
-            .query(params)
-            .header("Accept", "application/vnd.msgpack")
-            .header("Authorization", &self.auth_header)
-            .send()
-            .await?;
-
-        let response = check_response(response).await?;
-        let buffer = response.bytes().await?;
-        Ok(rmp_serde::from_slice(&buffer)?)
+    /// The quota reported by the failed response, if any.
+    pub fn quota(&self) -> Option<&quota::QuotaInfo> {
+        match self {
+            Self::RequestFailed { quota, .. } => quota.as_ref(),
+            _ => None,
+        }
     }
+}
 
-    pub async fn quote(&self, request: &QuoteRequest) -> Result<QuoteResponse, ClientError> {
-        let params = build_query_params(request);
-        let quotes = self.fetch_swap_quotes(&params).await?;
+/// Decodes a msgpack quote response, e.g. one saved from
+/// `TitanClient::fetch_swap_quotes_raw`.
+pub fn decode_swap_quotes(buffer: &[u8]) -> Result<SwapQuotes, ClientError> {
+    Ok(rmp_serde::from_slice(buffer)?)
+}
 
-        let route = quotes
-            .quotes
-            .into_values()
-            .next()
-            .ok_or(ClientError::NoRoutesAvailable)?;
+/// Like [`decode_swap_quotes`], skipping each route's instructions, lookup
+/// tables and transaction. The routes can be priced but not swapped.
+pub fn decode_price_only_quotes(buffer: &[u8]) -> Result<SwapQuotes, ClientError> {
+    let quotes: quote::PriceOnlySwapQuotes = rmp_serde::from_slice(buffer)?;
+    Ok(quotes.into_swap_quotes())
+}
 
-        let context_slot = route.context_slot.unwrap_or(0);
-        let route_plan: Vec<_> = route
-            .steps
-            .iter()
-            .map(|step| transform_step(step, context_slot))
-            .collect();
-
-        Ok(QuoteResponse {
-            input_mint: request.input_mint,
-            in_amount: request.amount,
-            output_mint: request.output_mint,
-            out_amount: route.out_amount,
-            swap_mode: request.swap_mode.clone().unwrap_or_default(),
-            slippage_bps: route.slippage_bps,
-            platform_fee: route.platform_fee.as_ref().map(|pf| quote::PlatformFee {
-                amount: pf.amount,
-                fee_bps: pf.fee_bps,
-            }),
-            raw_route: route.clone(),
-            route_plan,
-            context_slot: route.context_slot,
-            time_taken: route.time_taken_ns.map(|ns| ns as f64 / 1e9),
-        })
+/// Decodes a quote response in the shape served by `version`. v2 shares the
+/// v1 shape for now; its own target goes here once the two diverge.
+/// Lenient decoding applies to msgpack only, and not to price-only decoding.
+/// Price-only JSON is decoded in full and then stripped.
+#[cfg(feature = "native")]
+pub(crate) fn decode_swap_quotes_for(
+    version: api_version::ApiVersion,
+    format: format::ResponseFormat,
+    lenient: bool,
+    detail: quote::QuoteDetail,
+    buffer: &[u8],
+) -> Result<SwapQuotes, ClientError> {
+    let price_only = detail == quote::QuoteDetail::PriceOnly;
+    match version {
+        api_version::ApiVersion::V1 | api_version::ApiVersion::V2 => match format {
+            format::ResponseFormat::Json => {
+                let quotes: format::JsonSwapQuotes =
+                    serde_json::from_slice(buffer).map_err(|err| ClientError::InvalidResponse {
+                        field: "body",
+                        value: err.to_string(),
+                    })?;
+                let mut quotes: SwapQuotes = quotes.into();
+                if price_only {
+                    quotes
+                        .quotes
+                        .values_mut()
+                        .for_each(quote::SwapRoute::strip_execution_data);
+                }
+                Ok(quotes)
+            }
+            format::ResponseFormat::Msgpack if price_only => decode_price_only_quotes(buffer),
+            format::ResponseFormat::Msgpack if lenient => {
+                let quotes: quote::LenientSwapQuotes = rmp_serde::from_slice(buffer)?;
+                Ok(quotes.into_swap_quotes())
+            }
+            format::ResponseFormat::Msgpack => decode_swap_quotes(buffer),
+        },
     }
+}
 
-    pub fn swap(&self, quote: &QuoteResponse) -> Result<swap::SwapResponse, ClientError> {
-        let route = &quote.raw_route;
+/// Decodes a response from an endpoint that serves both msgpack and JSON,
+/// going by its `Content-Type`.
+#[cfg(feature = "native")]
+pub(crate) fn decode_body<T: serde::de::DeserializeOwned>(
+    content_type: Option<&str>,
+    buffer: &[u8],
+) -> Result<T, ClientError> {
+    if content_type.is_some_and(|content_type| content_type.contains("json")) {
+        return serde_json::from_slice(buffer).map_err(|err| ClientError::InvalidResponse {
+            field: "body",
+            value: err.to_string(),
+        });
+    }
 
-        if route.instructions.is_empty() {
-            return Err(ClientError::NoRoutesAvailable);
-        }
+    Ok(rmp_serde::from_slice(buffer)?)
+}
 
-        let solana_instructions: Vec<solana_sdk::instruction::Instruction> = route
-            .instructions
+/// Drops routes that go through an intermediate mint, for requests with
+/// `only_direct_routes` set. Splits across several pools of the input/output
+/// pair still count as direct. Fails with `NoRoutesAvailable` if routes were
+/// returned but none were direct.
+pub(crate) fn retain_direct_routes(quotes: &mut SwapQuotes) -> Result<(), ClientError> {
+    let returned = quotes.quotes.len();
+    let (input_mint, output_mint) = (quotes.input_mint, quotes.output_mint);
+    quotes.quotes.retain(|_, route| {
+        route
+            .steps
             .iter()
-            .map(|inst| solana_sdk::instruction::Instruction {
-                program_id: pubkey_from_bytes(&inst.p),
-                accounts: inst
-                    .a
-                    .iter()
-                    .map(|meta| solana_sdk::instruction::AccountMeta {
-                        pubkey: pubkey_from_bytes(&meta.p),
-                        is_signer: meta.s,
-                        is_writable: meta.w,
-                    })
-                    .collect(),
-                data: inst.d.clone(),
-            })
-            .collect();
-
-        let address_lookup_tables: Vec<Pubkey> = route
-            .address_lookup_tables
+            .all(|step| step.input_mint == input_mint && step.output_mint == output_mint)
+    });
+
+    finish_discarding(
+        quotes,
+        returned,
+        "multi-hop routes returned for onlyDirectRoutes",
+    )
+}
+
+/// Drops routes with a step through any of the `excluded` pools, for
+/// requests with `excluded_amm_keys` set. Fails with `NoRoutesAvailable` if
+/// routes were returned but all were excluded.
+pub(crate) fn retain_routes_avoiding(
+    quotes: &mut SwapQuotes,
+    excluded: &[Pubkey],
+) -> Result<(), ClientError> {
+    let returned = quotes.quotes.len();
+    quotes.quotes.retain(|_, route| {
+        !route
+            .steps
             .iter()
-            .map(pubkey_from_bytes)
-            .collect();
-
-        Ok(swap::SwapResponse {
-            instructions: solana_instructions,
-            address_lookup_table_addresses: address_lookup_tables,
-            compute_unit_limit: route.compute_units.unwrap_or(0) as u32,
-            compute_units_safe: route.compute_units_safe,
-            context_slot: route.context_slot,
-            expires_at_ms: route.expires_at_ms,
-            expires_after_slot: route.expires_after_slot,
-        })
-    }
+            .any(|step| excluded.contains(&Pubkey::from(step.amm_key)))
+    });
+
+    finish_discarding(quotes, returned, "routes through excluded AMM keys")
 }
 
-fn build_query_params(request: &QuoteRequest) -> Vec<(&'static str, String)> {
-    let mut params = vec![
-        ("inputMint", request.input_mint.to_string()),
-        ("outputMint", request.output_mint.to_string()),
-        ("amount", request.amount.to_string()),
-        ("userPublicKey", request.user_pubkey.to_string()),
-    ];
+/// Drops routes missing any of the `required` fields. Fails with
+/// `MissingRouteField`, naming a field the first route lacked, if routes were
+/// returned but none had them all.
+pub(crate) fn retain_complete_routes(
+    quotes: &mut SwapQuotes,
+    required: &[quote::RouteField],
+) -> Result<(), ClientError> {
+    let missing =
+        |route: &quote::SwapRoute| required.iter().copied().find(|field| !field.is_set(route));
+    let first_missing = quotes.quotes.values().find_map(missing);
+    let returned = quotes.quotes.len();
+    quotes.quotes.retain(|_, route| missing(route).is_none());
 
-    if let Some(max_accounts) = request.max_accounts {
-        params.push(("accountsLimitTotal", max_accounts.to_string()));
-    }
-    if let Some(swap_mode) = &request.swap_mode {
-        params.push((
-            "swapMode",
-            match swap_mode {
-                SwapMode::ExactIn => "ExactIn",
-                SwapMode::ExactOut => "ExactOut",
-            }
-            .to_string(),
-        ));
-    }
-    if request.slippage_bps > 0 {
-        params.push(("slippageBps", request.slippage_bps.to_string()));
-    }
-    if let Some(only_direct_routes) = request.only_direct_routes {
-        params.push(("onlyDirectRoutes", only_direct_routes.to_string()));
-    }
-    if let Some(ref excluded_dexes) = request.excluded_dexes {
-        params.push(("excludeDexes", excluded_dexes.clone()));
+    finish_discarding(quotes, returned, "routes missing required fields")
+        .map_err(|err| first_missing.map_or(err, ClientError::MissingRouteField))
+}
+
+fn finish_discarding(
+    quotes: &SwapQuotes,
+    returned: usize,
+    what: &'static str,
+) -> Result<(), ClientError> {
+    let discarded = returned - quotes.quotes.len();
+    if discarded > 0 {
+        note_discarded_routes(discarded, quotes.quotes.is_empty(), what);
     }
-    if let Some(size_constraints) = request.size_constraints {
-        params.push(("sizeConstraint", size_constraints.to_string()));
+    if returned > 0 && quotes.quotes.is_empty() {
+        return Err(ClientError::NoRoutesAvailable);
     }
-    if let Some(accounts_limit_writable) = request.accounts_limit_writable {
-        params.push(("accountsLimitWritable", accounts_limit_writable.to_string()));
+    Ok(())
+}
+
+#[cfg(feature = "tracing")]
+fn note_discarded_routes(discarded: usize, none_left: bool, what: &'static str) {
+    tracing::debug!(discarded, none_left, "discarded {what}");
+}
+
+#[cfg(not(feature = "tracing"))]
+fn note_discarded_routes(_discarded: usize, _none_left: bool, _what: &'static str) {}
+
+/// Fails before anything is sent on account limits or referral settings
+/// the server would reject.
+pub(crate) fn validate_request(request: &QuoteRequest) -> Result<(), ClientError> {
+    request.account_limits.validate()?;
+    request.validate_referral()?;
+    Ok(())
+}
+
+pub(crate) fn quotes_by_provider(
+    request: &QuoteRequest,
+    quotes: SwapQuotes,
+) -> Result<BTreeMap<String, QuoteResponse>, ClientError> {
+    if quotes.quotes.is_empty() {
+        return Err(ClientError::NoRoutesAvailable);
     }
 
-    if let Some(ref providers) = request.providers {
-        params.push(("providers", providers.to_string()));
+    Ok(quotes
+        .quotes
+        .into_iter()
+        .map(|(key, route)| {
+            let quote = QuoteResponse::from_route(request, route).with_provider(key.attribution());
+            (key.to_string(), quote)
+        })
+        .collect())
+}
+
+pub(crate) fn error_from_response(
+    status: http::StatusCode,
+    content_type: Option<String>,
+    mut body: String,
+    limit: usize,
+    quota: Option<quota::QuotaInfo>,
+) -> ClientError {
+    if status == http::StatusCode::NOT_FOUND && body.contains("No routes") {
+        return ClientError::NoRoutesAvailable;
     }
 
-    params
-}
-
-fn transform_step(
-    step: &crate::quote::RoutePlanStepData,
-    default_context_slot: u64,
-) -> crate::quote::RoutePlanStep {
-    crate::quote::RoutePlanStep {
-        swap_info: crate::quote::SwapInfo {
-            amm_key: pubkey_from_bytes(&step.amm_key),
-            label: step.label.clone(),
-            input_mint: pubkey_from_bytes(&step.input_mint),
-            output_mint: pubkey_from_bytes(&step.output_mint),
-            in_amount: step.in_amount,
-            out_amount: step.out_amount,
-            alloc_ppb: step.alloc_ppb as u64,
-            fee_mint: step
-                .fee_mint
-                .as_ref()
-                .map_or_else(Pubkey::default, pubkey_from_bytes),
-            fee_amount: step.fee_amount.unwrap_or(0),
-            context_slot: step.context_slot.unwrap_or(default_context_slot),
-        },
-        percent: 100,
+    let body_len = body.len();
+    if body_len > limit {
+        let end = (0..=limit)
+            .rev()
+            .find(|&i| body.is_char_boundary(i))
+            .unwrap_or(0);
+        body.truncate(end);
+    }
+    ClientError::RequestFailed {
+        status,
+        body,
+        content_type,
+        body_len,
+        quota,
     }
 }
 
-async fn check_response(response: Response) -> Result<Response, ClientError> {
-    if response.status().is_success() {
-        return Ok(response);
-    }
+/// The media type of a response, without parameters such as `charset`.
+pub(crate) fn content_type_of(headers: &http::HeaderMap) -> Option<String> {
+    let value = headers.get(http::header::CONTENT_TYPE)?.to_str().ok()?;
+    let media_type = value.split(';').next().unwrap_or_default().trim();
+    (!media_type.is_empty()).then(|| media_type.to_ascii_lowercase())
+}
 
-    let status = response.status();
-    let body = response.text().await.unwrap_or_default();
+fn content_type_note(content_type: &Option<String>) -> String {
+    content_type
+        .as_ref()
+        .map_or_else(String::new, |content_type| format!(" [{}]", content_type))
+}
 
-    if status == reqwest::StatusCode::NOT_FOUND && body.contains("No routes") {
-        return Err(ClientError::NoRoutesAvailable);
+fn truncation_note(body: &str, body_len: usize) -> String {
+    if body.len() < body_len {
+        format!("... ({} of {} bytes)", body.len(), body_len)
+    } else {
+        String::new()
     }
-
-    Err(ClientError::RequestFailed { status, body })
 }
 
 #[inline]
-fn pubkey_from_bytes(bytes: &[u8; 32]) -> Pubkey {
+pub(crate) fn pubkey_from_bytes(bytes: &[u8; 32]) -> Pubkey {
     Pubkey::from(*bytes)
 }
@@ -0,0 +1,186 @@
+use crate::clock::Clock;
+use crate::swap::SwapWarning;
+use bytes::Bytes;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuoteResultKind {
+    Success,
+    NoRoutes,
+    Error,
+}
+
+impl QuoteResultKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::NoRoutes => "no_routes",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// Hooks invoked after each quote and HTTP exchange.
+///
+/// Inside a Tokio runtime, calls are dispatched on a spawned task, so a slow
+/// observer doesn't delay quoting and a panicking one only takes down its own
+/// task. Without a runtime, e.g. `swap()` called from synchronous code or a
+/// blocking thread, the call runs inline: a slow observer blocks the caller,
+/// but a panic is caught there and goes no further.
+pub trait Observer: Send + Sync + 'static {
+    fn on_quote(
+        &self,
+        _kind: QuoteResultKind,
+        _latency: Duration,
+        _provider: Option<&str>,
+        _route_hops: usize,
+    ) {
+    }
+
+    /// `status` is `None` when no response was received.
+    fn on_http(&self, _status: Option<u16>, _latency: Duration, _bytes: usize) {}
+
+    /// Called when `swap` builds a swap from a suspect route.
+    fn on_swap_warning(&self, _warning: SwapWarning) {}
+}
+
+/// Receives `(url, status, body)` for every quote response, before it is
+/// decoded. Unlike [`Observer`] it runs inline, so the body is handled before
+/// the quote is returned; keep it fast.
+pub type RawResponseHook = Arc<dyn Fn(&str, u16, &Bytes) + Send + Sync>;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopObserver;
+
+impl Observer for NoopObserver {}
+
+pub(crate) fn notify_quote(
+    observer: &Option<Arc<dyn Observer>>,
+    kind: QuoteResultKind,
+    latency: Duration,
+    provider: Option<String>,
+    route_hops: usize,
+) {
+    if let Some(observer) = observer.clone() {
+        dispatch(move || observer.on_quote(kind, latency, provider.as_deref(), route_hops));
+    }
+}
+
+pub(crate) fn notify_http(
+    observer: &Option<Arc<dyn Observer>>,
+    status: Option<u16>,
+    latency: Duration,
+    bytes: usize,
+) {
+    if let Some(observer) = observer.clone() {
+        dispatch(move || observer.on_http(status, latency, bytes));
+    }
+}
+
+pub(crate) fn notify_swap_warning(observer: &Option<Arc<dyn Observer>>, warning: SwapWarning) {
+    if let Some(observer) = observer.clone() {
+        dispatch(move || observer.on_swap_warning(warning));
+    }
+}
+
+/// Runs `call` on a spawned task, or inline when there's no runtime to spawn
+/// it on, e.g. when `swap` is called from synchronous code or an attempt is
+/// dropped along with its runtime. Either way a panic in `call` never reaches
+/// the caller.
+fn dispatch(call: impl FnOnce() + Send + 'static) {
+    match tokio::runtime::Handle::try_current() {
+        Ok(runtime) => {
+            runtime.spawn(async move { call() });
+        }
+        Err(_) => {
+            let _ = std::panic::catch_unwind(AssertUnwindSafe(call));
+        }
+    }
+}
+
+/// Reports one HTTP attempt to the observer exactly once. An attempt dropped
+/// before `finish` (e.g. the losing side of a hedged request) is reported
+/// with no status.
+pub(crate) struct HttpAttempt<'a> {
+    observer: &'a Option<Arc<dyn Observer>>,
+    clock: &'a dyn Clock,
+    started: Instant,
+    finished: bool,
+}
+
+impl<'a> HttpAttempt<'a> {
+    pub(crate) fn start(observer: &'a Option<Arc<dyn Observer>>, clock: &'a dyn Clock) -> Self {
+        Self {
+            observer,
+            clock,
+            started: clock.instant(),
+            finished: false,
+        }
+    }
+
+    pub(crate) fn finish(mut self, status: Option<u16>, bytes: usize) {
+        self.finished = true;
+        notify_http(
+            self.observer,
+            status,
+            self.clock.elapsed(self.started),
+            bytes,
+        );
+    }
+}
+
+impl Drop for HttpAttempt<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            notify_http(self.observer, None, self.clock.elapsed(self.started), 0);
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+pub use metrics_observer::MetricsObserver;
+
+#[cfg(feature = "metrics")]
+mod metrics_observer {
+    use super::{Observer, QuoteResultKind};
+    use std::time::Duration;
+
+    /// Emits to the `metrics` facade:
+    ///
+    /// - `titan_quote_total{outcome, provider}` counter
+    /// - `titan_quote_latency_seconds{outcome}` histogram
+    /// - `titan_quote_route_hops` histogram
+    /// - `titan_http_requests_total{status}` counter
+    /// - `titan_http_latency_seconds` histogram
+    /// - `titan_http_response_bytes` histogram
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct MetricsObserver;
+
+    impl Observer for MetricsObserver {
+        fn on_quote(
+            &self,
+            kind: QuoteResultKind,
+            latency: Duration,
+            provider: Option<&str>,
+            route_hops: usize,
+        ) {
+            let provider = provider.unwrap_or("none").to_string();
+            metrics::counter!("titan_quote_total", "outcome" => kind.as_str(), "provider" => provider)
+                .increment(1);
+            metrics::histogram!("titan_quote_latency_seconds", "outcome" => kind.as_str())
+                .record(latency.as_secs_f64());
+            if kind == QuoteResultKind::Success {
+                metrics::histogram!("titan_quote_route_hops").record(route_hops as f64);
+            }
+        }
+
+        fn on_http(&self, status: Option<u16>, latency: Duration, bytes: usize) {
+            let status = status.map_or_else(|| "none".to_string(), |s| s.to_string());
+            metrics::counter!("titan_http_requests_total", "status" => status).increment(1);
+            metrics::histogram!("titan_http_latency_seconds").record(latency.as_secs_f64());
+            metrics::histogram!("titan_http_response_bytes").record(bytes as f64);
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::price::QuoteGuard;
+pub use crate::quote::QuoteDetail;
+use std::time::{Duration, Instant};
+
+pub use tokio_util::sync::CancellationToken;
+
+/// Per-call limits for [`crate::TitanClient::quote_with_options`]. Both bound
+/// the whole call, failover retries and hedged requests included; when both
+/// are set the earlier one applies.
+#[derive(Debug, Clone, Default)]
+pub struct QuoteRequestOptions {
+    pub timeout: Option<Duration>,
+    pub deadline: Option<Instant>,
+    /// Cancelling aborts any in-flight request and fails the call with
+    /// `ClientError::Cancelled`.
+    pub cancel: Option<CancellationToken>,
+    /// Fails the call with `ClientError::QuoteRejected` if the quote is
+    /// priced too far below the guard's reference.
+    pub guard: Option<QuoteGuard>,
+    /// `PriceOnly` skips decoding the route's execution data. The quote is
+    /// marked with its detail and `swap()` refuses it.
+    pub detail: QuoteDetail,
+}
+
+impl QuoteRequestOptions {
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_deadline(deadline: Instant) -> Self {
+        Self {
+            deadline: Some(deadline),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_cancel(cancel: CancellationToken) -> Self {
+        Self {
+            cancel: Some(cancel),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_guard(guard: QuoteGuard) -> Self {
+        Self {
+            guard: Some(guard),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_detail(detail: QuoteDetail) -> Self {
+        Self {
+            detail,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn effective_deadline(&self, started: Instant) -> Option<Instant> {
+        let from_timeout = self.timeout.map(|timeout| started + timeout);
+        match (self.deadline, from_timeout) {
+            (Some(deadline), Some(from_timeout)) => Some(deadline.min(from_timeout)),
+            (deadline, from_timeout) => deadline.or(from_timeout),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::quote::{PriceCheck, QuoteResponse};
+use crate::serde_helpers::pubkey_as_bytes;
+use crate::{ClientError, TitanClient};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+/// A spot reference price: `price` units of `quote_mint` per unit of
+/// `base_mint`, both in raw (smallest-unit) amounts.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceResponse {
+    #[serde(with = "pubkey_as_bytes")]
+    pub base_mint: Pubkey,
+    #[serde(with = "pubkey_as_bytes")]
+    pub quote_mint: Pubkey,
+    pub price: f64,
+    pub timestamp_ms: u64,
+}
+
+impl PriceResponse {
+    /// The price in whole tokens, given each mint's decimals.
+    pub fn ui_price(&self, base_decimals: u8, quote_decimals: u8) -> f64 {
+        self.price * 10f64.powi(i32::from(base_decimals) - i32::from(quote_decimals))
+    }
+}
+
+/// Refuses quotes priced too far below a reference, as set on
+/// `QuoteRequestOptions::guard`.
+///
+/// `reference_price` is whole output tokens per whole input token, whatever
+/// the swap mode. A quote is rejected with `ClientError::QuoteRejected` when
+/// its effective price is more than `max_deviation_bps` worse than that.
+/// Better prices always pass.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteGuard {
+    pub reference_price: f64,
+    pub max_deviation_bps: u16,
+    pub in_decimals: u8,
+    pub out_decimals: u8,
+}
+
+impl QuoteGuard {
+    /// A guard from a price endpoint reference, which may be quoted either
+    /// way round: `None` unless `price` is between `input_mint` and
+    /// `output_mint`.
+    pub fn from_price(
+        price: &PriceResponse,
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+        max_deviation_bps: u16,
+        in_decimals: u8,
+        out_decimals: u8,
+    ) -> Option<Self> {
+        let reference_price = if (price.base_mint, price.quote_mint) == (input_mint, output_mint) {
+            price.ui_price(in_decimals, out_decimals)
+        } else if (price.base_mint, price.quote_mint) == (output_mint, input_mint) {
+            1.0 / price.ui_price(out_decimals, in_decimals)
+        } else {
+            return None;
+        };
+
+        Some(QuoteGuard {
+            reference_price,
+            max_deviation_bps,
+            in_decimals,
+            out_decimals,
+        })
+    }
+
+    /// How far `quote`'s effective price is from the reference, negative
+    /// when worse. NaN when either price is zero or not finite.
+    pub fn deviation_bps(&self, quote: &QuoteResponse) -> f64 {
+        let in_amount = quote.input_amount();
+        if in_amount == 0 || !self.reference_price.is_finite() || self.reference_price <= 0.0 {
+            return f64::NAN;
+        }
+        let raw_price = quote.out_amount as f64 / in_amount as f64;
+        let price =
+            raw_price * 10f64.powi(i32::from(self.in_decimals) - i32::from(self.out_decimals));
+        (price - self.reference_price) / self.reference_price * 10_000.0
+    }
+
+    /// Passes a quote at most `max_deviation_bps` worse than the reference.
+    /// A quote that can't be priced is rejected.
+    pub fn check(&self, quote: &QuoteResponse) -> Result<(), ClientError> {
+        let deviation_bps = self.deviation_bps(quote);
+        // Written so that NaN fails.
+        if deviation_bps >= -f64::from(self.max_deviation_bps) {
+            Ok(())
+        } else {
+            Err(ClientError::QuoteRejected { deviation_bps })
+        }
+    }
+}
+
+impl TitanClient {
+    pub async fn price(
+        &self,
+        base_mint: Pubkey,
+        quote_mint: Pubkey,
+    ) -> Result<PriceResponse, ClientError> {
+        let params = [
+            ("baseMint", base_mint.to_string()),
+            ("quoteMint", quote_mint.to_string()),
+        ];
+        self.get_decoded("/price", &params).await
+    }
+
+    /// Prices for several base mints against the same quote mint in one request.
+    pub async fn prices(
+        &self,
+        base_mints: &[Pubkey],
+        quote_mint: Pubkey,
+    ) -> Result<Vec<PriceResponse>, ClientError> {
+        let base_mints = base_mints
+            .iter()
+            .map(Pubkey::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        let params = [
+            ("baseMints", base_mints),
+            ("quoteMint", quote_mint.to_string()),
+        ];
+        self.get_decoded("/prices", &params).await
+    }
+
+    /// Compares `quote`'s execution price against the reference price.
+    pub(crate) async fn check_price(
+        &self,
+        quote: &QuoteResponse,
+        max_deviation_bps: u16,
+    ) -> Result<PriceCheck, ClientError> {
+        let reference = self.price(quote.input_mint, quote.output_mint).await?;
+        Ok(PriceCheck::new(quote, reference.price, max_deviation_bps))
+    }
+}
@@ -0,0 +1,84 @@
+//! The public key type used throughout the quote types.
+//!
+//! With the `native` feature this is `solana_sdk::pubkey::Pubkey`. Without it
+//! (e.g. on wasm32) it is a lightweight 32-byte newtype with the same
+//! base58 string representation, so the quote path doesn't pull in solana-sdk.
+
+#[cfg(feature = "native")]
+pub use solana_sdk::pubkey::Pubkey;
+
+#[cfg(not(feature = "native"))]
+pub use raw::Pubkey;
+
+#[cfg(not(feature = "native"))]
+mod raw {
+    use std::fmt;
+    use std::str::FromStr;
+    use thiserror::Error;
+
+    #[derive(Debug, Error, PartialEq, Eq)]
+    #[error("Invalid base58 pubkey")]
+    pub struct ParsePubkeyError;
+
+    #[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct Pubkey([u8; 32]);
+
+    impl Pubkey {
+        pub const fn new_from_array(bytes: [u8; 32]) -> Self {
+            Self(bytes)
+        }
+
+        pub const fn to_bytes(self) -> [u8; 32] {
+            self.0
+        }
+
+        pub fn as_array(&self) -> &[u8; 32] {
+            &self.0
+        }
+
+        /// Decodes a base58 key at compile time, like `solana_sdk`'s
+        /// `Pubkey::from_str_const`. Panics if `s` isn't one.
+        pub const fn from_str_const(s: &str) -> Self {
+            Self(bs58::decode(s.as_bytes()).into_array_const_unwrap())
+        }
+    }
+
+    impl From<[u8; 32]> for Pubkey {
+        fn from(bytes: [u8; 32]) -> Self {
+            Self(bytes)
+        }
+    }
+
+    impl AsRef<[u8]> for Pubkey {
+        fn as_ref(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    impl fmt::Display for Pubkey {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&bs58::encode(self.0).into_string())
+        }
+    }
+
+    impl fmt::Debug for Pubkey {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(self, f)
+        }
+    }
+
+    impl FromStr for Pubkey {
+        type Err = ParsePubkeyError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let mut bytes = [0u8; 32];
+            let len = bs58::decode(s)
+                .onto(&mut bytes)
+                .map_err(|_| ParsePubkeyError)?;
+            if len != 32 {
+                return Err(ParsePubkeyError);
+            }
+            Ok(Self(bytes))
+        }
+    }
+}
@@ -0,0 +1,374 @@
+//! The `/quote` query string a [`QuoteRequest`] is sent as, in both
+//! directions, so a request copied out of a log can be replayed.
+
+use crate::account_limits::AccountLimits;
+use crate::pubkey::Pubkey;
+use crate::quote::{Provider, QuoteRequest, SizeConstraint, SwapMode};
+use crate::venues;
+use std::fmt::Display;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ParseError {
+    #[error("Unknown query parameter {0}")]
+    UnknownParameter(String),
+    #[error("Query parameter {0} given more than once")]
+    DuplicateParameter(String),
+    #[error("Missing query parameter {0}")]
+    MissingParameter(&'static str),
+    #[error("Invalid value {value:?} for query parameter {name}: {reason}")]
+    InvalidValue {
+        name: &'static str,
+        value: String,
+        reason: String,
+    },
+    #[error("Invalid percent-encoding in {0:?}")]
+    InvalidEncoding(String),
+}
+
+impl QuoteRequest {
+    /// The parameters sent to `/quote`. Unset options and zero slippage are
+    /// left out for the server's defaults.
+    #[allow(deprecated)]
+    pub fn query_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("inputMint", self.input_mint.to_string()),
+            ("outputMint", self.output_mint.to_string()),
+            ("amount", self.amount.to_string()),
+        ];
+
+        if let Some(user_pubkey) = &self.user_pubkey {
+            params.push(("userPublicKey", user_pubkey.to_string()));
+        }
+
+        if let Some(total) = self.account_limits.total {
+            params.push(("accountsLimitTotal", total.to_string()));
+        }
+        if let Some(swap_mode) = &self.swap_mode {
+            params.push(("swapMode", swap_mode.to_string()));
+        }
+        if self.slippage_bps > 0 {
+            params.push(("slippageBps", self.slippage_bps.to_string()));
+        }
+        if let Some(only_direct_routes) = self.only_direct_routes {
+            params.push(("onlyDirectRoutes", only_direct_routes.to_string()));
+        }
+        if let Some(ref excluded_dexes) = self.excluded_dexes {
+            params.push(("excludeDexes", venues::canonicalize_dexes(excluded_dexes)));
+        }
+        if let Some(size_constraint) = self
+            .size_constraint
+            .map(u64::from)
+            .or(self.size_constraints)
+        {
+            params.push(("sizeConstraint", size_constraint.to_string()));
+        }
+        if let Some(writable) = self.account_limits.writable {
+            params.push(("accountsLimitWritable", writable.to_string()));
+        }
+
+        if let Some(ref providers) = self.providers {
+            params.push(("providers", providers.to_string()));
+        }
+        if let Some(referral_account) = &self.referral_account {
+            params.push(("referralAccount", referral_account.to_string()));
+        }
+        if let Some(referral_fee_bps) = self.referral_fee_bps {
+            params.push(("referralFeeBps", referral_fee_bps.to_string()));
+        }
+
+        params
+    }
+
+    /// `query_params` encoded as `application/x-www-form-urlencoded`, without
+    /// a leading `?`.
+    pub fn to_query_string(&self) -> String {
+        self.query_params()
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, encode(value)))
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    /// Identifies the quote this request gets: the query string, plus the
+    /// client-side filters that aren't sent. Two requests with the same key
+    /// may share a cached or in-flight quote.
+    pub fn cache_key(&self) -> String {
+        self.to_query_string() + &client_side_key(self)
+    }
+
+    /// The inverse of `to_query_string`. Anything up to a `?` is ignored, so
+    /// a whole URL can be passed.
+    pub fn from_query_str(query: &str) -> Result<Self, ParseError> {
+        let query = query.split_once('?').map_or(query, |(_, query)| query);
+        let mut request = QuoteRequest::default();
+        let mut seen = Vec::new();
+        let (mut input_mint, mut output_mint, mut amount) = (None, None, None);
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let name = decode(name)?;
+            let value = decode(value)?;
+            if seen.contains(&name) {
+                return Err(ParseError::DuplicateParameter(name));
+            }
+
+            match name.as_str() {
+                "inputMint" => input_mint = Some(parse::<Pubkey>("inputMint", &value)?),
+                "outputMint" => output_mint = Some(parse::<Pubkey>("outputMint", &value)?),
+                "amount" => amount = Some(parse("amount", &value)?),
+                "userPublicKey" => request.user_pubkey = Some(parse("userPublicKey", &value)?),
+                "accountsLimitTotal" => {
+                    request.account_limits.total = Some(parse_limit("accountsLimitTotal", &value)?)
+                }
+                "swapMode" => {
+                    if value.is_empty() {
+                        return Err(invalid("swapMode", &value, "empty"));
+                    }
+                    request.swap_mode = Some(
+                        value
+                            .parse()
+                            .unwrap_or_else(|_| SwapMode::Other(value.clone())),
+                    );
+                }
+                "slippageBps" => request.slippage_bps = parse("slippageBps", &value)?,
+                "onlyDirectRoutes" => {
+                    request.only_direct_routes = Some(parse("onlyDirectRoutes", &value)?)
+                }
+                "excludeDexes" => request.excluded_dexes = Some(value.clone()),
+                "sizeConstraint" => {
+                    let bytes: u64 = parse("sizeConstraint", &value)?;
+                    request.size_constraint = Some(
+                        SizeConstraint::try_from(bytes)
+                            .map_err(|err| invalid("sizeConstraint", &value, err))?,
+                    );
+                }
+                "accountsLimitWritable" => {
+                    request.account_limits.writable =
+                        Some(parse_limit("accountsLimitWritable", &value)?)
+                }
+                "providers" => match value.as_str() {
+                    "Titan" => request.providers = Some(Provider::Titan),
+                    _ => return Err(invalid("providers", &value, "expected Titan")),
+                },
+                "referralAccount" => {
+                    request.referral_account = Some(parse("referralAccount", &value)?)
+                }
+                "referralFeeBps" => {
+                    request.referral_fee_bps = Some(parse("referralFeeBps", &value)?)
+                }
+                _ => return Err(ParseError::UnknownParameter(name)),
+            }
+            seen.push(name);
+        }
+
+        request.input_mint = input_mint.ok_or(ParseError::MissingParameter("inputMint"))?;
+        request.output_mint = output_mint.ok_or(ParseError::MissingParameter("outputMint"))?;
+        request.amount = amount.ok_or(ParseError::MissingParameter("amount"))?;
+        if let Some(writable) = request.account_limits.writable {
+            request
+                .account_limits
+                .validate()
+                .map_err(|err| invalid("accountsLimitWritable", &writable.to_string(), err))?;
+        }
+        if let Some(fee_bps) = request.referral_fee_bps {
+            request
+                .validate_referral()
+                .map_err(|err| invalid("referralFeeBps", &fee_bps.to_string(), err))?;
+        }
+        Ok(request)
+    }
+}
+
+/// Where `query_params` puts the amount.
+const AMOUNT_PARAM: usize = 2;
+
+/// A [`QuoteRequest`] whose query parameters are encoded once, for sending
+/// repeatedly with only the amount changed. Formatting pubkeys dominates the
+/// cost of `query_params`; the template only formats the amount.
+#[derive(Debug, Clone)]
+pub struct QuoteRequestTemplate {
+    request: QuoteRequest,
+    params: Vec<(&'static str, String)>,
+    /// `to_query_string` either side of the amount's value.
+    query_prefix: String,
+    query_suffix: String,
+}
+
+impl QuoteRequestTemplate {
+    pub fn new(request: QuoteRequest) -> Self {
+        let params = request.query_params();
+        debug_assert_eq!(params[AMOUNT_PARAM].0, "amount");
+        let encoded = |params: &[(&str, String)]| {
+            params
+                .iter()
+                .map(|(name, value)| format!("{}={}&", name, encode(value)))
+                .collect::<String>()
+        };
+        let query_prefix = encoded(&params[..AMOUNT_PARAM]) + "amount=";
+        let mut query_suffix = encoded(&params[AMOUNT_PARAM + 1..]);
+        query_suffix.pop();
+        if !query_suffix.is_empty() {
+            query_suffix.insert(0, '&');
+        }
+
+        Self {
+            request,
+            params,
+            query_prefix,
+            query_suffix,
+        }
+    }
+
+    /// The request the template was made from, with its original amount.
+    pub fn request(&self) -> &QuoteRequest {
+        &self.request
+    }
+
+    pub fn request_with_amount(&self, amount: u64) -> QuoteRequest {
+        QuoteRequest {
+            amount,
+            ..self.request.clone()
+        }
+    }
+
+    /// Equal to `request_with_amount(amount).query_params()`.
+    pub fn query_params_with_amount(&self, amount: u64) -> Vec<(&'static str, String)> {
+        let mut params = self.params.clone();
+        params[AMOUNT_PARAM].1 = amount.to_string();
+        params
+    }
+
+    /// Equal to `request_with_amount(amount).cache_key()`.
+    pub fn cache_key_with_amount(&self, amount: u64) -> String {
+        self.query_string_with_amount(amount) + &client_side_key(&self.request)
+    }
+
+    /// Equal to `request_with_amount(amount).to_query_string()`.
+    pub fn query_string_with_amount(&self, amount: u64) -> String {
+        let amount = amount.to_string();
+        let mut query =
+            String::with_capacity(self.query_prefix.len() + amount.len() + self.query_suffix.len());
+        query.push_str(&self.query_prefix);
+        query.push_str(&amount);
+        query.push_str(&self.query_suffix);
+        query
+    }
+}
+
+impl From<QuoteRequest> for QuoteRequestTemplate {
+    fn from(request: QuoteRequest) -> Self {
+        Self::new(request)
+    }
+}
+
+#[cfg(feature = "native")]
+impl QuoteRequestTemplate {
+    /// Like `client.quote(&self.request_with_amount(amount))`, without
+    /// re-encoding the rest of the request.
+    pub async fn quote_with_amount(
+        &self,
+        client: &crate::TitanClient,
+        amount: u64,
+    ) -> Result<crate::quote::QuoteResponse, crate::ClientError> {
+        self.quote_with_amount_and_options(client, amount, &Default::default())
+            .await
+    }
+
+    pub async fn quote_with_amount_and_options(
+        &self,
+        client: &crate::TitanClient,
+        amount: u64,
+        options: &crate::options::QuoteRequestOptions,
+    ) -> Result<crate::quote::QuoteResponse, crate::ClientError> {
+        client
+            .quote_with_params(
+                &self.request_with_amount(amount),
+                &self.query_params_with_amount(amount),
+                options,
+            )
+            .await
+    }
+}
+
+// `excluded_amm_keys` filters routes once received, so requests differing
+// only in it send the same query but can get different quotes.
+fn client_side_key(request: &QuoteRequest) -> String {
+    match &request.excluded_amm_keys {
+        Some(keys) => {
+            let mut keys: Vec<String> = keys.iter().map(ToString::to_string).collect();
+            keys.sort_unstable();
+            keys.dedup();
+            format!("#excludedAmmKeys={}", keys.join(","))
+        }
+        None => String::new(),
+    }
+}
+
+fn parse<T>(name: &'static str, value: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|err: T::Err| invalid(name, value, err))
+}
+
+// Each limit alone must be within the lock limit; the two are checked
+// against each other once both are parsed.
+fn parse_limit(name: &'static str, value: &str) -> Result<usize, ParseError> {
+    let limit = parse(name, value)?;
+    AccountLimits::with_total(limit)
+        .validate()
+        .map_err(|err| invalid(name, value, err))?;
+    Ok(limit)
+}
+
+fn invalid(name: &'static str, value: &str, reason: impl Display) -> ParseError {
+    ParseError::InvalidValue {
+        name,
+        value: value.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+// Percent-encodes everything but the unreserved characters, as
+// `form_urlencoded` does apart from writing spaces as `+`.
+pub(crate) fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'*' => {
+                encoded.push(byte as char)
+            }
+            b' ' => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn decode(value: &str) -> Result<String, ParseError> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let byte = value
+                    .get(i + 1..i + 3)
+                    .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| ParseError::InvalidEncoding(value.to_string()))?;
+                decoded.push(byte);
+                i += 2;
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8(decoded).map_err(|_| ParseError::InvalidEncoding(value.to_string()))
+}
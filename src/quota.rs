@@ -0,0 +1,82 @@
+//! Quota usage reported by the server in response headers.
+
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+#[cfg(feature = "native")]
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Reset values at or above this are Unix timestamps; below it, seconds
+/// from now.
+#[cfg(feature = "native")]
+const EPOCH_RESET_THRESHOLD: u64 = 1_000_000_000;
+
+/// What the server last reported about the client's quota. Each field is
+/// `None` when its header was absent or unparseable.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaInfo {
+    pub remaining: Option<u64>,
+    pub limit: Option<u64>,
+    pub reset_at: Option<SystemTime>,
+}
+
+impl QuotaInfo {
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == Some(0)
+    }
+}
+
+/// The response headers [`QuotaInfo`] is read from. The reset header may
+/// hold either a Unix timestamp or a number of seconds from now.
+#[cfg(feature = "native")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuotaHeaders {
+    pub remaining: reqwest::header::HeaderName,
+    pub limit: reqwest::header::HeaderName,
+    pub reset: reqwest::header::HeaderName,
+}
+
+#[cfg(feature = "native")]
+impl Default for QuotaHeaders {
+    fn default() -> Self {
+        Self {
+            remaining: reqwest::header::HeaderName::from_static("x-ratelimit-remaining"),
+            limit: reqwest::header::HeaderName::from_static("x-ratelimit-limit"),
+            reset: reqwest::header::HeaderName::from_static("x-ratelimit-reset"),
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+impl QuotaHeaders {
+    /// `None` when none of the headers is present.
+    pub fn parse(&self, headers: &reqwest::header::HeaderMap) -> Option<QuotaInfo> {
+        self.parse_at(headers, SystemTime::now())
+    }
+
+    /// Like [`parse`](Self::parse), with a reset given in seconds counted
+    /// from `now`.
+    pub fn parse_at(
+        &self,
+        headers: &reqwest::header::HeaderMap,
+        now: SystemTime,
+    ) -> Option<QuotaInfo> {
+        let number = |name: &reqwest::header::HeaderName| {
+            headers.get(name)?.to_str().ok()?.trim().parse::<u64>().ok()
+        };
+        let present = [&self.remaining, &self.limit, &self.reset]
+            .into_iter()
+            .any(|name| headers.contains_key(name));
+        present.then(|| QuotaInfo {
+            remaining: number(&self.remaining),
+            limit: number(&self.limit),
+            reset_at: number(&self.reset).map(|reset| {
+                if reset >= EPOCH_RESET_THRESHOLD {
+                    UNIX_EPOCH + Duration::from_secs(reset)
+                } else {
+                    now + Duration::from_secs(reset)
+                }
+            }),
+        })
+    }
+}
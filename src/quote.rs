@@ -1,37 +1,90 @@
+use std::fmt;
 use std::str::FromStr;
+use std::sync::Arc;
 
-use crate::serde_helpers::field_as_string;
-use anyhow::{anyhow, Error};
-use serde::{Deserialize, Serialize};
-use solana_sdk::pubkey::Pubkey;
+use crate::account_limits::AccountLimits;
+use crate::amount_math;
+use crate::pubkey::Pubkey;
+use crate::pubkey_from_bytes;
+use crate::quota::QuotaInfo;
+#[cfg(feature = "native")]
+use crate::serde_helpers::lenient::Lenient;
+use crate::serde_helpers::{bytes_as_base64, field_as_string, vec_field_as_string};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
 
-#[derive(Serialize, Deserialize, Default, PartialEq, Clone, Debug)]
+/// Decodes any spelling [`FromStr`] accepts. Modes this version doesn't know
+/// decode to `Other` rather than failing, so a new server-side mode doesn't
+/// break every quote.
+/// Not `Copy`, as `Other` carries the server's spelling.
+#[derive(Default, PartialEq, Eq, Hash, Clone, Debug)]
 pub enum SwapMode {
     #[default]
     ExactIn,
     ExactOut,
+    Other(String),
 }
 
+impl SwapMode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::ExactIn => "ExactIn",
+            Self::ExactOut => "ExactOut",
+            Self::Other(mode) => mode,
+        }
+    }
+}
+
+impl Serialize for SwapMode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for SwapMode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mode = String::deserialize(deserializer)?;
+        Ok(mode.parse().unwrap_or(Self::Other(mode)))
+    }
+}
+
+/// Accepts any casing with or without separators: `ExactIn`, `exactIn`,
+/// `EXACT_IN` and `exact-in` all parse.
 impl FromStr for SwapMode {
-    type Err = Error;
+    type Err = ParseSwapModeError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "ExactIn" => Ok(Self::ExactIn),
-            "ExactOut" => Ok(Self::ExactOut),
-            _ => Err(anyhow!("{} is not a valid SwapMode", s)),
+        let normalized: String = s
+            .chars()
+            .filter(|c| *c != '_' && *c != '-')
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        match normalized.as_str() {
+            "exactin" => Ok(Self::ExactIn),
+            "exactout" => Ok(Self::ExactOut),
+            _ => Err(ParseSwapModeError(s.to_string())),
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+impl fmt::Display for SwapMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("{0} is not a valid SwapMode")]
+pub struct ParseSwapModeError(pub String);
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RoutePlanStep {
     pub swap_info: SwapInfo,
     pub percent: u8,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[serde(rename_all = "camelCase")]
 pub struct SwapInfo {
     #[serde(with = "field_as_string")]
@@ -47,20 +100,47 @@ pub struct SwapInfo {
     pub out_amount: u64,
     #[serde(default)]
     pub alloc_ppb: u64,
-    #[serde(with = "field_as_string", default)]
-    pub fee_mint: Pubkey,
-    #[serde(with = "field_as_string", default)]
-    pub fee_amount: u64,
+    /// Unset when the step doesn't report a fee, as opposed to a zero fee.
+    #[serde(
+        with = "field_as_string::option",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee_mint: Option<Pubkey>,
+    #[serde(
+        with = "field_as_string::option",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee_amount: Option<u64>,
+    /// The slot this step's pool state was read at, when the step reports one.
+    /// See `QuoteResponse::context_slot` for the route-level slot.
     #[serde(default)]
-    pub context_slot: u64,
+    pub context_slot: Option<u64>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct PlatformFee {
     #[serde(with = "field_as_string")]
     pub amount: u64,
-    pub fee_bps: u8,
+    pub fee_bps: u16,
+}
+
+/// How much of each route a quote decodes. `PriceOnly` skips the
+/// instructions, lookup tables and transaction, which make up most of a
+/// response; such a quote prices the route but cannot be swapped.
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuoteDetail {
+    #[default]
+    Full,
+    PriceOnly,
+}
+
+impl QuoteDetail {
+    fn is_full(&self) -> bool {
+        *self == QuoteDetail::Full
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -77,28 +157,343 @@ pub struct QuoteResponse {
     pub swap_mode: SwapMode,
     pub slippage_bps: u16,
     pub platform_fee: Option<PlatformFee>,
-    pub raw_route: SwapRoute,
+    /// The share of the fee paid to the request's `referral_account`,
+    /// reported separately from `platform_fee`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub referral_fee: Option<PlatformFee>,
+    /// The route's instructions, lookup tables and transaction, shared
+    /// between clones. `None` once dropped with `without_route_data`; such a
+    /// quote can still be compared and displayed but not swapped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_route: Option<Arc<QuoteRouteData>>,
+    /// The route's steps in execution order.
     pub route_plan: Vec<RoutePlanStep>,
     #[serde(default)]
     pub context_slot: Option<u64>,
+    #[deprecated(note = "use `timing` instead")]
     #[serde(default)]
     pub time_taken: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<QuoteTiming>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_check: Option<PriceCheck>,
+    /// The quota the server reported alongside this quote.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota: Option<QuotaInfo>,
+    /// Route fields that were dropped by lenient decoding, and step fields
+    /// the route left out that are filled in or ignored instead; see
+    /// [`DecodeWarning`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<DecodeWarning>,
+    /// The key this route had in the response's quotes map, e.g. `Titan`.
+    /// Unset for quotes built directly with `from_route`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Whether the output mint is owned by Token-2022, once looked up with
+    /// `resolve_output_mint`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_is_token2022: Option<bool>,
+    /// Quoted without a user public key: good for pricing, but its
+    /// instructions are not built for a signer and it cannot be swapped.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub indicative: bool,
+    /// `PriceOnly` when decoded without execution data; see [`QuoteDetail`].
+    #[serde(default, skip_serializing_if = "QuoteDetail::is_full")]
+    pub detail: QuoteDetail,
+}
+
+/// A response field that could not be decoded and was left unset, or a
+/// route step field that was missing or zero:
+///
+/// - `feeMint`: no fee mint reported, so `SwapInfo::fee_mint` is `None`.
+/// - `contextSlot`: not reported, so `SwapInfo::context_slot` is `None` even
+///   when the route has a slot.
+/// - `allocPpb`: an allocation of zero, read as the whole amount.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DecodeWarning {
+    pub field: String,
+    pub message: String,
+    /// Index into `route_plan` of the step the field belongs to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step: Option<usize>,
+}
+
+/// Where the time went for a single `quote()` call. `server_ns` is the
+/// routing time reported by Titan; the rest are measured client-side.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteTiming {
+    pub server_ns: Option<u64>,
+    pub http_ms: u64,
+    pub decode_ms: u64,
+    pub total_ms: u64,
+}
+
+/// How far a quote's execution price is from the spot reference price.
+/// Negative deviations mean the quote is worse than the reference.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceCheck {
+    pub reference_price: f64,
+    pub deviation_bps: f64,
+    pub flagged: bool,
+}
+
+impl PriceCheck {
+    pub fn new(quote: &QuoteResponse, reference_price: f64, max_deviation_bps: u16) -> Self {
+        let in_amount = quote.input_amount();
+        let deviation_bps = if in_amount == 0 || reference_price <= 0.0 {
+            0.0
+        } else {
+            let quoted = quote.out_amount as f64 / in_amount as f64;
+            (quoted - reference_price) / reference_price * 10_000.0
+        };
+
+        PriceCheck {
+            reference_price,
+            deviation_bps,
+            flagged: deviation_bps.abs() > f64::from(max_deviation_bps),
+        }
+    }
+}
+
+impl QuoteResponse {
+    #[allow(deprecated)]
+    pub fn from_route(request: &QuoteRequest, route: SwapRoute) -> Self {
+        let route_plan: Vec<_> = route.steps.iter().map(transform_step).collect();
+        let mut warnings = route.warnings.clone();
+        step_warnings(&route, &mut warnings);
+
+        QuoteResponse {
+            input_mint: request.input_mint,
+            in_amount: request.amount,
+            output_mint: request.output_mint,
+            out_amount: route.out_amount,
+            swap_mode: request.swap_mode.clone().unwrap_or_default(),
+            slippage_bps: route.slippage_bps,
+            platform_fee: route.platform_fee.as_ref().map(|pf| PlatformFee {
+                amount: pf.amount,
+                fee_bps: pf.fee_bps,
+            }),
+            referral_fee: route.referral_fee.as_ref().map(|rf| PlatformFee {
+                amount: rf.amount,
+                fee_bps: rf.fee_bps,
+            }),
+            route_plan,
+            context_slot: route.context_slot,
+            time_taken: route.time_taken_ns.map(amount_math::ns_to_secs),
+            timing: None,
+            price_check: None,
+            quota: None,
+            warnings,
+            provider: None,
+            output_is_token2022: None,
+            indicative: request.user_pubkey.is_none(),
+            detail: if route.price_only {
+                QuoteDetail::PriceOnly
+            } else {
+                QuoteDetail::Full
+            },
+            raw_route: Some(Arc::new(route)),
+        }
+    }
+
+    /// Whether [`swap`](crate::TitanClient::swap) can build this quote: it was
+    /// made for a user, fully decoded, and still has its route data.
+    pub fn is_executable(&self) -> bool {
+        !self.indicative && self.detail == QuoteDetail::Full && self.raw_route.is_some()
+    }
+
+    pub fn route_data(&self) -> Option<&Arc<QuoteRouteData>> {
+        self.raw_route.as_ref()
+    }
+
+    /// Copies the route data first if another clone shares it.
+    pub fn route_data_mut(&mut self) -> Option<&mut QuoteRouteData> {
+        self.raw_route.as_mut().map(Arc::make_mut)
+    }
+
+    /// The quote without its route data, for consumers that only need the
+    /// amounts and route plan.
+    pub fn without_route_data(mut self) -> Self {
+        self.raw_route = None;
+        self
+    }
+
+    /// The route's actual input, which for ExactOut differs from the
+    /// requested `in_amount`. Falls back to `in_amount` once the route data
+    /// is dropped.
+    pub fn input_amount(&self) -> u64 {
+        self.raw_route
+            .as_ref()
+            .map_or(self.in_amount, |route| route.in_amount)
+    }
+
+    /// The least the swap can output: `out_amount` less slippage for
+    /// ExactIn, or `out_amount` itself for ExactOut, where the output is
+    /// fixed.
+    pub fn min_out_amount(&self) -> u64 {
+        match self.swap_mode {
+            SwapMode::ExactOut => self.out_amount,
+            SwapMode::ExactIn | SwapMode::Other(_) => {
+                amount_math::min_out_after_slippage(self.out_amount, self.slippage_bps)
+            }
+        }
+    }
+
+    /// The mint the platform fee is charged in, if there is one: the side the
+    /// user didn't fix, so the input for ExactOut and the output otherwise.
+    pub fn platform_fee_mint(&self) -> Option<Pubkey> {
+        self.platform_fee.map(|_| match self.swap_mode {
+            SwapMode::ExactOut => self.input_mint,
+            SwapMode::ExactIn | SwapMode::Other(_) => self.output_mint,
+        })
+    }
+
+    /// What the user receives once a platform fee charged in the output
+    /// mint is taken: the route's gross output, summed over the final hop's
+    /// steps, less the fee. Titan reports `out_amount` net of the fee, so
+    /// the two agree for a well-formed response. Without a route plan
+    /// `out_amount` is taken as the gross output.
+    pub fn net_out_amount(&self) -> u64 {
+        let Some(fee) = self.platform_fee else {
+            return self.out_amount;
+        };
+        if self.platform_fee_mint() != Some(self.output_mint) {
+            return self.out_amount;
+        }
+        let gross = if self.route_plan.is_empty() {
+            self.out_amount
+        } else {
+            self.route_plan
+                .iter()
+                .map(|step| &step.swap_info)
+                .filter(|info| info.output_mint == self.output_mint)
+                .fold(0u64, |total, info| total.saturating_add(info.out_amount))
+        };
+        gross.saturating_sub(fee.amount)
+    }
+
+    /// The most the swap can take as input: the route's input plus slippage
+    /// for ExactOut, or the input itself for ExactIn. Capped at `u64::MAX`.
+    pub fn max_in_amount(&self) -> u64 {
+        match self.swap_mode {
+            SwapMode::ExactOut => {
+                amount_math::max_in_after_slippage(self.input_amount(), self.slippage_bps)
+                    .unwrap_or(u64::MAX)
+            }
+            SwapMode::ExactIn | SwapMode::Other(_) => self.input_amount(),
+        }
+    }
+
+    pub fn with_provider(mut self, provider: impl Into<String>) -> Self {
+        self.provider = Some(provider.into());
+        self
+    }
+
+    /// The provider that produced this route, when known.
+    pub fn provider(&self) -> Option<&str> {
+        self.provider.as_deref()
+    }
+
+    /// The earliest slot any step's pool state was read at, falling back to
+    /// the route-level slot when no step reports its own.
+    pub fn oldest_context_slot(&self) -> Option<u64> {
+        self.step_context_slots().min().or(self.context_slot)
+    }
+
+    /// The latest slot any step's pool state was read at, falling back to the
+    /// route-level slot when no step reports its own.
+    pub fn newest_context_slot(&self) -> Option<u64> {
+        self.step_context_slots().max().or(self.context_slot)
+    }
+
+    fn step_context_slots(&self) -> impl Iterator<Item = u64> + '_ {
+        self.route_plan
+            .iter()
+            .filter_map(|step| step.swap_info.context_slot)
+    }
+
+    pub fn to_json_string(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json_str(s: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(s)
+    }
 }
 
 type Dexes = String;
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Provider {
     Titan,
 }
 
-impl ToString for Provider {
-    fn to_string(&self) -> String {
-        "Titan".to_string()
+impl fmt::Display for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Titan")
+    }
+}
+
+/// The largest transaction Solana accepts, in bytes.
+pub const MAX_TRANSACTION_BYTES: u16 = 1232;
+
+/// The most bytes the route's serialized transaction may take, sent as
+/// `sizeConstraint`. Constrain it to leave room for instructions of your
+/// own in the same transaction.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(try_from = "u64", into = "u64")]
+pub struct SizeConstraint(u16);
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+#[error("Size constraint of {0} bytes is outside 1..=1232")]
+pub struct SizeConstraintError(pub u64);
+
+impl SizeConstraint {
+    pub fn max_transaction_bytes(bytes: u16) -> Result<Self, SizeConstraintError> {
+        Self::try_from(u64::from(bytes))
+    }
+
+    /// Leaves `bytes` of the largest transaction free.
+    pub fn reserving_bytes(bytes: u16) -> Result<Self, SizeConstraintError> {
+        MAX_TRANSACTION_BYTES
+            .checked_sub(bytes)
+            .ok_or(SizeConstraintError(0))
+            .and_then(Self::max_transaction_bytes)
+    }
+
+    pub fn bytes(self) -> u16 {
+        self.0
     }
 }
 
-#[derive(Serialize, Debug, Default, Clone)]
+impl TryFrom<u64> for SizeConstraint {
+    type Error = SizeConstraintError;
+
+    fn try_from(bytes: u64) -> Result<Self, Self::Error> {
+        match u16::try_from(bytes) {
+            Ok(bytes @ 1..=MAX_TRANSACTION_BYTES) => Ok(Self(bytes)),
+            _ => Err(SizeConstraintError(bytes)),
+        }
+    }
+}
+
+impl From<SizeConstraint> for u64 {
+    fn from(constraint: SizeConstraint) -> Self {
+        u64::from(constraint.0)
+    }
+}
+
+impl fmt::Display for SizeConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Deserializes from the same camelCase form it serializes to, so requests
+/// can be kept in config files. Only the mints and amount are required.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct QuoteRequest {
     #[serde(with = "field_as_string")]
@@ -107,32 +502,214 @@ pub struct QuoteRequest {
     pub output_mint: Pubkey,
     #[serde(with = "field_as_string")]
     pub amount: u64,
-    #[serde(with = "field_as_string")]
-    pub user_pubkey: Pubkey,
-    pub max_accounts: Option<usize>,
+    /// The swap signer. Leave unset for indicative quotes, which price the
+    /// route but cannot be swapped.
+    #[serde(
+        with = "field_as_string::option",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub user_pubkey: Option<Pubkey>,
+    /// Serialized flattened, as `maxAccounts` and `accountsLimitWritable`.
+    #[serde(flatten)]
+    pub account_limits: AccountLimits,
+    #[serde(default)]
     pub swap_mode: Option<SwapMode>,
+    #[serde(default)]
     pub slippage_bps: u16,
+    #[serde(default)]
     pub only_direct_routes: Option<bool>,
+    #[serde(default)]
     pub excluded_dexes: Option<Dexes>,
+    /// Pools to avoid. Not sent to the API: routes with a step through any
+    /// of them are discarded when the response is received.
+    #[serde(with = "vec_field_as_string::option", default)]
+    pub excluded_amm_keys: Option<Vec<Pubkey>>,
+    #[serde(default)]
+    pub size_constraint: Option<SizeConstraint>,
+    /// Sent unchecked as `sizeConstraint` when `size_constraint` is unset.
+    #[deprecated(note = "use `size_constraint` instead")]
+    #[serde(default)]
     pub size_constraints: Option<u64>,
-    pub accounts_limit_writable: Option<u64>,
+    #[serde(default)]
     pub providers: Option<Provider>,
+    /// The referral program account credited for this swap, sent as
+    /// `referralAccount`. Titan pays it in place of a platform fee account.
+    #[serde(
+        with = "field_as_string::option",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub referral_account: Option<Pubkey>,
+    /// The referral fee, sent as `referralFeeBps`. Needs `referral_account`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub referral_fee_bps: Option<u16>,
+    /// Set by `without_defaults`. Not sent to the API.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip_defaults: bool,
+}
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum ReferralError {
+    #[error("Referral fee of {0} bps set without a referral account")]
+    FeeWithoutAccount(u16),
+    #[error("Referral fee of {0} bps exceeds 10000")]
+    FeeOverMax(u16),
+    /// Titan pays a referred swap's fee to the referral account, so a swap
+    /// can't also name a platform fee account.
+    #[error("Referral quote can't be swapped with a platform fee account")]
+    FeeAccountConflict,
+}
+
+impl QuoteRequest {
+    /// Checks `referral_fee_bps` is at most 10000 and only set alongside
+    /// `referral_account`.
+    pub fn validate_referral(&self) -> Result<(), ReferralError> {
+        match (self.referral_account, self.referral_fee_bps) {
+            (None, Some(fee_bps)) => Err(ReferralError::FeeWithoutAccount(fee_bps)),
+            (Some(_), Some(fee_bps)) if fee_bps > 10_000 => Err(ReferralError::FeeOverMax(fee_bps)),
+            _ => Ok(()),
+        }
+    }
 }
 
 type MsgpackPubkey = [u8; 32];
 
 #[allow(dead_code)]
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SwapQuotes {
     pub id: String,
+    #[serde(with = "bytes_as_base64")]
     pub input_mint: MsgpackPubkey,
+    #[serde(with = "bytes_as_base64")]
     pub output_mint: MsgpackPubkey,
     pub swap_mode: SwapMode,
     pub amount: u64,
-    pub quotes: std::collections::HashMap<String, SwapRoute>,
+    /// Routes keyed by provider. Ordered by provider name, so iteration and
+    /// serialization are the same on every run.
+    pub quotes: std::collections::BTreeMap<QuoteKey, SwapRoute>,
 }
 
+/// A key of [`SwapQuotes::quotes`]. Titan keys each route by the name of the
+/// provider that produced it, e.g. `Titan`, which may be followed by
+/// `:`-separated segments: a variant, then any params, as in
+/// `Titan:split:v2`. A key that doesn't fit, such as an empty key or one with
+/// an empty or padded segment, is kept as `Raw`. Either way it displays and
+/// serializes as the string it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum QuoteKey {
+    Provider {
+        provider: String,
+        variant: Option<String>,
+        params: Vec<String>,
+    },
+    Raw(String),
+}
+
+impl QuoteKey {
+    pub fn parse(key: &str) -> Self {
+        let valid = |segment: &&str| {
+            !segment.is_empty()
+                && segment.trim() == *segment
+                && !segment.chars().any(char::is_control)
+        };
+        let segments: Vec<&str> = key.split(':').collect();
+        if !segments.iter().all(valid) {
+            return Self::Raw(key.to_string());
+        }
+
+        let mut segments = segments.into_iter().map(str::to_string);
+        Self::Provider {
+            provider: segments.next().expect("split yields a segment"),
+            variant: segments.next(),
+            params: segments.collect(),
+        }
+    }
+
+    pub fn provider(&self) -> Option<&str> {
+        match self {
+            Self::Provider { provider, .. } => Some(provider),
+            Self::Raw(_) => None,
+        }
+    }
+
+    pub fn variant(&self) -> Option<&str> {
+        match self {
+            Self::Provider { variant, .. } => variant.as_deref(),
+            Self::Raw(_) => None,
+        }
+    }
+
+    pub fn params(&self) -> &[String] {
+        match self {
+            Self::Provider { params, .. } => params,
+            Self::Raw(_) => &[],
+        }
+    }
+
+    /// Whether routes under this key come from `provider`, ignoring case. A
+    /// raw key matches only if it equals `provider` as a whole.
+    pub fn matches_provider(&self, provider: &str) -> bool {
+        self.attribution().eq_ignore_ascii_case(provider)
+    }
+
+    /// The name to attribute routes under this key to: the provider, or the
+    /// whole key if it is raw.
+    pub fn attribution(&self) -> &str {
+        match self {
+            Self::Provider { provider, .. } => provider,
+            Self::Raw(key) => key,
+        }
+    }
+}
+
+impl From<&str> for QuoteKey {
+    fn from(key: &str) -> Self {
+        Self::parse(key)
+    }
+}
+
+impl From<String> for QuoteKey {
+    fn from(key: String) -> Self {
+        Self::parse(&key)
+    }
+}
+
+impl fmt::Display for QuoteKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Provider {
+                provider,
+                variant,
+                params,
+            } => {
+                f.write_str(provider)?;
+                for segment in variant.iter().chain(params) {
+                    write!(f, ":{}", segment)?;
+                }
+                Ok(())
+            }
+            Self::Raw(key) => f.write_str(key),
+        }
+    }
+}
+
+impl Serialize for QuoteKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for QuoteKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// A [`SwapRoute`] as held by a [`QuoteResponse`].
+pub type QuoteRouteData = SwapRoute;
+
 #[allow(dead_code)]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -142,8 +719,12 @@ pub struct SwapRoute {
     pub slippage_bps: u16,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) platform_fee: Option<PlatformFeeData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) referral_fee: Option<PlatformFeeData>,
+    /// In execution order.
     pub(crate) steps: Vec<RoutePlanStepData>,
     pub(crate) instructions: Vec<InstructionData>,
+    #[serde(with = "bytes_as_base64::vec")]
     pub address_lookup_tables: Vec<MsgpackPubkey>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context_slot: Option<u64>,
@@ -157,24 +738,301 @@ pub struct SwapRoute {
     pub compute_units: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub compute_units_safe: Option<u64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        with = "bytes_as_base64::option",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub transaction: Option<Vec<u8>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reference_id: Option<String>,
+    #[serde(skip)]
+    pub warnings: Vec<DecodeWarning>,
+    /// Decoded without its instructions, lookup tables and transaction.
+    #[serde(skip)]
+    pub(crate) price_only: bool,
+}
+
+/// An optional [`SwapRoute`] field that
+/// [`TitanClientBuilder::required_route_fields`](crate::TitanClientBuilder::required_route_fields)
+/// can make mandatory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RouteField {
+    ContextSlot,
+    TimeTakenNs,
+    ExpiresAtMs,
+    ExpiresAfterSlot,
+    ComputeUnits,
+    ComputeUnitsSafe,
+}
+
+impl RouteField {
+    /// The field's name on the wire.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RouteField::ContextSlot => "contextSlot",
+            RouteField::TimeTakenNs => "timeTakenNs",
+            RouteField::ExpiresAtMs => "expiresAtMs",
+            RouteField::ExpiresAfterSlot => "expiresAfterSlot",
+            RouteField::ComputeUnits => "computeUnits",
+            RouteField::ComputeUnitsSafe => "computeUnitsSafe",
+        }
+    }
+
+    pub fn is_set(self, route: &SwapRoute) -> bool {
+        match self {
+            RouteField::ContextSlot => route.context_slot.is_some(),
+            RouteField::TimeTakenNs => route.time_taken_ns.is_some(),
+            RouteField::ExpiresAtMs => route.expires_at_ms.is_some(),
+            RouteField::ExpiresAfterSlot => route.expires_after_slot.is_some(),
+            RouteField::ComputeUnits => route.compute_units.is_some(),
+            RouteField::ComputeUnitsSafe => route.compute_units_safe.is_some(),
+        }
+    }
+}
+
+impl fmt::Display for RouteField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// [`SwapQuotes`] with each route's optional fields decoded independently:
+/// one that fails to decode is left unset and reported in `warnings`.
+#[cfg(feature = "native")]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LenientSwapQuotes {
+    id: String,
+    #[serde(with = "bytes_as_base64")]
+    input_mint: MsgpackPubkey,
+    #[serde(with = "bytes_as_base64")]
+    output_mint: MsgpackPubkey,
+    swap_mode: SwapMode,
+    amount: u64,
+    quotes: std::collections::BTreeMap<String, LenientSwapRoute>,
+}
+
+#[cfg(feature = "native")]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LenientSwapRoute {
+    in_amount: u64,
+    out_amount: u64,
+    slippage_bps: u16,
+    #[serde(default)]
+    platform_fee: Lenient<Option<PlatformFeeData>>,
+    #[serde(default)]
+    referral_fee: Lenient<Option<PlatformFeeData>>,
+    steps: Vec<RoutePlanStepData>,
+    instructions: Vec<InstructionData>,
+    #[serde(with = "bytes_as_base64::vec")]
+    address_lookup_tables: Vec<MsgpackPubkey>,
+    #[serde(default)]
+    context_slot: Lenient<Option<u64>>,
+    #[serde(default)]
+    time_taken_ns: Lenient<Option<u64>>,
+    #[serde(default)]
+    expires_at_ms: Lenient<Option<u64>>,
+    #[serde(default)]
+    expires_after_slot: Lenient<Option<u64>>,
+    #[serde(default)]
+    compute_units: Lenient<Option<u64>>,
+    #[serde(default)]
+    compute_units_safe: Lenient<Option<u64>>,
+    #[serde(with = "bytes_as_base64::option", default)]
+    transaction: Option<Vec<u8>>,
+    #[serde(default)]
+    reference_id: Lenient<Option<String>>,
+}
+
+#[cfg(feature = "native")]
+impl LenientSwapQuotes {
+    pub(crate) fn into_swap_quotes(self) -> SwapQuotes {
+        SwapQuotes {
+            id: self.id,
+            input_mint: self.input_mint,
+            output_mint: self.output_mint,
+            swap_mode: self.swap_mode,
+            amount: self.amount,
+            quotes: self
+                .quotes
+                .into_iter()
+                .map(|(key, route)| (QuoteKey::from(key), route.into_swap_route()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+impl LenientSwapRoute {
+    fn into_swap_route(self) -> SwapRoute {
+        let mut warnings = Vec::new();
+        let mut keep =
+            |field: &str, value: Lenient<Option<u64>>| recover(&mut warnings, field, value);
+        let context_slot = keep("contextSlot", self.context_slot);
+        let time_taken_ns = keep("timeTakenNs", self.time_taken_ns);
+        let expires_at_ms = keep("expiresAtMs", self.expires_at_ms);
+        let expires_after_slot = keep("expiresAfterSlot", self.expires_after_slot);
+        let compute_units = keep("computeUnits", self.compute_units);
+        let compute_units_safe = keep("computeUnitsSafe", self.compute_units_safe);
+        let platform_fee = recover(&mut warnings, "platformFee", self.platform_fee);
+        let referral_fee = recover(&mut warnings, "referralFee", self.referral_fee);
+        let reference_id = recover(&mut warnings, "referenceId", self.reference_id);
+
+        SwapRoute {
+            in_amount: self.in_amount,
+            out_amount: self.out_amount,
+            slippage_bps: self.slippage_bps,
+            platform_fee,
+            referral_fee,
+            steps: self.steps,
+            instructions: self.instructions,
+            address_lookup_tables: self.address_lookup_tables,
+            context_slot,
+            time_taken_ns,
+            expires_at_ms,
+            expires_after_slot,
+            compute_units,
+            compute_units_safe,
+            transaction: self.transaction,
+            reference_id,
+            warnings,
+            price_only: false,
+        }
+    }
+}
+
+/// [`SwapQuotes`] without each route's instructions, lookup tables and
+/// transaction, which are skipped while decoding rather than allocated.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PriceOnlySwapQuotes {
+    id: String,
+    #[serde(with = "bytes_as_base64")]
+    input_mint: MsgpackPubkey,
+    #[serde(with = "bytes_as_base64")]
+    output_mint: MsgpackPubkey,
+    swap_mode: SwapMode,
+    amount: u64,
+    quotes: std::collections::BTreeMap<String, PriceOnlySwapRoute>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PriceOnlySwapRoute {
+    in_amount: u64,
+    out_amount: u64,
+    slippage_bps: u16,
+    #[serde(default)]
+    platform_fee: Option<PlatformFeeData>,
+    #[serde(default)]
+    referral_fee: Option<PlatformFeeData>,
+    steps: Vec<RoutePlanStepData>,
+    #[serde(default)]
+    context_slot: Option<u64>,
+    #[serde(default)]
+    time_taken_ns: Option<u64>,
+    #[serde(default)]
+    expires_at_ms: Option<u64>,
+    #[serde(default)]
+    expires_after_slot: Option<u64>,
+    #[serde(default)]
+    compute_units: Option<u64>,
+    #[serde(default)]
+    compute_units_safe: Option<u64>,
+    #[serde(default)]
+    reference_id: Option<String>,
+}
+
+impl PriceOnlySwapQuotes {
+    pub(crate) fn into_swap_quotes(self) -> SwapQuotes {
+        SwapQuotes {
+            id: self.id,
+            input_mint: self.input_mint,
+            output_mint: self.output_mint,
+            swap_mode: self.swap_mode,
+            amount: self.amount,
+            quotes: self
+                .quotes
+                .into_iter()
+                .map(|(key, route)| (QuoteKey::from(key), route.into_swap_route()))
+                .collect(),
+        }
+    }
+}
+
+impl PriceOnlySwapRoute {
+    fn into_swap_route(self) -> SwapRoute {
+        SwapRoute {
+            in_amount: self.in_amount,
+            out_amount: self.out_amount,
+            slippage_bps: self.slippage_bps,
+            platform_fee: self.platform_fee,
+            referral_fee: self.referral_fee,
+            steps: self.steps,
+            instructions: Vec::new(),
+            address_lookup_tables: Vec::new(),
+            context_slot: self.context_slot,
+            time_taken_ns: self.time_taken_ns,
+            expires_at_ms: self.expires_at_ms,
+            expires_after_slot: self.expires_after_slot,
+            compute_units: self.compute_units,
+            compute_units_safe: self.compute_units_safe,
+            transaction: None,
+            reference_id: self.reference_id,
+            warnings: Vec::new(),
+            price_only: true,
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+impl SwapRoute {
+    /// Drops what only a swap needs, as a price-only decode would have.
+    pub(crate) fn strip_execution_data(&mut self) {
+        self.instructions = Vec::new();
+        self.address_lookup_tables = Vec::new();
+        self.transaction = None;
+        self.price_only = true;
+    }
+}
+
+#[cfg(feature = "native")]
+fn recover<T>(
+    warnings: &mut Vec<DecodeWarning>,
+    field: &str,
+    value: Lenient<Option<T>>,
+) -> Option<T> {
+    value.0.unwrap_or_else(|message| {
+        warnings.push(DecodeWarning {
+            field: field.to_string(),
+            message,
+            step: None,
+        });
+        None
+    })
 }
 
 #[allow(dead_code)]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct RoutePlanStepData {
+    #[serde(with = "bytes_as_base64")]
     pub amm_key: MsgpackPubkey,
     pub label: String,
+    #[serde(with = "bytes_as_base64")]
     pub input_mint: MsgpackPubkey,
+    #[serde(with = "bytes_as_base64")]
     pub output_mint: MsgpackPubkey,
     pub in_amount: u64,
     pub out_amount: u64,
     pub alloc_ppb: u32,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        with = "bytes_as_base64::option",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub fee_mint: Option<MsgpackPubkey>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_amount: Option<u64>,
@@ -185,14 +1043,17 @@ pub(crate) struct RoutePlanStepData {
 #[allow(dead_code)]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub(crate) struct InstructionData {
+    #[serde(with = "bytes_as_base64")]
     pub p: MsgpackPubkey,
     pub a: Vec<AccountMetaData>,
+    #[serde(with = "bytes_as_base64")]
     pub d: Vec<u8>,
 }
 
 #[allow(dead_code)]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub(crate) struct AccountMetaData {
+    #[serde(with = "bytes_as_base64")]
     pub p: MsgpackPubkey,
     pub s: bool,
     pub w: bool,
@@ -202,5 +1063,55 @@ pub(crate) struct AccountMetaData {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub(crate) struct PlatformFeeData {
     pub amount: u64,
-    pub fee_bps: u8,
+    pub fee_bps: u16,
+}
+
+/// Appends a warning for each step field the route left out that the client
+/// substitutes. Nothing is allocated when every step is complete.
+fn step_warnings(route: &SwapRoute, warnings: &mut Vec<DecodeWarning>) {
+    for (index, step) in route.steps.iter().enumerate() {
+        let mut warn = |field: &str, message: &str| {
+            note_step_warning(index, field, message);
+            warnings.push(DecodeWarning {
+                field: field.to_string(),
+                message: message.to_string(),
+                step: Some(index),
+            });
+        };
+        if step.fee_mint.is_none() {
+            warn("feeMint", "missing; no fee mint reported");
+        }
+        if step.context_slot.is_none() && route.context_slot.is_some() {
+            warn("contextSlot", "missing; not reported");
+        }
+        if step.alloc_ppb == 0 {
+            warn("allocPpb", "zero; treated as the whole amount");
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn note_step_warning(step: usize, field: &str, message: &str) {
+    tracing::debug!(step, field, "route step field {message}");
+}
+
+#[cfg(not(feature = "tracing"))]
+fn note_step_warning(_step: usize, _field: &str, _message: &str) {}
+
+pub(crate) fn transform_step(step: &RoutePlanStepData) -> RoutePlanStep {
+    RoutePlanStep {
+        swap_info: SwapInfo {
+            amm_key: pubkey_from_bytes(&step.amm_key),
+            label: step.label.clone(),
+            input_mint: pubkey_from_bytes(&step.input_mint),
+            output_mint: pubkey_from_bytes(&step.output_mint),
+            in_amount: step.in_amount,
+            out_amount: step.out_amount,
+            alloc_ppb: u64::from(step.alloc_ppb),
+            fee_mint: step.fee_mint.as_ref().map(pubkey_from_bytes),
+            fee_amount: step.fee_amount,
+            context_slot: step.context_slot,
+        },
+        percent: 100,
+    }
 }
@@ -0,0 +1,242 @@
+use crate::account_limits::AccountLimits;
+use crate::quote::{Provider, QuoteRequest, SizeConstraint, SwapMode};
+use crate::serde_helpers::{field_as_string, vec_field_as_string};
+use crate::tokens::TokenRegistry;
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum QuoteRequestError {
+    #[error("Missing {0}")]
+    Missing(&'static str),
+    #[error("Token symbols need a registry attached")]
+    NoRegistry,
+    #[error("Unknown token symbol {0}")]
+    UnknownSymbol(String),
+    #[error("Unknown decimals for mint {0}")]
+    UnknownDecimals(Pubkey),
+    #[error("Invalid UI amount {0}")]
+    InvalidUiAmount(f64),
+}
+
+enum Token {
+    Mint(Pubkey),
+    Symbol(String),
+}
+
+enum Amount {
+    Raw(u64),
+    Ui(f64),
+}
+
+/// Builds a [`QuoteRequest`], optionally naming tokens by symbol and amounts
+/// in UI units when a [`TokenRegistry`] is attached.
+#[derive(Default)]
+pub struct QuoteRequestBuilder<'a> {
+    registry: Option<&'a TokenRegistry>,
+    input: Option<Token>,
+    output: Option<Token>,
+    amount: Option<Amount>,
+    request: QuoteRequest,
+}
+
+impl QuoteRequest {
+    pub fn builder<'a>() -> QuoteRequestBuilder<'a> {
+        QuoteRequestBuilder::default()
+    }
+}
+
+impl<'a> QuoteRequestBuilder<'a> {
+    pub fn registry(mut self, registry: &'a TokenRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    pub fn input_mint(mut self, mint: Pubkey) -> Self {
+        self.input = Some(Token::Mint(mint));
+        self
+    }
+
+    pub fn output_mint(mut self, mint: Pubkey) -> Self {
+        self.output = Some(Token::Mint(mint));
+        self
+    }
+
+    pub fn input_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.input = Some(Token::Symbol(symbol.into()));
+        self
+    }
+
+    pub fn output_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.output = Some(Token::Symbol(symbol.into()));
+        self
+    }
+
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.amount = Some(Amount::Raw(amount));
+        self
+    }
+
+    /// An amount in whole tokens of the input mint (of the output mint for
+    /// ExactOut), converted using the registry's decimals.
+    pub fn ui_amount(mut self, amount: f64) -> Self {
+        self.amount = Some(Amount::Ui(amount));
+        self
+    }
+
+    pub fn user_pubkey(mut self, user_pubkey: Pubkey) -> Self {
+        self.request.user_pubkey = Some(user_pubkey);
+        self
+    }
+
+    pub fn swap_mode(mut self, swap_mode: SwapMode) -> Self {
+        self.request.swap_mode = Some(swap_mode);
+        self
+    }
+
+    pub fn slippage_bps(mut self, slippage_bps: u16) -> Self {
+        self.request.slippage_bps = slippage_bps;
+        self
+    }
+
+    pub fn max_accounts(mut self, max_accounts: usize) -> Self {
+        self.request.account_limits.total = Some(max_accounts);
+        self
+    }
+
+    pub fn only_direct_routes(mut self, only_direct_routes: bool) -> Self {
+        self.request.only_direct_routes = Some(only_direct_routes);
+        self
+    }
+
+    pub fn excluded_dexes(mut self, excluded_dexes: impl Into<String>) -> Self {
+        self.request.excluded_dexes = Some(excluded_dexes.into());
+        self
+    }
+
+    pub fn excluded_amm_keys(mut self, amm_keys: impl IntoIterator<Item = Pubkey>) -> Self {
+        self.request.excluded_amm_keys = Some(amm_keys.into_iter().collect());
+        self
+    }
+
+    pub fn providers(mut self, providers: Provider) -> Self {
+        self.request.providers = Some(providers);
+        self
+    }
+
+    /// Credits `account` as the referrer, optionally with a fee of its own.
+    pub fn referral(mut self, account: Pubkey, fee_bps: Option<u16>) -> Self {
+        self.request.referral_account = Some(account);
+        self.request.referral_fee_bps = fee_bps;
+        self
+    }
+
+    pub fn build(self) -> Result<QuoteRequest, QuoteRequestError> {
+        let mut request = self.request;
+        request.input_mint = resolve(self.registry, self.input, "input mint")?;
+        request.output_mint = resolve(self.registry, self.output, "output mint")?;
+
+        request.amount = match self.amount.ok_or(QuoteRequestError::Missing("amount"))? {
+            Amount::Raw(amount) => amount,
+            Amount::Ui(amount) => {
+                let registry = self.registry.ok_or(QuoteRequestError::NoRegistry)?;
+                let mint = match request.swap_mode {
+                    Some(SwapMode::ExactOut) => request.output_mint,
+                    _ => request.input_mint,
+                };
+                registry
+                    .decimals(&mint)
+                    .ok_or(QuoteRequestError::UnknownDecimals(mint))?;
+                registry
+                    .raw_amount(&mint, amount)
+                    .ok_or(QuoteRequestError::InvalidUiAmount(amount))?
+            }
+        };
+
+        Ok(request)
+    }
+}
+
+fn resolve(
+    registry: Option<&TokenRegistry>,
+    token: Option<Token>,
+    field: &'static str,
+) -> Result<Pubkey, QuoteRequestError> {
+    match token.ok_or(QuoteRequestError::Missing(field))? {
+        Token::Mint(mint) => Ok(mint),
+        Token::Symbol(symbol) => registry
+            .ok_or(QuoteRequestError::NoRegistry)?
+            .resolve(&symbol)
+            .ok_or(QuoteRequestError::UnknownSymbol(symbol)),
+    }
+}
+
+/// The config-file form of a [`QuoteRequest`]: the same fields, except that
+/// `inputMint` and `outputMint` may name a token symbol instead of a mint
+/// address, resolved by [`QuoteRequestConfig::finalize`].
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteRequestConfig {
+    pub input_mint: String,
+    pub output_mint: String,
+    #[serde(with = "field_as_string")]
+    pub amount: u64,
+    #[serde(with = "field_as_string::option", default)]
+    pub user_pubkey: Option<Pubkey>,
+    #[serde(flatten)]
+    pub account_limits: AccountLimits,
+    #[serde(default)]
+    pub swap_mode: Option<SwapMode>,
+    #[serde(default)]
+    pub slippage_bps: u16,
+    #[serde(default)]
+    pub only_direct_routes: Option<bool>,
+    #[serde(default)]
+    pub excluded_dexes: Option<String>,
+    #[serde(with = "vec_field_as_string::option", default)]
+    pub excluded_amm_keys: Option<Vec<Pubkey>>,
+    #[serde(default)]
+    pub size_constraint: Option<SizeConstraint>,
+    #[deprecated(note = "use `size_constraint` instead")]
+    #[serde(default)]
+    pub size_constraints: Option<u64>,
+    #[serde(default)]
+    pub providers: Option<Provider>,
+    #[serde(with = "field_as_string::option", default)]
+    pub referral_account: Option<Pubkey>,
+    #[serde(default)]
+    pub referral_fee_bps: Option<u16>,
+}
+
+impl QuoteRequestConfig {
+    #[allow(deprecated)]
+    pub fn finalize(self, registry: &TokenRegistry) -> Result<QuoteRequest, QuoteRequestError> {
+        Ok(QuoteRequest {
+            input_mint: resolve(Some(registry), Some(token(self.input_mint)), "input mint")?,
+            output_mint: resolve(Some(registry), Some(token(self.output_mint)), "output mint")?,
+            amount: self.amount,
+            user_pubkey: self.user_pubkey,
+            account_limits: self.account_limits,
+            swap_mode: self.swap_mode,
+            slippage_bps: self.slippage_bps,
+            only_direct_routes: self.only_direct_routes,
+            excluded_dexes: self.excluded_dexes,
+            excluded_amm_keys: self.excluded_amm_keys,
+            size_constraint: self.size_constraint,
+            size_constraints: self.size_constraints,
+            providers: self.providers,
+            referral_account: self.referral_account,
+            referral_fee_bps: self.referral_fee_bps,
+            skip_defaults: false,
+        })
+    }
+}
+
+// A mint address if it parses as one, otherwise a symbol.
+fn token(value: String) -> Token {
+    match value.parse() {
+        Ok(mint) => Token::Mint(mint),
+        Err(_) => Token::Symbol(value),
+    }
+}
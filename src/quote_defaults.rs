@@ -0,0 +1,92 @@
+//! Request parameters a client fills in for every [`QuoteRequest`] that
+//! leaves them unset.
+
+use crate::account_limits::AccountLimits;
+use crate::quote::{QuoteRequest, SizeConstraint};
+use crate::venues;
+
+/// Set with `TitanClientBuilder::default_quote_options`. Each field is
+/// merged into a request as [`merge`](Self::merge) documents; `None` (or, for
+/// `account_limits`, both limits unset) leaves the request's value alone.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuoteDefaults {
+    pub slippage_bps: Option<u16>,
+    pub account_limits: AccountLimits,
+    pub only_direct_routes: Option<bool>,
+    /// A comma-separated list, like `QuoteRequest::excluded_dexes`.
+    pub excluded_dexes: Option<String>,
+    pub dex_exclusion: DexExclusion,
+    pub size_constraint: Option<SizeConstraint>,
+}
+
+/// How the default DEX exclusions combine with a request's own.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DexExclusion {
+    /// Exclude the DEXes of both lists.
+    #[default]
+    Union,
+    /// A request's list, even an empty one, is used instead of the default.
+    Replace,
+}
+
+impl QuoteDefaults {
+    /// `request` with the defaults filled in. A request made with
+    /// `without_defaults` is returned unchanged. Otherwise:
+    ///
+    /// - `slippage_bps` applies when the request's is zero, which is never
+    ///   sent.
+    /// - `account_limits` applies when the request sets neither limit. The
+    ///   two limits are never mixed between the defaults and the request, so
+    ///   a request can't end up with a writable limit above its total.
+    /// - `only_direct_routes` and `size_constraint` apply when the request's
+    ///   are `None`. A request with the deprecated `size_constraints` keeps
+    ///   it.
+    /// - `excluded_dexes` combine with the request's as [`DexExclusion`]
+    ///   says, and apply when the request's is `None`. A union is written as
+    ///   canonical DEX ids without duplicates, the defaults first.
+    #[allow(deprecated)]
+    pub fn merge(&self, request: &QuoteRequest) -> QuoteRequest {
+        let mut merged = request.clone();
+        if request.skip_defaults {
+            return merged;
+        }
+
+        if merged.slippage_bps == 0 {
+            merged.slippage_bps = self.slippage_bps.unwrap_or(0);
+        }
+        if merged.account_limits.total.is_none() && merged.account_limits.writable.is_none() {
+            merged.account_limits = self.account_limits;
+        }
+        merged.only_direct_routes = merged.only_direct_routes.or(self.only_direct_routes);
+        if merged.size_constraints.is_none() {
+            merged.size_constraint = merged.size_constraint.or(self.size_constraint);
+        }
+        merged.excluded_dexes = match (&self.excluded_dexes, merged.excluded_dexes) {
+            (Some(defaults), own) if self.dex_exclusion == DexExclusion::Union => {
+                Some(union_dexes(defaults, own.as_deref().unwrap_or_default()))
+            }
+            (defaults, own) => own.or_else(|| defaults.clone()),
+        };
+        merged
+    }
+}
+
+fn union_dexes(defaults: &str, own: &str) -> String {
+    let canonical = venues::canonicalize_dexes(&format!("{defaults},{own}"));
+    let mut dexes: Vec<&str> = Vec::new();
+    for dex in canonical.split(',') {
+        if !dexes.contains(&dex) {
+            dexes.push(dex);
+        }
+    }
+    dexes.join(",")
+}
+
+impl QuoteRequest {
+    /// Sends the request exactly as it is, without the client's
+    /// [`QuoteDefaults`].
+    pub fn without_defaults(mut self) -> Self {
+        self.skip_defaults = true;
+        self
+    }
+}
@@ -0,0 +1,123 @@
+use crate::clock::Clock;
+use crate::quota::QuotaInfo;
+use crate::ClientError;
+use std::sync::{Arc, PoisonError};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RateLimitStrategy {
+    /// Wait until the budget allows the request.
+    #[default]
+    Delay,
+    /// Fail immediately with `ClientError::RateLimited`.
+    Reject,
+}
+
+/// Token bucket holding up to `capacity` tokens, refilled at `capacity` per `per`.
+///
+/// Waiters queue on a FIFO mutex, so under contention requests are released
+/// in arrival order. The server's reported quota can lower the budget; see
+/// [`observe_quota`](Self::observe_quota).
+pub(crate) struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    strategy: RateLimitStrategy,
+    bucket: Mutex<Bucket>,
+    paused_until: std::sync::Mutex<Option<Instant>>,
+    clock: Arc<dyn Clock>,
+}
+
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(
+        requests: u32,
+        per: Duration,
+        strategy: RateLimitStrategy,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        let capacity = f64::from(requests.max(1));
+        Self {
+            capacity,
+            refill_per_sec: capacity / per.as_secs_f64().max(f64::EPSILON),
+            strategy,
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                refilled_at: clock.instant(),
+            }),
+            paused_until: std::sync::Mutex::new(None),
+            clock,
+        }
+    }
+
+    /// Caps the budget at the server's remaining quota and, once it reports
+    /// none left, holds every request until its reset time.
+    pub(crate) fn observe_quota(&self, quota: &QuotaInfo) {
+        let paused_until = quota
+            .reset_at
+            .filter(|_| quota.is_exhausted())
+            .map(|reset_at| {
+                self.clock.instant()
+                    + reset_at
+                        .duration_since(self.clock.system_time())
+                        .unwrap_or_default()
+            });
+        *self
+            .paused_until
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = paused_until;
+
+        // A waiter holding the bucket will see the pause instead.
+        if let (Some(remaining), Ok(mut bucket)) = (quota.remaining, self.bucket.try_lock()) {
+            self.refill(&mut bucket);
+            bucket.tokens = bucket.tokens.min(remaining as f64);
+        }
+    }
+
+    pub(crate) async fn acquire(&self) -> Result<(), ClientError> {
+        let paused = self
+            .paused_until
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .map(|until| until.saturating_duration_since(self.clock.instant()))
+            .filter(|wait| !wait.is_zero());
+        if let Some(wait) = paused {
+            match self.strategy {
+                RateLimitStrategy::Reject => {
+                    return Err(ClientError::RateLimited { retry_after: wait });
+                }
+                RateLimitStrategy::Delay => tokio::time::sleep(wait).await,
+            }
+        }
+
+        let mut bucket = self.bucket.lock().await;
+        self.refill(&mut bucket);
+
+        if bucket.tokens < 1.0 {
+            let wait = Duration::from_secs_f64((1.0 - bucket.tokens) / self.refill_per_sec);
+            match self.strategy {
+                RateLimitStrategy::Reject => {
+                    return Err(ClientError::RateLimited { retry_after: wait });
+                }
+                RateLimitStrategy::Delay => {
+                    tokio::time::sleep(wait).await;
+                    self.refill(&mut bucket);
+                }
+            }
+        }
+
+        bucket.tokens = (bucket.tokens - 1.0).max(0.0);
+        Ok(())
+    }
+
+    fn refill(&self, bucket: &mut Bucket) {
+        let now = self.clock.instant();
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        bucket.refilled_at = now;
+    }
+}
@@ -0,0 +1,172 @@
+//! Plain-data mirrors of the response types with pubkeys as `[u8; 32]` and
+//! instructions as tuples, for callers whose solana-sdk version differs from
+//! the one this crate is built against.
+
+use crate::pubkey::Pubkey;
+use crate::quota::QuotaInfo;
+use crate::quote::{
+    DecodeWarning, PlatformFee, PriceCheck, QuoteDetail, QuoteResponse, QuoteRouteData,
+    QuoteTiming, RoutePlanStep, SwapInfo, SwapMode,
+};
+use std::sync::Arc;
+
+pub type RawPubkey = [u8; 32];
+
+/// `(pubkey, is_signer, is_writable)`
+pub type RawAccountMeta = (RawPubkey, bool, bool);
+
+/// `(program_id, accounts, data)`
+pub type RawInstruction = (RawPubkey, Vec<RawAccountMeta>, Vec<u8>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawSwapResponse {
+    pub instructions: Vec<RawInstruction>,
+    pub address_lookup_table_addresses: Vec<RawPubkey>,
+    pub compute_unit_limit: Option<u32>,
+    pub compute_units_safe: Option<u64>,
+    pub context_slot: Option<u64>,
+    pub expires_at_ms: Option<u64>,
+    pub expires_after_slot: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawSwapInfo {
+    pub amm_key: RawPubkey,
+    pub label: String,
+    pub input_mint: RawPubkey,
+    pub output_mint: RawPubkey,
+    pub in_amount: u64,
+    pub out_amount: u64,
+    pub alloc_ppb: u64,
+    pub fee_mint: Option<RawPubkey>,
+    pub fee_amount: Option<u64>,
+    pub context_slot: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawRoutePlanStep {
+    pub swap_info: RawSwapInfo,
+    pub percent: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct RawQuoteResponse {
+    pub input_mint: RawPubkey,
+    pub in_amount: u64,
+    pub output_mint: RawPubkey,
+    pub out_amount: u64,
+    pub swap_mode: SwapMode,
+    pub slippage_bps: u16,
+    pub platform_fee: Option<PlatformFee>,
+    pub referral_fee: Option<PlatformFee>,
+    pub raw_route: Option<Arc<QuoteRouteData>>,
+    pub route_plan: Vec<RawRoutePlanStep>,
+    pub context_slot: Option<u64>,
+    pub time_taken: Option<f64>,
+    pub timing: Option<QuoteTiming>,
+    pub price_check: Option<PriceCheck>,
+    pub quota: Option<QuotaInfo>,
+    pub warnings: Vec<DecodeWarning>,
+    pub provider: Option<String>,
+    pub output_is_token2022: Option<bool>,
+    pub indicative: bool,
+    pub detail: QuoteDetail,
+}
+
+impl QuoteResponse {
+    #[allow(deprecated)]
+    pub fn to_raw(&self) -> RawQuoteResponse {
+        RawQuoteResponse {
+            input_mint: self.input_mint.to_bytes(),
+            in_amount: self.in_amount,
+            output_mint: self.output_mint.to_bytes(),
+            out_amount: self.out_amount,
+            swap_mode: self.swap_mode.clone(),
+            slippage_bps: self.slippage_bps,
+            platform_fee: self.platform_fee,
+            referral_fee: self.referral_fee,
+            raw_route: self.raw_route.clone(),
+            route_plan: self.route_plan.iter().map(RoutePlanStep::to_raw).collect(),
+            context_slot: self.context_slot,
+            time_taken: self.time_taken,
+            timing: self.timing,
+            price_check: self.price_check,
+            quota: self.quota,
+            warnings: self.warnings.clone(),
+            provider: self.provider.clone(),
+            output_is_token2022: self.output_is_token2022,
+            indicative: self.indicative,
+            detail: self.detail,
+        }
+    }
+
+    #[allow(deprecated)]
+    pub fn from_raw(raw: RawQuoteResponse) -> Self {
+        QuoteResponse {
+            input_mint: Pubkey::from(raw.input_mint),
+            in_amount: raw.in_amount,
+            output_mint: Pubkey::from(raw.output_mint),
+            out_amount: raw.out_amount,
+            swap_mode: raw.swap_mode,
+            slippage_bps: raw.slippage_bps,
+            platform_fee: raw.platform_fee,
+            referral_fee: raw.referral_fee,
+            raw_route: raw.raw_route,
+            route_plan: raw
+                .route_plan
+                .into_iter()
+                .map(RoutePlanStep::from_raw)
+                .collect(),
+            context_slot: raw.context_slot,
+            time_taken: raw.time_taken,
+            timing: raw.timing,
+            price_check: raw.price_check,
+            quota: raw.quota,
+            warnings: raw.warnings,
+            provider: raw.provider,
+            output_is_token2022: raw.output_is_token2022,
+            indicative: raw.indicative,
+            detail: raw.detail,
+        }
+    }
+}
+
+impl RoutePlanStep {
+    pub fn to_raw(&self) -> RawRoutePlanStep {
+        let info = &self.swap_info;
+        RawRoutePlanStep {
+            swap_info: RawSwapInfo {
+                amm_key: info.amm_key.to_bytes(),
+                label: info.label.clone(),
+                input_mint: info.input_mint.to_bytes(),
+                output_mint: info.output_mint.to_bytes(),
+                in_amount: info.in_amount,
+                out_amount: info.out_amount,
+                alloc_ppb: info.alloc_ppb,
+                fee_mint: info.fee_mint.map(|mint| mint.to_bytes()),
+                fee_amount: info.fee_amount,
+                context_slot: info.context_slot,
+            },
+            percent: self.percent,
+        }
+    }
+
+    pub fn from_raw(raw: RawRoutePlanStep) -> Self {
+        let info = raw.swap_info;
+        RoutePlanStep {
+            swap_info: SwapInfo {
+                amm_key: Pubkey::from(info.amm_key),
+                label: info.label,
+                input_mint: Pubkey::from(info.input_mint),
+                output_mint: Pubkey::from(info.output_mint),
+                in_amount: info.in_amount,
+                out_amount: info.out_amount,
+                alloc_ppb: info.alloc_ppb,
+                fee_mint: info.fee_mint.map(Pubkey::from),
+                fee_amount: info.fee_amount,
+                context_slot: info.context_slot,
+            },
+            percent: raw.percent,
+        }
+    }
+}
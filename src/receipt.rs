@@ -0,0 +1,240 @@
+//! What a swap was quoted to do next to what it did on chain, for keeping a
+//! record of fills.
+
+use crate::pubkey::Pubkey;
+use crate::quote::QuoteResponse;
+use crate::serde_helpers::field_as_string;
+use crate::summary::QuoteSummary;
+use serde::{Deserialize, Serialize};
+use solana_sdk::signature::Signature;
+
+/// The signer's total balance of one mint before and after the swap.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenBalanceChange {
+    #[serde(with = "field_as_string")]
+    pub mint: Pubkey,
+    pub decimals: u8,
+    #[serde(with = "field_as_string")]
+    pub pre_amount: u64,
+    #[serde(with = "field_as_string")]
+    pub post_amount: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionReceipt {
+    pub quote: QuoteSummary,
+    #[serde(with = "field_as_string")]
+    pub signature: Signature,
+    pub slot: u64,
+    /// The transaction error, if it landed but failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Ordered by mint.
+    pub token_balances: Vec<TokenBalanceChange>,
+    /// How much of the input mint the signer actually spent.
+    #[serde(with = "field_as_string")]
+    pub in_amount: u64,
+    /// How much of the output mint the signer actually received.
+    #[serde(with = "field_as_string")]
+    pub out_amount: u64,
+    /// Raw output units per raw input unit; `None` when nothing was spent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub realized_price: Option<f64>,
+    /// How far the realized price fell short of the quoted one, negative
+    /// when the fill was better than quoted. `None` when either price is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slippage_bps: Option<f64>,
+}
+
+impl ExecutionReceipt {
+    pub fn new(
+        quote: &QuoteResponse,
+        signature: Signature,
+        slot: u64,
+        token_balances: Vec<TokenBalanceChange>,
+    ) -> Self {
+        let quote = quote.summary();
+        let balance = |mint: &Pubkey| token_balances.iter().find(|change| change.mint == *mint);
+        let in_amount = balance(&quote.input_mint).map_or(0, |change| {
+            change.pre_amount.saturating_sub(change.post_amount)
+        });
+        let out_amount = balance(&quote.output_mint).map_or(0, |change| {
+            change.post_amount.saturating_sub(change.pre_amount)
+        });
+
+        let price = |in_amount: u64, out_amount: u64| {
+            (in_amount > 0).then(|| out_amount as f64 / in_amount as f64)
+        };
+        let realized_price = price(in_amount, out_amount);
+        let quoted_price = price(quote.in_amount, quote.out_amount).filter(|&price| price > 0.0);
+        let slippage_bps = realized_price
+            .zip(quoted_price)
+            .map(|(realized, quoted)| (quoted - realized) / quoted * 10_000.0);
+
+        Self {
+            quote,
+            signature,
+            slot,
+            error: None,
+            token_balances,
+            in_amount,
+            out_amount,
+            realized_price,
+            slippage_bps,
+        }
+    }
+}
+
+#[cfg(feature = "solana-client")]
+mod rpc {
+    use super::{ExecutionReceipt, TokenBalanceChange};
+    use crate::constants::is_native_sol;
+    use crate::quote::QuoteResponse;
+    use crate::ClientError;
+    use solana_client::nonblocking::rpc_client::RpcClient;
+    use solana_client::rpc_config::RpcTransactionConfig;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::Signature;
+    use solana_transaction_status_client_types::option_serializer::OptionSerializer;
+    use solana_transaction_status_client_types::{
+        EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding, UiTransactionTokenBalance,
+    };
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
+
+    impl ExecutionReceipt {
+        /// Fetches the confirmed transaction `signature` and builds its
+        /// receipt with [`from_transaction`](Self::from_transaction).
+        pub async fn fetch(
+            rpc: &RpcClient,
+            signature: &Signature,
+            quote: &QuoteResponse,
+        ) -> Result<Self, ClientError> {
+            let config = RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: None,
+                max_supported_transaction_version: Some(0),
+            };
+            let transaction = rpc
+                .get_transaction_with_config(signature, config)
+                .await
+                .map_err(|err| ClientError::Rpc(Box::new(err)))?;
+            Self::from_transaction(&transaction, quote)
+        }
+
+        /// Builds a receipt from a confirmed transaction fetched with a
+        /// binary encoding, taking the balances of its fee payer.
+        ///
+        /// When either side of the quote is SOL, the fee payer's lamports,
+        /// less the transaction fee, count towards the SOL balance, so
+        /// wrapping and unwrapping inside the swap is accounted for. Rent
+        /// paid for accounts the swap opened is counted too.
+        pub fn from_transaction(
+            transaction: &EncodedConfirmedTransactionWithStatusMeta,
+            quote: &QuoteResponse,
+        ) -> Result<Self, ClientError> {
+            let invalid = |field, value: String| ClientError::InvalidResponse { field, value };
+            let decoded = transaction
+                .transaction
+                .transaction
+                .decode()
+                .ok_or_else(|| invalid("transaction", "not binary encoded".to_string()))?;
+            let signature = *decoded
+                .signatures
+                .first()
+                .ok_or_else(|| invalid("transaction", "no signatures".to_string()))?;
+            let signer = *decoded
+                .message
+                .static_account_keys()
+                .first()
+                .ok_or_else(|| invalid("transaction", "no account keys".to_string()))?;
+            let meta = transaction
+                .transaction
+                .meta
+                .as_ref()
+                .ok_or_else(|| invalid("meta", "missing".to_string()))?;
+
+            let mut balances = BTreeMap::<Pubkey, TokenBalanceChange>::new();
+            for (token_balances, is_pre) in [
+                (&meta.pre_token_balances, true),
+                (&meta.post_token_balances, false),
+            ] {
+                let OptionSerializer::Some(token_balances) = token_balances else {
+                    continue;
+                };
+                for token_balance in token_balances {
+                    if !is_owned_by(token_balance, &signer) {
+                        continue;
+                    }
+                    let mint = Pubkey::from_str(&token_balance.mint)
+                        .map_err(|_| invalid("token balance mint", token_balance.mint.clone()))?;
+                    let amount = token_balance
+                        .ui_token_amount
+                        .amount
+                        .parse::<u64>()
+                        .map_err(|_| {
+                            invalid(
+                                "token balance amount",
+                                token_balance.ui_token_amount.amount.clone(),
+                            )
+                        })?;
+                    let change = balances.entry(mint).or_insert(TokenBalanceChange {
+                        mint,
+                        decimals: token_balance.ui_token_amount.decimals,
+                        pre_amount: 0,
+                        post_amount: 0,
+                    });
+                    let total = if is_pre {
+                        &mut change.pre_amount
+                    } else {
+                        &mut change.post_amount
+                    };
+                    *total = total.saturating_add(amount);
+                }
+            }
+
+            if let Some(sol_mint) = [quote.input_mint, quote.output_mint]
+                .into_iter()
+                .find(is_native_sol)
+            {
+                let lamports = |balances: &[u64]| balances.first().copied().unwrap_or(0);
+                let change = balances.entry(sol_mint).or_insert(TokenBalanceChange {
+                    mint: sol_mint,
+                    decimals: 9,
+                    pre_amount: 0,
+                    post_amount: 0,
+                });
+                change.pre_amount = change
+                    .pre_amount
+                    .saturating_add(lamports(&meta.pre_balances));
+                change.post_amount = change
+                    .post_amount
+                    .saturating_add(lamports(&meta.post_balances))
+                    .saturating_add(meta.fee);
+            }
+
+            let mut receipt = Self::new(
+                quote,
+                signature,
+                transaction.slot,
+                balances.into_values().collect(),
+            );
+            receipt.error = meta.err.as_ref().map(|err| format!("{err:?}"));
+            Ok(receipt)
+        }
+    }
+
+    fn is_owned_by(token_balance: &UiTransactionTokenBalance, owner: &Pubkey) -> bool {
+        match &token_balance.owner {
+            OptionSerializer::Some(token_owner) => *token_owner == owner.to_string(),
+            _ => false,
+        }
+    }
+}
+
+/// The confirmed transaction type [`ExecutionReceipt::from_transaction`]
+/// reads.
+#[cfg(feature = "solana-client")]
+pub use solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta;
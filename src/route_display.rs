@@ -0,0 +1,191 @@
+//! Text renderings of a route plan for logs and alerts.
+//!
+//! ```text
+//! 5 SOL → 938.1 USDC (Titan, 100 bps slippage)
+//! 1. SOL ─(Whirlpool 60%)→ BONK: 3 SOL → 1,200,000 BONK, fee 0.0009 SOL
+//!    SOL ─(Raydium CLMM 40%)→ BONK: 2 SOL → 800,000 BONK, fee 0.0005 SOL
+//! 2. BONK ─(Phoenix 100%)→ USDC: 2,000,000 BONK → 938.1 USDC
+//! ```
+//!
+//! Steps splitting the same hop share a number; each sequential hop gets the
+//! next one.
+
+use crate::amount_math;
+use crate::pubkey::Pubkey;
+use crate::quote::{QuoteResponse, SwapInfo};
+use crate::ui::UiAmount;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Renders a quote's route plan. Mints without a symbol are shown as an
+/// abbreviated address and their amounts in atoms.
+#[derive(Debug, Clone)]
+pub struct RouteDisplay<'a> {
+    quote: &'a QuoteResponse,
+    tokens: HashMap<Pubkey, (String, u8)>,
+}
+
+impl QuoteResponse {
+    pub fn display(&self) -> RouteDisplay<'_> {
+        RouteDisplay {
+            quote: self,
+            tokens: HashMap::new(),
+        }
+    }
+}
+
+impl fmt::Display for QuoteResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.display(), f)
+    }
+}
+
+impl<'a> RouteDisplay<'a> {
+    pub fn token(mut self, mint: Pubkey, symbol: impl Into<String>, decimals: u8) -> Self {
+        self.tokens.insert(mint, (symbol.into(), decimals));
+        self
+    }
+
+    /// Symbols and decimals for every mint in the route known to `registry`.
+    #[cfg(feature = "native")]
+    pub fn registry(mut self, registry: &crate::tokens::TokenRegistry) -> Self {
+        for step in &self.quote.route_plan {
+            for mint in [step.swap_info.input_mint, step.swap_info.output_mint] {
+                if let Some(token) = registry.get(&mint) {
+                    self.tokens.insert(mint, (token.symbol, token.decimals));
+                }
+            }
+        }
+        self
+    }
+
+    /// The route on one line, without per-step amounts:
+    /// `5 SOL → 938.1 USDC: SOL ─(Whirlpool 60%, Raydium CLMM 40%)→ BONK ─(Phoenix 100%)→ USDC`.
+    pub fn compact(&self) -> String {
+        let mut line = self.totals();
+        line.push(':');
+        let mut last_output = None;
+        for hop in self.hops() {
+            let first = hop[0];
+            if last_output != Some(first.input_mint) {
+                if last_output.is_some() {
+                    line.push(';');
+                }
+                line.push(' ');
+                line.push_str(&self.symbol(&first.input_mint));
+            }
+            let venues: Vec<String> = hop
+                .iter()
+                .map(|step| format!("{} {}%", step.label, split_percent(step)))
+                .collect();
+            line.push_str(&format!(
+                " ─({})→ {}",
+                venues.join(", "),
+                self.symbol(&first.output_mint)
+            ));
+            last_output = Some(first.output_mint);
+        }
+        line
+    }
+
+    fn symbol(&self, mint: &Pubkey) -> String {
+        match self.tokens.get(mint) {
+            Some((symbol, _)) => symbol.clone(),
+            None => {
+                let address = mint.to_string();
+                if address.len() > 8 {
+                    format!("{}…{}", &address[..4], &address[address.len() - 4..])
+                } else {
+                    address
+                }
+            }
+        }
+    }
+
+    fn amount(&self, mint: &Pubkey, amount: u64) -> String {
+        let decimals = self.tokens.get(mint).map_or(0, |(_, decimals)| *decimals);
+        format!("{} {}", UiAmount::new(amount, decimals), self.symbol(mint))
+    }
+
+    fn totals(&self) -> String {
+        let quote = self.quote;
+        let mut totals = format!(
+            "{} → {}",
+            self.amount(&quote.input_mint, quote.input_amount()),
+            self.amount(&quote.output_mint, quote.out_amount)
+        );
+        if let (Some(fee), Some(fee_mint)) = (&quote.platform_fee, quote.platform_fee_mint()) {
+            totals.push_str(&format!(
+                ", platform fee {} ({} bps)",
+                self.amount(&fee_mint, fee.amount),
+                fee.fee_bps
+            ));
+        }
+        totals
+    }
+
+    /// Consecutive steps between the same pair of mints, i.e. the splits of
+    /// one hop.
+    fn hops(&self) -> Vec<Vec<&'a SwapInfo>> {
+        let mut hops: Vec<Vec<&SwapInfo>> = Vec::new();
+        for step in &self.quote.route_plan {
+            let info = &step.swap_info;
+            match hops.last_mut() {
+                Some(hop)
+                    if hop[0].input_mint == info.input_mint
+                        && hop[0].output_mint == info.output_mint =>
+                {
+                    hop.push(info)
+                }
+                _ => hops.push(vec![info]),
+            }
+        }
+        hops
+    }
+}
+
+impl fmt::Display for RouteDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.totals())?;
+        let mut details = Vec::new();
+        details.extend(self.quote.provider.as_deref().map(str::to_string));
+        details.push(format!("{} bps slippage", self.quote.slippage_bps));
+        write!(f, " ({})", details.join(", "))?;
+
+        for (index, hop) in self.hops().iter().enumerate() {
+            let number = format!("{}. ", index + 1);
+            for (split, step) in hop.iter().enumerate() {
+                let prefix = if split == 0 {
+                    number.clone()
+                } else {
+                    " ".repeat(number.len())
+                };
+                write!(
+                    f,
+                    "\n{prefix}{} ─({} {}%)→ {}: {} → {}",
+                    self.symbol(&step.input_mint),
+                    step.label,
+                    split_percent(step),
+                    self.symbol(&step.output_mint),
+                    self.amount(&step.input_mint, step.in_amount),
+                    self.amount(&step.output_mint, step.out_amount),
+                )?;
+                if let (Some(fee_mint), Some(fee_amount)) = (step.fee_mint, step.fee_amount) {
+                    if fee_amount > 0 {
+                        write!(f, ", fee {}", self.amount(&fee_mint, fee_amount))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Older quotes carry no allocation; treat it as the whole input.
+fn split_percent(step: &SwapInfo) -> u8 {
+    if step.alloc_ppb == 0 {
+        100
+    } else {
+        amount_math::percent_from_ppb(step.alloc_ppb)
+    }
+}
@@ -0,0 +1,173 @@
+//! The structure of a route, independent of its amounts, for spotting when
+//! routing for a pair changes venues, hops or splits.
+
+use crate::amount_math;
+use crate::pubkey::Pubkey;
+use crate::quote::QuoteResponse;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Splits are rounded to this many percent, so small reallocations between
+/// the same venues don't count as a new shape.
+pub const SPLIT_BUCKET_PERCENT: u8 = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShapeStep {
+    pub label: String,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    /// Share of the step's input, rounded to `SPLIT_BUCKET_PERCENT`.
+    pub split_percent: u8,
+}
+
+impl ShapeStep {
+    fn venue(&self) -> Venue {
+        Venue {
+            label: self.label.clone(),
+            input_mint: self.input_mint,
+            output_mint: self.output_mint,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct RouteShape {
+    pub steps: Vec<ShapeStep>,
+}
+
+impl RouteShape {
+    /// Distinct input/output pairs: parallel splits across venues are one hop.
+    pub fn hop_count(&self) -> usize {
+        self.steps
+            .iter()
+            .map(|step| (step.input_mint, step.output_mint))
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+
+    pub fn diff(&self, other: &RouteShape) -> Vec<ShapeChange> {
+        shape_diff(self, other)
+    }
+
+    fn splits(&self) -> BTreeMap<Venue, u16> {
+        let mut splits = BTreeMap::new();
+        for step in &self.steps {
+            *splits.entry(step.venue()).or_insert(0) += u16::from(step.split_percent);
+        }
+        splits
+    }
+}
+
+impl QuoteResponse {
+    pub fn route_shape(&self) -> RouteShape {
+        RouteShape {
+            steps: self
+                .route_plan
+                .iter()
+                .map(|step| {
+                    let info = &step.swap_info;
+                    // Older quotes carry no allocation; treat it as the whole input.
+                    let split_percent = if info.alloc_ppb == 0 {
+                        100
+                    } else {
+                        amount_math::bucketed_percent_from_ppb(info.alloc_ppb, SPLIT_BUCKET_PERCENT)
+                    };
+                    ShapeStep {
+                        label: info.label.clone(),
+                        input_mint: info.input_mint,
+                        output_mint: info.output_mint,
+                        split_percent,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A venue used for one hop of a route.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Venue {
+    pub label: String,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+}
+
+impl fmt::Display for Venue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({} -> {})",
+            self.label, self.input_mint, self.output_mint
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ShapeChange {
+    HopCount { from: usize, to: usize },
+    VenueRemoved { venue: Venue, split_percent: u16 },
+    VenueAdded { venue: Venue, split_percent: u16 },
+    SplitChanged { venue: Venue, from: u16, to: u16 },
+}
+
+impl fmt::Display for ShapeChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HopCount { from, to } => write!(f, "hops {} -> {}", from, to),
+            Self::VenueRemoved {
+                venue,
+                split_percent,
+            } => write!(f, "removed {} at {}%", venue, split_percent),
+            Self::VenueAdded {
+                venue,
+                split_percent,
+            } => write!(f, "added {} at {}%", venue, split_percent),
+            Self::SplitChanged { venue, from, to } => {
+                write!(f, "{} split {}% -> {}%", venue, from, to)
+            }
+        }
+    }
+}
+
+/// How `new` differs from `old`: hop count first, then removed, added and
+/// re-split venues, each in venue order. Empty when the shapes match, even
+/// if their steps are listed in a different order.
+pub fn shape_diff(old: &RouteShape, new: &RouteShape) -> Vec<ShapeChange> {
+    let mut changes = Vec::new();
+    let (old_hops, new_hops) = (old.hop_count(), new.hop_count());
+    if old_hops != new_hops {
+        changes.push(ShapeChange::HopCount {
+            from: old_hops,
+            to: new_hops,
+        });
+    }
+
+    let (old_splits, new_splits) = (old.splits(), new.splits());
+    for (venue, &split_percent) in &old_splits {
+        if !new_splits.contains_key(venue) {
+            changes.push(ShapeChange::VenueRemoved {
+                venue: venue.clone(),
+                split_percent,
+            });
+        }
+    }
+    for (venue, &split_percent) in &new_splits {
+        if !old_splits.contains_key(venue) {
+            changes.push(ShapeChange::VenueAdded {
+                venue: venue.clone(),
+                split_percent,
+            });
+        }
+    }
+    for (venue, &from) in &old_splits {
+        match new_splits.get(venue) {
+            Some(&to) if to != from => changes.push(ShapeChange::SplitChanged {
+                venue: venue.clone(),
+                from,
+                to,
+            }),
+            _ => {}
+        }
+    }
+    changes
+}
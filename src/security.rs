@@ -0,0 +1,62 @@
+use crate::constants::{
+    ASSOCIATED_TOKEN_PROGRAM_ID, COMPUTE_BUDGET_PROGRAM_ID, JUPITER_PROGRAM_ID, MEMO_PROGRAM_ID,
+    TITAN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+use crate::venues::Dex;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SecurityError {
+    #[error("Instruction {index} invokes unexpected program {program_id}")]
+    UnexpectedProgram { index: usize, program_id: Pubkey },
+    #[error("Instruction {index} requires signer {signer} for unexpected program {program_id}")]
+    UnexpectedSigner {
+        index: usize,
+        program_id: Pubkey,
+        signer: Pubkey,
+    },
+    /// `lamports` is the total tipped up to and including instruction
+    /// `index`.
+    #[error("Instruction {index} brings tips to {lamports} lamports, over the limit of {max}")]
+    TipTooLarge {
+        index: usize,
+        lamports: u64,
+        max: u64,
+    },
+    /// A token transfer, approval or account close the user signs, paying
+    /// `recipient` when it isn't one of the user's own accounts.
+    #[error("Instruction {index} moves the user's tokens to unexpected account {recipient}")]
+    UnexpectedTokenRecipient { index: usize, recipient: Pubkey },
+    /// A token instruction the user signs that a swap has no use for, such
+    /// as `SetAuthority` or `Burn`. `tag` is its instruction tag, `None` when
+    /// it has no data.
+    #[error("Instruction {index} is an unexpected token instruction (tag {tag:?}) the user signs")]
+    UnexpectedTokenInstruction { index: usize, tag: Option<u8> },
+}
+
+/// Programs besides the DEXes in [`Dex::ALL`] that a swap may invoke. The
+/// System program isn't one: with it, any transfer of the user's SOL would
+/// pass. `SwapResponse::verify_programs` instead lets through only the system
+/// transfers a swap makes itself.
+pub const KNOWN_PROGRAM_IDS: &[Pubkey] = &[
+    // Runtime and token programs
+    COMPUTE_BUDGET_PROGRAM_ID,
+    TOKEN_PROGRAM_ID,
+    TOKEN_2022_PROGRAM_ID,
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+    MEMO_PROGRAM_ID,
+    // Routers
+    TITAN_PROGRAM_ID,
+    JUPITER_PROGRAM_ID,
+];
+
+/// [`KNOWN_PROGRAM_IDS`] and every [`Dex`]'s program.
+pub fn default_program_allowlist() -> HashSet<Pubkey> {
+    KNOWN_PROGRAM_IDS
+        .iter()
+        .copied()
+        .chain(Dex::ALL.iter().map(Dex::program_id))
+        .collect()
+}
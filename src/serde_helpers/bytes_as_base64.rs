@@ -0,0 +1,98 @@
+use {
+    base64::{engine::general_purpose::STANDARD, Engine},
+    serde::{de, Deserializer, Serializer},
+    serde::{Deserialize, Serialize},
+};
+
+// Human-readable formats (JSON) get base64 strings; binary formats (msgpack)
+// go through serde_bytes so payloads are read as a single bin blob rather
+// than element by element.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: AsRef<[u8]> + serde_bytes::Serialize,
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        STANDARD.encode(bytes.as_ref()).serialize(serializer)
+    } else {
+        serde_bytes::serialize(bytes, serializer)
+    }
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: TryFrom<Vec<u8>> + serde_bytes::Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        let s: String = String::deserialize(deserializer)?;
+        let bytes = STANDARD
+            .decode(s)
+            .map_err(|e| de::Error::custom(format!("Base64 decode error: {:?}", e)))?;
+        let len = bytes.len();
+        T::try_from(bytes).map_err(|_| de::Error::invalid_length(len, &"a fixed-size byte array"))
+    } else {
+        serde_bytes::deserialize(deserializer)
+    }
+}
+
+struct Wrapper<T>(T);
+
+impl<T> Serialize for Wrapper<T>
+where
+    T: AsRef<[u8]> + serde_bytes::Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Wrapper<T>
+where
+    T: TryFrom<Vec<u8>> + serde_bytes::Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(Wrapper)
+    }
+}
+
+pub mod option {
+    use super::*;
+
+    pub fn serialize<T, S>(bytes: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]> + serde_bytes::Serialize,
+        S: Serializer,
+    {
+        bytes.as_ref().map(Wrapper).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: TryFrom<Vec<u8>> + serde_bytes::Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<Wrapper<T>>::deserialize(deserializer)?.map(|w| w.0))
+    }
+}
+
+pub mod vec {
+    use super::*;
+
+    pub fn serialize<T, S>(items: &[T], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]> + serde_bytes::Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(items.iter().map(Wrapper))
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        T: TryFrom<Vec<u8>> + serde_bytes::Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let items: Vec<Wrapper<T>> = Vec::deserialize(deserializer)?;
+        Ok(items.into_iter().map(|w| w.0).collect())
+    }
+}
@@ -0,0 +1,78 @@
+use {
+    crate::serde_helpers::{bytes_as_base64, field_as_string},
+    serde::{Deserialize, Deserializer, Serialize, Serializer},
+    solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InstructionRepr {
+    #[serde(with = "field_as_string")]
+    program_id: Pubkey,
+    accounts: Vec<AccountMetaRepr>,
+    #[serde(with = "bytes_as_base64")]
+    data: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountMetaRepr {
+    #[serde(with = "field_as_string")]
+    pubkey: Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+impl From<&Instruction> for InstructionRepr {
+    fn from(ix: &Instruction) -> Self {
+        Self {
+            program_id: ix.program_id,
+            accounts: ix
+                .accounts
+                .iter()
+                .map(|meta| AccountMetaRepr {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: ix.data.clone(),
+        }
+    }
+}
+
+impl From<InstructionRepr> for Instruction {
+    fn from(repr: InstructionRepr) -> Self {
+        Self {
+            program_id: repr.program_id,
+            accounts: repr
+                .accounts
+                .into_iter()
+                .map(|meta| AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: repr.data,
+        }
+    }
+}
+
+pub fn serialize<S>(instructions: &[Instruction], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_seq(instructions.iter().map(InstructionRepr::from))
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Instruction>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let instructions: Vec<InstructionRepr> = Vec::deserialize(deserializer)?;
+    Ok(instructions.into_iter().map(Instruction::from).collect())
+}
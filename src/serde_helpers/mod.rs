@@ -1 +1,8 @@
+pub mod bytes_as_base64;
 pub mod field_as_string;
+#[cfg(feature = "native")]
+pub mod instructions;
+#[cfg(feature = "native")]
+pub(crate) mod lenient;
+pub mod pubkey_as_bytes;
+pub mod vec_field_as_string;
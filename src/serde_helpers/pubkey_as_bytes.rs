@@ -0,0 +1,32 @@
+use {
+    crate::pubkey::Pubkey,
+    serde::{de, Deserializer, Serializer},
+    serde::{Deserialize, Serialize},
+};
+
+// Base58 strings in human-readable formats (JSON), raw 32-byte bins in
+// binary ones (msgpack), matching how Titan encodes pubkeys on the wire.
+pub fn serialize<S>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        pubkey.to_string().serialize(serializer)
+    } else {
+        serde_bytes::serialize(&pubkey.to_bytes(), serializer)
+    }
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Pubkey, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        let s: String = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|e| de::Error::custom(format!("Parse error: {:?}", e)))
+    } else {
+        let bytes: [u8; 32] = serde_bytes::deserialize(deserializer)?;
+        Ok(Pubkey::from(bytes))
+    }
+}
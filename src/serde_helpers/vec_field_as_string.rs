@@ -0,0 +1,67 @@
+use {
+    serde::{de, Deserializer, Serializer},
+    serde::{Deserialize, Serialize},
+    std::str::FromStr,
+};
+
+pub fn serialize<T, S>(items: &[T], serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ToString,
+    S: Serializer,
+{
+    items
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .serialize(serializer)
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    T: FromStr,
+    D: Deserializer<'de>,
+    <T as FromStr>::Err: std::fmt::Debug,
+{
+    let items: Vec<String> = Vec::deserialize(deserializer)?;
+    items
+        .iter()
+        .map(|s| {
+            s.parse()
+                .map_err(|e| de::Error::custom(format!("Parse error: {:?}", e)))
+        })
+        .collect()
+}
+
+pub mod option {
+    use super::*;
+
+    pub fn serialize<T, S>(items: &Option<Vec<T>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ToString,
+        S: Serializer,
+    {
+        items
+            .as_ref()
+            .map(|items| items.iter().map(ToString::to_string).collect::<Vec<_>>())
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+    where
+        T: FromStr,
+        D: Deserializer<'de>,
+        <T as FromStr>::Err: std::fmt::Debug,
+    {
+        Option::<Vec<String>>::deserialize(deserializer)?
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|s| {
+                        s.parse()
+                            .map_err(|e| de::Error::custom(format!("Parse error: {:?}", e)))
+                    })
+                    .collect()
+            })
+            .transpose()
+    }
+}
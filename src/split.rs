@@ -0,0 +1,181 @@
+//! Splitting an order too large for one route into child orders that are
+//! quoted and executed one after another.
+
+use crate::execute::{ExecuteOptions, SubmittedSwap, SwapRpc};
+use crate::ladder::QuoteLadder;
+use crate::quote::{QuoteRequest, QuoteResponse};
+use crate::{ClientError, TitanClient};
+use solana_sdk::signature::Signer;
+use thiserror::Error;
+
+/// The most parts [`split_order`] makes, each being its own transaction.
+pub const MAX_PARTS: u64 = 1_000;
+
+/// How [`split_order`] sizes the parts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SplitStrategy<'a> {
+    /// `n` parts differing by at most one unit, the larger ones first.
+    EvenParts(u32),
+    /// Parts of the given amount, then whatever is left over.
+    FixedClip(u64),
+    /// Parts the size of the ladder's largest level with at most this price
+    /// impact, then whatever is left over. The ladder should be for the
+    /// same pair and swap mode as the request.
+    MaxImpactBps(f64, &'a QuoteLadder),
+}
+
+#[derive(Debug, Error, Clone, Copy, PartialEq)]
+pub enum SplitError {
+    #[error("Cannot split an order into zero parts")]
+    ZeroParts,
+    #[error("Cannot split an order into clips of zero")]
+    ZeroClip,
+    #[error("No ladder level has a price impact within {0} bps")]
+    NoLevelWithin(f64),
+    #[error("Order would be split into {0} parts, more than {MAX_PARTS}")]
+    TooManyParts(u64),
+}
+
+/// `request` cut into parts by `strategy`, each a copy of it with its own
+/// amount. The amounts add up to `request.amount` exactly, and none is zero,
+/// so an order of zero has no parts. Fails with `TooManyParts` past
+/// [`MAX_PARTS`].
+pub fn split_order(
+    request: &QuoteRequest,
+    strategy: SplitStrategy<'_>,
+) -> Result<Vec<QuoteRequest>, SplitError> {
+    let amounts = match strategy {
+        SplitStrategy::EvenParts(0) => return Err(SplitError::ZeroParts),
+        SplitStrategy::EvenParts(parts) => {
+            check_part_count(u64::from(parts).min(request.amount))?;
+            even_parts(request.amount, parts)
+        }
+        SplitStrategy::FixedClip(0) => return Err(SplitError::ZeroClip),
+        SplitStrategy::FixedClip(clip) => {
+            check_part_count(request.amount.div_ceil(clip))?;
+            clips(request.amount, clip)
+        }
+        SplitStrategy::MaxImpactBps(impact_bps, ladder) => {
+            let clip = ladder
+                .worst_level_within(impact_bps)
+                .map(|level| level.amount)
+                .filter(|amount| *amount > 0)
+                .ok_or(SplitError::NoLevelWithin(impact_bps))?;
+            check_part_count(request.amount.div_ceil(clip))?;
+            clips(request.amount, clip)
+        }
+    };
+    Ok(amounts
+        .into_iter()
+        .map(|amount| QuoteRequest {
+            amount,
+            ..request.clone()
+        })
+        .collect())
+}
+
+fn check_part_count(parts: u64) -> Result<(), SplitError> {
+    if parts > MAX_PARTS {
+        return Err(SplitError::TooManyParts(parts));
+    }
+    Ok(())
+}
+
+fn even_parts(amount: u64, parts: u32) -> Vec<u64> {
+    let parts = u64::from(parts);
+    let (base, remainder) = (amount / parts, amount % parts);
+    // With more parts than units, only the first `remainder` are non-zero.
+    (0..parts.min(amount))
+        .map(|index| base + u64::from(index < remainder))
+        .collect()
+}
+
+fn clips(amount: u64, clip: u64) -> Vec<u64> {
+    let mut amounts = vec![clip; (amount / clip) as usize];
+    if !amount.is_multiple_of(clip) {
+        amounts.push(amount % clip);
+    }
+    amounts
+}
+
+/// One part that was executed.
+#[derive(Debug, Clone)]
+pub struct SplitFill {
+    pub request: QuoteRequest,
+    pub quote: QuoteResponse,
+    pub swap: SubmittedSwap,
+}
+
+/// What [`TitanClient::execute_split`] got through.
+#[derive(Debug)]
+pub struct SplitReport {
+    pub fills: Vec<SplitFill>,
+    /// The parts not executed, starting with the one that failed.
+    pub remaining: Vec<QuoteRequest>,
+    /// Why the first remaining part failed. `None` when every part filled.
+    pub error: Option<ClientError>,
+}
+
+impl SplitReport {
+    pub fn is_complete(&self) -> bool {
+        self.remaining.is_empty()
+    }
+
+    /// The requested amounts of the parts that filled.
+    pub fn filled_amount(&self) -> u64 {
+        self.fills.iter().map(|fill| fill.request.amount).sum()
+    }
+
+    /// The requested amounts of the parts that didn't.
+    pub fn remaining_amount(&self) -> u64 {
+        self.remaining.iter().map(|request| request.amount).sum()
+    }
+}
+
+impl TitanClient {
+    /// Quotes and executes each of `parts` in turn with
+    /// [`execute_swap`](Self::execute_swap), stopping at the first that
+    /// fails. Each part is quoted only once the one before it was sent.
+    pub async fn execute_split<R, S>(
+        &self,
+        parts: Vec<QuoteRequest>,
+        rpc: &R,
+        signer: &S,
+        options: &ExecuteOptions,
+    ) -> SplitReport
+    where
+        R: SwapRpc,
+        S: Signer + Sync + ?Sized,
+    {
+        let mut fills = Vec::with_capacity(parts.len());
+        let mut parts = parts.into_iter();
+        while let Some(request) = parts.next() {
+            let filled = match self.quote(&request).await {
+                Ok(quote) => self
+                    .execute_swap(&quote, rpc, signer, options)
+                    .await
+                    .map(|swap| (quote, swap)),
+                Err(err) => Err(err),
+            };
+            match filled {
+                Ok((quote, swap)) => fills.push(SplitFill {
+                    request,
+                    quote,
+                    swap,
+                }),
+                Err(err) => {
+                    return SplitReport {
+                        fills,
+                        remaining: std::iter::once(request).chain(parts).collect(),
+                        error: Some(err),
+                    }
+                }
+            }
+        }
+        SplitReport {
+            fills,
+            remaining: Vec::new(),
+            error: None,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::quote::QuoteResponse;
+use crate::ClientError;
+use solana_client::nonblocking::rpc_client::RpcClient;
+
+/// How far each step's pool state lags the chain, measured when the report
+/// was taken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StalenessReport {
+    pub current_slot: u64,
+    /// Lag of the route-level context slot, if the route reported one.
+    pub route_lag: Option<u64>,
+    /// Lag of each step in `route_plan` order; `None` for steps without
+    /// their own context slot.
+    pub step_lags: Vec<Option<u64>>,
+}
+
+impl StalenessReport {
+    pub fn new(quote: &QuoteResponse, current_slot: u64) -> Self {
+        let lag = |slot: u64| current_slot.saturating_sub(slot);
+        Self {
+            current_slot,
+            route_lag: quote.context_slot.map(lag),
+            step_lags: quote
+                .route_plan
+                .iter()
+                .map(|step| step.swap_info.context_slot.map(lag))
+                .collect(),
+        }
+    }
+
+    /// The lag of the oldest pool snapshot in the route, falling back to the
+    /// route-level lag when no step reports its own slot.
+    pub fn max_lag(&self) -> Option<u64> {
+        self.step_lags
+            .iter()
+            .flatten()
+            .max()
+            .copied()
+            .or(self.route_lag)
+    }
+}
+
+impl QuoteResponse {
+    /// Compares the quote's context slots against the current slot on `rpc`.
+    pub async fn staleness(&self, rpc: &RpcClient) -> Result<StalenessReport, ClientError> {
+        let current_slot = rpc
+            .get_slot()
+            .await
+            .map_err(|err| ClientError::Rpc(Box::new(err)))?;
+        Ok(StalenessReport::new(self, current_slot))
+    }
+}
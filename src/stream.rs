@@ -0,0 +1,469 @@
+//! Quote subscriptions multiplexed over one streaming connection.
+//!
+//! A [`TitanStreamClient`] keeps a single [`StreamConnection`] open and sends
+//! every subscription over it. Subscribing twice to the same request shares
+//! one server subscription, which is dropped once the last
+//! [`SubscriptionHandle`] for it is. When the connection drops, each handle
+//! gets a `StreamError::Disconnected`, and every live subscription is sent
+//! again on the next connection.
+//!
+//! Each handle buffers up to `StreamOptions::buffer_capacity` updates. A
+//! handle that isn't polled loses its oldest updates rather than holding up
+//! the connection.
+//!
+//! The client sends JSON text frames:
+//!
+//! ```text
+//! {"op":"subscribe","id":1,"query":"inputMint=...&outputMint=...&amount=..."}
+//! {"op":"unsubscribe","id":1}
+//! ```
+//!
+//! and the server answers with msgpack binary frames, maps with the
+//! subscription's `id` and either `quotes`, a quote response as served over
+//! HTTP, or an `error` message. Other frames are ignored.
+
+use crate::quote::{QuoteRequest, QuoteResponse, SwapQuotes};
+use crate::transform::{quote_from_swap_quotes, verify_swap_quotes, RouteChecks};
+use crate::transport::TransportError;
+use crate::ClientError;
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+/// One open connection, e.g. a websocket.
+pub trait StreamConnection: Send + 'static {
+    fn send(&mut self, frame: Frame) -> impl Future<Output = Result<(), TransportError>> + Send;
+
+    /// The next frame, or `None` once the connection has closed. Must be
+    /// cancel safe: it is dropped unfinished whenever there's a frame to send.
+    fn recv(&mut self) -> impl Future<Output = Option<Result<Frame, TransportError>>> + Send;
+}
+
+/// Opens connections for a [`TitanStreamClient`], once at the start and again
+/// after each disconnect.
+pub trait StreamConnector: Send + Sync + 'static {
+    type Connection: StreamConnection;
+
+    fn connect(&self) -> impl Future<Output = Result<Self::Connection, TransportError>> + Send;
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum StreamError {
+    /// The connection dropped. Updates resume once it's back.
+    #[error("Stream disconnected")]
+    Disconnected,
+    #[error("Stream subscription failed: {0}")]
+    Server(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamOptions {
+    /// Updates each handle holds before dropping the oldest.
+    pub buffer_capacity: usize,
+    /// How long to wait before reconnecting.
+    pub reconnect_delay: Duration,
+    /// The route checks applied to every update, as `TitanClient` applies
+    /// them to its quotes.
+    pub route_checks: RouteChecks,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self {
+            buffer_capacity: 16,
+            reconnect_delay: Duration::from_secs(1),
+            route_checks: RouteChecks::default(),
+        }
+    }
+}
+
+/// Quote subscriptions over one connection. Clones share it. Dropping the
+/// last clone closes the connection and ends every handle's stream.
+#[derive(Clone)]
+pub struct TitanStreamClient {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    registry: Arc<Mutex<Registry>>,
+    commands: mpsc::UnboundedSender<Command>,
+    buffer_capacity: usize,
+    cancel: CancellationToken,
+}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+impl TitanStreamClient {
+    /// Starts connecting through `connector` in the background. Must be
+    /// called within a Tokio runtime.
+    pub fn new<C: StreamConnector>(connector: C, options: StreamOptions) -> Self {
+        let registry = Arc::new(Mutex::new(Registry::default()));
+        let (commands, receiver) = mpsc::unbounded_channel();
+        let cancel = CancellationToken::new();
+
+        let driver = Driver {
+            connector,
+            registry: registry.clone(),
+            commands: receiver,
+            reconnect_delay: options.reconnect_delay,
+            route_checks: options.route_checks,
+        };
+        let stopped = cancel.clone();
+        tokio::spawn(async move {
+            let registry = driver.registry.clone();
+            tokio::select! {
+                _ = stopped.cancelled() => {}
+                _ = driver.run() => {}
+            }
+            for subscription in registry.lock().unwrap().subscriptions.values() {
+                subscription
+                    .listeners
+                    .iter()
+                    .for_each(|buffer| buffer.close());
+            }
+        });
+
+        Self {
+            shared: Arc::new(Shared {
+                registry,
+                commands,
+                buffer_capacity: options.buffer_capacity.max(1),
+                cancel,
+            }),
+        }
+    }
+
+    /// Subscribes to quotes for `request`. Requests with the same
+    /// `cache_key` share a server subscription.
+    pub fn subscribe(&self, request: QuoteRequest) -> SubscriptionHandle {
+        let buffer = Arc::new(Buffer::new(self.shared.buffer_capacity));
+        let key = request.cache_key();
+        let mut registry = self.shared.registry.lock().unwrap();
+
+        let id = match registry.by_key.get(&key) {
+            Some(&id) => id,
+            None => {
+                registry.next_id += 1;
+                let id = registry.next_id;
+                registry.by_key.insert(key.clone(), id);
+                registry.subscriptions.insert(
+                    id,
+                    Subscription {
+                        key,
+                        request,
+                        listeners: Vec::new(),
+                    },
+                );
+                let _ = self.shared.commands.send(Command::Subscribe(id));
+                id
+            }
+        };
+        registry
+            .subscriptions
+            .get_mut(&id)
+            .expect("subscription was just looked up")
+            .listeners
+            .push(buffer.clone());
+
+        SubscriptionHandle {
+            id,
+            buffer,
+            registry: self.shared.registry.clone(),
+            commands: self.shared.commands.clone(),
+        }
+    }
+
+    /// How many server subscriptions are live.
+    pub fn subscription_count(&self) -> usize {
+        self.shared.registry.lock().unwrap().subscriptions.len()
+    }
+}
+
+/// The quotes for one subscription, as a [`Stream`] or through
+/// [`next`](Self::next). Dropping it unsubscribes, unless another handle
+/// shares the subscription.
+pub struct SubscriptionHandle {
+    id: u64,
+    buffer: Arc<Buffer>,
+    registry: Arc<Mutex<Registry>>,
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+impl SubscriptionHandle {
+    /// The next update, or `None` once the client is dropped.
+    pub async fn next(&mut self) -> Option<Result<QuoteResponse, ClientError>> {
+        std::future::poll_fn(|cx| self.buffer.poll_next(cx)).await
+    }
+
+    /// How many updates were dropped because the buffer was full.
+    pub fn dropped(&self) -> u64 {
+        self.buffer.state.lock().unwrap().dropped
+    }
+}
+
+impl Stream for SubscriptionHandle {
+    type Item = Result<QuoteResponse, ClientError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.buffer.poll_next(cx)
+    }
+}
+
+impl Drop for SubscriptionHandle {
+    fn drop(&mut self) {
+        let mut registry = self.registry.lock().unwrap();
+        let Some(subscription) = registry.subscriptions.get_mut(&self.id) else {
+            return;
+        };
+        subscription
+            .listeners
+            .retain(|buffer| !Arc::ptr_eq(buffer, &self.buffer));
+        if subscription.listeners.is_empty() {
+            let key = subscription.key.clone();
+            registry.subscriptions.remove(&self.id);
+            registry.by_key.remove(&key);
+            let _ = self.commands.send(Command::Unsubscribe(self.id));
+        }
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    next_id: u64,
+    // By id, so subscriptions are resent in the order they were made.
+    subscriptions: BTreeMap<u64, Subscription>,
+    by_key: HashMap<String, u64>,
+}
+
+struct Subscription {
+    key: String,
+    request: QuoteRequest,
+    listeners: Vec<Arc<Buffer>>,
+}
+
+enum Command {
+    Subscribe(u64),
+    Unsubscribe(u64),
+}
+
+/// A handle's updates, filled by the connection task and drained by the
+/// handle.
+struct Buffer {
+    capacity: usize,
+    state: Mutex<BufferState>,
+}
+
+#[derive(Default)]
+struct BufferState {
+    updates: VecDeque<Result<QuoteResponse, ClientError>>,
+    dropped: u64,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+impl Buffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::default(),
+        }
+    }
+
+    fn push(&self, update: Result<QuoteResponse, ClientError>) {
+        let mut state = self.state.lock().unwrap();
+        if state.closed {
+            return;
+        }
+        if state.updates.len() >= self.capacity {
+            state.updates.pop_front();
+            state.dropped += 1;
+        }
+        state.updates.push_back(update);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn close(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.closed = true;
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn poll_next(&self, cx: &mut Context<'_>) -> Poll<Option<Result<QuoteResponse, ClientError>>> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(update) = state.updates.pop_front() {
+            return Poll::Ready(Some(update));
+        }
+        if state.closed {
+            return Poll::Ready(None);
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum ClientFrame<'a> {
+    Subscribe { id: u64, query: &'a str },
+    Unsubscribe { id: u64 },
+}
+
+impl ClientFrame<'_> {
+    fn into_frame(self) -> Frame {
+        Frame::Text(serde_json::to_string(&self).expect("frame serializes"))
+    }
+}
+
+#[derive(Deserialize)]
+struct ServerFrame {
+    id: u64,
+    #[serde(default)]
+    quotes: Option<SwapQuotes>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+struct Driver<C> {
+    connector: C,
+    registry: Arc<Mutex<Registry>>,
+    commands: mpsc::UnboundedReceiver<Command>,
+    reconnect_delay: Duration,
+    route_checks: RouteChecks,
+}
+
+impl<C: StreamConnector> Driver<C> {
+    async fn run(mut self) {
+        loop {
+            if let Ok(connection) = self.connector.connect().await {
+                if !self.serve(connection).await {
+                    return;
+                }
+                self.notify_all(|| StreamError::Disconnected.into());
+            }
+            tokio::time::sleep(self.reconnect_delay).await;
+        }
+    }
+
+    /// Resubscribes everything live, then relays frames until the connection
+    /// drops. Returns `false` once no client or handle is left to command it.
+    async fn serve(&mut self, mut connection: C::Connection) -> bool {
+        // What this connection is subscribed to, so a subscription made while
+        // reconnecting isn't sent twice.
+        let mut subscribed = HashSet::new();
+        let live: Vec<(u64, String)> = {
+            let registry = self.registry.lock().unwrap();
+            registry
+                .subscriptions
+                .iter()
+                .map(|(id, subscription)| (*id, subscription.request.to_query_string()))
+                .collect()
+        };
+        for (id, query) in live {
+            subscribed.insert(id);
+            let frame = ClientFrame::Subscribe { id, query: &query }.into_frame();
+            if connection.send(frame).await.is_err() {
+                return true;
+            }
+        }
+
+        loop {
+            tokio::select! {
+                command = self.commands.recv() => {
+                    let frame = match command {
+                        None => return false,
+                        Some(Command::Subscribe(id)) => {
+                            let query = self
+                                .registry
+                                .lock()
+                                .unwrap()
+                                .subscriptions
+                                .get(&id)
+                                .map(|subscription| subscription.request.to_query_string());
+                            match query {
+                                Some(query) if subscribed.insert(id) => {
+                                    ClientFrame::Subscribe { id, query: &query }.into_frame()
+                                }
+                                _ => continue,
+                            }
+                        }
+                        Some(Command::Unsubscribe(id)) => {
+                            if !subscribed.remove(&id) {
+                                continue;
+                            }
+                            ClientFrame::Unsubscribe { id }.into_frame()
+                        }
+                    };
+                    if connection.send(frame).await.is_err() {
+                        return true;
+                    }
+                }
+                frame = connection.recv() => match frame {
+                    Some(Ok(Frame::Binary(bytes))) => self.dispatch(&bytes),
+                    Some(Ok(Frame::Text(_))) => {}
+                    Some(Err(_)) | None => return true,
+                },
+            }
+        }
+    }
+
+    fn dispatch(&self, bytes: &[u8]) {
+        let Ok(frame) = rmp_serde::from_slice::<ServerFrame>(bytes) else {
+            return;
+        };
+        let registry = self.registry.lock().unwrap();
+        let Some(subscription) = registry.subscriptions.get(&frame.id) else {
+            return;
+        };
+        for buffer in &subscription.listeners {
+            let update = match (&frame.quotes, &frame.error) {
+                (_, Some(error)) => Err(StreamError::Server(error.clone()).into()),
+                (Some(quotes), None) => {
+                    quote_from(&subscription.request, quotes, &self.route_checks)
+                }
+                (None, None) => Err(ClientError::NoRoutesAvailable),
+            };
+            buffer.push(update);
+        }
+    }
+
+    fn notify_all(&self, error: impl Fn() -> ClientError) {
+        for subscription in self.registry.lock().unwrap().subscriptions.values() {
+            subscription
+                .listeners
+                .iter()
+                .for_each(|buffer| buffer.push(Err(error())));
+        }
+    }
+}
+
+fn quote_from(
+    request: &QuoteRequest,
+    quotes: &SwapQuotes,
+    checks: &RouteChecks,
+) -> Result<QuoteResponse, ClientError> {
+    verify_swap_quotes(request, quotes)?;
+    quote_from_swap_quotes(request, quotes.clone(), None, checks)
+}
@@ -0,0 +1,57 @@
+//! A quote's headline numbers without its route data, cheap to clone into
+//! every subscriber of a quote stream.
+
+use crate::pubkey::Pubkey;
+use crate::quote::{PlatformFee, QuoteResponse, SwapMode};
+use crate::serde_helpers::field_as_string;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteSummary {
+    #[serde(with = "field_as_string")]
+    pub input_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub output_mint: Pubkey,
+    /// The route's actual input; see `QuoteResponse::input_amount`.
+    #[serde(with = "field_as_string")]
+    pub in_amount: u64,
+    #[serde(with = "field_as_string")]
+    pub out_amount: u64,
+    pub swap_mode: SwapMode,
+    pub slippage_bps: u16,
+    #[serde(default)]
+    pub platform_fee: Option<PlatformFee>,
+    #[serde(default)]
+    pub context_slot: Option<u64>,
+    #[serde(default)]
+    pub expires_at_ms: Option<u64>,
+    #[serde(default)]
+    pub compute_units: Option<u64>,
+    /// Each step's venue label, in route plan order.
+    pub labels: Arc<[String]>,
+}
+
+impl QuoteResponse {
+    pub fn summary(&self) -> QuoteSummary {
+        let route = self.route_data();
+        QuoteSummary {
+            input_mint: self.input_mint,
+            output_mint: self.output_mint,
+            in_amount: self.input_amount(),
+            out_amount: self.out_amount,
+            swap_mode: self.swap_mode.clone(),
+            slippage_bps: self.slippage_bps,
+            platform_fee: self.platform_fee,
+            context_slot: self.context_slot,
+            expires_at_ms: route.and_then(|route| route.expires_at_ms),
+            compute_units: route.and_then(|route| route.compute_units),
+            labels: self
+                .route_plan
+                .iter()
+                .map(|step| step.swap_info.label.clone())
+                .collect(),
+        }
+    }
+}
@@ -1,12 +1,629 @@
-use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use crate::amount_math;
+use crate::compose;
+use crate::constants::{
+    COMPUTE_BUDGET_PROGRAM_ID, LAMPORTS_PER_SIGNATURE, SYSTEM_PROGRAM_ID, TOKEN_2022_PROGRAM_ID,
+    TOKEN_PROGRAM_ID, WSOL_MINT,
+};
+use crate::inspect::{self, InstructionKind};
+use crate::quote::{QuoteResponse, MAX_TRANSACTION_BYTES};
+use crate::raw::{RawInstruction, RawSwapResponse};
+use crate::security::SecurityError;
+use crate::serde_helpers::{field_as_string, instructions, vec_field_as_string};
+use crate::token_2022::associated_token_address;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
 
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct SwapResponse {
+    #[serde(with = "instructions")]
     pub instructions: Vec<Instruction>,
+    #[serde(with = "vec_field_as_string")]
     pub address_lookup_table_addresses: Vec<Pubkey>,
-    pub compute_unit_limit: u32,
+    pub compute_unit_limit: Option<u32>,
     pub compute_units_safe: Option<u64>,
     pub context_slot: Option<u64>,
     pub expires_at_ms: Option<u64>,
     pub expires_after_slot: Option<u64>,
 }
+
+impl SwapResponse {
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json(s: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(s)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec_named(self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(bytes)
+    }
+
+    pub fn to_raw(&self) -> RawSwapResponse {
+        RawSwapResponse {
+            instructions: self.instructions.iter().map(instruction_to_raw).collect(),
+            address_lookup_table_addresses: self
+                .address_lookup_table_addresses
+                .iter()
+                .map(|address| address.to_bytes())
+                .collect(),
+            compute_unit_limit: self.compute_unit_limit,
+            compute_units_safe: self.compute_units_safe,
+            context_slot: self.context_slot,
+            expires_at_ms: self.expires_at_ms,
+            expires_after_slot: self.expires_after_slot,
+        }
+    }
+
+    pub fn from_raw(raw: RawSwapResponse) -> Self {
+        SwapResponse {
+            instructions: raw
+                .instructions
+                .into_iter()
+                .map(instruction_from_raw)
+                .collect(),
+            address_lookup_table_addresses: raw
+                .address_lookup_table_addresses
+                .into_iter()
+                .map(Pubkey::from)
+                .collect(),
+            compute_unit_limit: raw.compute_unit_limit,
+            compute_units_safe: raw.compute_units_safe,
+            context_slot: raw.context_slot,
+            expires_at_ms: raw.expires_at_ms,
+            expires_after_slot: raw.expires_after_slot,
+        }
+    }
+
+    /// Rejects the swap if any instruction invokes a program outside `allowlist`,
+    /// as `UnexpectedSigner` when `user` signs it. System transfers are let
+    /// through only as Jito tips, or to wrap SOL into `user`'s own wrapped SOL
+    /// account, whether or not `allowlist` lists the System program. Tips
+    /// together may come to at most `max_tip_lamports`; more fails with
+    /// `TipTooLarge`.
+    ///
+    /// Of the token instructions `user` signs, only transfers, approvals,
+    /// revokes, account closes and SOL syncs pass; any other, such as
+    /// `SetAuthority` or `Burn`, fails with `UnexpectedTokenInstruction`.
+    /// Transfers, approvals and closes fail with `UnexpectedTokenRecipient`
+    /// unless they pay `user` or one of its associated token accounts for a
+    /// mint the swap names. Pool and vault accounts of the route never count
+    /// as the user's.
+    pub fn verify_programs(
+        &self,
+        allowlist: &HashSet<Pubkey>,
+        user: Pubkey,
+        max_tip_lamports: u64,
+    ) -> Result<(), SecurityError> {
+        let wrapped_sol = associated_token_address(&user, &WSOL_MINT, &TOKEN_PROGRAM_ID);
+        let mut own_accounts = None;
+        let mut tipped = 0u64;
+        for (index, ix) in self.instructions.iter().enumerate() {
+            if ix.program_id == SYSTEM_PROGRAM_ID {
+                match inspect::classify_instruction(ix) {
+                    InstructionKind::Tip { lamports, .. } => {
+                        tipped = tipped.saturating_add(lamports);
+                        if tipped > max_tip_lamports {
+                            return Err(SecurityError::TipTooLarge {
+                                index,
+                                lamports: tipped,
+                                max: max_tip_lamports,
+                            });
+                        }
+                        continue;
+                    }
+                    InstructionKind::Transfer { to, .. } if to == wrapped_sol => continue,
+                    _ => {}
+                }
+            } else if allowlist.contains(&ix.program_id) {
+                if !is_token_program(&ix.program_id) || !signs(ix, user) {
+                    continue;
+                }
+                match user_token_instruction(ix) {
+                    UserTokenInstruction::Pays(Some(recipient))
+                        if !own_accounts
+                            .get_or_insert_with(|| self.own_accounts(user))
+                            .contains(&recipient) =>
+                    {
+                        return Err(SecurityError::UnexpectedTokenRecipient { index, recipient });
+                    }
+                    UserTokenInstruction::Pays(_) | UserTokenInstruction::PaysNobody => continue,
+                    UserTokenInstruction::Unexpected => {
+                        return Err(SecurityError::UnexpectedTokenInstruction {
+                            index,
+                            tag: ix.data.first().copied(),
+                        });
+                    }
+                }
+            }
+
+            if signs(ix, user) {
+                return Err(SecurityError::UnexpectedSigner {
+                    index,
+                    program_id: ix.program_id,
+                    signer: user,
+                });
+            }
+
+            return Err(SecurityError::UnexpectedProgram {
+                index,
+                program_id: ix.program_id,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// What a token instruction `user` signs may pay: `user` itself and its
+    /// associated token accounts, wrapped SOL's and those for every account
+    /// the swap names that could be a mint. That covers the accounts the swap
+    /// creates for `user`, while a route's pools and vaults, which aren't
+    /// derived from `user`, never match.
+    fn own_accounts(&self, user: Pubkey) -> HashSet<Pubkey> {
+        let mints: HashSet<Pubkey> = self
+            .instructions
+            .iter()
+            .flat_map(|ix| ix.accounts.iter().map(|meta| meta.pubkey))
+            .chain([WSOL_MINT])
+            .collect();
+        let mut accounts = HashSet::from([user]);
+        for mint in mints {
+            for token_program in [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID] {
+                accounts.insert(associated_token_address(&user, &mint, &token_program));
+            }
+        }
+        accounts
+    }
+
+    /// The lookup tables to fetch for this swap, each once, in the order
+    /// they were listed.
+    pub fn lookup_table_addresses(&self) -> Vec<Pubkey> {
+        let mut seen = HashSet::new();
+        self.address_lookup_table_addresses
+            .iter()
+            .copied()
+            .filter(|address| seen.insert(*address))
+            .collect()
+    }
+
+    /// Warns when the swap has no lookup tables but its transaction would
+    /// be too large without them, likely a malformed route.
+    pub fn lookup_table_warning(&self) -> Option<SwapWarning> {
+        if !self.address_lookup_table_addresses.is_empty() {
+            return None;
+        }
+        let estimated_size = compose::estimate_size_without_lookup_tables(&self.instructions);
+        (estimated_size > usize::from(MAX_TRANSACTION_BYTES))
+            .then_some(SwapWarning::MissingLookupTables { estimated_size })
+    }
+
+    /// See [`inspect::onchain_min_out`].
+    pub fn has_onchain_min_out(&self) -> Option<bool> {
+        inspect::onchain_min_out(&self.instructions)
+    }
+
+    pub fn account_summary(&self) -> AccountSummary {
+        let mut summary = AccountSummary::default();
+
+        for ix in &self.instructions {
+            *summary
+                .instructions_per_program
+                .entry(ix.program_id)
+                .or_insert(0) += 1;
+
+            for meta in &ix.accounts {
+                if meta.is_writable {
+                    summary.writable.insert(meta.pubkey);
+                }
+                if meta.is_signer {
+                    summary.signers.insert(meta.pubkey);
+                }
+            }
+        }
+
+        summary
+    }
+
+    /// Lamports to land this swap: the base fee for `num_signatures` plus the
+    /// prioritization fee at `cu_price_micro_lamports`, charged on
+    /// `compute_units_safe` (or `compute_unit_limit` if that wasn't
+    /// reported). `None` if the server reported neither, or on overflow.
+    pub fn estimated_fee_lamports(
+        &self,
+        cu_price_micro_lamports: u64,
+        num_signatures: usize,
+    ) -> Option<u64> {
+        let compute_units = self
+            .compute_units_safe
+            .or(self.compute_unit_limit.map(u64::from))?;
+        let priority_fee =
+            amount_math::priority_fee_lamports(compute_units, cu_price_micro_lamports).ok()?;
+        let base_fee = u64::try_from(num_signatures)
+            .ok()?
+            .checked_mul(LAMPORTS_PER_SIGNATURE)?;
+        amount_math::checked_add(base_fee, priority_fee).ok()
+    }
+
+    /// The compute budget the route's own instructions set, if any. Adding
+    /// a second budget instruction of the same kind makes the transaction
+    /// invalid, so use [`set_compute_unit_limit`](Self::set_compute_unit_limit)
+    /// and [`set_compute_unit_price`](Self::set_compute_unit_price) rather
+    /// than prepending your own.
+    pub fn existing_compute_budget(&self) -> Option<ComputeBudgetSummary> {
+        let mut summary = ComputeBudgetSummary::default();
+        for (index, ix) in self.instructions.iter().enumerate() {
+            match inspect::classify_instruction(ix) {
+                InstructionKind::SetComputeUnitLimit { units } => {
+                    summary.unit_limit.get_or_insert(units);
+                }
+                InstructionKind::SetComputeUnitPrice { micro_lamports } => {
+                    summary
+                        .unit_price_micro_lamports
+                        .get_or_insert(micro_lamports);
+                }
+                _ if ix.program_id == COMPUTE_BUDGET_PROGRAM_ID => {}
+                _ => continue,
+            }
+            summary.instruction_indices.push(index);
+        }
+        (!summary.instruction_indices.is_empty()).then_some(summary)
+    }
+
+    /// Each Jito tip transfer in the route, as `(tip account, lamports)`.
+    pub fn existing_tips(&self) -> Vec<(Pubkey, u64)> {
+        self.instructions
+            .iter()
+            .filter_map(|ix| match inspect::classify_instruction(ix) {
+                InstructionKind::Tip { lamports, to } => Some((to, lamports)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Sets the compute unit limit, rewriting the route's own
+    /// SetComputeUnitLimit when it has one and prepending one otherwise.
+    pub fn set_compute_unit_limit(&mut self, units: u32) {
+        self.replace_or_prepend(
+            |kind| matches!(kind, InstructionKind::SetComputeUnitLimit { .. }),
+            ComputeBudgetInstruction::set_compute_unit_limit(units),
+        );
+        self.compute_unit_limit = Some(units);
+    }
+
+    /// Sets the priority fee, rewriting the route's own SetComputeUnitPrice
+    /// when it has one and prepending one otherwise.
+    pub fn set_compute_unit_price(&mut self, micro_lamports: u64) {
+        self.replace_or_prepend(
+            |kind| matches!(kind, InstructionKind::SetComputeUnitPrice { .. }),
+            ComputeBudgetInstruction::set_compute_unit_price(micro_lamports),
+        );
+    }
+
+    /// Tips `lamports` to `tip_account`, paid by `payer`. When the route
+    /// already tips, its first tip is rewritten to `lamports`, keeping its
+    /// own payer and tip account, and any further tips are removed.
+    /// Otherwise the transfer is appended.
+    pub fn set_tip(&mut self, payer: Pubkey, tip_account: Pubkey, lamports: u64) {
+        let mut tipped = false;
+        self.instructions.retain_mut(|ix| {
+            if !matches!(
+                inspect::classify_instruction(ix),
+                InstructionKind::Tip { .. }
+            ) {
+                return true;
+            }
+            if tipped {
+                return false;
+            }
+            ix.data = transfer_data(lamports);
+            tipped = true;
+            true
+        });
+        if !tipped {
+            self.instructions.push(Instruction {
+                program_id: SYSTEM_PROGRAM_ID,
+                accounts: vec![
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new(tip_account, false),
+                ],
+                data: transfer_data(lamports),
+            });
+        }
+    }
+
+    fn replace_or_prepend(&mut self, matches: impl Fn(&InstructionKind) -> bool, ix: Instruction) {
+        let existing = self
+            .instructions
+            .iter()
+            .position(|existing| matches(&inspect::classify_instruction(existing)));
+        match existing {
+            Some(index) => self.instructions[index] = ix,
+            None => self.instructions.insert(0, ix),
+        }
+    }
+
+    /// Splits the instructions into setup, core and cleanup, on a
+    /// best-effort basis.
+    ///
+    /// Setup is ATA creation, wrapping SOL into an account that is then
+    /// synced, and the sync itself, before the first other instruction.
+    /// Cleanup is closing token accounts after the last core instruction.
+    /// Everything else, including compute budget instructions and tips, is
+    /// core; if the route has no other instruction, all of it is. Each part
+    /// keeps the original order.
+    pub fn partition_instructions(&self) -> PartitionedInstructions {
+        let mut partitioned = PartitionedInstructions::default();
+        for (ix, role) in self.instructions.iter().zip(self.instruction_roles()) {
+            match role {
+                InstructionRole::Setup => partitioned.setup.push(ix.clone()),
+                InstructionRole::Core => partitioned.core.push(ix.clone()),
+                InstructionRole::Cleanup => partitioned.cleanup.push(ix.clone()),
+            }
+        }
+        partitioned
+    }
+
+    /// The swap without the setup instructions of
+    /// [`partition_instructions`](Self::partition_instructions), for when
+    /// the transaction already creates and funds the accounts.
+    pub fn without_setup(self) -> Self {
+        self.without_role(InstructionRole::Setup)
+    }
+
+    /// The swap without the cleanup instructions of
+    /// [`partition_instructions`](Self::partition_instructions), for when
+    /// the transaction closes the accounts itself.
+    pub fn without_cleanup(self) -> Self {
+        self.without_role(InstructionRole::Cleanup)
+    }
+
+    fn without_role(mut self, removed: InstructionRole) -> Self {
+        let mut roles = self.instruction_roles().into_iter();
+        self.instructions.retain(|_| roles.next() != Some(removed));
+        self
+    }
+
+    fn instruction_roles(&self) -> Vec<InstructionRole> {
+        let kinds = inspect::classify_instructions(self);
+        let is_setup_kind = |kind: &InstructionKind| {
+            matches!(
+                kind,
+                InstructionKind::SetComputeUnitLimit { .. }
+                    | InstructionKind::SetComputeUnitPrice { .. }
+                    | InstructionKind::CreateAssociatedTokenAccount { .. }
+                    | InstructionKind::Transfer { .. }
+                    | InstructionKind::SyncNative { .. }
+            )
+        };
+        let mut roles = vec![InstructionRole::Core; kinds.len()];
+        let Some(first_core) = kinds.iter().position(|kind| !is_setup_kind(kind)) else {
+            return roles;
+        };
+
+        // A transfer is only wrapping SOL if the recipient is then synced.
+        let synced: HashSet<Pubkey> = self.instructions[..first_core]
+            .iter()
+            .zip(&kinds)
+            .filter(|(_, kind)| matches!(kind, InstructionKind::SyncNative { .. }))
+            .filter_map(|(ix, _)| ix.accounts.first().map(|meta| meta.pubkey))
+            .collect();
+        for (role, kind) in roles.iter_mut().zip(&kinds[..first_core]) {
+            let is_setup = match kind {
+                InstructionKind::CreateAssociatedTokenAccount { .. }
+                | InstructionKind::SyncNative { .. } => true,
+                InstructionKind::Transfer { to, .. } => synced.contains(to),
+                _ => false,
+            };
+            if is_setup {
+                *role = InstructionRole::Setup;
+            }
+        }
+
+        let last_core = kinds
+            .iter()
+            .rposition(|kind| {
+                !matches!(
+                    kind,
+                    InstructionKind::CloseAccount { .. } | InstructionKind::Tip { .. }
+                )
+            })
+            .map_or(first_core, |last| last.max(first_core));
+        for (role, kind) in roles.iter_mut().zip(&kinds).skip(last_core + 1) {
+            if matches!(kind, InstructionKind::CloseAccount { .. }) {
+                *role = InstructionRole::Cleanup;
+            }
+        }
+        roles
+    }
+
+    pub fn touches(&self, account: &Pubkey) -> bool {
+        self.instructions.iter().any(|ix| {
+            ix.program_id == *account || ix.accounts.iter().any(|meta| meta.pubkey == *account)
+        })
+    }
+}
+
+/// Something off about a route that didn't stop it being turned into a swap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapWarning {
+    /// The route listed `listed` lookup tables, of which only `unique` were
+    /// distinct. The swap keeps one of each.
+    DuplicateLookupTables { listed: usize, unique: usize },
+    /// The route has no lookup tables, and its transaction is estimated at
+    /// `estimated_size` bytes without them, over the size limit.
+    MissingLookupTables { estimated_size: usize },
+}
+
+impl fmt::Display for SwapWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateLookupTables { listed, unique } => write!(
+                f,
+                "route listed {listed} lookup tables, {unique} of them distinct"
+            ),
+            Self::MissingLookupTables { estimated_size } => write!(
+                f,
+                "route has no lookup tables and needs an estimated {estimated_size} bytes without them"
+            ),
+        }
+    }
+}
+
+/// The compute budget set by a route's own instructions. When the route
+/// repeats an instruction, the first one is reported.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComputeBudgetSummary {
+    pub unit_limit: Option<u32>,
+    pub unit_price_micro_lamports: Option<u64>,
+    /// Where the compute budget instructions are in `instructions`,
+    /// including any whose kind isn't recognized.
+    pub instruction_indices: Vec<usize>,
+}
+
+/// A swap's instructions split by [`SwapResponse::partition_instructions`].
+/// The split is a heuristic: check it against the routes you use before
+/// dropping a part, and use `SwapResponse::instructions` as returned when in
+/// doubt.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PartitionedInstructions {
+    pub setup: Vec<Instruction>,
+    pub core: Vec<Instruction>,
+    pub cleanup: Vec<Instruction>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InstructionRole {
+    Setup,
+    Core,
+    Cleanup,
+}
+
+/// Options for [`crate::TitanClient::swap_instructions`]. Unset fields use
+/// the server defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapInstructionsOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wrap_and_unwrap_sol: Option<bool>,
+    #[serde(
+        with = "field_as_string::option",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee_account: Option<Pubkey>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compute_unit_price_micro_lamports: Option<u64>,
+}
+
+/// Result of [`crate::TitanClient::swap_fresh`]: the quote the swap was built
+/// from, which is the refreshed one when `requoted` is set.
+#[derive(Debug, Clone)]
+pub struct FreshSwap {
+    pub quote: QuoteResponse,
+    pub swap: SwapResponse,
+    pub requoted: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccountSummary {
+    pub writable: BTreeSet<Pubkey>,
+    pub signers: BTreeSet<Pubkey>,
+    pub instructions_per_program: BTreeMap<Pubkey, usize>,
+}
+
+impl fmt::Display for AccountSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "signers ({}):", self.signers.len())?;
+        for signer in &self.signers {
+            writeln!(f, "  {}", signer)?;
+        }
+        writeln!(f, "writable ({}):", self.writable.len())?;
+        for account in &self.writable {
+            writeln!(f, "  {}", account)?;
+        }
+        write!(f, "programs ({}):", self.instructions_per_program.len())?;
+        for (program_id, count) in &self.instructions_per_program {
+            write!(f, "\n  {} x{}", program_id, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// A System Program transfer: tag 2 as a little-endian u32, then the
+/// lamports.
+fn transfer_data(lamports: u64) -> Vec<u8> {
+    let mut data = vec![2, 0, 0, 0];
+    data.extend_from_slice(&lamports.to_le_bytes());
+    data
+}
+
+fn is_token_program(program_id: &Pubkey) -> bool {
+    *program_id == TOKEN_PROGRAM_ID || *program_id == TOKEN_2022_PROGRAM_ID
+}
+
+fn signs(ix: &Instruction, user: Pubkey) -> bool {
+    ix.accounts
+        .iter()
+        .any(|meta| meta.is_signer && meta.pubkey == user)
+}
+
+/// What a token instruction the user signs does with the user's tokens.
+enum UserTokenInstruction {
+    /// A transfer's destination, an approval's delegate, or where a closed
+    /// account's lamports go; `None` when the account is missing.
+    Pays(Option<Pubkey>),
+    /// Revoke and SyncNative, which move nothing.
+    PaysNobody,
+    /// Anything else, e.g. `SetAuthority` handing the account over or `Burn`.
+    Unexpected,
+}
+
+fn user_token_instruction(ix: &Instruction) -> UserTokenInstruction {
+    let position = match ix.data.first() {
+        // Transfer, Approve, CloseAccount
+        Some(3 | 4 | 9) => 1,
+        // TransferChecked, ApproveChecked
+        Some(12 | 13) => 2,
+        // Revoke, SyncNative
+        Some(5 | 17) => return UserTokenInstruction::PaysNobody,
+        _ => return UserTokenInstruction::Unexpected,
+    };
+    UserTokenInstruction::Pays(ix.accounts.get(position).map(|meta| meta.pubkey))
+}
+
+fn instruction_to_raw(ix: &Instruction) -> RawInstruction {
+    (
+        ix.program_id.to_bytes(),
+        ix.accounts
+            .iter()
+            .map(|meta| (meta.pubkey.to_bytes(), meta.is_signer, meta.is_writable))
+            .collect(),
+        ix.data.clone(),
+    )
+}
+
+fn instruction_from_raw((program_id, accounts, data): RawInstruction) -> Instruction {
+    Instruction {
+        program_id: Pubkey::from(program_id),
+        accounts: accounts
+            .into_iter()
+            .map(|(pubkey, is_signer, is_writable)| AccountMeta {
+                pubkey: Pubkey::from(pubkey),
+                is_signer,
+                is_writable,
+            })
+            .collect(),
+        data,
+    }
+}
@@ -0,0 +1,598 @@
+use crate::clock::Clock;
+use crate::constants::{SOL_MINT, USDC_MINT};
+use crate::price::PriceResponse;
+use crate::quote::{
+    AccountMetaData, InstructionData, PlatformFeeData, QuoteKey, QuoteRequest, RoutePlanStepData,
+    SwapMode, SwapQuotes, SwapRoute,
+};
+use crate::tokens::TokenInfo;
+use crate::venues::VenueInfo;
+use crate::{TitanClient, TitanClientBuilder};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const QUOTE_PATH: &str = "/api/v1/quote/swap";
+const TOKENS_PATH: &str = "/api/v1/tokens";
+const PRICE_PATH: &str = "/api/v1/price";
+const PRICES_PATH: &str = "/api/v1/prices";
+const VENUES_PATH: &str = "/api/v1/venues";
+const HEALTH_PATH: &str = "/api/v1/health";
+const SWAP_INSTRUCTIONS_PATH: &str = "/api/v1/swap/instructions";
+
+/// A fake Titan API for tests, built on `wiremock`.
+///
+/// Responses are matched in the order they are mounted, so a scripted
+/// sequence is a series of `times(n)` responses followed by a fallback.
+pub struct MockTitanServer {
+    server: MockServer,
+}
+
+impl MockTitanServer {
+    pub async fn start() -> Self {
+        Self {
+            server: MockServer::start().await,
+        }
+    }
+
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    pub fn client(&self) -> TitanClient {
+        self.client_builder()
+            .build()
+            .expect("mock client configuration is valid")
+    }
+
+    pub fn client_builder(&self) -> TitanClientBuilder {
+        TitanClient::builder("test-token").base_path(self.uri())
+    }
+
+    /// Serves `route` for the mints, swap mode and amount each request asks
+    /// for, as the API does. Use `respond_with_body` with
+    /// [`SwapRouteBuilder::to_msgpack`] to serve a response as built.
+    pub fn respond_with_route(&self, route: SwapRouteBuilder) -> ScriptedResponse<'_> {
+        ScriptedResponse::echoing(self, route.to_swap_quotes())
+    }
+
+    /// Serves one quote response carrying every route, keyed by each
+    /// builder's provider, echoing each request like `respond_with_route`.
+    pub fn respond_with_routes(
+        &self,
+        routes: impl IntoIterator<Item = SwapRouteBuilder>,
+    ) -> ScriptedResponse<'_> {
+        let mut routes = routes.into_iter();
+        let mut quotes = routes.next().expect("at least one route").to_swap_quotes();
+        for route in routes {
+            let provider = route.provider.clone();
+            quotes
+                .quotes
+                .insert(QuoteKey::from(provider), route.build());
+        }
+        ScriptedResponse::echoing(self, quotes)
+    }
+
+    pub fn respond_with_body(&self, body: impl Into<Vec<u8>>) -> ScriptedResponse<'_> {
+        let template = ResponseTemplate::new(200)
+            .insert_header("content-type", "application/vnd.msgpack")
+            .set_body_bytes(body.into());
+        ScriptedResponse::new(self, template)
+    }
+
+    /// Serves `body` as a JSON quote response.
+    pub fn respond_with_json_body(&self, body: impl Into<Vec<u8>>) -> ScriptedResponse<'_> {
+        let template = ResponseTemplate::new(200)
+            .insert_header("content-type", "application/json")
+            .set_body_bytes(body.into());
+        ScriptedResponse::new(self, template)
+    }
+
+    pub fn respond_with_status(&self, status: u16) -> ScriptedResponse<'_> {
+        ScriptedResponse::new(self, ResponseTemplate::new(status))
+    }
+
+    pub fn respond_with_error(&self, status: u16, body: &str) -> ScriptedResponse<'_> {
+        ScriptedResponse::new(self, ResponseTemplate::new(status).set_body_string(body))
+    }
+
+    pub fn respond_with_tokens(&self, tokens: &[TokenInfo]) -> ScriptedResponse<'_> {
+        ScriptedResponse::at(self, TOKENS_PATH, msgpack_template(tokens))
+    }
+
+    pub fn respond_with_tokens_json(&self, tokens: &[TokenInfo]) -> ScriptedResponse<'_> {
+        let template = ResponseTemplate::new(200).set_body_json(tokens);
+        ScriptedResponse::at(self, TOKENS_PATH, template)
+    }
+
+    pub fn respond_with_price(&self, price: &PriceResponse) -> ScriptedResponse<'_> {
+        ScriptedResponse::at(self, PRICE_PATH, msgpack_template(price))
+    }
+
+    pub fn respond_with_prices(&self, prices: &[PriceResponse]) -> ScriptedResponse<'_> {
+        ScriptedResponse::at(self, PRICES_PATH, msgpack_template(prices))
+    }
+
+    pub fn respond_with_venues(&self, venues: &[VenueInfo]) -> ScriptedResponse<'_> {
+        ScriptedResponse::at(self, VENUES_PATH, msgpack_template(venues))
+    }
+
+    pub fn respond_with_health(&self, status: u16, body: &str) -> ScriptedResponse<'_> {
+        let template = ResponseTemplate::new(status).set_body_string(body);
+        ScriptedResponse::at(self, HEALTH_PATH, template)
+    }
+
+    /// Serves `body` (msgpack-encoded `SwapResponse`) from the swap
+    /// instructions endpoint.
+    pub fn respond_with_swap_instructions(&self, body: impl Into<Vec<u8>>) -> ScriptedResponse<'_> {
+        let template = ResponseTemplate::new(200)
+            .insert_header("content-type", "application/vnd.msgpack")
+            .set_body_bytes(body.into());
+        let mut response = ScriptedResponse::at(self, SWAP_INSTRUCTIONS_PATH, template);
+        response.method = Some("POST");
+        response
+    }
+
+    pub fn respond_with_delay(
+        &self,
+        route: SwapRouteBuilder,
+        delay: Duration,
+    ) -> ScriptedResponse<'_> {
+        self.respond_with_route(route).delay(delay)
+    }
+
+    /// Query parameters of every quote request received so far, oldest first.
+    /// For POSTed quotes these are read from the JSON body.
+    pub async fn received_query_params(&self) -> Vec<HashMap<String, String>> {
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|request| request.url.path() == QUOTE_PATH)
+            .map(quote_params)
+            .collect()
+    }
+
+    /// Path of every request received so far, oldest first.
+    pub async fn received_paths(&self) -> Vec<String> {
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .map(|request| request.url.path().to_string())
+            .collect()
+    }
+
+    /// Value of header `name` on every quote request received so far, oldest first.
+    pub async fn received_quote_headers(&self, name: &str) -> Vec<Option<String>> {
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|request| request.url.path() == QUOTE_PATH)
+            .map(|request| {
+                request
+                    .headers
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_owned)
+            })
+            .collect()
+    }
+
+    /// HTTP method of every quote request received so far, oldest first.
+    pub async fn received_quote_methods(&self) -> Vec<String> {
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|request| request.url.path() == QUOTE_PATH)
+            .map(|request| request.method.to_string())
+            .collect()
+    }
+
+    /// JSON bodies of every swap instructions request received so far.
+    pub async fn received_swap_instructions_bodies(&self) -> Vec<serde_json::Value> {
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|request| request.url.path() == SWAP_INSTRUCTIONS_PATH)
+            .map(|request| serde_json::from_slice(&request.body).expect("JSON request body"))
+            .collect()
+    }
+
+    pub async fn received_request_count(&self) -> usize {
+        self.received_query_params().await.len()
+    }
+
+    /// Panics unless the most recent quote request carried `key=value`.
+    pub async fn assert_query_param(&self, key: &str, value: &str) {
+        let params = self.received_query_params().await;
+        let last = params.last().expect("no quote requests received");
+        assert_eq!(
+            last.get(key).map(String::as_str),
+            Some(value),
+            "query param {} mismatch in {:?}",
+            key,
+            last
+        );
+    }
+}
+
+/// The parameters of a quote request, from its query string or, when it was
+/// POSTed, its JSON body.
+fn quote_params(request: &wiremock::Request) -> HashMap<String, String> {
+    if request.method.as_str() == "POST" {
+        serde_json::from_slice(&request.body).expect("JSON quote body")
+    } else {
+        request.url.query_pairs().into_owned().collect()
+    }
+}
+
+fn echo_request(quotes: &mut SwapQuotes, params: &HashMap<String, String>) {
+    let mint = |key| {
+        params
+            .get(key)
+            .and_then(|mint| Pubkey::from_str(mint).ok())
+            .map(|mint| mint.to_bytes())
+    };
+    if let Some(input_mint) = mint("inputMint") {
+        quotes.input_mint = input_mint;
+    }
+    if let Some(output_mint) = mint("outputMint") {
+        quotes.output_mint = output_mint;
+    }
+    if let Some(swap_mode) = params.get("swapMode").and_then(|mode| mode.parse().ok()) {
+        quotes.swap_mode = swap_mode;
+    }
+    if let Some(amount) = params.get("amount").and_then(|amount| amount.parse().ok()) {
+        quotes.amount = amount;
+    }
+}
+
+fn msgpack_template<T: serde::Serialize + ?Sized>(body: &T) -> ResponseTemplate {
+    ResponseTemplate::new(200)
+        .insert_header("content-type", "application/vnd.msgpack")
+        .set_body_bytes(rmp_serde::to_vec_named(body).expect("body encodes to msgpack"))
+}
+
+pub struct ScriptedResponse<'a> {
+    server: &'a MockTitanServer,
+    method: Option<&'static str>,
+    path: &'static str,
+    template: ResponseTemplate,
+    /// Served with its top-level fields taken from each request.
+    echo: Option<SwapQuotes>,
+    times: Option<u64>,
+}
+
+impl<'a> ScriptedResponse<'a> {
+    /// Quote responses are served for both GET and POST quoting.
+    fn new(server: &'a MockTitanServer, template: ResponseTemplate) -> Self {
+        let mut response = Self::at(server, QUOTE_PATH, template);
+        response.method = None;
+        response
+    }
+
+    fn at(server: &'a MockTitanServer, path: &'static str, template: ResponseTemplate) -> Self {
+        Self {
+            server,
+            method: Some("GET"),
+            path,
+            template,
+            echo: None,
+            times: None,
+        }
+    }
+
+    fn echoing(server: &'a MockTitanServer, quotes: SwapQuotes) -> Self {
+        let template =
+            ResponseTemplate::new(200).insert_header("content-type", "application/vnd.msgpack");
+        let mut response = Self::new(server, template);
+        response.echo = Some(quotes);
+        response
+    }
+
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.template = self.template.set_delay(delay);
+        self
+    }
+
+    pub fn header(mut self, name: &str, value: impl ToString) -> Self {
+        self.template = self
+            .template
+            .insert_header(name, value.to_string().as_str());
+        self
+    }
+
+    /// Only serve this response for the next `n` requests.
+    pub fn times(mut self, n: u64) -> Self {
+        self.times = Some(n);
+        self
+    }
+
+    pub async fn mount(self) {
+        let given = match self.method {
+            Some(verb) => Mock::given(method(verb)).and(path(self.path)),
+            None => Mock::given(path(self.path)),
+        };
+        let template = self.template;
+        let mut mock = match self.echo {
+            Some(quotes) => given.respond_with(move |request: &wiremock::Request| {
+                let mut quotes = quotes.clone();
+                echo_request(&mut quotes, &quote_params(request));
+                template.clone().set_body_bytes(
+                    rmp_serde::to_vec_named(&quotes).expect("SwapQuotes serializes"),
+                )
+            }),
+            None => given.respond_with(template),
+        };
+        if let Some(n) = self.times {
+            mock = mock.up_to_n_times(n);
+        }
+        mock.mount(&self.server.server).await;
+    }
+}
+
+/// A quote for 1 SOL of USDC, everything else left to the server. Tests that
+/// need more set it on top: `QuoteRequest { slippage_bps: 50, ..sample_request() }`.
+pub fn sample_request() -> QuoteRequest {
+    QuoteRequest {
+        input_mint: SOL_MINT,
+        output_mint: USDC_MINT,
+        amount: 1_000_000_000,
+        ..Default::default()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SwapRouteBuilder {
+    input_mint: Pubkey,
+    output_mint: Pubkey,
+    swap_mode: SwapMode,
+    provider: String,
+    route: SwapRoute,
+}
+
+impl Default for SwapRouteBuilder {
+    fn default() -> Self {
+        Self::new(
+            Pubkey::new_from_array([1; 32]),
+            Pubkey::new_from_array([2; 32]),
+        )
+    }
+}
+
+impl SwapRouteBuilder {
+    /// A single-hop route swapping 1_000_000 `input_mint` for 1_000_000 `output_mint`.
+    pub fn new(input_mint: Pubkey, output_mint: Pubkey) -> Self {
+        Self {
+            input_mint,
+            output_mint,
+            swap_mode: SwapMode::ExactIn,
+            provider: "Titan".to_string(),
+            route: SwapRoute {
+                in_amount: 1_000_000,
+                out_amount: 1_000_000,
+                slippage_bps: 50,
+                platform_fee: None,
+                referral_fee: None,
+                steps: vec![],
+                instructions: vec![],
+                address_lookup_tables: vec![],
+                context_slot: None,
+                time_taken_ns: None,
+                expires_at_ms: None,
+                expires_after_slot: None,
+                compute_units: None,
+                compute_units_safe: None,
+                transaction: None,
+                reference_id: None,
+                warnings: vec![],
+                price_only: false,
+            },
+        }
+    }
+
+    pub fn provider(mut self, provider: impl Into<String>) -> Self {
+        self.provider = provider.into();
+        self
+    }
+
+    pub fn swap_mode(mut self, swap_mode: SwapMode) -> Self {
+        self.swap_mode = swap_mode;
+        self
+    }
+
+    pub fn amounts(mut self, in_amount: u64, out_amount: u64) -> Self {
+        self.route.in_amount = in_amount;
+        self.route.out_amount = out_amount;
+        self
+    }
+
+    pub fn slippage_bps(mut self, slippage_bps: u16) -> Self {
+        self.route.slippage_bps = slippage_bps;
+        self
+    }
+
+    pub fn platform_fee(mut self, amount: u64, fee_bps: u16) -> Self {
+        self.route.platform_fee = Some(PlatformFeeData { amount, fee_bps });
+        self
+    }
+
+    pub fn referral_fee(mut self, amount: u64, fee_bps: u16) -> Self {
+        self.route.referral_fee = Some(PlatformFeeData { amount, fee_bps });
+        self
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn step(
+        mut self,
+        label: &str,
+        amm_key: Pubkey,
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+        in_amount: u64,
+        out_amount: u64,
+        alloc_ppb: u32,
+    ) -> Self {
+        self.route.steps.push(RoutePlanStepData {
+            amm_key: amm_key.to_bytes(),
+            label: label.to_string(),
+            input_mint: input_mint.to_bytes(),
+            output_mint: output_mint.to_bytes(),
+            in_amount,
+            out_amount,
+            alloc_ppb,
+            fee_mint: None,
+            fee_amount: None,
+            context_slot: None,
+        });
+        self
+    }
+
+    pub fn instruction(mut self, instruction: &Instruction) -> Self {
+        self.route.instructions.push(InstructionData {
+            p: instruction.program_id.to_bytes(),
+            a: instruction
+                .accounts
+                .iter()
+                .map(|meta| AccountMetaData {
+                    p: meta.pubkey.to_bytes(),
+                    s: meta.is_signer,
+                    w: meta.is_writable,
+                })
+                .collect(),
+            d: instruction.data.clone(),
+        });
+        self
+    }
+
+    pub fn address_lookup_table(mut self, address: Pubkey) -> Self {
+        self.route.address_lookup_tables.push(address.to_bytes());
+        self
+    }
+
+    pub fn context_slot(mut self, slot: u64) -> Self {
+        self.route.context_slot = Some(slot);
+        self
+    }
+
+    pub fn expires(mut self, at_ms: u64, after_slot: u64) -> Self {
+        self.route.expires_at_ms = Some(at_ms);
+        self.route.expires_after_slot = Some(after_slot);
+        self
+    }
+
+    pub fn compute_units(mut self, compute_units: u64, compute_units_safe: u64) -> Self {
+        self.route.compute_units = Some(compute_units);
+        self.route.compute_units_safe = Some(compute_units_safe);
+        self
+    }
+
+    /// Fills in a single full-allocation step and a no-op instruction when none were given,
+    /// so the route is accepted by both `quote()` and `swap()`.
+    pub fn build(self) -> SwapRoute {
+        let mut builder = self;
+        if builder.route.steps.is_empty() {
+            let (input_mint, output_mint) = (builder.input_mint, builder.output_mint);
+            let (in_amount, out_amount) = (builder.route.in_amount, builder.route.out_amount);
+            builder = builder.step(
+                "Mock",
+                Pubkey::new_from_array([3; 32]),
+                input_mint,
+                output_mint,
+                in_amount,
+                out_amount,
+                1_000_000_000,
+            );
+        }
+        if builder.route.instructions.is_empty() {
+            builder = builder.instruction(&Instruction::new_with_bytes(
+                Pubkey::new_from_array([4; 32]),
+                &[],
+                vec![],
+            ));
+        }
+        builder.route
+    }
+
+    pub fn to_swap_quotes(self) -> SwapQuotes {
+        let (input_mint, output_mint, swap_mode) =
+            (self.input_mint, self.output_mint, self.swap_mode.clone());
+        let provider = self.provider.clone();
+        let route = self.build();
+        SwapQuotes {
+            id: "mock".to_string(),
+            input_mint: input_mint.to_bytes(),
+            output_mint: output_mint.to_bytes(),
+            swap_mode,
+            amount: route.in_amount,
+            quotes: BTreeMap::from([(QuoteKey::from(provider), route)]),
+        }
+    }
+
+    pub fn to_msgpack(self) -> Vec<u8> {
+        rmp_serde::to_vec_named(&self.to_swap_quotes()).expect("SwapQuotes serializes")
+    }
+}
+
+/// A [`Clock`] that only moves when told to. Clones share the same time, so
+/// a test can keep one and hand another to `TitanClientBuilder::clock`.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    time: Arc<Mutex<MockTime>>,
+}
+
+#[derive(Debug)]
+struct MockTime {
+    now_ms: u64,
+    instant: Instant,
+}
+
+impl MockClock {
+    /// Starts at `now_ms` Unix milliseconds.
+    pub fn new(now_ms: u64) -> Self {
+        Self {
+            time: Arc::new(Mutex::new(MockTime {
+                now_ms,
+                instant: Instant::now(),
+            })),
+        }
+    }
+
+    /// Moves both the wall clock and the monotonic clock forward.
+    pub fn advance(&self, by: Duration) {
+        let mut time = self.time.lock().unwrap();
+        time.now_ms = time.now_ms.saturating_add(by.as_millis() as u64);
+        time.instant += by;
+    }
+
+    /// Sets the wall clock, which may go backwards. The monotonic clock
+    /// doesn't move.
+    pub fn set_now_ms(&self, now_ms: u64) {
+        self.time.lock().unwrap().now_ms = now_ms;
+    }
+}
+
+impl Clock for MockClock {
+    fn now_ms(&self) -> u64 {
+        self.time.lock().unwrap().now_ms
+    }
+
+    fn instant(&self) -> Instant {
+        self.time.lock().unwrap().instant
+    }
+}
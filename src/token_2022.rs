@@ -0,0 +1,305 @@
+//! Token-2022 mint extensions that change what a swap delivers: transfer
+//! fees withheld from the output, and transfer hooks.
+
+use crate::amount_math;
+use crate::constants::ASSOCIATED_TOKEN_PROGRAM_ID;
+use crate::quote::{QuoteResponse, SwapMode};
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+
+// Extended mints are padded to the token account length so the two can be
+// told apart, then carry an account type byte and TLV extensions.
+const MINT_BASE_LEN: usize = 82;
+const ACCOUNT_TYPE_OFFSET: usize = 165;
+const ACCOUNT_TYPE_MINT: u8 = 1;
+const EXTENSION_TRANSFER_FEE_CONFIG: u16 = 1;
+const EXTENSION_TRANSFER_HOOK: u16 = 14;
+const TRANSFER_FEE_CONFIG_LEN: usize = 108;
+const TRANSFER_HOOK_LEN: usize = 64;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Token2022Error {
+    #[error("Mint data is truncated")]
+    Truncated,
+    #[error("Account is not a mint")]
+    NotAMint,
+}
+
+/// The transfer fee in effect from `epoch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferFee {
+    pub epoch: u64,
+    pub maximum_fee: u64,
+    pub transfer_fee_basis_points: u16,
+}
+
+impl TransferFee {
+    /// The fee withheld from a transfer of `amount`, rounded up as the
+    /// token program does.
+    pub fn calculate_fee(&self, amount: u64) -> u64 {
+        amount_math::capped_bps_fee(amount, self.transfer_fee_basis_points, self.maximum_fee)
+    }
+}
+
+/// What is taken from a quote's output between the route and the user's
+/// account, beyond what `out_amount` already nets out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReceivedAdjustments {
+    /// The output mint's transfer fee in effect, withheld from the transfer
+    /// into the user's account.
+    pub transfer_fee: Option<TransferFee>,
+    /// A fee on the output that the route doesn't charge, such as one taken
+    /// by the integrator after the swap.
+    pub platform_fee_bps: Option<u16>,
+}
+
+impl ReceivedAdjustments {
+    /// A transfer fee of `bps` with no maximum.
+    pub fn transfer_fee_bps(bps: u16) -> Self {
+        Self {
+            transfer_fee: Some(TransferFee {
+                epoch: 0,
+                maximum_fee: u64::MAX,
+                transfer_fee_basis_points: bps,
+            }),
+            platform_fee_bps: None,
+        }
+    }
+
+    pub fn with_platform_fee_bps(mut self, bps: u16) -> Self {
+        self.platform_fee_bps = Some(bps);
+        self
+    }
+
+    /// What reaches the user's account from `amount` of route output: the
+    /// platform fee is taken first, then the transfer fee. Both fees round
+    /// up, so the result rounds down.
+    pub fn apply(&self, amount: u64) -> u64 {
+        let amount = self
+            .platform_fee_bps
+            .map_or(amount, |bps| amount_math::after_bps_fee(amount, bps));
+        let fee = self.transfer_fee.map_or(0, |fee| fee.calculate_fee(amount));
+        amount.saturating_sub(fee)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferFeeConfig {
+    pub older_transfer_fee: TransferFee,
+    pub newer_transfer_fee: TransferFee,
+}
+
+impl TransferFeeConfig {
+    pub fn fee_for(&self, epoch: u64) -> &TransferFee {
+        if epoch >= self.newer_transfer_fee.epoch {
+            &self.newer_transfer_fee
+        } else {
+            &self.older_transfer_fee
+        }
+    }
+}
+
+/// The extensions of a mint that affect swaps. Both are unset for legacy
+/// mints and for Token-2022 mints without them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MintExtensions {
+    pub transfer_fee: Option<TransferFeeConfig>,
+    pub transfer_hook_program: Option<Pubkey>,
+}
+
+impl MintExtensions {
+    /// Parses raw mint account data, as owned by either token program.
+    pub fn parse(data: &[u8]) -> Result<Self, Token2022Error> {
+        if data.len() < MINT_BASE_LEN {
+            return Err(Token2022Error::Truncated);
+        }
+        let mut extensions = MintExtensions::default();
+        if data.len() <= ACCOUNT_TYPE_OFFSET {
+            return Ok(extensions);
+        }
+        if data[ACCOUNT_TYPE_OFFSET] != ACCOUNT_TYPE_MINT {
+            return Err(Token2022Error::NotAMint);
+        }
+
+        let mut tlv = &data[ACCOUNT_TYPE_OFFSET + 1..];
+        while tlv.len() >= 4 {
+            let kind = u16::from_le_bytes([tlv[0], tlv[1]]);
+            let len = usize::from(u16::from_le_bytes([tlv[2], tlv[3]]));
+            let value = tlv.get(4..4 + len).ok_or(Token2022Error::Truncated)?;
+            match kind {
+                // Zeroed space left for extensions not yet initialized.
+                0 => break,
+                EXTENSION_TRANSFER_FEE_CONFIG if len == TRANSFER_FEE_CONFIG_LEN => {
+                    extensions.transfer_fee = Some(TransferFeeConfig {
+                        older_transfer_fee: transfer_fee(&value[72..90]),
+                        newer_transfer_fee: transfer_fee(&value[90..108]),
+                    });
+                }
+                EXTENSION_TRANSFER_HOOK if len == TRANSFER_HOOK_LEN => {
+                    let program = pubkey(&value[32..64]);
+                    extensions.transfer_hook_program =
+                        (program != Pubkey::default()).then_some(program);
+                }
+                _ => {}
+            }
+            tlv = &tlv[4 + len..];
+        }
+        Ok(extensions)
+    }
+
+    /// What arrives in the recipient's account when `amount` is sent in
+    /// `epoch`.
+    pub fn received_amount(&self, amount: u64, epoch: u64) -> u64 {
+        let fee = self
+            .transfer_fee
+            .map_or(0, |config| config.fee_for(epoch).calculate_fee(amount));
+        amount.saturating_sub(fee)
+    }
+
+    /// The transfer fee in effect in `epoch`, as adjustments to a quote's
+    /// output.
+    pub fn received_adjustments(&self, epoch: u64) -> ReceivedAdjustments {
+        ReceivedAdjustments {
+            transfer_fee: self.transfer_fee.map(|config| *config.fee_for(epoch)),
+            platform_fee_bps: None,
+        }
+    }
+}
+
+fn pubkey(bytes: &[u8]) -> Pubkey {
+    Pubkey::new_from_array(bytes.try_into().expect("32-byte slice"))
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().expect("8-byte slice"))
+}
+
+fn transfer_fee(bytes: &[u8]) -> TransferFee {
+    TransferFee {
+        epoch: u64_at(bytes, 0),
+        maximum_fee: u64_at(bytes, 8),
+        transfer_fee_basis_points: u16::from_le_bytes([bytes[16], bytes[17]]),
+    }
+}
+
+/// The associated token account of `wallet` for `mint` under
+/// `token_program`, which must be the program owning `mint`.
+pub fn associated_token_address(wallet: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+impl QuoteResponse {
+    /// `out_amount` as received after the output mint's transfer fee.
+    pub fn out_amount_after_transfer_fee(&self, output: &MintExtensions, epoch: u64) -> u64 {
+        output.received_amount(self.out_amount, epoch)
+    }
+
+    /// The least the user can receive for ExactIn: `out_amount` less
+    /// slippage, then less the transfer fee. For ExactOut the output is
+    /// fixed, so this is `out_amount` less the fee.
+    pub fn min_received_amount(&self, output: &MintExtensions, epoch: u64) -> u64 {
+        output.received_amount(self.min_out_amount(), epoch)
+    }
+
+    /// What the user should expect to receive: [`net_out_amount`], which
+    /// takes out a platform fee the final hop pays in the output mint, then
+    /// `adjustments`.
+    ///
+    /// [`net_out_amount`]: Self::net_out_amount
+    pub fn expected_received(&self, adjustments: &ReceivedAdjustments) -> u64 {
+        adjustments.apply(self.net_out_amount())
+    }
+
+    /// Like [`min_out_amount`](Self::min_out_amount), computed from
+    /// [`expected_received`](Self::expected_received): the least the user's
+    /// balance should rise by. For ExactIn that is the expected amount less
+    /// slippage, rounded down.
+    pub fn min_out_amount_with(&self, adjustments: &ReceivedAdjustments) -> u64 {
+        let expected = self.expected_received(adjustments);
+        match self.swap_mode {
+            SwapMode::ExactOut => expected,
+            SwapMode::ExactIn | SwapMode::Other(_) => {
+                amount_math::min_out_after_slippage(expected, self.slippage_bps)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "solana-client")]
+mod rpc {
+    use super::{MintExtensions, ReceivedAdjustments};
+    use crate::constants::{token_program_for, TOKEN_2022_PROGRAM_ID};
+    use crate::quote::QuoteResponse;
+    use crate::ClientError;
+    use solana_client::nonblocking::rpc_client::RpcClient;
+    use solana_sdk::pubkey::Pubkey;
+
+    /// `wallet`'s associated token account for `mint`, under whichever
+    /// token program owns the mint.
+    pub async fn associated_token_address_for(
+        wallet: &Pubkey,
+        mint: &Pubkey,
+        rpc: &RpcClient,
+    ) -> Result<Pubkey, ClientError> {
+        let token_program = token_program_for(mint, rpc).await?;
+        Ok(super::associated_token_address(
+            wallet,
+            mint,
+            &token_program,
+        ))
+    }
+
+    /// Fetches and parses `mint`, returning whether it is a Token-2022 mint
+    /// along with its extensions.
+    pub async fn fetch_mint_extensions(
+        mint: &Pubkey,
+        rpc: &RpcClient,
+    ) -> Result<(bool, MintExtensions), ClientError> {
+        let account = rpc
+            .get_account(mint)
+            .await
+            .map_err(|err| ClientError::Rpc(Box::new(err)))?;
+        let extensions =
+            MintExtensions::parse(&account.data).map_err(|err| ClientError::InvalidResponse {
+                field: "mint data",
+                value: err.to_string(),
+            })?;
+        Ok((account.owner == TOKEN_2022_PROGRAM_ID, extensions))
+    }
+
+    /// The transfer fee `mint` charges in the current epoch, for
+    /// [`QuoteResponse::expected_received`].
+    pub async fn fetch_received_adjustments(
+        mint: &Pubkey,
+        rpc: &RpcClient,
+    ) -> Result<ReceivedAdjustments, ClientError> {
+        let (_, extensions) = fetch_mint_extensions(mint, rpc).await?;
+        let epoch = rpc
+            .get_epoch_info()
+            .await
+            .map_err(|err| ClientError::Rpc(Box::new(err)))?
+            .epoch;
+        Ok(extensions.received_adjustments(epoch))
+    }
+
+    impl QuoteResponse {
+        /// Looks up the output mint, setting `output_is_token2022` and
+        /// returning its extensions for use with
+        /// [`min_received_amount`](Self::min_received_amount).
+        pub async fn resolve_output_mint(
+            &mut self,
+            rpc: &RpcClient,
+        ) -> Result<MintExtensions, ClientError> {
+            let (is_token2022, extensions) = fetch_mint_extensions(&self.output_mint, rpc).await?;
+            self.output_is_token2022 = Some(is_token2022);
+            Ok(extensions)
+        }
+    }
+}
+
+#[cfg(feature = "solana-client")]
+pub use rpc::{associated_token_address_for, fetch_mint_extensions, fetch_received_adjustments};
@@ -0,0 +1,138 @@
+use crate::amount_math;
+use crate::quote::QuoteResponse;
+use crate::serde_helpers::pubkey_as_bytes;
+use crate::ui::UiAmount;
+use crate::{ClientError, TitanClient};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo {
+    #[serde(with = "pubkey_as_bytes")]
+    pub mint: Pubkey,
+    pub symbol: String,
+    pub name: String,
+    pub decimals: u8,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl TitanClient {
+    pub async fn tokens(&self) -> Result<Vec<TokenInfo>, ClientError> {
+        self.get_decoded("/tokens", &[]).await
+    }
+}
+
+/// A cached token list for symbol and decimals lookups.
+///
+/// Symbols are matched case-insensitively. When several mints share a
+/// symbol, the first one in the list wins.
+pub struct TokenRegistry {
+    client: Option<TitanClient>,
+    ttl: Duration,
+    state: RwLock<RegistryState>,
+}
+
+#[derive(Default)]
+struct RegistryState {
+    by_symbol: HashMap<String, Pubkey>,
+    by_mint: HashMap<Pubkey, TokenInfo>,
+    fetched_at: Option<Instant>,
+}
+
+impl RegistryState {
+    fn new(tokens: Vec<TokenInfo>, fetched_at: Option<Instant>) -> Self {
+        let mut state = RegistryState {
+            fetched_at,
+            ..Default::default()
+        };
+        for token in tokens {
+            state
+                .by_symbol
+                .entry(token.symbol.to_uppercase())
+                .or_insert(token.mint);
+            state.by_mint.insert(token.mint, token);
+        }
+        state
+    }
+}
+
+impl TokenRegistry {
+    /// An empty registry filled from `client` on the first
+    /// [`refresh_if_stale`](Self::refresh_if_stale).
+    pub fn new(client: TitanClient, ttl: Duration) -> Self {
+        Self {
+            client: Some(client),
+            ttl,
+            state: RwLock::new(RegistryState::default()),
+        }
+    }
+
+    /// A fixed registry that never refreshes.
+    pub fn from_tokens(tokens: Vec<TokenInfo>) -> Self {
+        Self {
+            client: None,
+            ttl: Duration::MAX,
+            state: RwLock::new(RegistryState::new(tokens, None)),
+        }
+    }
+
+    pub async fn refresh(&self) -> Result<(), ClientError> {
+        let Some(client) = &self.client else {
+            return Ok(());
+        };
+
+        let tokens = client.tokens().await?;
+        *self.state.write().unwrap() = RegistryState::new(tokens, Some(client.clock.instant()));
+        Ok(())
+    }
+
+    /// Refreshes if the list was never fetched or is older than the TTL.
+    pub async fn refresh_if_stale(&self) -> Result<(), ClientError> {
+        let Some(client) = &self.client else {
+            return Ok(());
+        };
+        let stale = match self.state.read().unwrap().fetched_at {
+            Some(fetched_at) => client.clock.elapsed(fetched_at) >= self.ttl,
+            None => true,
+        };
+        if stale {
+            self.refresh().await?;
+        }
+        Ok(())
+    }
+
+    pub fn resolve(&self, symbol: &str) -> Option<Pubkey> {
+        let state = self.state.read().unwrap();
+        state.by_symbol.get(&symbol.to_uppercase()).copied()
+    }
+
+    pub fn get(&self, mint: &Pubkey) -> Option<TokenInfo> {
+        self.state.read().unwrap().by_mint.get(mint).cloned()
+    }
+
+    pub fn decimals(&self, mint: &Pubkey) -> Option<u8> {
+        let state = self.state.read().unwrap();
+        state.by_mint.get(mint).map(|token| token.decimals)
+    }
+
+    pub fn ui_amount(&self, mint: &Pubkey, amount: u64) -> Option<f64> {
+        Some(UiAmount::new(amount, self.decimals(mint)?).to_f64())
+    }
+
+    /// The raw amount for `ui_amount`, rounded down to the mint's precision.
+    pub fn raw_amount(&self, mint: &Pubkey, ui_amount: f64) -> Option<u64> {
+        amount_math::raw_from_ui(ui_amount, self.decimals(mint)?)
+    }
+
+    /// Output tokens received per input token, in UI units.
+    pub fn effective_price(&self, quote: &QuoteResponse) -> Option<f64> {
+        let in_amount = self.ui_amount(&quote.input_mint, quote.input_amount())?;
+        let out_amount = self.ui_amount(&quote.output_mint, quote.out_amount)?;
+        (in_amount > 0.0).then(|| out_amount / in_amount)
+    }
+}
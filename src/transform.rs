@@ -0,0 +1,273 @@
+//! The steps between a quote response and a swap, as pure functions that
+//! need no client or network: decode a [`SwapQuotes`], pick one of its
+//! routes, and turn that into a [`QuoteResponse`] or [`SwapResponse`].
+//! [`TitanClient`](crate::TitanClient) runs the same steps around its
+//! requests.
+//!
+//! Everything except [`route_to_swap_response`] is available without the
+//! `native` feature.
+
+use crate::amount_math;
+#[cfg(feature = "native")]
+use crate::pubkey_from_bytes;
+use crate::quote::{
+    QuoteKey, QuoteRequest, QuoteResponse, RouteField, SwapMode, SwapQuotes, SwapRoute,
+};
+#[cfg(feature = "native")]
+use crate::swap::SwapResponse;
+pub use crate::{decode_price_only_quotes, decode_swap_quotes};
+use crate::{retain_complete_routes, retain_direct_routes, retain_routes_avoiding, ClientError};
+#[cfg(feature = "native")]
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+/// Which of a response's routes to use.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum RouteSelection {
+    /// The route with the most output, or for ExactOut the least input. Ties
+    /// go to the first by provider name. Clients pick this way unless asked
+    /// for a provider.
+    #[default]
+    Best,
+    /// The first route by provider name.
+    First,
+    /// The first route from this provider, matched against each key with
+    /// [`QuoteKey::matches_provider`].
+    Provider(String),
+}
+
+/// The key and route picked by `selection`. Fails with
+/// `NoRoutesAvailable` if there are no routes at all, so callers can tell an
+/// empty response from a missing provider, and with `ProviderUnavailable` if
+/// the provider returned none.
+pub fn select_route(
+    quotes: &SwapQuotes,
+    selection: RouteSelection,
+) -> Result<(&QuoteKey, &SwapRoute), ClientError> {
+    let mut routes = quotes.quotes.iter();
+    let selected = match &selection {
+        RouteSelection::Best => routes.reduce(|best, candidate| {
+            let better = match quotes.swap_mode {
+                SwapMode::ExactOut => candidate.1.in_amount < best.1.in_amount,
+                _ => candidate.1.out_amount > best.1.out_amount,
+            };
+            if better {
+                candidate
+            } else {
+                best
+            }
+        }),
+        RouteSelection::First => routes.next(),
+        RouteSelection::Provider(provider) => {
+            routes.find(|(key, _)| key.matches_provider(provider))
+        }
+    };
+    match (selected, selection) {
+        (Some((key, route)), _) => Ok((key, route)),
+        (None, RouteSelection::Provider(provider)) if !quotes.quotes.is_empty() => {
+            Err(ClientError::ProviderUnavailable(provider))
+        }
+        (None, _) => Err(ClientError::NoRoutesAvailable),
+    }
+}
+
+/// [`select_route`], moving the route out of `quotes` rather than borrowing
+/// it.
+pub(crate) fn take_route(
+    mut quotes: SwapQuotes,
+    selection: RouteSelection,
+) -> Result<(QuoteKey, SwapRoute), ClientError> {
+    let key = select_route(&quotes, selection)?.0.clone();
+    let route = quotes
+        .quotes
+        .remove(&key)
+        .expect("selected route is in the response");
+    Ok((key, route))
+}
+
+/// Checks that `quotes` answers `request`: the same input and output mints,
+/// the requested amount, and the requested swap mode, which is ExactIn when
+/// unset as it is for the server. Fails with `ClientError::ResponseMismatch`
+/// on the first field that differs.
+pub fn verify_swap_quotes(request: &QuoteRequest, quotes: &SwapQuotes) -> Result<(), ClientError> {
+    let mismatch = |field, expected: &dyn ToString, actual: &dyn ToString| {
+        Err(ClientError::ResponseMismatch {
+            field,
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        })
+    };
+    for (field, expected, actual) in [
+        ("inputMint", &request.input_mint, &quotes.input_mint),
+        ("outputMint", &request.output_mint, &quotes.output_mint),
+    ] {
+        if expected.to_bytes() != *actual {
+            return mismatch(field, expected, &crate::pubkey_from_bytes(actual));
+        }
+    }
+    let swap_mode = request.swap_mode.clone().unwrap_or_default();
+    if swap_mode != quotes.swap_mode {
+        return mismatch("swapMode", &swap_mode, &quotes.swap_mode);
+    }
+    if request.amount != quotes.amount {
+        return mismatch("amount", &request.amount, &quotes.amount);
+    }
+    Ok(())
+}
+
+/// How far above the requested amount an ExactOut route may deliver, unless
+/// the builder's `exact_out_tolerance_bps` says otherwise.
+pub const DEFAULT_EXACT_OUT_TOLERANCE_BPS: u16 = 10;
+
+/// Checks that an ExactOut `route` delivers the amount `request` asks for,
+/// and no more than `tolerance_bps` above it. Fails with
+/// `ClientError::QuoteMismatch`; other modes always pass.
+pub fn verify_exact_out(
+    request: &QuoteRequest,
+    route: &SwapRoute,
+    tolerance_bps: u16,
+) -> Result<(), ClientError> {
+    if request.swap_mode != Some(SwapMode::ExactOut) {
+        return Ok(());
+    }
+    let ceiling =
+        amount_math::max_in_after_slippage(request.amount, tolerance_bps).unwrap_or(u64::MAX);
+    if (request.amount..=ceiling).contains(&route.out_amount) {
+        Ok(())
+    } else {
+        Err(ClientError::QuoteMismatch {
+            requested: request.amount,
+            out_amount: route.out_amount,
+        })
+    }
+}
+
+/// The route checks a client applies on top of the request's own filters,
+/// as set on [`TitanClientBuilder`](crate::TitanClientBuilder).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteChecks {
+    /// Drop routes with more than one hop when the request asks for direct
+    /// routes only.
+    pub enforce_direct_routes: bool,
+    /// Fields every route must carry.
+    pub required_route_fields: Vec<RouteField>,
+    /// How far above the requested amount an ExactOut route may deliver.
+    pub exact_out_tolerance_bps: u16,
+}
+
+impl Default for RouteChecks {
+    fn default() -> Self {
+        Self {
+            enforce_direct_routes: true,
+            required_route_fields: Vec::new(),
+            exact_out_tolerance_bps: DEFAULT_EXACT_OUT_TOLERANCE_BPS,
+        }
+    }
+}
+
+/// Drops the routes `request` or `checks` rule out: indirect routes when only
+/// direct ones were asked for, routes through `excluded_amm_keys`, and routes
+/// missing a required field. Fails with `NoRoutesAvailable` or
+/// `MissingRouteField` if routes were returned but none survived.
+pub fn filter_routes(
+    request: &QuoteRequest,
+    quotes: &mut SwapQuotes,
+    checks: &RouteChecks,
+) -> Result<(), ClientError> {
+    if checks.enforce_direct_routes && request.only_direct_routes == Some(true) {
+        retain_direct_routes(quotes)?;
+    }
+    if let Some(excluded) = &request.excluded_amm_keys {
+        retain_routes_avoiding(quotes, excluded)?;
+    }
+    if !checks.required_route_fields.is_empty() {
+        retain_complete_routes(quotes, &checks.required_route_fields)?;
+    }
+    Ok(())
+}
+
+/// What a client does with a decoded response: [`filter_routes`], pick the
+/// best remaining route or the first from `provider`, [`verify_exact_out`], and
+/// convert it into a [`QuoteResponse`] attributed to its provider.
+pub fn quote_from_swap_quotes(
+    request: &QuoteRequest,
+    mut quotes: SwapQuotes,
+    provider: Option<&str>,
+    checks: &RouteChecks,
+) -> Result<QuoteResponse, ClientError> {
+    filter_routes(request, &mut quotes, checks)?;
+
+    let selection = provider.map_or(RouteSelection::Best, |provider| {
+        RouteSelection::Provider(provider.to_string())
+    });
+    let (key, route) = take_route(quotes, selection)?;
+
+    verify_exact_out(request, &route, checks.exact_out_tolerance_bps)?;
+
+    Ok(QuoteResponse::from_route(request, route).with_provider(key.attribution()))
+}
+
+/// The quote `route` answers `request` with, without a provider set.
+pub fn route_to_quote_response(route: &SwapRoute, request: &QuoteRequest) -> QuoteResponse {
+    QuoteResponse::from_route(request, route.clone())
+}
+
+/// The swap instructions, lookup tables and limits carried by `route`.
+/// Lookup tables are kept as listed, repeats included; see
+/// [`SwapResponse::lookup_table_addresses`]. Fails with `NoRoutesAvailable`
+/// if it has no instructions.
+#[cfg(feature = "native")]
+pub fn route_to_swap_response(route: &SwapRoute) -> Result<SwapResponse, ClientError> {
+    if route.instructions.is_empty() {
+        return Err(ClientError::NoRoutesAvailable);
+    }
+
+    let solana_instructions: Vec<Instruction> = route
+        .instructions
+        .iter()
+        .map(|inst| Instruction {
+            program_id: pubkey_from_bytes(&inst.p),
+            accounts: inst
+                .a
+                .iter()
+                .map(|meta| AccountMeta {
+                    pubkey: pubkey_from_bytes(&meta.p),
+                    is_signer: meta.s,
+                    is_writable: meta.w,
+                })
+                .collect(),
+            data: inst.d.clone(),
+        })
+        .collect();
+
+    let address_lookup_tables: Vec<Pubkey> = route
+        .address_lookup_tables
+        .iter()
+        .map(pubkey_from_bytes)
+        .collect();
+
+    let compute_unit_limit = route
+        .compute_units
+        .map(|cu| checked_u32("computeUnits", cu))
+        .transpose()?;
+
+    Ok(SwapResponse {
+        instructions: solana_instructions,
+        address_lookup_table_addresses: address_lookup_tables,
+        compute_unit_limit,
+        compute_units_safe: route.compute_units_safe,
+        context_slot: route.context_slot,
+        expires_at_ms: route.expires_at_ms,
+        expires_after_slot: route.expires_after_slot,
+    })
+}
+
+#[cfg(feature = "native")]
+fn checked_u32(field: &'static str, value: u64) -> Result<u32, ClientError> {
+    u32::try_from(value).map_err(|_| ClientError::InvalidResponse {
+        field,
+        value: value.to_string(),
+    })
+}
@@ -0,0 +1,330 @@
+//! A quote client over a pluggable HTTP transport, for runtimes other than
+//! tokio (smol, async-std) or for HTTP stacks other than reqwest.
+//!
+//! Everything but the request itself is plain code shared with the other
+//! clients: [`prepare_request`], [`quote_url`] and [`quote_headers`] build
+//! the request, [`decode_quote_response`] turns the response into routes and
+//! [`validate_quotes`] checks them against the request. Like the wasm and
+//! blocking clients, [`TransportClient`] issues a single request per call,
+//! with none of the native client's failover, hedging or rate limiting.
+//! The native client sends its own quote GETs through [`HttpTransport`] too.
+//!
+//! Only [`ReqwestTransport`] needs reqwest, behind the `reqwest-transport`
+//! feature; statuses and headers are the `http` crate's types.
+
+use crate::api_version::ApiVersion;
+use crate::quote::{QuoteRequest, QuoteResponse, SwapQuotes};
+use crate::transform::{filter_routes, quote_from_swap_quotes, verify_swap_quotes, RouteChecks};
+use crate::{
+    content_type_of, decode_swap_quotes, error_from_response, quotes_by_provider, validate_request,
+    ClientError, DEFAULT_ERROR_BODY_LIMIT, TITAN_API_URL,
+};
+use bytes::Bytes;
+#[cfg(all(feature = "reqwest-transport", not(target_arch = "wasm32")))]
+use bytes::BytesMut;
+use http::{HeaderMap, StatusCode};
+use std::collections::BTreeMap;
+use std::future::Future;
+
+/// Largest response body a client accepts unless told otherwise, 8 MiB.
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 8 * 1024 * 1024;
+
+/// Whatever the transport failed with before a response arrived.
+pub type TransportError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+/// Sends GET requests for a [`TransportClient`].
+pub trait HttpTransport: Send + Sync {
+    /// GETs `url` with `query` appended as a query string and `headers`
+    /// set, returning the response whatever its status. A transport that
+    /// gives up on an oversized body can fail with a boxed
+    /// `ClientError::ResponseTooLarge`, which the client passes on as is.
+    fn get(
+        &self,
+        url: &str,
+        headers: &[(&str, String)],
+        query: &[(&str, String)],
+    ) -> impl Future<Output = Result<HttpResponse, TransportError>> + Send;
+}
+
+/// A response body collected a chunk at a time, failing with
+/// `ResponseTooLarge` as soon as it passes `limit` instead of buffering
+/// whatever the server sends.
+#[cfg(all(feature = "reqwest-transport", not(target_arch = "wasm32")))]
+pub(crate) struct LimitedBody {
+    limit: usize,
+    body: BytesMut,
+}
+
+#[cfg(all(feature = "reqwest-transport", not(target_arch = "wasm32")))]
+impl LimitedBody {
+    /// Fails straight away when the declared `content_length` is already
+    /// over `limit`.
+    pub(crate) fn new(limit: usize, content_length: Option<u64>) -> Result<Self, ClientError> {
+        if let Some(length) = content_length {
+            if length > limit as u64 {
+                return Err(ClientError::ResponseTooLarge {
+                    limit,
+                    received_at_least: usize::try_from(length).unwrap_or(usize::MAX),
+                });
+            }
+        }
+        Ok(Self {
+            limit,
+            body: BytesMut::new(),
+        })
+    }
+
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> Result<(), ClientError> {
+        let received = self.body.len() + chunk.len();
+        if received > self.limit {
+            return Err(ClientError::ResponseTooLarge {
+                limit: self.limit,
+                received_at_least: received,
+            });
+        }
+        self.body.extend_from_slice(chunk);
+        Ok(())
+    }
+
+    pub(crate) fn finish(self) -> Bytes {
+        self.body.freeze()
+    }
+}
+
+/// Reads `response`'s body with [`LimitedBody`], passing any read error
+/// through `map_err`.
+#[cfg(all(feature = "reqwest-transport", not(target_arch = "wasm32")))]
+pub(crate) async fn read_limited(
+    mut response: reqwest::Response,
+    limit: usize,
+    map_err: impl Fn(reqwest::Error) -> ClientError,
+) -> Result<Bytes, ClientError> {
+    let mut body = LimitedBody::new(limit, response.content_length())?;
+    while let Some(chunk) = response.chunk().await.map_err(&map_err)? {
+        body.push(&chunk)?;
+    }
+    Ok(body.finish())
+}
+
+/// [`read_limited`] for `reqwest::blocking`.
+#[cfg(feature = "blocking")]
+pub(crate) fn read_limited_blocking(
+    mut response: reqwest::blocking::Response,
+    limit: usize,
+) -> Result<Bytes, ClientError> {
+    use std::io::{ErrorKind, Read};
+
+    let mut body = LimitedBody::new(limit, response.content_length())?;
+    let mut chunk = [0u8; 16 * 1024];
+    loop {
+        match response.read(&mut chunk) {
+            Ok(0) => return Ok(body.finish()),
+            Ok(read) => body.push(&chunk[..read])?,
+            Err(err) if err.kind() == ErrorKind::Interrupted => {}
+            Err(err) => return Err(ClientError::Transport(err.into())),
+        }
+    }
+}
+
+/// [`HttpTransport`] over a `reqwest::Client`, reusing its connection pool.
+/// Bodies over `DEFAULT_MAX_RESPONSE_BYTES` are abandoned unless
+/// [`with_max_response_bytes`](Self::with_max_response_bytes) says
+/// otherwise.
+#[cfg(all(feature = "reqwest-transport", not(target_arch = "wasm32")))]
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+    max_response_bytes: usize,
+}
+
+#[cfg(all(feature = "reqwest-transport", not(target_arch = "wasm32")))]
+impl Default for ReqwestTransport {
+    fn default() -> Self {
+        Self::new(reqwest::Client::default())
+    }
+}
+
+#[cfg(all(feature = "reqwest-transport", not(target_arch = "wasm32")))]
+impl ReqwestTransport {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+        }
+    }
+
+    /// Largest response body to accept. The download is abandoned with
+    /// `ResponseTooLarge` as soon as it goes over.
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+}
+
+#[cfg(all(feature = "reqwest-transport", not(target_arch = "wasm32")))]
+impl HttpTransport for ReqwestTransport {
+    async fn get(
+        &self,
+        url: &str,
+        headers: &[(&str, String)],
+        query: &[(&str, String)],
+    ) -> Result<HttpResponse, TransportError> {
+        let mut request = self.client.get(url).query(query);
+        for (name, value) in headers {
+            request = request.header(*name, value);
+        }
+        let response = request.send().await?;
+
+        Ok(HttpResponse {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: read_limited(response, self.max_response_bytes, ClientError::from).await?,
+        })
+    }
+}
+
+/// A transport failure as a `ClientError`, unwrapping one the transport
+/// boxed itself.
+pub(crate) fn transport_error(err: TransportError) -> ClientError {
+    err.downcast::<ClientError>()
+        .map_or_else(ClientError::Transport, |err| *err)
+}
+
+/// The query parameters for quoting `request`. Fails before anything is
+/// sent if its account limits or referral settings are invalid.
+pub fn prepare_request(request: &QuoteRequest) -> Result<Vec<(&'static str, String)>, ClientError> {
+    validate_request(request)?;
+    Ok(request.query_params())
+}
+
+/// The quote endpoint, below the API prefix.
+pub(crate) const QUOTE_PATH: &str = "/quote/swap";
+
+/// Full URL of `path` (e.g. `/quote/swap`) below `api_prefix` on `base_path`.
+pub(crate) fn endpoint_url(base_path: &str, api_prefix: &str, path: &str) -> String {
+    format!("{}{}{}", base_path, api_prefix, path)
+}
+
+/// The quote endpoint of `api_version` on `base_path`.
+pub fn quote_url(base_path: &str, api_version: ApiVersion) -> String {
+    endpoint_url(base_path, api_version.prefix(), QUOTE_PATH)
+}
+
+pub fn quote_headers(auth_token: &str) -> Vec<(&'static str, String)> {
+    vec![
+        ("Accept", "application/vnd.msgpack".to_string()),
+        ("Authorization", format!("Bearer {}", auth_token)),
+    ]
+}
+
+/// Decodes a quote response, or the error a failed one carries, with the
+/// error body cut to `DEFAULT_ERROR_BODY_LIMIT`.
+pub fn decode_quote_response(response: &HttpResponse) -> Result<SwapQuotes, ClientError> {
+    if !response.status.is_success() {
+        return Err(error_from_response(
+            response.status,
+            content_type_of(&response.headers),
+            String::from_utf8_lossy(&response.body).into_owned(),
+            DEFAULT_ERROR_BODY_LIMIT,
+            None,
+        ));
+    }
+
+    decode_swap_quotes(&response.body)
+}
+
+/// Checks `quotes` as the native client does by default: it must answer
+/// `request`, failing with `ResponseMismatch` otherwise, and routes that
+/// ignore `only_direct_routes` or `excluded_amm_keys` are dropped, failing
+/// with `NoRoutesAvailable` if none is left.
+pub fn validate_quotes(request: &QuoteRequest, quotes: &mut SwapQuotes) -> Result<(), ClientError> {
+    verify_swap_quotes(request, quotes)?;
+    filter_routes(request, quotes, &RouteChecks::default())
+}
+
+/// The first route of a validated response as the quote for `request`. An
+/// ExactOut route must also deliver the requested amount.
+pub(crate) fn quote_from_response(
+    request: &QuoteRequest,
+    quotes: SwapQuotes,
+) -> Result<QuoteResponse, ClientError> {
+    verify_swap_quotes(request, &quotes)?;
+    quote_from_swap_quotes(request, quotes, None, &RouteChecks::default())
+}
+
+/// Every route of a validated response, keyed as `quote_all` returns them.
+pub(crate) fn quotes_from_response(
+    request: &QuoteRequest,
+    mut quotes: SwapQuotes,
+) -> Result<BTreeMap<String, QuoteResponse>, ClientError> {
+    validate_quotes(request, &mut quotes)?;
+    quotes_by_provider(request, quotes)
+}
+
+#[derive(Clone)]
+pub struct TransportClient<T> {
+    transport: T,
+    base_path: String,
+    api_version: ApiVersion,
+    headers: Vec<(&'static str, String)>,
+}
+
+impl<T: HttpTransport> TransportClient<T> {
+    pub fn new(transport: T, auth_token: String, base_path: Option<String>) -> Self {
+        Self {
+            transport,
+            base_path: base_path.unwrap_or_else(|| TITAN_API_URL.to_string()),
+            api_version: ApiVersion::default(),
+            headers: quote_headers(&auth_token),
+        }
+    }
+
+    /// Sends requests to `api_version`'s endpoints. Defaults to
+    /// `ApiVersion::V1`.
+    pub fn with_api_version(mut self, api_version: ApiVersion) -> Self {
+        self.api_version = api_version;
+        self
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    async fn fetch_swap_quotes(
+        &self,
+        params: &[(&str, String)],
+    ) -> Result<SwapQuotes, ClientError> {
+        let response = self
+            .transport
+            .get(
+                &quote_url(&self.base_path, self.api_version),
+                &self.headers,
+                params,
+            )
+            .await
+            .map_err(transport_error)?;
+        decode_quote_response(&response)
+    }
+
+    pub async fn quote(&self, request: &QuoteRequest) -> Result<QuoteResponse, ClientError> {
+        let quotes = self.fetch_swap_quotes(&prepare_request(request)?).await?;
+        quote_from_response(request, quotes)
+    }
+
+    /// One quote per route in the response, keyed by its full `QuoteKey` as
+    /// a string and ordered by provider name.
+    pub async fn quote_all(
+        &self,
+        request: &QuoteRequest,
+    ) -> Result<BTreeMap<String, QuoteResponse>, ClientError> {
+        let quotes = self.fetch_swap_quotes(&prepare_request(request)?).await?;
+        quotes_from_response(request, quotes)
+    }
+}
@@ -0,0 +1,146 @@
+//! Raw token amounts paired with their mint's decimals, for display and
+//! logging in whole-token units.
+
+use crate::quote::{QuoteResponse, SwapInfo};
+use std::fmt;
+
+/// `amount` atoms of a mint with `decimals` decimals.
+///
+/// `Display` renders whole tokens with `,` thousands separators and no
+/// trailing zeros, e.g. `1,234.5`. A precision rounds half up to that many
+/// places instead: `format!("{:.2}", amount)` gives `1,234.50`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UiAmount {
+    pub amount: u64,
+    pub decimals: u8,
+}
+
+impl UiAmount {
+    pub fn new(amount: u64, decimals: u8) -> Self {
+        Self { amount, decimals }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.amount as f64 / 10f64.powi(i32::from(self.decimals))
+    }
+
+    /// The exact value, or `None` if `decimals` exceeds what `Decimal` can
+    /// scale (28).
+    #[cfg(feature = "rust_decimal")]
+    pub fn to_decimal(&self) -> Option<rust_decimal::Decimal> {
+        rust_decimal::Decimal::try_from_i128_with_scale(
+            i128::from(self.amount),
+            u32::from(self.decimals),
+        )
+        .ok()
+    }
+}
+
+impl fmt::Display for UiAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let decimals = usize::from(self.decimals);
+        match f.precision() {
+            None => write_scaled(f, u128::from(self.amount), decimals, true, 0),
+            Some(precision) if precision >= decimals => write_scaled(
+                f,
+                u128::from(self.amount),
+                decimals,
+                false,
+                precision - decimals,
+            ),
+            Some(precision) => {
+                // u64::MAX < 10^20, so anything scaled down further rounds to 0.
+                let rounded = u32::try_from(decimals - precision)
+                    .ok()
+                    .and_then(|exp| 10u128.checked_pow(exp))
+                    .map_or(0, |divisor| {
+                        (u128::from(self.amount) + divisor / 2) / divisor
+                    });
+                write_scaled(f, rounded, precision, false, 0)
+            }
+        }
+    }
+}
+
+/// Writes `value / 10^scale` with grouped thousands, then `zeros` extra
+/// fractional zeros.
+fn write_scaled(
+    f: &mut fmt::Formatter<'_>,
+    value: u128,
+    scale: usize,
+    trim: bool,
+    zeros: usize,
+) -> fmt::Result {
+    let digits = format!("{:0>width$}", value, width = scale + 1);
+    let (int, frac) = digits.split_at(digits.len() - scale);
+    let frac = if trim {
+        frac.trim_end_matches('0')
+    } else {
+        frac
+    };
+
+    for (i, digit) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            f.write_str(",")?;
+        }
+        write!(f, "{digit}")?;
+    }
+    if !frac.is_empty() || zeros > 0 {
+        write!(f, ".{frac}{:0<zeros$}", "")?;
+    }
+    Ok(())
+}
+
+/// A quote's or step's input and output amounts in whole-token units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiAmounts {
+    pub in_amount: UiAmount,
+    pub out_amount: UiAmount,
+}
+
+impl QuoteResponse {
+    /// The route's actual input, which for ExactOut differs from the
+    /// requested `in_amount`.
+    pub fn ui_in_amount(&self, decimals: u8) -> UiAmount {
+        UiAmount::new(self.input_amount(), decimals)
+    }
+
+    pub fn ui_out_amount(&self, decimals: u8) -> UiAmount {
+        UiAmount::new(self.out_amount, decimals)
+    }
+
+    /// Both amounts, with decimals looked up in `registry`. `None` if either
+    /// mint is unknown to it.
+    #[cfg(feature = "native")]
+    pub fn ui_amounts(&self, registry: &crate::tokens::TokenRegistry) -> Option<UiAmounts> {
+        Some(UiAmounts {
+            in_amount: self.ui_in_amount(registry.decimals(&self.input_mint)?),
+            out_amount: self.ui_out_amount(registry.decimals(&self.output_mint)?),
+        })
+    }
+}
+
+impl SwapInfo {
+    pub fn ui_in_amount(&self, decimals: u8) -> UiAmount {
+        UiAmount::new(self.in_amount, decimals)
+    }
+
+    pub fn ui_out_amount(&self, decimals: u8) -> UiAmount {
+        UiAmount::new(self.out_amount, decimals)
+    }
+
+    /// `decimals` are those of `fee_mint`. `None` when the step reports no
+    /// fee.
+    pub fn ui_fee_amount(&self, decimals: u8) -> Option<UiAmount> {
+        self.fee_amount
+            .map(|fee_amount| UiAmount::new(fee_amount, decimals))
+    }
+
+    #[cfg(feature = "native")]
+    pub fn ui_amounts(&self, registry: &crate::tokens::TokenRegistry) -> Option<UiAmounts> {
+        Some(UiAmounts {
+            in_amount: self.ui_in_amount(registry.decimals(&self.input_mint)?),
+            out_amount: self.ui_out_amount(registry.decimals(&self.output_mint)?),
+        })
+    }
+}
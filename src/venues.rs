@@ -0,0 +1,232 @@
+use crate::pubkey::Pubkey;
+use crate::serde_helpers::pubkey_as_bytes;
+use serde::{Deserialize, Serialize};
+
+/// A DEX supported by the API, as listed by the venues endpoint.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VenueInfo {
+    pub id: String,
+    pub name: String,
+    #[serde(with = "pubkey_as_bytes")]
+    pub program_id: Pubkey,
+}
+
+// `from_str_const` is what `pubkey!` expands to, and unlike the macro it
+// exists without the `native` feature too.
+pub const RAYDIUM_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
+pub const RAYDIUM_CLMM_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK");
+pub const RAYDIUM_CPMM_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+pub const WHIRLPOOL_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+pub const METEORA_DLMM_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo");
+pub const METEORA_DAMM_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB");
+pub const PHOENIX_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY");
+pub const OPENBOOK_V2_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb");
+pub const LIFINITY_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("2wT8Yq49kHgDzXuPxZSaeLaH1qbmGXtEyPy64bL7aD3c");
+pub const PUMPSWAP_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dex {
+    Raydium,
+    RaydiumClmm,
+    RaydiumCpmm,
+    Whirlpool,
+    MeteoraDlmm,
+    MeteoraDamm,
+    Phoenix,
+    OpenBookV2,
+    Lifinity,
+    PumpSwap,
+}
+
+impl Dex {
+    pub const ALL: &'static [Dex] = &[
+        Dex::Raydium,
+        Dex::RaydiumClmm,
+        Dex::RaydiumCpmm,
+        Dex::Whirlpool,
+        Dex::MeteoraDlmm,
+        Dex::MeteoraDamm,
+        Dex::Phoenix,
+        Dex::OpenBookV2,
+        Dex::Lifinity,
+        Dex::PumpSwap,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Raydium => "Raydium AMM",
+            Self::RaydiumClmm => "Raydium CLMM",
+            Self::RaydiumCpmm => "Raydium CPMM",
+            Self::Whirlpool => "Whirlpool",
+            Self::MeteoraDlmm => "Meteora DLMM",
+            Self::MeteoraDamm => "Meteora DAMM",
+            Self::Phoenix => "Phoenix",
+            Self::OpenBookV2 => "OpenBook V2",
+            Self::Lifinity => "Lifinity",
+            Self::PumpSwap => "PumpSwap",
+        }
+    }
+
+    /// The canonical id the API expects in `excludeDexes`.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Raydium => "raydium",
+            Self::RaydiumClmm => "raydium-clmm",
+            Self::RaydiumCpmm => "raydium-cpmm",
+            Self::Whirlpool => "whirlpool",
+            Self::MeteoraDlmm => "meteora-dlmm",
+            Self::MeteoraDamm => "meteora-damm",
+            Self::Phoenix => "phoenix",
+            Self::OpenBookV2 => "openbook-v2",
+            Self::Lifinity => "lifinity-v2",
+            Self::PumpSwap => "pumpswap",
+        }
+    }
+
+    pub const fn program_id(&self) -> Pubkey {
+        match self {
+            Self::Raydium => RAYDIUM_PROGRAM_ID,
+            Self::RaydiumClmm => RAYDIUM_CLMM_PROGRAM_ID,
+            Self::RaydiumCpmm => RAYDIUM_CPMM_PROGRAM_ID,
+            Self::Whirlpool => WHIRLPOOL_PROGRAM_ID,
+            Self::MeteoraDlmm => METEORA_DLMM_PROGRAM_ID,
+            Self::MeteoraDamm => METEORA_DAMM_PROGRAM_ID,
+            Self::Phoenix => PHOENIX_PROGRAM_ID,
+            Self::OpenBookV2 => OPENBOOK_V2_PROGRAM_ID,
+            Self::Lifinity => LIFINITY_PROGRAM_ID,
+            Self::PumpSwap => PUMPSWAP_PROGRAM_ID,
+        }
+    }
+
+    /// Labels this venue has been reported under, besides its id.
+    fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            Self::Raydium => &["Raydium AMM", "Raydium V4"],
+            Self::RaydiumClmm => &["Raydium CLMM", "Raydium Concentrated"],
+            Self::RaydiumCpmm => &["Raydium CP", "Raydium CPMM"],
+            Self::Whirlpool => &["Orca", "Orca Whirlpool", "Whirlpools"],
+            Self::MeteoraDlmm => &["Meteora DLMM", "DLMM"],
+            Self::MeteoraDamm => &["Meteora", "Meteora Pools", "Meteora DAMM"],
+            Self::Phoenix => &[],
+            Self::OpenBookV2 => &["OpenBook", "Openbook V2"],
+            Self::Lifinity => &["Lifinity", "Lifinity V2"],
+            Self::PumpSwap => &["Pump.fun Amm", "Pump AMM", "PumpFun AMM"],
+        }
+    }
+
+    pub fn from_program_id(program_id: &Pubkey) -> Option<Dex> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|dex| dex.program_id() == *program_id)
+    }
+}
+
+/// Lowercase alphanumerics only, so "Raydium CLMM", "raydium-clmm" and
+/// "raydium_clmm" compare equal.
+fn label_key(label: &str) -> String {
+    label
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Maps a free-form route step label to a [`Dex`].
+///
+/// Exact matches on the id or a known alias win; otherwise the longest id or
+/// alias the label starts with, so "Raydium CLMM (new)" still maps to
+/// `RaydiumClmm` rather than `Raydium`.
+pub fn normalize_label(label: &str) -> Option<Dex> {
+    let key = label_key(label);
+    if key.is_empty() {
+        return None;
+    }
+
+    let candidates = || {
+        Dex::ALL.iter().flat_map(|dex| {
+            std::iter::once(dex.id())
+                .chain(dex.aliases().iter().copied())
+                .map(move |name| (*dex, label_key(name)))
+        })
+    };
+
+    if let Some((dex, _)) = candidates().find(|(_, name)| *name == key) {
+        return Some(dex);
+    }
+
+    candidates()
+        .filter(|(_, name)| key.starts_with(name.as_str()))
+        .max_by_key(|(_, name)| name.len())
+        .map(|(dex, _)| dex)
+}
+
+/// The API's venue list, used to resolve labels and ids it knows about
+/// before falling back to [`normalize_label`].
+#[derive(Debug, Clone, Default)]
+pub struct Venues {
+    venues: Vec<VenueInfo>,
+}
+
+impl Venues {
+    pub fn new(venues: Vec<VenueInfo>) -> Self {
+        Self { venues }
+    }
+
+    pub fn find(&self, label: &str) -> Option<&VenueInfo> {
+        let key = label_key(label);
+        self.venues
+            .iter()
+            .find(|venue| label_key(&venue.id) == key || label_key(&venue.name) == key)
+    }
+
+    pub fn normalize(&self, label: &str) -> Option<Dex> {
+        self.find(label)
+            .and_then(|venue| Dex::from_program_id(&venue.program_id))
+            .or_else(|| normalize_label(label))
+    }
+
+    /// The id to send in `excludeDexes` for `label`.
+    pub fn canonical_id(&self, label: &str) -> Option<String> {
+        if let Some(venue) = self.find(label) {
+            return Some(venue.id.clone());
+        }
+
+        let dex = normalize_label(label)?;
+        let venue = self
+            .venues
+            .iter()
+            .find(|venue| venue.program_id == dex.program_id());
+        Some(venue.map_or_else(|| dex.id().to_string(), |venue| venue.id.clone()))
+    }
+}
+
+/// Rewrites a comma-separated DEX list to canonical ids. Unrecognized
+/// entries are passed through trimmed.
+pub(crate) fn canonicalize_dexes(excluded_dexes: &str) -> String {
+    excluded_dexes
+        .split(',')
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .map(|label| normalize_label(label).map_or(label, |dex| dex.id()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(feature = "native")]
+impl crate::TitanClient {
+    pub async fn venues(&self) -> Result<Vec<VenueInfo>, crate::ClientError> {
+        self.get_decoded("/venues", &[]).await
+    }
+}
@@ -0,0 +1,75 @@
+//! A quote-only client for `wasm32-unknown-unknown`, built on reqwest's
+//! fetch backend.
+//!
+//! Failover, hedging, circuit breaking and rate limiting rely on tokio and
+//! `std::time::Instant`, neither of which is available in the browser, so
+//! this client issues a single request per call. Transaction building stays
+//! native-only.
+
+use crate::api_version::ApiVersion;
+use crate::quote::{QuoteRequest, QuoteResponse, SwapQuotes};
+use crate::transport::{
+    decode_quote_response, prepare_request, quote_from_response, quote_headers, quote_url,
+    quotes_from_response, HttpResponse,
+};
+use crate::{ClientError, TITAN_API_URL};
+use std::collections::BTreeMap;
+
+#[derive(Clone)]
+pub struct TitanClient {
+    client: reqwest::Client,
+    base_path: String,
+    api_version: ApiVersion,
+    headers: Vec<(&'static str, String)>,
+}
+
+impl TitanClient {
+    pub fn new(auth_token: String, base_path: Option<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_path: base_path.unwrap_or_else(|| TITAN_API_URL.to_string()),
+            api_version: ApiVersion::default(),
+            headers: quote_headers(&auth_token),
+        }
+    }
+
+    /// Sends requests to `api_version`'s endpoints. Defaults to
+    /// `ApiVersion::V1`.
+    pub fn with_api_version(mut self, api_version: ApiVersion) -> Self {
+        self.api_version = api_version;
+        self
+    }
+
+    async fn fetch_swap_quotes(
+        &self,
+        params: &[(&str, String)],
+    ) -> Result<SwapQuotes, ClientError> {
+        let mut request = self
+            .client
+            .get(quote_url(&self.base_path, self.api_version))
+            .query(params);
+        for (name, value) in &self.headers {
+            request = request.header(*name, value);
+        }
+        let response = request.send().await?;
+
+        decode_quote_response(&HttpResponse {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: response.bytes().await?,
+        })
+    }
+
+    pub async fn quote(&self, request: &QuoteRequest) -> Result<QuoteResponse, ClientError> {
+        let quotes = self.fetch_swap_quotes(&prepare_request(request)?).await?;
+        quote_from_response(request, quotes)
+    }
+
+    pub async fn quote_all(
+        &self,
+        request: &QuoteRequest,
+    ) -> Result<BTreeMap<String, QuoteResponse>, ClientError> {
+        let quotes = self.fetch_swap_quotes(&prepare_request(request)?).await?;
+        quotes_from_response(request, quotes)
+    }
+}
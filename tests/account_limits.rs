@@ -0,0 +1,196 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+};
+use solana_system_interface::instruction as system_instruction;
+use titan_swap_api_client::{
+    account_limits::{AccountLimits, AccountLimitsError, ACCOUNT_LOCK_LIMIT},
+    constants::{
+        ASSOCIATED_TOKEN_PROGRAM_ID, JITO_TIP_ACCOUNTS, MEMO_PROGRAM_ID, SYSTEM_PROGRAM_ID,
+        TOKEN_PROGRAM_ID, USDC_MINT,
+    },
+    query::ParseError,
+    quote::QuoteRequest,
+    test_utils::MockTitanServer,
+    ClientError,
+};
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn tip() -> Instruction {
+    system_instruction::transfer(&USER, &JITO_TIP_ACCOUNTS[0], 10_000)
+}
+
+fn memo() -> Instruction {
+    Instruction {
+        program_id: MEMO_PROGRAM_ID,
+        accounts: vec![AccountMeta::new_readonly(USER, true)],
+        data: b"order 42".to_vec(),
+    }
+}
+
+fn create_ata(wallet: Pubkey, mint: Pubkey) -> Instruction {
+    Instruction {
+        program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(USER, true),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(wallet, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data: vec![1],
+    }
+}
+
+fn total_for(existing: &[Instruction]) -> usize {
+    AccountLimits::for_transaction_with(existing)
+        .unwrap()
+        .total
+        .unwrap()
+}
+
+#[test]
+fn nothing_else_leaves_the_whole_lock_limit() {
+    assert_eq!(
+        AccountLimits::for_transaction_with(&[]),
+        Ok(AccountLimits::with_total(ACCOUNT_LOCK_LIMIT))
+    );
+}
+
+#[test]
+fn derivation_against_known_instructions() {
+    // The tip account and the system program; the user signs the swap too.
+    assert_eq!(total_for(&[tip()]), 62);
+    // Just the memo program.
+    assert_eq!(total_for(&[memo()]), 63);
+    // The ATA, mint, system, token and ATA programs. The wallet is the
+    // signing user, so it is already counted by the swap.
+    assert_eq!(total_for(&[create_ata(USER, USDC_MINT)]), 59);
+    // Another wallet's ATA needs that wallet too.
+    assert_eq!(
+        total_for(&[create_ata(Pubkey::new_unique(), USDC_MINT)]),
+        58
+    );
+
+    // 2 + 1 + 5, less the system program the tip and ATA creation share.
+    assert_eq!(total_for(&[tip(), memo(), create_ata(USER, USDC_MINT)]), 57);
+}
+
+#[test]
+fn duplicate_instructions_count_once() {
+    assert_eq!(total_for(&[tip(), tip(), memo(), memo()]), 61);
+}
+
+#[test]
+fn no_room_left_is_an_error() {
+    let crowded: Vec<_> = (0..ACCOUNT_LOCK_LIMIT)
+        .map(|_| system_instruction::transfer(&USER, &Pubkey::new_unique(), 1))
+        .collect();
+    assert_eq!(
+        AccountLimits::for_transaction_with(&crowded),
+        Err(AccountLimitsError::Zero)
+    );
+}
+
+#[test]
+fn validation() {
+    let limits = |total, writable| AccountLimits { total, writable };
+
+    assert_eq!(limits(None, None).validate(), Ok(()));
+    assert_eq!(limits(Some(40), Some(20)).validate(), Ok(()));
+    assert_eq!(limits(Some(40), Some(40)).validate(), Ok(()));
+    assert_eq!(limits(None, Some(64)).validate(), Ok(()));
+    assert_eq!(
+        limits(Some(20), Some(21)).validate(),
+        Err(AccountLimitsError::WritableExceedsTotal {
+            writable: 21,
+            total: 20
+        })
+    );
+    assert_eq!(
+        limits(Some(65), None).validate(),
+        Err(AccountLimitsError::OverLockLimit(65))
+    );
+    assert_eq!(
+        limits(Some(40), Some(0)).validate(),
+        Err(AccountLimitsError::Zero)
+    );
+}
+
+#[test]
+fn parsing_validates_limits() {
+    let query = |params: &str| {
+        QuoteRequest::from_query_str(&format!(
+            "inputMint={USDC_MINT}&outputMint={USDC_MINT}&amount=1&{params}"
+        ))
+    };
+
+    let request = query("accountsLimitTotal=40&accountsLimitWritable=20").unwrap();
+    assert_eq!(
+        request.account_limits,
+        AccountLimits {
+            total: Some(40),
+            writable: Some(20)
+        }
+    );
+
+    for (params, name) in [
+        ("accountsLimitTotal=65", "accountsLimitTotal"),
+        ("accountsLimitWritable=0", "accountsLimitWritable"),
+        (
+            "accountsLimitWritable=30&accountsLimitTotal=20",
+            "accountsLimitWritable",
+        ),
+    ] {
+        assert!(
+            matches!(
+                query(params),
+                Err(ParseError::InvalidValue { name: actual, .. }) if actual == name
+            ),
+            "{params}"
+        );
+    }
+}
+
+#[test]
+fn serde_keeps_the_flat_field_names() {
+    let request: QuoteRequest = serde_json::from_str(&format!(
+        r#"{{"inputMint": "{USDC_MINT}", "outputMint": "{USDC_MINT}", "amount": "1",
+            "maxAccounts": 30, "accountsLimitWritable": 10}}"#
+    ))
+    .unwrap();
+    assert_eq!(
+        request.account_limits,
+        AccountLimits {
+            total: Some(30),
+            writable: Some(10)
+        }
+    );
+
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["maxAccounts"], 30);
+    assert_eq!(json["accountsLimitWritable"], 10);
+}
+
+#[tokio::test]
+async fn invalid_limits_fail_before_sending() {
+    let server = MockTitanServer::start().await;
+    let request = QuoteRequest {
+        account_limits: AccountLimits {
+            total: Some(10),
+            writable: Some(11),
+        },
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        server.client().quote(&request).await,
+        Err(ClientError::InvalidAccountLimits(
+            AccountLimitsError::WritableExceedsTotal { .. }
+        ))
+    ));
+    assert_eq!(server.received_request_count().await, 0);
+}
@@ -0,0 +1,57 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use titan_swap_api_client::swap::SwapResponse;
+
+const USER: Pubkey = Pubkey::new_from_array([7; 32]);
+const POOL: Pubkey = Pubkey::new_from_array([8; 32]);
+const ORACLE: Pubkey = Pubkey::new_from_array([9; 32]);
+const ROUTER: Pubkey = Pubkey::new_from_array([10; 32]);
+const DEX: Pubkey = Pubkey::new_from_array([11; 32]);
+
+fn swap() -> SwapResponse {
+    let hop = |program_id| {
+        Instruction::new_with_bytes(
+            program_id,
+            &[],
+            vec![
+                AccountMeta::new(USER, true),
+                AccountMeta::new(POOL, false),
+                AccountMeta::new_readonly(ORACLE, false),
+            ],
+        )
+    };
+    SwapResponse {
+        instructions: vec![hop(ROUTER), hop(DEX), hop(ROUTER)],
+        address_lookup_table_addresses: vec![],
+        compute_unit_limit: None,
+        compute_units_safe: None,
+        context_slot: None,
+        expires_at_ms: None,
+        expires_after_slot: None,
+    }
+}
+
+#[test]
+fn accounts_are_counted_once_across_instructions() {
+    let summary = swap().account_summary();
+
+    assert_eq!(summary.writable, BTreeSet::from([USER, POOL]));
+    assert_eq!(summary.signers, BTreeSet::from([USER]));
+    assert_eq!(
+        summary.instructions_per_program,
+        BTreeMap::from([(ROUTER, 2), (DEX, 1)])
+    );
+}
+
+#[test]
+fn touches_accounts_and_programs_but_nothing_else() {
+    let swap = swap();
+
+    assert!(swap.touches(&POOL));
+    assert!(swap.touches(&ORACLE));
+    assert!(swap.touches(&DEX));
+    assert!(!swap.touches(&Pubkey::new_unique()));
+}
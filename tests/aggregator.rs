@@ -0,0 +1,139 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+};
+use titan_swap_api_client::{
+    aggregator::AggregatorClient,
+    constants::{SOL_MINT, USDC_MINT},
+    jupiter_client::JupiterClient,
+    quote::{QuoteRequest, SwapMode},
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+};
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const JUPITER_QUOTE: &str = include_str!("fixtures/jupiter_quote_v6.json");
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey: Some(USER),
+        swap_mode: Some(SwapMode::ExactIn),
+        slippage_bps: 50,
+        ..sample_request()
+    }
+}
+
+async fn jupiter_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .and(query_param("inputMint", SOL_MINT.to_string()))
+        .and(query_param("slippageBps", "50"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(JUPITER_QUOTE))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/swap-instructions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "computeBudgetInstructions": [{
+                "programId": "ComputeBudget111111111111111111111111111111",
+                "accounts": [],
+                "data": "AsBcFQA="
+            }],
+            "setupInstructions": [],
+            "swapInstruction": {
+                "programId": "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4",
+                "accounts": [{
+                    "pubkey": USER.to_string(),
+                    "isSigner": true,
+                    "isWritable": true
+                }],
+                "data": "5RfLl3rjrSoBAAAA"
+            },
+            "cleanupInstruction": null,
+            "otherInstructions": [],
+            "addressLookupTableAddresses": [],
+            "prioritizationFeeLamports": 0,
+            "computeUnitLimit": 400000
+        })))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn titan_and_jupiter_are_interchangeable() {
+    let titan = MockTitanServer::start().await;
+    titan
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+                .amounts(1_000_000_000, 187_654_321)
+                .instruction(&Instruction::new_with_bytes(
+                    pubkey!("T1TANpTeScyeqVzzgNViGDNrkQ6qHz9KrSBS4aNXvGT"),
+                    &[1, 2, 3],
+                    vec![AccountMeta::new(USER, true)],
+                )),
+        )
+        .mount()
+        .await;
+    let jupiter = jupiter_server().await;
+
+    let clients: Vec<Box<dyn AggregatorClient>> = vec![
+        Box::new(titan.client()),
+        Box::new(JupiterClient::new(None, Some(jupiter.uri()))),
+    ];
+
+    let mut out_amounts = Vec::new();
+    for client in &clients {
+        let quote = client.quote(&request()).await.unwrap();
+        client.swap_instructions(&quote, &USER).await.unwrap();
+        out_amounts.push((client.name().to_string(), quote.out_amount));
+    }
+
+    assert_eq!(
+        out_amounts,
+        vec![
+            ("Titan".to_string(), 187_654_321),
+            ("Jupiter".to_string(), 187_702_114),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn jupiter_quote_maps_route_plan() {
+    let jupiter = jupiter_server().await;
+    let client = JupiterClient::new(None, Some(jupiter.uri()));
+
+    let quote = client.quote(&request()).await.unwrap();
+    assert_eq!(quote.route_plan.len(), 2);
+    assert_eq!(quote.route_plan[0].swap_info.label, "Whirlpool");
+    assert_eq!(quote.route_plan[0].swap_info.alloc_ppb, 600_000_000);
+    assert_eq!(quote.context_slot, Some(312_000_012));
+    assert_eq!(quote.to_jupiter().route_plan[1].percent, 40);
+}
+
+#[tokio::test]
+async fn jupiter_swap_instructions_skip_compute_budget() {
+    let jupiter = jupiter_server().await;
+    let client = JupiterClient::new(None, Some(jupiter.uri()));
+
+    let quote = client.quote(&request()).await.unwrap();
+    let swap = client.swap_instructions(&quote, &USER).await.unwrap();
+
+    assert_eq!(swap.instructions.len(), 1);
+    assert_eq!(
+        swap.instructions[0].program_id,
+        pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4")
+    );
+    assert_eq!(swap.compute_unit_limit, Some(400_000));
+
+    let requests = jupiter.received_requests().await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+    assert_eq!(body["userPublicKey"], USER.to_string());
+    assert_eq!(body["quoteResponse"]["outAmount"], "187702114");
+}
@@ -0,0 +1,130 @@
+use proptest::prelude::*;
+use titan_swap_api_client::{
+    compare::CompareError,
+    jupiter::JupiterQuote,
+    quote::{QuoteRequest, QuoteResponse, SwapMode},
+    test_utils::SwapRouteBuilder,
+    ui::UiAmount,
+    AmountOverflow,
+};
+
+fn quote(swap_mode: SwapMode, in_amount: u64, out_amount: u64, slippage_bps: u16) -> QuoteResponse {
+    let request = QuoteRequest {
+        swap_mode: Some(swap_mode.clone()),
+        ..Default::default()
+    };
+    let route = SwapRouteBuilder::default()
+        .swap_mode(swap_mode)
+        .amounts(in_amount, out_amount)
+        .slippage_bps(slippage_bps)
+        .build();
+    QuoteResponse::from_route(&request, route)
+}
+
+fn bps() -> impl Strategy<Value = u16> {
+    prop_oneof![
+        Just(0),
+        Just(1),
+        Just(9_999),
+        Just(10_000),
+        Just(10_001),
+        Just(u16::MAX),
+        any::<u16>()
+    ]
+}
+
+fn amount() -> impl Strategy<Value = u64> {
+    prop_oneof![
+        Just(0),
+        Just(1),
+        Just(u64::MAX - 1),
+        Just(u64::MAX),
+        any::<u64>()
+    ]
+}
+
+#[test]
+fn slippage_at_the_limits() {
+    let exact_in = JupiterQuote::from(quote(SwapMode::ExactIn, 1, u64::MAX, 50));
+    assert_eq!(exact_in.other_amount_threshold, 18_354_510_353_341_003_856);
+
+    let exact_out = JupiterQuote::from(quote(SwapMode::ExactOut, u64::MAX, 1, 50));
+    assert_eq!(exact_out.other_amount_threshold, u64::MAX);
+
+    let everything = JupiterQuote::from(quote(SwapMode::ExactIn, 1, u64::MAX, u16::MAX));
+    assert_eq!(everything.other_amount_threshold, 0);
+}
+
+#[test]
+fn compute_unit_delta_overflow_is_an_error() {
+    let mut ours = quote(SwapMode::ExactIn, 1, 1, 50);
+    let mut theirs = ours.clone();
+    ours.route_data_mut().unwrap().compute_units = Some(0);
+    theirs.route_data_mut().unwrap().compute_units = Some(u64::MAX);
+
+    assert_eq!(
+        ours.compare(&theirs),
+        Err(CompareError::AmountOverflow(AmountOverflow))
+    );
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(4096))]
+
+    #[test]
+    fn min_out_never_wraps(out_amount in amount(), slippage_bps in bps()) {
+        let jupiter = JupiterQuote::from(quote(SwapMode::ExactIn, 1, out_amount, slippage_bps));
+
+        let kept = 10_000u128.saturating_sub(u128::from(slippage_bps));
+        let expected = u128::from(out_amount) * kept / 10_000;
+        prop_assert_eq!(u128::from(jupiter.other_amount_threshold), expected);
+        prop_assert!(jupiter.other_amount_threshold <= out_amount);
+    }
+
+    #[test]
+    fn max_in_never_wraps(in_amount in amount(), slippage_bps in bps()) {
+        let jupiter = JupiterQuote::from(quote(SwapMode::ExactOut, in_amount, 1, slippage_bps));
+
+        let expected = (u128::from(in_amount) * (10_000 + u128::from(slippage_bps))).div_ceil(10_000);
+        prop_assert_eq!(
+            u128::from(jupiter.other_amount_threshold),
+            expected.min(u128::from(u64::MAX))
+        );
+        prop_assert!(jupiter.other_amount_threshold >= in_amount);
+    }
+
+    #[test]
+    fn step_percent_stays_in_range(alloc_ppb in amount()) {
+        let mut quote = quote(SwapMode::ExactIn, 1, 1, 50);
+        quote.route_plan[0].swap_info.alloc_ppb = alloc_ppb;
+
+        let percent = JupiterQuote::from(quote).route_plan[0].percent;
+        prop_assert!(percent <= 100);
+    }
+
+    #[test]
+    fn compute_unit_delta_is_exact_or_an_error(ours in amount(), theirs in amount()) {
+        let mut a = quote(SwapMode::ExactIn, 1, 1, 50);
+        let mut b = a.clone();
+        a.route_data_mut().unwrap().compute_units = Some(ours);
+        b.route_data_mut().unwrap().compute_units = Some(theirs);
+
+        let expected = i128::from(theirs) - i128::from(ours);
+        match a.compare(&b) {
+            Ok(delta) => prop_assert_eq!(delta.compute_units.map(i128::from), Some(expected)),
+            Err(err) => {
+                prop_assert_eq!(err, CompareError::AmountOverflow(AmountOverflow));
+                prop_assert!(i64::try_from(expected).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn ui_amount_display_keeps_every_digit(amount in amount(), decimals in 0u8..=30) {
+        let shown = UiAmount::new(amount, decimals).to_string().replace(',', "");
+        let (int, frac) = shown.split_once('.').unwrap_or((&shown, ""));
+        let digits = format!("{int}{frac:0<width$}", width = usize::from(decimals));
+
+        prop_assert_eq!(digits.parse::<u128>().unwrap(), u128::from(amount));
+    }
+}
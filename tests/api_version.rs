@@ -0,0 +1,74 @@
+use titan_swap_api_client::{
+    api_version::ApiVersion,
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    TitanClient,
+};
+
+async fn paths_hit(server: &MockTitanServer, client: TitanClient) -> Vec<String> {
+    let _ = client.quote(&sample_request()).await;
+    let _ = client.tokens().await;
+    let _ = client.health().await;
+    server.received_paths().await
+}
+
+#[tokio::test]
+async fn v1_is_the_default() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default().amounts(1_000_000, 990_000))
+        .mount()
+        .await;
+
+    let client = server.client();
+    assert_eq!(client.api_version(), ApiVersion::V1);
+    assert_eq!(
+        client.endpoint("/quote/swap"),
+        format!("{}/api/v1/quote/swap", server.uri())
+    );
+
+    let quote = client.quote(&sample_request()).await.unwrap();
+    assert_eq!(quote.out_amount, 990_000);
+    assert_eq!(
+        paths_hit(&server, client).await,
+        vec![
+            "/api/v1/quote/swap",
+            "/api/v1/quote/swap",
+            "/api/v1/tokens",
+            "/api/v1/health"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn v2_uses_the_v2_prefix() {
+    let server = MockTitanServer::start().await;
+    let client = server
+        .client_builder()
+        .api_version(ApiVersion::V2)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        paths_hit(&server, client).await,
+        vec!["/api/v2/quote/swap", "/api/v2/tokens", "/api/v2/health"]
+    );
+}
+
+#[tokio::test]
+async fn custom_prefix_overrides_the_version() {
+    let server = MockTitanServer::start().await;
+    let client = server
+        .client_builder()
+        .path_prefix("/staging/api/v1")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        paths_hit(&server, client).await,
+        vec![
+            "/staging/api/v1/quote/swap",
+            "/staging/api/v1/tokens",
+            "/staging/api/v1/health"
+        ]
+    );
+}
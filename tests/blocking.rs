@@ -0,0 +1,187 @@
+#![cfg(feature = "blocking")]
+
+use solana_sdk::pubkey::Pubkey;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use titan_swap_api_client::{
+    blocking::TitanClient,
+    quote::{QuoteRequest, SwapMode},
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+const SINGLE_HOP: &[u8] = include_bytes!("fixtures/single_hop.msgpack");
+const USER: Pubkey = Pubkey::new_from_array([9; 32]);
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Runtime::new().unwrap()
+}
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey: Some(USER),
+        slippage_bps: 50,
+        ..sample_request()
+    }
+}
+
+#[test]
+fn quotes_and_swaps() {
+    let rt = runtime();
+    let server = rt.block_on(async {
+        let server = MockTitanServer::start().await;
+        server.respond_with_body(SINGLE_HOP).mount().await;
+        server
+    });
+
+    let client = TitanClient::new("test-token".to_string(), Some(server.uri()));
+    let quote = client.quote(&request()).unwrap();
+    assert_eq!(quote.out_amount, 187_654_321);
+    assert_eq!(quote.route_plan.len(), 1);
+
+    let swap = client.swap(&quote).unwrap();
+    assert_eq!(swap.instructions.len(), 2);
+    assert_eq!(swap.compute_unit_limit, Some(180_000));
+
+    rt.block_on(server.assert_query_param("slippageBps", "50"));
+}
+
+#[test]
+fn quote_all_keys_by_provider() {
+    let rt = runtime();
+    let server = rt.block_on(async {
+        let server = MockTitanServer::start().await;
+        server
+            .respond_with_route(SwapRouteBuilder::default().provider("Titan"))
+            .mount()
+            .await;
+        server
+    });
+
+    let client = TitanClient::new("test-token".to_string(), Some(server.uri()));
+    let quotes = client.quote_all(&request()).unwrap();
+    assert_eq!(quotes.len(), 1);
+    assert!(quotes.contains_key("Titan"));
+}
+
+#[test]
+fn maps_error_responses() {
+    let rt = runtime();
+    let server = rt.block_on(async {
+        let server = MockTitanServer::start().await;
+        server
+            .respond_with_error(404, "No routes found")
+            .mount()
+            .await;
+        server
+    });
+
+    let client = TitanClient::new("test-token".to_string(), Some(server.uri()));
+    assert!(matches!(
+        client.quote(&request()),
+        Err(ClientError::NoRoutesAvailable)
+    ));
+}
+
+#[test]
+fn responses_for_another_request_are_rejected() {
+    let rt = runtime();
+    let server = rt.block_on(async {
+        let server = MockTitanServer::start().await;
+        server.respond_with_body(SINGLE_HOP).mount().await;
+        server
+    });
+
+    let client = TitanClient::new("test-token".to_string(), Some(server.uri()));
+    let exact_out = QuoteRequest {
+        swap_mode: Some(SwapMode::ExactOut),
+        ..request()
+    };
+    assert!(matches!(
+        client.quote(&exact_out),
+        Err(ClientError::ResponseMismatch {
+            field: "swapMode",
+            ..
+        })
+    ));
+    let other_amount = QuoteRequest {
+        amount: 5,
+        ..request()
+    };
+    assert!(matches!(
+        client.quote_all(&other_amount),
+        Err(ClientError::ResponseMismatch {
+            field: "amount",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn declared_length_over_the_limit_is_rejected() {
+    let rt = runtime();
+    let server = rt.block_on(async {
+        let server = MockTitanServer::start().await;
+        server.respond_with_body(vec![0u8; 64 * 1024]).mount().await;
+        server
+    });
+
+    let client = TitanClient::new("test-token".to_string(), Some(server.uri()))
+        .with_max_response_bytes(1024);
+    assert!(matches!(
+        client.quote(&request()),
+        Err(ClientError::ResponseTooLarge {
+            limit: 1024,
+            received_at_least: 65_536
+        })
+    ));
+}
+
+/// Streams chunks until the client hangs up, so a client that buffered the
+/// whole body before checking its size would never return.
+fn endless_chunked_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let (mut socket, _) = listener.accept().unwrap();
+        let mut request = [0u8; 4096];
+        let _ = socket.read(&mut request);
+        let headers = "HTTP/1.1 200 OK\r\n\
+                       Content-Type: application/vnd.msgpack\r\n\
+                       Transfer-Encoding: chunked\r\n\r\n";
+        if socket.write_all(headers.as_bytes()).is_err() {
+            return;
+        }
+        let chunk = format!("{:x}\r\n{}\r\n", 4096, "a".repeat(4096));
+        while socket.write_all(chunk.as_bytes()).is_ok() {}
+    });
+    format!("http://{address}")
+}
+
+#[test]
+fn streamed_body_is_abandoned_once_over_the_limit() {
+    let client = TitanClient::new("test-token".to_string(), Some(endless_chunked_server()))
+        .with_max_response_bytes(64 * 1024);
+
+    match client.quote(&request()) {
+        Err(ClientError::ResponseTooLarge {
+            limit,
+            received_at_least,
+        }) => {
+            assert_eq!(limit, 64 * 1024);
+            assert!(received_at_least > limit);
+        }
+        other => panic!("unexpected result {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn does_not_panic_inside_runtime() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_body(SINGLE_HOP).mount().await;
+
+    let client = TitanClient::new("test-token".to_string(), Some(server.uri()));
+    assert!(client.quote(&request()).is_ok());
+    // Dropping the blocking client also has to happen off the runtime.
+    std::thread::spawn(move || drop(client)).join().unwrap();
+}
@@ -0,0 +1,67 @@
+use std::time::{Duration, Instant};
+use titan_swap_api_client::{
+    options::{CancellationToken, QuoteRequestOptions},
+    quote::QuoteRequest,
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+#[tokio::test]
+async fn cancelling_returns_promptly_and_leaves_the_client_usable() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_delay(SwapRouteBuilder::default(), Duration::from_secs(5))
+        .times(1)
+        .mount()
+        .await;
+    server
+        .respond_with_route(SwapRouteBuilder::default().amounts(1_000, 990))
+        .mount()
+        .await;
+    let client = server.client();
+
+    let cancel = CancellationToken::new();
+    let trigger = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        trigger.cancel();
+    });
+
+    let started = Instant::now();
+    let err = client
+        .quote_with_options(
+            &QuoteRequest::default(),
+            &QuoteRequestOptions::with_cancel(cancel),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, ClientError::Cancelled), "{err:?}");
+    assert!(started.elapsed() < Duration::from_secs(1));
+
+    let quote = client.quote(&QuoteRequest::default()).await.unwrap();
+    assert_eq!(quote.out_amount, 990);
+}
+
+#[tokio::test]
+async fn already_cancelled_token_sends_nothing() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+    let err = server
+        .client()
+        .quote_with_options(
+            &QuoteRequest::default(),
+            &QuoteRequestOptions::with_cancel(cancel),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, ClientError::Cancelled), "{err:?}");
+    assert_eq!(server.received_request_count().await, 0);
+}
@@ -0,0 +1,77 @@
+use solana_sdk::pubkey::Pubkey;
+use titan_swap_api_client::{
+    quote::{QuoteRequest, QuoteResponse},
+    swap::SwapResponse,
+    test_utils::SwapRouteBuilder,
+    ClientError, TitanClient,
+};
+
+fn swap(route: SwapRouteBuilder) -> Result<SwapResponse, ClientError> {
+    let request = QuoteRequest {
+        user_pubkey: Some(Pubkey::new_unique()),
+        ..Default::default()
+    };
+    let quote = QuoteResponse::from_route(&request, route.build());
+    TitanClient::new(String::new(), None).swap(&quote)
+}
+
+#[test]
+fn compute_units_omitted() {
+    let swap = swap(SwapRouteBuilder::default()).unwrap();
+    assert_eq!(swap.compute_unit_limit, None);
+    assert_eq!(swap.compute_units_safe, None);
+}
+
+#[test]
+fn compute_units_boundaries() {
+    for value in [0, 1, u64::from(u32::MAX)] {
+        let swap = swap(SwapRouteBuilder::default().compute_units(value, value)).unwrap();
+        assert_eq!(swap.compute_unit_limit, Some(value as u32));
+        assert_eq!(swap.compute_units_safe, Some(value));
+    }
+}
+
+#[test]
+fn compute_units_overflow() {
+    for value in [u64::from(u32::MAX) + 1, u64::MAX] {
+        let err = swap(SwapRouteBuilder::default().compute_units(value, value)).unwrap_err();
+        match err {
+            ClientError::InvalidResponse {
+                field,
+                value: reported,
+            } => {
+                assert_eq!(field, "computeUnits");
+                assert_eq!(reported, value.to_string());
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+}
+
+#[test]
+fn alloc_ppb_boundaries() {
+    for alloc_ppb in [0, 1_000_000_000, u32::MAX] {
+        let route = SwapRouteBuilder::default().step(
+            "Mock",
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            1,
+            1,
+            alloc_ppb,
+        );
+        let quote = QuoteResponse::from_route(&QuoteRequest::default(), route.build());
+        assert_eq!(
+            quote.route_plan[0].swap_info.alloc_ppb,
+            u64::from(alloc_ppb)
+        );
+    }
+}
+
+#[test]
+fn amount_boundaries() {
+    let route = SwapRouteBuilder::default().amounts(u64::MAX, u64::MAX);
+    let quote = QuoteResponse::from_route(&QuoteRequest::default(), route.build());
+    assert_eq!(quote.out_amount, u64::MAX);
+    assert_eq!(quote.route_plan[0].swap_info.in_amount, u64::MAX);
+}
@@ -0,0 +1,163 @@
+use std::time::Duration;
+use titan_swap_api_client::{
+    circuit_breaker::CircuitState,
+    quote::QuoteRequest,
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+const OPEN_DURATION: Duration = Duration::from_millis(200);
+
+#[tokio::test]
+async fn walks_the_breaker_lifecycle() {
+    let server = MockTitanServer::start().await;
+    // Two failures to trip, one failed half-open probe, then healthy.
+    server.respond_with_status(503).times(3).mount().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+
+    let client = server
+        .client_builder()
+        .circuit_breaker(2, OPEN_DURATION)
+        .build()
+        .unwrap();
+    let request = QuoteRequest::default();
+
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+    assert!(client.quote(&request).await.is_err());
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+    assert!(client.quote(&request).await.is_err());
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+
+    // Open: fail fast without touching the server, including from clones.
+    let err = client.clone().quote(&request).await.unwrap_err();
+    assert!(matches!(err, ClientError::CircuitOpen { .. }));
+    assert_eq!(server.received_request_count().await, 2);
+
+    // Half-open probe fails and re-opens the circuit.
+    tokio::time::sleep(OPEN_DURATION + Duration::from_millis(50)).await;
+    assert!(matches!(
+        client.quote(&request).await,
+        Err(ClientError::RequestFailed { .. })
+    ));
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+    assert!(matches!(
+        client.quote(&request).await,
+        Err(ClientError::CircuitOpen { .. })
+    ));
+
+    // Half-open probe succeeds and closes the circuit.
+    tokio::time::sleep(OPEN_DURATION + Duration::from_millis(50)).await;
+    client.quote(&request).await.unwrap();
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+    client.quote(&request).await.unwrap();
+    assert_eq!(server.received_request_count().await, 5);
+}
+
+#[tokio::test]
+async fn late_successes_do_not_close_the_circuit() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default())
+        .delay(Duration::from_millis(150))
+        .times(1)
+        .mount()
+        .await;
+    server.respond_with_status(503).times(1).mount().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+
+    let client = server
+        .client_builder()
+        .circuit_breaker(1, OPEN_DURATION)
+        .build()
+        .unwrap();
+    let request = QuoteRequest::default();
+
+    // A slow call is let through while closed; a fast one trips the
+    // circuit before it returns.
+    let slow = tokio::spawn({
+        let client = client.clone();
+        async move { client.quote(&QuoteRequest::default()).await }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(client.quote(&request).await.is_err());
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+
+    slow.await.unwrap().unwrap();
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+    assert!(matches!(
+        client.quote(&request).await,
+        Err(ClientError::CircuitOpen { .. })
+    ));
+
+    // Only the half-open probe closes it.
+    tokio::time::sleep(OPEN_DURATION).await;
+    client.quote(&request).await.unwrap();
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+    assert_eq!(server.received_request_count().await, 3);
+}
+
+#[tokio::test]
+async fn non_retryable_errors_do_not_trip() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_error(404, "No routes found")
+        .mount()
+        .await;
+
+    let client = server
+        .client_builder()
+        .circuit_breaker(1, OPEN_DURATION)
+        .build()
+        .unwrap();
+    for _ in 0..3 {
+        assert!(matches!(
+            client.quote(&QuoteRequest::default()).await,
+            Err(ClientError::NoRoutesAvailable)
+        ));
+    }
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+}
+
+#[tokio::test]
+async fn a_dropped_probe_reopens_the_circuit() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_status(503).times(2).mount().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default())
+        .delay(Duration::from_secs(5))
+        .times(1)
+        .mount()
+        .await;
+    server
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+
+    let client = server
+        .client_builder()
+        .circuit_breaker(2, OPEN_DURATION)
+        .build()
+        .unwrap();
+    let request = QuoteRequest::default();
+    for _ in 0..2 {
+        assert!(client.quote(&request).await.is_err());
+    }
+
+    // The half-open probe is abandoned before it gets a response.
+    tokio::time::sleep(OPEN_DURATION + Duration::from_millis(50)).await;
+    let probe = client.quote(&request);
+    assert!(tokio::time::timeout(Duration::from_millis(50), probe)
+        .await
+        .is_err());
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+
+    tokio::time::sleep(OPEN_DURATION + Duration::from_millis(50)).await;
+    client.quote(&request).await.unwrap();
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+}
@@ -0,0 +1,143 @@
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+use std::process::Output;
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    tokens::TokenInfo,
+};
+use tokio::process::Command;
+
+const QUOTE_ARGS: &[&str] = &[
+    "--in",
+    "SOL",
+    "--out",
+    "USDC",
+    "--amount",
+    "1.5",
+    "--slippage-bps",
+    "50",
+];
+
+fn token(mint: Pubkey, symbol: &str, decimals: u8) -> TokenInfo {
+    TokenInfo {
+        mint,
+        symbol: symbol.to_string(),
+        name: symbol.to_string(),
+        decimals,
+        tags: vec![],
+    }
+}
+
+async fn server_with_tokens() -> MockTitanServer {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_tokens(&[token(SOL_MINT, "SOL", 9), token(USDC_MINT, "USDC", 6)])
+        .mount()
+        .await;
+    server
+}
+
+fn route() -> SwapRouteBuilder {
+    SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_500_000_000, 187_000_000)
+}
+
+async fn titan_quote(url: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_titan-quote"))
+        .args(args)
+        .env("TITAN_API_URL", url)
+        .env("TITAN_API_TOKEN", "test-token")
+        .output()
+        .await
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+#[tokio::test]
+async fn missing_and_malformed_arguments_are_usage_errors() {
+    let output = titan_quote("http://127.0.0.1:1", &["--in", "SOL"]).await;
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("--out <TOKEN>"));
+    assert!(stderr(&output).contains("--amount <AMOUNT>"));
+
+    let args = [&QUOTE_ARGS[..6], &["--slippage-bps", "lots"]].concat();
+    let output = titan_quote("http://127.0.0.1:1", &args).await;
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("--slippage-bps"));
+    assert!(stdout(&output).is_empty());
+}
+
+#[tokio::test]
+async fn resolves_symbols_and_ui_amounts() {
+    let server = server_with_tokens().await;
+    server.respond_with_route(route()).mount().await;
+
+    let output = titan_quote(&server.uri(), QUOTE_ARGS).await;
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("1.5 SOL → 187 USDC"));
+
+    let params = server.received_query_params().await;
+    let quote = params.last().unwrap();
+    assert_eq!(quote["inputMint"], SOL_MINT.to_string());
+    assert_eq!(quote["outputMint"], USDC_MINT.to_string());
+    assert_eq!(quote["amount"], "1500000000");
+    assert_eq!(quote["slippageBps"], "50");
+}
+
+#[tokio::test]
+async fn json_output_is_the_quote_response() {
+    let server = server_with_tokens().await;
+    server.respond_with_route(route()).mount().await;
+
+    let args = [QUOTE_ARGS, &["--json"]].concat();
+    let output = titan_quote(&server.uri(), &args).await;
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+
+    let quote: Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(quote["inputMint"], SOL_MINT.to_string());
+    assert_eq!(quote["outputMint"], USDC_MINT.to_string());
+    assert_eq!(quote["inAmount"], "1500000000");
+    assert_eq!(quote["outAmount"], "187000000");
+    assert_eq!(quote["swapMode"], "ExactIn");
+    assert_eq!(quote["slippageBps"], 50);
+    assert_eq!(quote["routePlan"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn exit_codes_tell_failures_apart() {
+    let server = server_with_tokens().await;
+    let mut no_routes = route().to_swap_quotes();
+    no_routes.quotes.clear();
+    server
+        .respond_with_body(rmp_serde::to_vec_named(&no_routes).unwrap())
+        .times(1)
+        .mount()
+        .await;
+    server.respond_with_status(401).mount().await;
+
+    let no_route = titan_quote(&server.uri(), QUOTE_ARGS).await;
+    assert_eq!(no_route.status.code(), Some(3), "{}", stderr(&no_route));
+    assert!(stderr(&no_route).contains("No routes available"));
+
+    let auth = titan_quote(&server.uri(), QUOTE_ARGS).await;
+    assert_eq!(auth.status.code(), Some(4), "{}", stderr(&auth));
+
+    let unknown = titan_quote(
+        &server.uri(),
+        &["--in", "WIF", "--out", "USDC", "--amount", "1"],
+    )
+    .await;
+    assert_eq!(unknown.status.code(), Some(2));
+    assert!(stderr(&unknown).contains("Unknown token symbol WIF"));
+
+    // Nothing listens on port 1.
+    let network = titan_quote("http://127.0.0.1:1", QUOTE_ARGS).await;
+    assert_eq!(network.status.code(), Some(5), "{}", stderr(&network));
+}
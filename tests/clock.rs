@@ -0,0 +1,237 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use std::sync::Arc;
+use std::time::Duration;
+use titan_swap_api_client::{
+    cache::QuoteCache,
+    circuit_breaker::CircuitState,
+    clock::{Clock, SystemClock},
+    constants::{SOL_MINT, USDC_MINT},
+    execution_budget::{ExecutionBudget, ExpiryReason},
+    quote::QuoteRequest,
+    test_utils::{sample_request, MockClock, MockTitanServer, SwapRouteBuilder},
+    tokens::{TokenInfo, TokenRegistry},
+    ClientError,
+};
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+const START_MS: u64 = 1_700_000_000_000;
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey: Some(USER),
+        ..sample_request()
+    }
+}
+
+/// A route valid for `valid_for` from `START_MS`.
+fn route(out_amount: u64, valid_for: Duration) -> SwapRouteBuilder {
+    SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+        .amounts(1_000_000_000, out_amount)
+        .expires(START_MS + valid_for.as_millis() as u64, 0)
+}
+
+#[test]
+fn mock_clock_moves_only_when_advanced() {
+    let clock = MockClock::new(START_MS);
+    let started = clock.instant();
+    assert_eq!(clock.now_ms(), START_MS);
+    assert_eq!(clock.elapsed(started), Duration::ZERO);
+
+    clock.clone().advance(Duration::from_millis(1_500));
+    assert_eq!(clock.now_ms(), START_MS + 1_500);
+    assert_eq!(clock.elapsed(started), Duration::from_millis(1_500));
+
+    clock.set_now_ms(START_MS);
+    assert_eq!(clock.now_ms(), START_MS);
+    assert_eq!(clock.elapsed(started), Duration::from_millis(1_500));
+}
+
+#[test]
+fn system_clock_reads_the_current_time() {
+    let clock = SystemClock;
+    assert!(clock.now_ms() > START_MS);
+    let started = clock.instant();
+    assert!(clock.instant() >= started);
+}
+
+#[tokio::test]
+async fn cache_ttl_follows_the_clock() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route(100, Duration::from_secs(60)))
+        .mount()
+        .await;
+    let clock = MockClock::new(START_MS);
+    let client = server
+        .client_builder()
+        .clock(Arc::new(clock.clone()))
+        .build()
+        .unwrap();
+    let cache = QuoteCache::new(client, Duration::from_secs(10));
+
+    cache.quote(&request()).await.unwrap();
+    clock.advance(Duration::from_secs(10));
+    let cached = cache.quote(&request()).await.unwrap();
+    assert_eq!(cached.age, Duration::from_secs(10));
+    assert_eq!(server.received_request_count().await, 1);
+
+    clock.advance(Duration::from_millis(1));
+    let refetched = cache.quote(&request()).await.unwrap();
+    assert_eq!(refetched.age, Duration::ZERO);
+    assert_eq!(server.received_request_count().await, 2);
+}
+
+#[tokio::test]
+async fn cache_never_serves_a_quote_past_its_expiry() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route(100, Duration::from_secs(5)))
+        .mount()
+        .await;
+    let clock = MockClock::new(START_MS);
+    let client = server
+        .client_builder()
+        .clock(Arc::new(clock.clone()))
+        .build()
+        .unwrap();
+    let cache = QuoteCache::new(client, Duration::from_secs(60)).stale_while_revalidate(true);
+
+    cache.quote(&request()).await.unwrap();
+    clock.advance(Duration::from_millis(4_999));
+    cache.quote(&request()).await.unwrap();
+    assert_eq!(server.received_request_count().await, 1);
+
+    clock.advance(Duration::from_millis(1));
+    cache.quote(&request()).await.unwrap();
+    assert_eq!(server.received_request_count().await, 2);
+}
+
+#[tokio::test]
+async fn swap_expiry_is_checked_against_the_clock() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route(100, Duration::from_secs(3)))
+        .mount()
+        .await;
+    let clock = MockClock::new(START_MS);
+    let client = server
+        .client_builder()
+        .clock(Arc::new(clock.clone()))
+        .execution_budget(ExecutionBudget {
+            build_ms: 500,
+            send_ms: 1_000,
+            safety_margin_ms: 500,
+        })
+        .build()
+        .unwrap();
+    let quote = client.quote(&request()).await.unwrap();
+
+    clock.advance(Duration::from_secs(1));
+    assert!(client.swap(&quote).is_ok());
+
+    clock.advance(Duration::from_millis(1));
+    let err = client.swap(&quote).unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::QuoteExpired {
+            reason: ExpiryReason::InsufficientMargin,
+            remaining_ms: 1_999,
+            budget_ms: 2_000,
+        }
+    ));
+
+    clock.advance(Duration::from_millis(1_999));
+    let err = client.swap(&quote).unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::QuoteExpired {
+            reason: ExpiryReason::Expired,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn measured_latencies_come_from_the_clock() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route(100, Duration::from_secs(60)))
+        .mount()
+        .await;
+    let clock = MockClock::new(START_MS);
+    let client = server
+        .client_builder()
+        .clock(Arc::new(clock.clone()))
+        .estimated_execution_budget(ExecutionBudget {
+            build_ms: 5_000,
+            send_ms: 5_000,
+            safety_margin_ms: 0,
+        })
+        .build()
+        .unwrap();
+
+    let quote = client.quote(&request()).await.unwrap();
+    assert_eq!(quote.timing.unwrap().total_ms, 0);
+    client.swap(&quote).unwrap();
+    assert_eq!(client.execution_budget().unwrap().build_ms, 0);
+}
+
+#[tokio::test]
+async fn circuit_breaker_reopens_after_the_clock_passes_the_cooldown() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_status(503).times(1).mount().await;
+    server
+        .respond_with_route(route(100, Duration::from_secs(60)))
+        .mount()
+        .await;
+    let clock = MockClock::new(START_MS);
+    let client = server
+        .client_builder()
+        .clock(Arc::new(clock.clone()))
+        .circuit_breaker(1, Duration::from_secs(30))
+        .build()
+        .unwrap();
+
+    assert!(client.quote(&request()).await.is_err());
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+
+    clock.advance(Duration::from_secs(29));
+    let err = client.quote(&request()).await.unwrap_err();
+    assert!(matches!(err, ClientError::CircuitOpen { .. }));
+
+    clock.advance(Duration::from_secs(1));
+    client.quote(&request()).await.unwrap();
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+}
+
+#[tokio::test]
+async fn token_registry_ttl_follows_the_clock() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_tokens(&[TokenInfo {
+            mint: SOL_MINT,
+            symbol: "SOL".to_string(),
+            name: "Wrapped SOL".to_string(),
+            decimals: 9,
+            tags: vec![],
+        }])
+        .mount()
+        .await;
+    let clock = MockClock::new(START_MS);
+    let client = server
+        .client_builder()
+        .clock(Arc::new(clock.clone()))
+        .build()
+        .unwrap();
+    let registry = TokenRegistry::new(client, Duration::from_secs(60));
+
+    registry.refresh_if_stale().await.unwrap();
+    clock.advance(Duration::from_millis(59_999));
+    registry.refresh_if_stale().await.unwrap();
+    assert_eq!(server.received_paths().await.len(), 1);
+
+    clock.advance(Duration::from_millis(1));
+    registry.refresh_if_stale().await.unwrap();
+    assert_eq!(server.received_paths().await.len(), 2);
+}
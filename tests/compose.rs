@@ -0,0 +1,162 @@
+use solana_sdk::{compute_budget::ComputeBudgetInstruction, pubkey::Pubkey};
+use std::collections::HashSet;
+use titan_swap_api_client::{
+    compose::{compose_swaps, compose_swaps_with, ComposeError, ComposeOptions},
+    constants::COMPUTE_BUDGET_PROGRAM_ID,
+    decode_swap_quotes,
+    quote::{QuoteRequest, QuoteResponse},
+    swap::SwapResponse,
+    TitanClient,
+};
+
+const SINGLE_HOP: &[u8] = include_bytes!("fixtures/single_hop.msgpack");
+const MULTI_HOP_SPLIT: &[u8] = include_bytes!("fixtures/multi_hop_split.msgpack");
+const PREBUILT_TRANSACTION: &[u8] = include_bytes!("fixtures/prebuilt_transaction.msgpack");
+
+fn swap(fixture: &[u8]) -> SwapResponse {
+    let quotes = decode_swap_quotes(fixture).unwrap();
+    let route = quotes.quotes.into_values().next().unwrap();
+    let request = QuoteRequest {
+        user_pubkey: Some(Pubkey::new_unique()),
+        ..Default::default()
+    };
+    let quote = QuoteResponse::from_route(&request, route);
+    TitanClient::new(String::new(), None).swap(&quote).unwrap()
+}
+
+fn writable_non_signers(swap: &SwapResponse) -> HashSet<Pubkey> {
+    swap.instructions
+        .iter()
+        .flat_map(|ix| &ix.accounts)
+        .filter(|meta| meta.is_writable && !meta.is_signer)
+        .map(|meta| meta.pubkey)
+        .collect()
+}
+
+#[test]
+fn composes_two_fixture_routes() {
+    let (single, multi) = (swap(SINGLE_HOP), swap(MULTI_HOP_SPLIT));
+
+    let composed = compose_swaps(&[single.clone(), multi.clone()]).unwrap();
+    let swap = &composed.swap;
+
+    // One budget for both, in place of each swap's own.
+    assert_eq!(swap.compute_unit_limit, Some(700_000));
+    assert_eq!(
+        swap.instructions[0],
+        ComputeBudgetInstruction::set_compute_unit_limit(700_000)
+    );
+    assert_eq!(
+        swap.instructions
+            .iter()
+            .filter(|ix| ix.program_id == COMPUTE_BUDGET_PROGRAM_ID)
+            .count(),
+        1
+    );
+    assert_eq!(composed.instruction_ranges, vec![1..2, 2..4]);
+    assert_eq!(swap.instructions[1], single.instructions[1]);
+    assert_eq!(swap.instructions[2..4], multi.instructions[1..3]);
+
+    // The shared lookup table appears once.
+    assert_eq!(
+        swap.address_lookup_table_addresses,
+        multi.address_lookup_table_addresses
+    );
+    assert!(swap
+        .address_lookup_table_addresses
+        .contains(&single.address_lookup_table_addresses[0]));
+
+    assert_eq!(swap.expires_at_ms, single.expires_at_ms);
+    assert_eq!(swap.expires_after_slot, single.expires_after_slot);
+    assert!(composed.account_count <= 64);
+    assert!(composed.estimated_size <= 1232);
+}
+
+#[test]
+fn keeps_the_highest_compute_unit_price() {
+    let mut single = swap(SINGLE_HOP);
+    let mut multi = swap(MULTI_HOP_SPLIT);
+    single
+        .instructions
+        .push(ComputeBudgetInstruction::set_compute_unit_price(1_000));
+    multi
+        .instructions
+        .push(ComputeBudgetInstruction::set_compute_unit_price(5_000));
+
+    let composed = compose_swaps(&[single, multi]).unwrap();
+
+    assert_eq!(
+        composed.swap.instructions[1],
+        ComputeBudgetInstruction::set_compute_unit_price(5_000)
+    );
+}
+
+#[test]
+fn shared_writable_accounts_conflict() {
+    let (single, prebuilt) = (swap(SINGLE_HOP), swap(PREBUILT_TRANSACTION));
+
+    let err = compose_swaps(&[single.clone(), prebuilt.clone()]).unwrap_err();
+    assert!(matches!(
+        err,
+        ComposeError::WritableConflict { account, first: 0, second: 1 }
+            if writable_non_signers(&single).contains(&account)
+    ));
+
+    let options = ComposeOptions {
+        shared_writable: writable_non_signers(&single),
+        ..Default::default()
+    };
+    assert!(compose_swaps_with(&[single, prebuilt], &options).is_ok());
+}
+
+#[test]
+fn limits_are_enforced() {
+    let swaps = [swap(SINGLE_HOP), swap(MULTI_HOP_SPLIT)];
+
+    let few_accounts = ComposeOptions {
+        max_accounts: 5,
+        ..Default::default()
+    };
+    assert!(matches!(
+        compose_swaps_with(&swaps, &few_accounts),
+        Err(ComposeError::TooManyAccounts { limit: 5, .. })
+    ));
+
+    let small = ComposeOptions {
+        max_transaction_size: 100,
+        ..Default::default()
+    };
+    assert!(matches!(
+        compose_swaps_with(&swaps, &small),
+        Err(ComposeError::TransactionTooLarge { limit: 100, .. })
+    ));
+
+    let mut heavy = swaps.clone();
+    for swap in &mut heavy {
+        swap.compute_unit_limit = Some(1_000_000);
+    }
+    assert_eq!(
+        compose_swaps(&heavy).unwrap_err(),
+        ComposeError::ComputeUnitsExceeded { units: 2_000_000 }
+    );
+}
+
+#[test]
+fn unknown_compute_units_stay_unknown() {
+    let mut single = swap(SINGLE_HOP);
+    single.compute_unit_limit = None;
+
+    let composed = compose_swaps(&[single, swap(MULTI_HOP_SPLIT)]).unwrap();
+
+    assert_eq!(composed.swap.compute_unit_limit, None);
+    assert!(composed
+        .swap
+        .instructions
+        .iter()
+        .all(|ix| ix.program_id != COMPUTE_BUDGET_PROGRAM_ID));
+}
+
+#[test]
+fn nothing_to_compose() {
+    assert_eq!(compose_swaps(&[]).unwrap_err(), ComposeError::Empty);
+}
@@ -0,0 +1,146 @@
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, pubkey, pubkey::Pubkey,
+};
+use solana_system_interface::instruction as system_instruction;
+use titan_swap_api_client::{
+    compose::compose_swaps,
+    constants::COMPUTE_BUDGET_PROGRAM_ID,
+    inspect::{classify_instructions, InstructionKind},
+    swap::{ComputeBudgetSummary, SwapResponse},
+};
+
+const TITAN_ROUTE_WITH_TIP: &str = include_str!("fixtures/swap_titan_route_with_tip.json");
+const SWAP_INSTRUCTIONS: &[u8] = include_bytes!("fixtures/swap_instructions_response.msgpack");
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+const JITO_TIP: Pubkey = pubkey!("96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5");
+const OTHER_JITO_TIP: Pubkey = pubkey!("HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe");
+
+fn with_budget() -> SwapResponse {
+    SwapResponse::from_json(TITAN_ROUTE_WITH_TIP).unwrap()
+}
+
+fn without_budget() -> SwapResponse {
+    SwapResponse::from_bytes(SWAP_INSTRUCTIONS).unwrap()
+}
+
+fn count(swap: &SwapResponse, matches: fn(&InstructionKind) -> bool) -> usize {
+    classify_instructions(swap)
+        .iter()
+        .filter(|kind| matches(kind))
+        .count()
+}
+
+fn is_limit(kind: &InstructionKind) -> bool {
+    matches!(kind, InstructionKind::SetComputeUnitLimit { .. })
+}
+
+fn is_price(kind: &InstructionKind) -> bool {
+    matches!(kind, InstructionKind::SetComputeUnitPrice { .. })
+}
+
+#[test]
+fn detects_the_routes_own_budget_and_tip() {
+    let swap = with_budget();
+    assert_eq!(
+        swap.existing_compute_budget(),
+        Some(ComputeBudgetSummary {
+            unit_limit: Some(240_000),
+            unit_price_micro_lamports: Some(50_000),
+            instruction_indices: vec![0, 1],
+        })
+    );
+    assert_eq!(swap.existing_tips(), [(JITO_TIP, 10_000)]);
+
+    let swap = without_budget();
+    assert_eq!(swap.existing_compute_budget(), None);
+    assert!(swap.existing_tips().is_empty());
+}
+
+#[test]
+fn unrecognized_budget_instructions_are_indexed() {
+    let mut swap = without_budget();
+    // RequestHeapFrame.
+    swap.instructions.push(Instruction::new_with_bytes(
+        COMPUTE_BUDGET_PROGRAM_ID,
+        &[1, 0, 0, 4, 0],
+        vec![],
+    ));
+
+    let budget = swap.existing_compute_budget().unwrap();
+    assert_eq!(budget.unit_limit, None);
+    assert_eq!(budget.unit_price_micro_lamports, None);
+    assert_eq!(budget.instruction_indices, [swap.instructions.len() - 1]);
+}
+
+#[test]
+fn setting_the_budget_replaces_the_routes_own() {
+    let mut swap = with_budget();
+    let len = swap.instructions.len();
+    swap.set_compute_unit_limit(300_000);
+    swap.set_compute_unit_price(75_000);
+
+    assert_eq!(swap.instructions.len(), len);
+    assert_eq!(
+        swap.instructions[0],
+        ComputeBudgetInstruction::set_compute_unit_limit(300_000)
+    );
+    assert_eq!(
+        swap.instructions[1],
+        ComputeBudgetInstruction::set_compute_unit_price(75_000)
+    );
+    assert_eq!(swap.compute_unit_limit, Some(300_000));
+}
+
+#[test]
+fn setting_the_budget_prepends_when_the_route_has_none() {
+    let mut swap = without_budget();
+    let route = swap.instructions.clone();
+    swap.set_compute_unit_limit(300_000);
+    swap.set_compute_unit_price(75_000);
+    swap.set_compute_unit_price(80_000);
+
+    assert_eq!(count(&swap, is_limit), 1);
+    assert_eq!(count(&swap, is_price), 1);
+    assert_eq!(swap.instructions[2..], route[..]);
+    assert_eq!(
+        swap.existing_compute_budget()
+            .unwrap()
+            .unit_price_micro_lamports,
+        Some(80_000)
+    );
+}
+
+#[test]
+fn setting_a_tip_rewrites_the_routes_own() {
+    let mut swap = with_budget();
+    swap.instructions
+        .push(system_instruction::transfer(&USER, &OTHER_JITO_TIP, 5_000));
+    let len = swap.instructions.len();
+    swap.set_tip(USER, OTHER_JITO_TIP, 25_000);
+
+    assert_eq!(swap.existing_tips(), [(JITO_TIP, 25_000)]);
+    assert_eq!(swap.instructions.len(), len - 1);
+}
+
+#[test]
+fn setting_a_tip_appends_when_the_route_has_none() {
+    let mut swap = without_budget();
+    swap.set_tip(USER, JITO_TIP, 25_000);
+
+    assert_eq!(swap.existing_tips(), [(JITO_TIP, 25_000)]);
+    assert_eq!(
+        swap.instructions.last(),
+        Some(&system_instruction::transfer(&USER, &JITO_TIP, 25_000))
+    );
+}
+
+#[test]
+fn compose_falls_back_to_the_routes_own_limit() {
+    let mut swap = with_budget();
+    swap.compute_unit_limit = None;
+
+    let composed = compose_swaps(&[swap]).unwrap();
+    assert_eq!(composed.swap.compute_unit_limit, Some(240_000));
+    assert_eq!(count(&composed.swap, is_limit), 1);
+}
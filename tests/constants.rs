@@ -0,0 +1,114 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use titan_swap_api_client::{
+    constants::{
+        is_native_sol, token_program_for, COMPUTE_BUDGET_PROGRAM_ID, SOL_MINT, SYSTEM_PROGRAM_ID,
+        TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, USDC_MINT, WSOL_MINT,
+    },
+    security::default_program_allowlist,
+    venues::{Dex, WHIRLPOOL_PROGRAM_ID},
+    ClientError,
+};
+use wiremock::{
+    matchers::{body_partial_json, method},
+    Mock, MockServer, ResponseTemplate,
+};
+
+async fn rpc_with_mint_owner(owner: Pubkey) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(
+            serde_json::json!({ "method": "getAccountInfo" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": {
+                "context": { "slot": 1 },
+                "value": {
+                    "data": ["", "base64"],
+                    "executable": false,
+                    "lamports": 1_461_600,
+                    "owner": owner.to_string(),
+                    "rentEpoch": 0,
+                    "space": 0
+                }
+            },
+            "id": 1
+        })))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[test]
+fn native_sol_is_the_wrapped_mint() {
+    assert_eq!(SOL_MINT, WSOL_MINT);
+    assert!(is_native_sol(&WSOL_MINT));
+    assert!(!is_native_sol(&USDC_MINT));
+}
+
+#[test]
+fn allowlist_includes_runtime_programs() {
+    let allowlist = default_program_allowlist();
+
+    assert!(allowlist.contains(&COMPUTE_BUDGET_PROGRAM_ID));
+    assert!(allowlist.contains(&TOKEN_PROGRAM_ID));
+    assert!(allowlist.contains(&TOKEN_2022_PROGRAM_ID));
+    assert!(!allowlist.contains(&SYSTEM_PROGRAM_ID));
+}
+
+#[test]
+fn allowlist_includes_every_dex() {
+    let allowlist = default_program_allowlist();
+
+    for dex in Dex::ALL {
+        assert!(allowlist.contains(&dex.program_id()), "{dex:?}");
+    }
+    assert_eq!(Dex::Whirlpool.program_id(), WHIRLPOOL_PROGRAM_ID);
+    assert_eq!(
+        WHIRLPOOL_PROGRAM_ID.to_string(),
+        "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"
+    );
+}
+
+#[tokio::test]
+async fn token_program_is_the_mint_owner() {
+    let server = rpc_with_mint_owner(TOKEN_2022_PROGRAM_ID).await;
+    let rpc = RpcClient::new(server.uri());
+
+    let program = token_program_for(&Pubkey::new_unique(), &rpc)
+        .await
+        .unwrap();
+
+    assert_eq!(program, TOKEN_2022_PROGRAM_ID);
+}
+
+#[tokio::test]
+async fn wrapped_sol_needs_no_lookup() {
+    let server = MockServer::start().await;
+    let rpc = RpcClient::new(server.uri());
+
+    assert_eq!(
+        token_program_for(&WSOL_MINT, &rpc).await.unwrap(),
+        TOKEN_PROGRAM_ID
+    );
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn non_token_owner_is_rejected() {
+    let server = rpc_with_mint_owner(Pubkey::new_unique()).await;
+    let rpc = RpcClient::new(server.uri());
+
+    let err = token_program_for(&Pubkey::new_unique(), &rpc)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        ClientError::InvalidResponse {
+            field: "mint owner",
+            ..
+        }
+    ));
+}
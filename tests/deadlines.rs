@@ -0,0 +1,128 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use titan_swap_api_client::{
+    options::QuoteRequestOptions,
+    quote::QuoteRequest,
+    test_utils::{MockClock, MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+#[tokio::test]
+async fn timeout_fails_slow_quotes() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_delay(SwapRouteBuilder::default(), Duration::from_secs(2))
+        .mount()
+        .await;
+
+    let started = Instant::now();
+    let err = server
+        .client()
+        .quote_with_options(
+            &QuoteRequest::default(),
+            &QuoteRequestOptions::with_timeout(Duration::from_millis(100)),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, ClientError::DeadlineExceeded), "{err:?}");
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn timeouts_run_on_real_time_whatever_the_clock() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_delay(SwapRouteBuilder::default(), Duration::from_secs(2))
+        .mount()
+        .await;
+    let clock = MockClock::new(1_000_000);
+    clock.advance(Duration::from_secs(3_600));
+    let client = server
+        .client_builder()
+        .clock(Arc::new(clock))
+        .build()
+        .unwrap();
+
+    let started = Instant::now();
+    let err = client
+        .quote_with_options(
+            &QuoteRequest::default(),
+            &QuoteRequestOptions::with_timeout(Duration::from_millis(100)),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, ClientError::DeadlineExceeded), "{err:?}");
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn fast_quotes_finish_within_the_timeout() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default().amounts(1_000, 990))
+        .mount()
+        .await;
+
+    let quote = server
+        .client()
+        .quote_with_options(
+            &QuoteRequest::default(),
+            &QuoteRequestOptions::with_timeout(Duration::from_secs(5)),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(quote.out_amount, 990);
+}
+
+#[tokio::test]
+async fn deadline_covers_hedged_requests() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_delay(SwapRouteBuilder::default(), Duration::from_secs(2))
+        .mount()
+        .await;
+    let client = server
+        .client_builder()
+        .hedge_after(Duration::from_millis(50))
+        .max_hedge_ratio(1.0)
+        .build()
+        .unwrap();
+
+    let started = Instant::now();
+    let err = client
+        .quote_with_options(
+            &QuoteRequest::default(),
+            &QuoteRequestOptions::with_deadline(started + Duration::from_millis(200)),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, ClientError::DeadlineExceeded), "{err:?}");
+    assert!(started.elapsed() < Duration::from_secs(1));
+    assert_eq!(server.received_request_count().await, 2);
+}
+
+#[tokio::test]
+async fn earlier_of_timeout_and_deadline_applies() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_delay(SwapRouteBuilder::default(), Duration::from_millis(300))
+        .mount()
+        .await;
+
+    let options = QuoteRequestOptions {
+        timeout: Some(Duration::from_secs(5)),
+        deadline: Some(Instant::now() + Duration::from_millis(50)),
+        ..Default::default()
+    };
+    let err = server
+        .client()
+        .quote_with_options(&QuoteRequest::default(), &options)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, ClientError::DeadlineExceeded), "{err:?}");
+}
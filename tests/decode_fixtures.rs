@@ -0,0 +1,242 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{DecodeWarning, QuoteRequest, QuoteResponse, SwapMode, SwapQuotes},
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    ClientError, TitanClient,
+};
+
+const SINGLE_HOP: &[u8] = include_bytes!("fixtures/single_hop.msgpack");
+const MULTI_HOP_SPLIT: &[u8] = include_bytes!("fixtures/multi_hop_split.msgpack");
+const PLATFORM_FEE: &[u8] = include_bytes!("fixtures/platform_fee.msgpack");
+const PLATFORM_FEE_300BPS: &[u8] = include_bytes!("fixtures/platform_fee_300bps.msgpack");
+const PREBUILT_TRANSACTION: &[u8] = include_bytes!("fixtures/prebuilt_transaction.msgpack");
+const ERROR_NO_ROUTES: &str = include_str!("fixtures/error_no_routes.txt");
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn request(amount: u64, swap_mode: SwapMode) -> QuoteRequest {
+    QuoteRequest {
+        amount,
+        user_pubkey: Some(USER),
+        swap_mode: Some(swap_mode),
+        slippage_bps: 50,
+        ..sample_request()
+    }
+}
+
+async fn quote_fixture(body: &[u8], request: &QuoteRequest) -> QuoteResponse {
+    let server = MockTitanServer::start().await;
+    server.respond_with_body(body).mount().await;
+    server.client().quote(request).await.unwrap()
+}
+
+#[test]
+fn decodes_every_fixture() {
+    for fixture in [
+        SINGLE_HOP,
+        MULTI_HOP_SPLIT,
+        PLATFORM_FEE,
+        PLATFORM_FEE_300BPS,
+        PREBUILT_TRANSACTION,
+    ] {
+        let quotes: SwapQuotes = rmp_serde::from_slice(fixture).unwrap();
+        assert_eq!(quotes.quotes.len(), 1);
+        assert_eq!(Pubkey::from(quotes.input_mint), SOL_MINT);
+        assert_eq!(Pubkey::from(quotes.output_mint), USDC_MINT);
+    }
+}
+
+#[tokio::test]
+async fn single_hop() {
+    let quote = quote_fixture(SINGLE_HOP, &request(1_000_000_000, SwapMode::ExactIn)).await;
+
+    assert_eq!(quote.out_amount, 187_654_321);
+    assert_eq!(quote.route_plan.len(), 1);
+    assert_eq!(quote.route_plan[0].swap_info.label, "Whirlpool");
+    assert_eq!(
+        quote.route_plan[0].swap_info.context_slot,
+        Some(312_000_000)
+    );
+    assert_eq!(quote.route_data().unwrap().address_lookup_tables.len(), 1);
+    assert_eq!(quote.timing.unwrap().server_ns, Some(2_100_000));
+
+    let swap = TitanClient::new(String::new(), None).swap(&quote).unwrap();
+    assert_eq!(swap.instructions.len(), 2);
+    assert_eq!(swap.compute_unit_limit, Some(180_000));
+    assert_eq!(swap.compute_units_safe, Some(240_000));
+    assert_eq!(swap.expires_at_ms, Some(1_760_000_000_000));
+    assert_eq!(swap.expires_after_slot, Some(312_000_150));
+}
+
+#[tokio::test]
+async fn multi_hop_split() {
+    let quote = quote_fixture(MULTI_HOP_SPLIT, &request(5_000_000_000, SwapMode::ExactIn)).await;
+
+    assert_eq!(quote.out_amount, 938_100_000);
+    assert_eq!(quote.slippage_bps, 100);
+    assert_eq!(quote.route_plan.len(), 3);
+    assert_eq!(quote.route_plan[0].swap_info.alloc_ppb, 600_000_000);
+    assert_eq!(quote.route_plan[1].swap_info.alloc_ppb, 400_000_000);
+    assert_eq!(quote.route_plan[0].swap_info.fee_mint, Some(SOL_MINT));
+    assert_eq!(quote.route_plan[0].swap_info.fee_amount, Some(900_000));
+    // Steps without their own slot leave it unset rather than inheriting the
+    // route's.
+    assert_eq!(quote.route_plan[0].swap_info.context_slot, None);
+    assert_eq!(
+        quote.route_plan[2].swap_info.context_slot,
+        Some(312_000_001)
+    );
+    assert_eq!(quote.context_slot, Some(312_000_002));
+    assert_eq!(quote.oldest_context_slot(), Some(312_000_001));
+    assert_eq!(quote.newest_context_slot(), Some(312_000_001));
+    assert_eq!(quote.route_data().unwrap().address_lookup_tables.len(), 3);
+    assert_eq!(
+        quote.route_data().unwrap().expires_after_slot,
+        Some(312_000_160)
+    );
+}
+
+fn step_warning(step: usize, field: &str, message: &str) -> DecodeWarning {
+    DecodeWarning {
+        field: field.to_string(),
+        message: message.to_string(),
+        step: Some(step),
+    }
+}
+
+#[tokio::test]
+async fn missing_step_fields_are_warned_about() {
+    let quote = quote_fixture(SINGLE_HOP, &request(1_000_000_000, SwapMode::ExactIn)).await;
+    assert_eq!(
+        quote.warnings,
+        [step_warning(0, "feeMint", "missing; no fee mint reported")]
+    );
+
+    let quote = quote_fixture(MULTI_HOP_SPLIT, &request(5_000_000_000, SwapMode::ExactIn)).await;
+    assert_eq!(
+        quote.warnings,
+        [
+            step_warning(0, "contextSlot", "missing; not reported"),
+            step_warning(1, "contextSlot", "missing; not reported"),
+            step_warning(2, "feeMint", "missing; no fee mint reported"),
+        ]
+    );
+    assert!(quote.context_slot.is_some());
+    assert_eq!(quote.route_plan[0].swap_info.context_slot, None);
+
+    let json = serde_json::to_value(&quote).unwrap();
+    assert_eq!(
+        json["warnings"][2],
+        serde_json::json!({
+            "field": "feeMint",
+            "message": "missing; no fee mint reported",
+            "step": 2,
+        })
+    );
+}
+
+#[tokio::test]
+async fn zero_allocations_are_warned_about() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::new(SOL_MINT, USDC_MINT).step(
+            "Whirlpool",
+            Pubkey::new_unique(),
+            SOL_MINT,
+            USDC_MINT,
+            1,
+            1,
+            0,
+        ))
+        .mount()
+        .await;
+    let quote = server
+        .client()
+        .quote(&request(1_000_000_000, SwapMode::ExactIn))
+        .await
+        .unwrap();
+    assert_eq!(
+        quote.warnings,
+        [
+            step_warning(0, "feeMint", "missing; no fee mint reported"),
+            step_warning(0, "allocPpb", "zero; treated as the whole amount"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn platform_fee() {
+    let quote = quote_fixture(PLATFORM_FEE, &request(1_000_000_000, SwapMode::ExactIn)).await;
+
+    let platform_fee = quote.platform_fee.as_ref().unwrap();
+    assert_eq!(platform_fee.amount, 187_654);
+    assert_eq!(platform_fee.fee_bps, 10);
+    assert_eq!(quote.out_amount, 187_466_667);
+    assert!(quote.route_data().unwrap().address_lookup_tables.is_empty());
+    assert_eq!(quote.timing.unwrap().server_ns, None);
+
+    let swap = TitanClient::new(String::new(), None).swap(&quote).unwrap();
+    assert_eq!(swap.compute_units_safe, None);
+    assert_eq!(swap.expires_at_ms, Some(1_760_000_002_000));
+}
+
+#[tokio::test]
+async fn prebuilt_transaction() {
+    let quote = quote_fixture(
+        PREBUILT_TRANSACTION,
+        &request(1_000_000, SwapMode::ExactOut),
+    )
+    .await;
+
+    assert_eq!(quote.swap_mode, SwapMode::ExactOut);
+    assert_eq!(quote.out_amount, 1_000_000);
+    assert_eq!(quote.route_data().unwrap().in_amount, 5_340_000);
+    assert_eq!(
+        quote.route_data().unwrap().reference_id.as_deref(),
+        Some("ref-4f2a")
+    );
+    assert_eq!(
+        quote
+            .route_data()
+            .unwrap()
+            .transaction
+            .as_ref()
+            .map(Vec::len),
+        Some(257)
+    );
+    assert_eq!(quote.route_data().unwrap().address_lookup_tables.len(), 2);
+    assert_eq!(
+        quote.route_data().unwrap().expires_at_ms,
+        Some(1_760_000_003_000)
+    );
+}
+
+#[tokio::test]
+async fn no_routes_error_body() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_error(404, ERROR_NO_ROUTES)
+        .mount()
+        .await;
+    let err = server
+        .client()
+        .quote(&request(1_000_000_000, SwapMode::ExactIn))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ClientError::NoRoutesAvailable));
+}
+
+#[tokio::test]
+async fn server_error_body() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_error(500, "internal").mount().await;
+    let err = server
+        .client()
+        .quote(&request(1_000_000_000, SwapMode::ExactIn))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, ClientError::RequestFailed { status, ref body, .. } if status == 500 && body == "internal")
+    );
+}
@@ -0,0 +1,57 @@
+use std::collections::{BTreeSet, HashMap};
+use std::hash::Hash;
+use titan_swap_api_client::{
+    pubkey::Pubkey,
+    quote::{
+        DecodeWarning, PlatformFee, Provider, QuoteRequest, RoutePlanStep, SwapInfo, SwapMode,
+    },
+};
+
+fn assert_eq<T: Eq>() {}
+fn assert_hash<T: Eq + Hash>() {}
+fn assert_ord<T: Ord>() {}
+
+#[test]
+fn quote_types_have_their_derives() {
+    assert_hash::<SwapMode>();
+    assert_hash::<SwapInfo>();
+    assert_hash::<RoutePlanStep>();
+    assert_hash::<PlatformFee>();
+    assert_hash::<DecodeWarning>();
+    assert_hash::<Provider>();
+    assert_ord::<SwapInfo>();
+    assert_ord::<RoutePlanStep>();
+    assert_eq::<QuoteRequest>();
+}
+
+#[test]
+fn pairs_key_a_hash_map_by_swap_mode() {
+    let (sol, usdc) = (
+        Pubkey::new_from_array([1; 32]),
+        Pubkey::new_from_array([2; 32]),
+    );
+    let mut quotes = HashMap::new();
+    quotes.insert((sol, usdc, SwapMode::ExactIn), 1);
+    quotes.insert((sol, usdc, SwapMode::ExactOut), 2);
+    quotes.insert((sol, usdc, SwapMode::Other("Split".to_string())), 3);
+    assert_eq!(quotes[&(sol, usdc, SwapMode::ExactOut)], 2);
+    assert_eq!(quotes.len(), 3);
+}
+
+#[test]
+fn route_steps_sort() {
+    let step = |amm_key: u8, percent: u8| RoutePlanStep {
+        swap_info: SwapInfo {
+            amm_key: Pubkey::new_from_array([amm_key; 32]),
+            ..Default::default()
+        },
+        percent,
+    };
+    let steps: BTreeSet<_> = [step(2, 100), step(1, 40), step(1, 60), step(1, 40)]
+        .into_iter()
+        .collect();
+    assert_eq!(
+        steps.into_iter().collect::<Vec<_>>(),
+        vec![step(1, 40), step(1, 60), step(2, 100)]
+    );
+}
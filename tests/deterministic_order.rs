@@ -0,0 +1,105 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{QuoteRequest, SwapQuotes},
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+};
+
+const BONK: Pubkey = pubkey!("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263");
+
+const FIXTURES: [&[u8]; 3] = [
+    include_bytes!("fixtures/single_hop.msgpack"),
+    include_bytes!("fixtures/multi_hop_split.msgpack"),
+    include_bytes!("fixtures/swap_quotes_large.msgpack"),
+];
+
+const PROVIDERS: [&str; 5] = ["Titan", "Okx", "Jupiter", "DFlow", "Hashflow"];
+
+fn route(provider: &str, out_amount: u64) -> SwapRouteBuilder {
+    SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+        .provider(provider)
+        .amounts(1_000_000_000, out_amount)
+        .step(
+            "Whirlpool",
+            Pubkey::new_unique(),
+            SOL_MINT,
+            BONK,
+            1_000_000_000,
+            9_000_000_000,
+            1_000_000_000,
+        )
+        .step(
+            "Phoenix",
+            Pubkey::new_unique(),
+            BONK,
+            USDC_MINT,
+            9_000_000_000,
+            out_amount,
+            1_000_000_000,
+        )
+}
+
+#[test]
+fn decoding_a_fixture_twice_serializes_identically() {
+    for fixture in FIXTURES {
+        let first: SwapQuotes = rmp_serde::from_slice(fixture).unwrap();
+        let second: SwapQuotes = rmp_serde::from_slice(fixture).unwrap();
+
+        assert_eq!(
+            serde_json::to_vec(&first).unwrap(),
+            serde_json::to_vec(&second).unwrap()
+        );
+        assert_eq!(
+            rmp_serde::to_vec_named(&first).unwrap(),
+            rmp_serde::to_vec_named(&second).unwrap()
+        );
+    }
+}
+
+#[tokio::test]
+async fn quote_all_is_ordered_by_provider() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_routes(
+            PROVIDERS
+                .iter()
+                .zip(1..)
+                .map(|(provider, n)| route(provider, 186_000_000 + n)),
+        )
+        .mount()
+        .await;
+    let client = server.client();
+
+    let first = client.quote_all(&QuoteRequest::default()).await.unwrap();
+    let second = client.quote_all(&QuoteRequest::default()).await.unwrap();
+
+    assert_eq!(
+        first.keys().collect::<Vec<_>>(),
+        ["DFlow", "Hashflow", "Jupiter", "Okx", "Titan"]
+    );
+    assert_eq!(
+        serde_json::to_vec(&first).unwrap(),
+        serde_json::to_vec(&second).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn route_plan_is_in_execution_order() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route("Titan", 186_000_000))
+        .mount()
+        .await;
+
+    let quote = server
+        .client()
+        .quote(&QuoteRequest::default())
+        .await
+        .unwrap();
+    let hops: Vec<_> = quote
+        .route_plan
+        .iter()
+        .map(|step| (step.swap_info.input_mint, step.swap_info.output_mint))
+        .collect();
+    assert_eq!(hops, [(SOL_MINT, BONK), (BONK, USDC_MINT)]);
+}
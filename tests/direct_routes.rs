@@ -0,0 +1,130 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::QuoteRequest,
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+const BONK: Pubkey = pubkey!("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263");
+
+fn request(only_direct_routes: Option<bool>) -> QuoteRequest {
+    QuoteRequest {
+        only_direct_routes,
+        ..sample_request()
+    }
+}
+
+fn direct(provider: &str) -> SwapRouteBuilder {
+    SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+        .provider(provider)
+        .amounts(1_000_000_000, 186_000_000)
+        .step(
+            "Whirlpool",
+            Pubkey::new_unique(),
+            SOL_MINT,
+            USDC_MINT,
+            600_000_000,
+            111_600_000,
+            600_000_000,
+        )
+        .step(
+            "Raydium",
+            Pubkey::new_unique(),
+            SOL_MINT,
+            USDC_MINT,
+            400_000_000,
+            74_400_000,
+            400_000_000,
+        )
+}
+
+fn two_hop(provider: &str) -> SwapRouteBuilder {
+    SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+        .provider(provider)
+        .amounts(1_000_000_000, 187_000_000)
+        .step(
+            "Whirlpool",
+            Pubkey::new_unique(),
+            SOL_MINT,
+            BONK,
+            1_000_000_000,
+            9_000_000_000,
+            1_000_000_000,
+        )
+        .step(
+            "Phoenix",
+            Pubkey::new_unique(),
+            BONK,
+            USDC_MINT,
+            9_000_000_000,
+            187_000_000,
+            1_000_000_000,
+        )
+}
+
+#[tokio::test]
+async fn honored_flag_keeps_direct_routes() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_route(direct("Titan")).mount().await;
+
+    let quote = server.client().quote(&request(Some(true))).await.unwrap();
+    // A split across two pools of the pair is still direct.
+    assert_eq!(quote.route_plan.len(), 2);
+    assert_eq!(quote.out_amount, 186_000_000);
+}
+
+#[tokio::test]
+async fn violating_routes_are_discarded() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_routes([two_hop("Titan"), direct("Other")])
+        .mount()
+        .await;
+    let client = server.client();
+
+    let quote = client.quote(&request(Some(true))).await.unwrap();
+    assert_eq!(quote.provider(), Some("Other"));
+    assert!(quote.route_plan.iter().all(
+        |step| step.swap_info.input_mint == SOL_MINT && step.swap_info.output_mint == USDC_MINT
+    ));
+
+    let quotes = client.quote_all(&request(Some(true))).await.unwrap();
+    assert_eq!(quotes.keys().collect::<Vec<_>>(), ["Other"]);
+}
+
+#[tokio::test]
+async fn all_routes_filtered_is_no_routes() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_route(two_hop("Titan")).mount().await;
+    let client = server.client();
+
+    assert!(matches!(
+        client.quote(&request(Some(true))).await,
+        Err(ClientError::NoRoutesAvailable)
+    ));
+    assert!(matches!(
+        client.quote_all(&request(Some(true))).await,
+        Err(ClientError::NoRoutesAvailable)
+    ));
+
+    // Multi-hop routes are fine when direct routes weren't asked for.
+    for only_direct_routes in [None, Some(false)] {
+        let quote = client.quote(&request(only_direct_routes)).await.unwrap();
+        assert_eq!(quote.route_plan.len(), 2);
+    }
+}
+
+#[tokio::test]
+async fn enforcement_can_be_turned_off() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_route(two_hop("Titan")).mount().await;
+    let client = server
+        .client_builder()
+        .enforce_direct_routes(false)
+        .build()
+        .unwrap();
+
+    let quote = client.quote(&request(Some(true))).await.unwrap();
+    assert_eq!(quote.route_plan[0].swap_info.output_mint, BONK);
+}
@@ -0,0 +1,128 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    quote::QuoteRequest, test_utils::sample_request, ClientError, TitanClient,
+};
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey: Some(USER),
+        ..sample_request()
+    }
+}
+
+async fn html_error_server() -> MockServer {
+    let server = MockServer::start().await;
+    let page = format!(
+        "<html><body><h1>502 Bad Gateway</h1>{}</body></html>",
+        "<p>upstream</p>".repeat(100)
+    );
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500).set_body_raw(page, "text/html; charset=utf-8"))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn error_bodies_are_truncated_with_their_content_type() {
+    let server = html_error_server().await;
+    let client = TitanClient::builder("token")
+        .base_path(server.uri())
+        .error_body_limit(32)
+        .build()
+        .unwrap();
+
+    let err = client.quote(&request()).await.unwrap_err();
+    match &err {
+        ClientError::RequestFailed {
+            body,
+            content_type,
+            body_len,
+            ..
+        } => {
+            assert_eq!(body, "<html><body><h1>502 Bad Gateway<");
+            assert_eq!(content_type.as_deref(), Some("text/html"));
+            assert_eq!(*body_len, 1_550);
+        }
+        err => panic!("unexpected error {err:?}"),
+    }
+    // Pinned for log parsers.
+    assert_eq!(
+        err.to_string(),
+        "Request failed with status 500 Internal Server Error [text/html]: \
+         <html><body><h1>502 Bad Gateway<... (32 of 1550 bytes)"
+    );
+}
+
+#[tokio::test]
+async fn default_limit_keeps_short_bodies_whole() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503).set_body_bytes("overloaded"))
+        .mount(&server)
+        .await;
+    let client = TitanClient::builder("token")
+        .base_path(server.uri())
+        .build()
+        .unwrap();
+
+    let err = client.quote(&request()).await.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Request failed with status 503 Service Unavailable: overloaded"
+    );
+}
+
+#[tokio::test]
+async fn truncation_respects_char_boundaries() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("ééééé"))
+        .mount(&server)
+        .await;
+    let client = TitanClient::builder("token")
+        .base_path(server.uri())
+        .error_body_limit(5)
+        .build()
+        .unwrap();
+
+    let err = client.quote(&request()).await.unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::RequestFailed { ref body, body_len: 10, .. } if body == "éé"
+    ));
+}
+
+async fn closed_port() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    drop(listener);
+    format!("http://{address}")
+}
+
+#[tokio::test]
+async fn http_errors_redact_the_query_string() {
+    let client = TitanClient::builder("token")
+        .base_path(closed_port().await)
+        .build()
+        .unwrap();
+    let err = client.quote(&request()).await.unwrap_err();
+    assert!(matches!(err, ClientError::HttpError(_)), "{err:?}");
+    let message = format!("{err} {err:?}");
+    assert!(!message.contains(&USER.to_string()), "{message}");
+    assert!(message.contains("/quote/swap"), "{message}");
+}
+
+#[tokio::test]
+async fn verbose_errors_keep_the_query_string() {
+    let client = TitanClient::builder("token")
+        .base_path(closed_port().await)
+        .verbose_errors(true)
+        .build()
+        .unwrap();
+    let err = client.quote(&request()).await.unwrap_err();
+    assert!(err.to_string().contains(&USER.to_string()), "{err}");
+}
@@ -0,0 +1,164 @@
+use solana_sdk::pubkey::Pubkey;
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{QuoteRequest, SwapMode},
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+fn request(swap_mode: SwapMode) -> QuoteRequest {
+    QuoteRequest {
+        swap_mode: Some(swap_mode),
+        ..sample_request()
+    }
+}
+
+fn route(swap_mode: SwapMode, out_amount: u64) -> SwapRouteBuilder {
+    SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+        .swap_mode(swap_mode)
+        .amounts(5_400_000_000, out_amount)
+        .slippage_bps(100)
+        .step(
+            "Whirlpool",
+            Pubkey::new_unique(),
+            SOL_MINT,
+            USDC_MINT,
+            5_400_000_000,
+            out_amount,
+            1_000_000_000,
+        )
+}
+
+#[tokio::test]
+async fn matching_out_amount_is_accepted() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route(SwapMode::ExactOut, 1_000_000_000))
+        .mount()
+        .await;
+
+    let quote = server
+        .client()
+        .quote(&request(SwapMode::ExactOut))
+        .await
+        .unwrap();
+    assert_eq!(quote.out_amount, 1_000_000_000);
+    // The output is fixed, so only the input side carries slippage.
+    assert_eq!(quote.min_out_amount(), 1_000_000_000);
+    assert_eq!(quote.max_in_amount(), 5_454_000_000);
+}
+
+#[tokio::test]
+async fn dust_above_the_request_is_accepted() {
+    let server = MockTitanServer::start().await;
+    // 10 bps over, the default tolerance.
+    server
+        .respond_with_route(route(SwapMode::ExactOut, 1_001_000_000))
+        .mount()
+        .await;
+
+    let quote = server
+        .client()
+        .quote(&request(SwapMode::ExactOut))
+        .await
+        .unwrap();
+    assert_eq!(quote.out_amount, 1_001_000_000);
+}
+
+#[tokio::test]
+async fn slightly_short_is_a_mismatch() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route(SwapMode::ExactOut, 999_999_999))
+        .mount()
+        .await;
+
+    let err = server
+        .client()
+        .quote(&request(SwapMode::ExactOut))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::QuoteMismatch {
+            requested: 1_000_000_000,
+            out_amount: 999_999_999,
+        }
+    ));
+}
+
+#[tokio::test]
+async fn wildly_off_is_a_mismatch() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route(SwapMode::ExactOut, 1_500_000_000))
+        .times(1)
+        .mount()
+        .await;
+    server
+        .respond_with_route(route(SwapMode::ExactOut, 500_000))
+        .mount()
+        .await;
+    let client = server.client();
+
+    for out_amount in [1_500_000_000, 500_000] {
+        let err = client
+            .quote(&request(SwapMode::ExactOut))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ClientError::QuoteMismatch {
+                    requested: 1_000_000_000,
+                    out_amount: actual,
+                } if actual == out_amount
+            ),
+            "{err}"
+        );
+        assert!(err.to_string().contains("1000000000"), "{err}");
+    }
+}
+
+#[tokio::test]
+async fn tolerance_is_configurable() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route(SwapMode::ExactOut, 1_001_000_000))
+        .mount()
+        .await;
+
+    let strict = server
+        .client_builder()
+        .exact_out_tolerance_bps(0)
+        .build()
+        .unwrap();
+    assert!(matches!(
+        strict.quote(&request(SwapMode::ExactOut)).await,
+        Err(ClientError::QuoteMismatch { .. })
+    ));
+
+    let lenient = server
+        .client_builder()
+        .exact_out_tolerance_bps(50)
+        .build()
+        .unwrap();
+    assert!(lenient.quote(&request(SwapMode::ExactOut)).await.is_ok());
+}
+
+#[tokio::test]
+async fn exact_in_is_not_checked() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route(SwapMode::ExactIn, 186_000_000))
+        .mount()
+        .await;
+
+    let quote = server
+        .client()
+        .quote(&request(SwapMode::ExactIn))
+        .await
+        .unwrap();
+    assert_eq!(quote.min_out_amount(), 184_140_000);
+    assert_eq!(quote.max_in_amount(), 5_400_000_000);
+}
@@ -0,0 +1,108 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::QuoteRequest,
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+const BAD_POOL: Pubkey = pubkey!("Czfq3xZZDmsdGdUyrNLtRhGc47cXcZtLG4crryfu44zE");
+
+fn request(excluded_amm_keys: Option<Vec<Pubkey>>) -> QuoteRequest {
+    QuoteRequest {
+        excluded_amm_keys,
+        ..sample_request()
+    }
+}
+
+fn route(provider: &str, out_amount: u64, pools: &[Pubkey]) -> SwapRouteBuilder {
+    let share = 1_000_000_000 / pools.len() as u64;
+    pools.iter().fold(
+        SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+            .provider(provider)
+            .amounts(1_000_000_000, out_amount),
+        |route, &pool| {
+            route.step(
+                "Whirlpool",
+                pool,
+                SOL_MINT,
+                USDC_MINT,
+                share,
+                out_amount / pools.len() as u64,
+                share as u32,
+            )
+        },
+    )
+}
+
+#[tokio::test]
+async fn denied_best_route_falls_back_to_the_next() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_routes([
+            route("Alpha", 185_000_000, &[Pubkey::new_unique()]),
+            route("Beta", 186_000_000, &[Pubkey::new_unique()]),
+            route("Zeta", 187_000_000, &[Pubkey::new_unique(), BAD_POOL]),
+        ])
+        .mount()
+        .await;
+    let client = server.client();
+
+    let best = client.quote(&request(None)).await.unwrap();
+    assert_eq!(best.provider(), Some("Zeta"));
+
+    let quote = client.quote(&request(Some(vec![BAD_POOL]))).await.unwrap();
+    assert_eq!(quote.provider(), Some("Beta"));
+    assert_eq!(quote.out_amount, 186_000_000);
+
+    let quotes = client
+        .quote_all(&request(Some(vec![BAD_POOL])))
+        .await
+        .unwrap();
+    assert_eq!(quotes.keys().collect::<Vec<_>>(), ["Alpha", "Beta"]);
+}
+
+#[tokio::test]
+async fn every_route_denied_is_no_routes() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route("Alpha", 187_000_000, &[BAD_POOL]))
+        .mount()
+        .await;
+
+    assert!(matches!(
+        server.client().quote(&request(Some(vec![BAD_POOL]))).await,
+        Err(ClientError::NoRoutesAvailable)
+    ));
+}
+
+#[tokio::test]
+async fn not_sent_to_the_api() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route("Alpha", 187_000_000, &[Pubkey::new_unique()]))
+        .mount()
+        .await;
+
+    server
+        .client()
+        .quote(&request(Some(vec![BAD_POOL])))
+        .await
+        .unwrap();
+
+    let params = &server.received_query_params().await[0];
+    assert!(!params
+        .values()
+        .any(|value| value.contains(&BAD_POOL.to_string())));
+}
+
+#[test]
+fn serde_as_base58_strings() {
+    let request = request(Some(vec![BAD_POOL]));
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["excludedAmmKeys"][0], BAD_POOL.to_string());
+    assert_eq!(
+        serde_json::from_value::<QuoteRequest>(json).unwrap(),
+        request
+    );
+}
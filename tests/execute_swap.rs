@@ -0,0 +1,461 @@
+use solana_sdk::{
+    hash::Hash,
+    message::AddressLookupTableAccount,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::VersionedTransaction,
+};
+use solana_system_interface::instruction as system_instruction;
+use std::sync::{
+    atomic::{AtomicU8, Ordering},
+    Arc, Mutex,
+};
+use std::time::Duration;
+use titan_swap_api_client::{
+    constants::{COMPUTE_BUDGET_PROGRAM_ID, SYSTEM_PROGRAM_ID},
+    execute::{CuSource, CuStrategy, ExecuteError, ExecuteOptions, RpcError, Simulation, SwapRpc},
+    execution_budget::{ExecutionBudget, ExpiryReason},
+    journal::{ExecutionStatus, QuoteJournal},
+    quote::{QuoteRequest, QuoteResponse},
+    security::{default_program_allowlist, SecurityError},
+    swap::SwapResponse,
+    test_utils::{MockClock, MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+const INPUT: Pubkey = Pubkey::new_from_array([1; 32]);
+const OUTPUT: Pubkey = Pubkey::new_from_array([2; 32]);
+
+/// Answers every call from a script and keeps what was sent.
+struct MockRpc {
+    simulation: Result<Simulation, String>,
+    /// How many sends fail with `BlockhashNotFound` before one goes through.
+    stale_sends: Mutex<u32>,
+    /// Moved forward by each simulation and send, when set.
+    clock: Option<MockClock>,
+    blockhashes: AtomicU8,
+    simulated: Mutex<Vec<VersionedTransaction>>,
+    sent: Mutex<Vec<VersionedTransaction>>,
+}
+
+impl MockRpc {
+    fn simulating(simulation: Result<Simulation, String>) -> Self {
+        Self {
+            simulation,
+            stale_sends: Mutex::new(0),
+            clock: None,
+            blockhashes: AtomicU8::new(0),
+            simulated: Mutex::default(),
+            sent: Mutex::default(),
+        }
+    }
+
+    fn with_stale_sends(stale_sends: u32) -> Self {
+        Self {
+            stale_sends: Mutex::new(stale_sends),
+            ..Self::simulating(Err("unused".to_string()))
+        }
+    }
+
+    fn sent_limit(&self) -> Option<u32> {
+        compute_unit_limit(&self.sent.lock().unwrap()[0])
+    }
+}
+
+impl SwapRpc for MockRpc {
+    async fn latest_blockhash(&self) -> Result<Hash, RpcError> {
+        let n = self.blockhashes.fetch_add(1, Ordering::SeqCst);
+        Ok(Hash::new_from_array([n + 1; 32]))
+    }
+
+    async fn lookup_tables(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<AddressLookupTableAccount>, RpcError> {
+        assert!(addresses.is_empty());
+        Ok(Vec::new())
+    }
+
+    async fn simulate(&self, transaction: &VersionedTransaction) -> Result<Simulation, RpcError> {
+        self.simulated.lock().unwrap().push(transaction.clone());
+        if let Some(clock) = &self.clock {
+            clock.advance(Duration::from_secs(1));
+        }
+        self.simulation
+            .clone()
+            .map_err(|err| RpcError::Other(err.into()))
+    }
+
+    async fn send(&self, transaction: &VersionedTransaction) -> Result<Signature, RpcError> {
+        self.sent.lock().unwrap().push(transaction.clone());
+        if let Some(clock) = &self.clock {
+            clock.advance(Duration::from_secs(1));
+        }
+        let mut stale_sends = self.stale_sends.lock().unwrap();
+        if *stale_sends > 0 {
+            *stale_sends -= 1;
+            return Err(RpcError::BlockhashNotFound);
+        }
+        Ok(transaction.signatures[0])
+    }
+}
+
+/// Keeps the executions it is given.
+#[derive(Default)]
+struct ExecutionLog(Mutex<Vec<(Signature, ExecutionStatus)>>);
+
+impl QuoteJournal for ExecutionLog {
+    fn record_quote(&self, _: &QuoteRequest, _: Result<&QuoteResponse, &ClientError>) {}
+
+    fn record_swap(&self, _: &QuoteResponse, _: Result<&SwapResponse, &ClientError>) {}
+
+    fn record_execution(&self, signature: &Signature, status: &ExecutionStatus) {
+        self.0.lock().unwrap().push((*signature, status.clone()));
+    }
+}
+
+/// The limit set by the transaction's SetComputeUnitLimit, if it has one.
+fn compute_unit_limit(transaction: &VersionedTransaction) -> Option<u32> {
+    let keys = transaction.message.static_account_keys();
+    transaction
+        .message
+        .instructions()
+        .iter()
+        .filter(|ix| keys[usize::from(ix.program_id_index)] == COMPUTE_BUDGET_PROGRAM_ID)
+        .find(|ix| ix.data.first() == Some(&2))
+        .map(|ix| u32::from_le_bytes(ix.data[1..5].try_into().unwrap()))
+}
+
+fn quote_request(signer: &Keypair) -> QuoteRequest {
+    QuoteRequest {
+        input_mint: INPUT,
+        output_mint: OUTPUT,
+        amount: 1_000_000,
+        user_pubkey: Some(signer.pubkey()),
+        ..Default::default()
+    }
+}
+
+async fn execute(rpc: &MockRpc, cu_strategy: CuStrategy) -> (CuSource, Option<u32>) {
+    let signer = Keypair::new();
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::new(INPUT, OUTPUT).compute_units(180_000, 240_000))
+        .mount()
+        .await;
+    let client = server.client();
+    let quote = client.quote(&quote_request(&signer)).await.unwrap();
+
+    let options = ExecuteOptions {
+        cu_strategy,
+        ..Default::default()
+    };
+    let submitted = client
+        .execute_swap(&quote, rpc, &signer, &options)
+        .await
+        .unwrap();
+    assert_eq!(rpc.sent.lock().unwrap().len(), 1);
+    assert_eq!(submitted.compute_unit_limit, rpc.sent_limit());
+    (submitted.cu_source, submitted.compute_unit_limit)
+}
+
+#[tokio::test]
+async fn server_strategies_use_the_reported_units() {
+    let rpc = MockRpc::simulating(Err("unused".to_string()));
+    assert_eq!(
+        execute(&rpc, CuStrategy::ServerReported).await,
+        (CuSource::ServerReported, Some(180_000))
+    );
+
+    let rpc = MockRpc::simulating(Err("unused".to_string()));
+    assert_eq!(
+        execute(&rpc, CuStrategy::ServerSafe).await,
+        (CuSource::ServerSafe, Some(240_000))
+    );
+    assert!(rpc.simulated.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn simulated_strategy_adds_the_margin_to_consumed_units() {
+    let rpc = MockRpc::simulating(Ok(Simulation {
+        units_consumed: Some(80_000),
+        err: None,
+    }));
+    assert_eq!(
+        execute(&rpc, CuStrategy::Simulated { margin_pct: 15 }).await,
+        (CuSource::Simulated, Some(92_000))
+    );
+
+    // The simulation itself ran at the maximum limit.
+    let simulated = rpc.simulated.lock().unwrap();
+    assert_eq!(compute_unit_limit(&simulated[0]), Some(1_400_000));
+}
+
+#[tokio::test]
+async fn simulated_strategy_caps_the_limit() {
+    let rpc = MockRpc::simulating(Ok(Simulation {
+        units_consumed: Some(1_300_000),
+        err: None,
+    }));
+    assert_eq!(
+        execute(&rpc, CuStrategy::Simulated { margin_pct: 50 }).await,
+        (CuSource::Simulated, Some(1_400_000))
+    );
+}
+
+#[tokio::test]
+async fn failed_simulations_fall_back_to_the_safe_units() {
+    let rpc = MockRpc::simulating(Err("node is behind".to_string()));
+    assert_eq!(
+        execute(&rpc, CuStrategy::Simulated { margin_pct: 10 }).await,
+        (CuSource::ServerSafe, Some(240_000))
+    );
+
+    let rpc = MockRpc::simulating(Ok(Simulation {
+        units_consumed: Some(50_000),
+        err: Some("InstructionError(0, Custom(1))".to_string()),
+    }));
+    assert_eq!(
+        execute(&rpc, CuStrategy::Simulated { margin_pct: 10 }).await,
+        (CuSource::ServerSafe, Some(240_000))
+    );
+}
+
+#[tokio::test]
+async fn stale_blockhashes_are_rebuilt_and_resent() {
+    let signer = Keypair::new();
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::new(INPUT, OUTPUT))
+        .mount()
+        .await;
+    let client = server.client();
+    let quote = client.quote(&quote_request(&signer)).await.unwrap();
+    let options = ExecuteOptions {
+        max_blockhash_rebuilds: 2,
+        ..Default::default()
+    };
+
+    let rpc = MockRpc::with_stale_sends(2);
+    let submitted = client
+        .execute_swap(&quote, &rpc, &signer, &options)
+        .await
+        .unwrap();
+    assert_eq!(submitted.attempts(), 3);
+    assert_eq!(
+        submitted.blockhashes,
+        [1, 2, 3].map(|n| Hash::new_from_array([n; 32]))
+    );
+    let last_sent = rpc.sent.lock().unwrap()[2].clone();
+    assert_eq!(
+        *last_sent.message.recent_blockhash(),
+        submitted.blockhashes[2]
+    );
+    assert_eq!(last_sent.signatures[0], submitted.signature);
+
+    // One stale send more than allowed surfaces the error.
+    let rpc = MockRpc::with_stale_sends(3);
+    let err = client
+        .execute_swap(&quote, &rpc, &signer, &options)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Blockhash not found"), "{err}");
+    assert_eq!(rpc.sent.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn rebuilds_stop_once_the_quote_expires() {
+    let signer = Keypair::new();
+    let clock = MockClock::new(1_000_000);
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::new(INPUT, OUTPUT).expires(1_001_500, 0))
+        .mount()
+        .await;
+    let client = server
+        .client_builder()
+        .clock(Arc::new(clock.clone()))
+        .build()
+        .unwrap();
+    let quote = client.quote(&quote_request(&signer)).await.unwrap();
+
+    let rpc = MockRpc {
+        clock: Some(clock),
+        ..MockRpc::with_stale_sends(5)
+    };
+    let options = ExecuteOptions {
+        max_blockhash_rebuilds: 5,
+        ..Default::default()
+    };
+    let err = client
+        .execute_swap(&quote, &rpc, &signer, &options)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::QuoteExpired {
+            reason: ExpiryReason::Expired,
+            ..
+        }
+    ));
+    // Rebuilt once at 1_001_000, then found expired at 1_002_000.
+    assert_eq!(rpc.sent.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn expired_quotes_are_not_sent_without_a_budget() {
+    let signer = Keypair::new();
+    let clock = MockClock::new(1_000_000);
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::new(INPUT, OUTPUT).expires(1_000_500, 0))
+        .mount()
+        .await;
+    let client = server
+        .client_builder()
+        .clock(Arc::new(clock.clone()))
+        .build()
+        .unwrap();
+    assert_eq!(client.execution_budget(), None);
+    let quote = client.quote(&quote_request(&signer)).await.unwrap();
+    let simulation = Ok(Simulation {
+        units_consumed: Some(100_000),
+        err: None,
+    });
+
+    // Valid when built, expired by the time the simulation comes back.
+    let rpc = MockRpc {
+        clock: Some(clock.clone()),
+        ..MockRpc::simulating(simulation.clone())
+    };
+    let options = ExecuteOptions {
+        cu_strategy: CuStrategy::Simulated { margin_pct: 10 },
+        ..Default::default()
+    };
+    let err = client
+        .execute_swap(&quote, &rpc, &signer, &options)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::QuoteExpired {
+            reason: ExpiryReason::Expired,
+            ..
+        }
+    ));
+    assert_eq!(rpc.simulated.lock().unwrap().len(), 1);
+    assert!(rpc.sent.lock().unwrap().is_empty());
+
+    // Already expired when execution starts.
+    let rpc = MockRpc::simulating(simulation);
+    let err = client
+        .execute_swap(&quote, &rpc, &signer, &options)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ClientError::QuoteExpired { .. }));
+    assert!(rpc.sent.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn program_allowlist_is_checked_before_signing() {
+    let signer = Keypair::new();
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::new(INPUT, OUTPUT).instruction(
+            &system_instruction::transfer(&signer.pubkey(), &Pubkey::new_unique(), 1_000_000),
+        ))
+        .mount()
+        .await;
+    let client = server.client();
+    let quote = client.quote(&quote_request(&signer)).await.unwrap();
+
+    let rpc = MockRpc::simulating(Err("unused".to_string()));
+    let options = ExecuteOptions {
+        program_allowlist: Some(default_program_allowlist()),
+        ..Default::default()
+    };
+    let err = client
+        .execute_swap(&quote, &rpc, &signer, &options)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::Execution(ExecuteError::Security(SecurityError::UnexpectedSigner {
+            index: 0,
+            program_id: SYSTEM_PROGRAM_ID,
+            ..
+        }))
+    ));
+    assert!(rpc.sent.lock().unwrap().is_empty());
+
+    // Opt-in: without an allowlist the same swap is sent.
+    client
+        .execute_swap(&quote, &rpc, &signer, &ExecuteOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(rpc.sent.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn sends_are_timed_and_journaled() {
+    let signer = Keypair::new();
+    let clock = MockClock::new(1_000_000);
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::new(INPUT, OUTPUT).expires(1_060_000, 0))
+        .mount()
+        .await;
+    let journal = Arc::new(ExecutionLog::default());
+    let client = server
+        .client_builder()
+        .clock(Arc::new(clock.clone()))
+        .estimated_execution_budget(ExecutionBudget {
+            build_ms: 0,
+            send_ms: 5_000,
+            safety_margin_ms: 0,
+        })
+        .journal(journal.clone())
+        .build()
+        .unwrap();
+    let quote = client.quote(&quote_request(&signer)).await.unwrap();
+    let options = ExecuteOptions {
+        max_blockhash_rebuilds: 1,
+        ..Default::default()
+    };
+
+    // The stale send isn't timed; the one that goes through takes a second.
+    let rpc = MockRpc {
+        clock: Some(clock.clone()),
+        ..MockRpc::with_stale_sends(1)
+    };
+    let submitted = client
+        .execute_swap(&quote, &rpc, &signer, &options)
+        .await
+        .unwrap();
+    assert_eq!(client.execution_budget().unwrap().send_ms, 1_000);
+    assert_eq!(
+        journal.0.lock().unwrap().as_slice(),
+        [(submitted.signature, ExecutionStatus::Sent)]
+    );
+
+    // Only the final failure is journaled, under the last signature sent.
+    let rpc = MockRpc {
+        clock: Some(clock),
+        ..MockRpc::with_stale_sends(2)
+    };
+    client
+        .execute_swap(&quote, &rpc, &signer, &options)
+        .await
+        .unwrap_err();
+    let last_sent = rpc.sent.lock().unwrap()[1].signatures[0];
+    assert_eq!(
+        journal.0.lock().unwrap()[1..],
+        [(
+            last_sent,
+            ExecutionStatus::Failed {
+                reason: "Blockhash not found".to_string(),
+            }
+        )]
+    );
+    assert_eq!(client.execution_budget().unwrap().send_ms, 1_000);
+}
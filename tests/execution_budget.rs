@@ -0,0 +1,189 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use titan_swap_api_client::{
+    execution_budget::{ExecutionBudget, ExpiryReason},
+    quote::QuoteResponse,
+    test_utils::MockTitanServer,
+    ClientError,
+};
+
+const QUOTE_V1: &str = include_str!("fixtures/quote_response_v1.json");
+
+const BUDGET: ExecutionBudget = ExecutionBudget {
+    build_ms: 200,
+    send_ms: 500,
+    safety_margin_ms: 300,
+};
+
+fn quote_expiring_at(expires_at_ms: Option<u64>) -> QuoteResponse {
+    let mut quote = QuoteResponse::from_json_str(QUOTE_V1).unwrap();
+    quote.route_data_mut().unwrap().expires_at_ms = expires_at_ms;
+    quote
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+fn expiry_reason(err: ClientError) -> (ExpiryReason, u64, u64) {
+    match err {
+        ClientError::QuoteExpired {
+            reason,
+            remaining_ms,
+            budget_ms,
+        } => (reason, remaining_ms, budget_ms),
+        other => panic!("expected QuoteExpired, got {other:?}"),
+    }
+}
+
+#[test]
+fn total_sums_every_part() {
+    assert_eq!(BUDGET.total_ms(), 1_000);
+    let huge = ExecutionBudget {
+        build_ms: u64::MAX,
+        send_ms: 1,
+        safety_margin_ms: 1,
+    };
+    assert_eq!(huge.total_ms(), u64::MAX);
+}
+
+#[test]
+fn quote_with_enough_time_left_passes() {
+    let quote = quote_expiring_at(Some(10_000));
+    assert!(BUDGET.check_at(&quote, 8_000).is_ok());
+    assert!(BUDGET.check_at(&quote, 9_000).is_ok());
+}
+
+#[test]
+fn quote_expiring_within_the_budget_is_rejected() {
+    let quote = quote_expiring_at(Some(10_000));
+    let err = BUDGET.check_at(&quote, 9_001).unwrap_err();
+    assert_eq!(
+        expiry_reason(err),
+        (ExpiryReason::InsufficientMargin, 999, 1_000)
+    );
+}
+
+#[test]
+fn quote_past_its_expiry_is_expired() {
+    let quote = quote_expiring_at(Some(10_000));
+    for now_ms in [10_000, 25_000] {
+        let err = BUDGET.check_at(&quote, now_ms).unwrap_err();
+        assert_eq!(expiry_reason(err), (ExpiryReason::Expired, 0, 1_000));
+    }
+}
+
+#[test]
+fn zero_budget_still_rejects_expired_quotes() {
+    let quote = quote_expiring_at(Some(10_000));
+    let budget = ExecutionBudget::default();
+    assert!(budget.check_at(&quote, 9_999).is_ok());
+    let err = budget.check_at(&quote, 10_000).unwrap_err();
+    assert_eq!(expiry_reason(err).0, ExpiryReason::Expired);
+}
+
+#[test]
+fn quote_without_expiry_always_passes() {
+    let quote = quote_expiring_at(None);
+    assert!(BUDGET.check_at(&quote, u64::MAX).is_ok());
+}
+
+#[test]
+fn error_names_the_reason() {
+    let quote = quote_expiring_at(Some(10_000));
+    let err = BUDGET.check_at(&quote, 9_500).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Quote expires before it can execute: 500 ms left for a 1000 ms execution budget"
+    );
+    let err = BUDGET.check_at(&quote, 11_000).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Quote has expired: 0 ms left for a 1000 ms execution budget"
+    );
+}
+
+#[tokio::test]
+async fn swap_without_budget_does_not_check_expiry() {
+    let server = MockTitanServer::start().await;
+    let client = server.client();
+    assert_eq!(client.execution_budget(), None);
+    assert!(client.swap(&quote_expiring_at(Some(1))).is_ok());
+}
+
+#[tokio::test]
+async fn swap_rejects_quotes_outside_the_budget() {
+    let server = MockTitanServer::start().await;
+    let client = server
+        .client_builder()
+        .execution_budget(BUDGET)
+        .build()
+        .unwrap();
+    assert_eq!(client.execution_budget(), Some(BUDGET));
+
+    let err = client.swap(&quote_expiring_at(Some(1))).unwrap_err();
+    assert_eq!(expiry_reason(err).0, ExpiryReason::Expired);
+
+    let soon = quote_expiring_at(Some(now_ms() + 100));
+    let err = client.swap(&soon).unwrap_err();
+    assert_eq!(expiry_reason(err).0, ExpiryReason::InsufficientMargin);
+
+    let later = quote_expiring_at(Some(now_ms() + 60_000));
+    assert!(client.swap(&later).is_ok());
+}
+
+#[tokio::test]
+async fn swap_instructions_rejects_expired_quotes_without_a_request() {
+    let server = MockTitanServer::start().await;
+    let client = server
+        .client_builder()
+        .execution_budget(BUDGET)
+        .build()
+        .unwrap();
+
+    let err = client
+        .swap_instructions(&quote_expiring_at(Some(1)), Default::default())
+        .await
+        .unwrap_err();
+
+    assert_eq!(expiry_reason(err).0, ExpiryReason::Expired);
+    assert!(server.received_paths().await.is_empty());
+}
+
+#[tokio::test]
+async fn estimated_budget_follows_measured_latencies() {
+    let initial = ExecutionBudget {
+        build_ms: 60_000,
+        send_ms: 60_000,
+        safety_margin_ms: 250,
+    };
+    let server = MockTitanServer::start().await;
+    let client = server
+        .client_builder()
+        .estimated_execution_budget(initial)
+        .build()
+        .unwrap();
+    assert_eq!(client.execution_budget(), Some(initial));
+
+    // Nothing measured yet, so 90 seconds of validity isn't enough.
+    let quote = quote_expiring_at(Some(now_ms() + 90_000));
+    assert!(client.swap(&quote).is_err());
+
+    client.record_send_latency(Duration::from_millis(400));
+    client.record_send_latency(Duration::from_millis(600));
+    let budget = client.execution_budget().unwrap();
+    assert_eq!(budget.send_ms, 500);
+    assert_eq!(budget.build_ms, 60_000);
+
+    // A swap far from expiry measures the build, which is well under a minute.
+    let clone = client.clone();
+    clone
+        .swap(&quote_expiring_at(Some(now_ms() + 600_000)))
+        .unwrap();
+    let budget = client.execution_budget().unwrap();
+    assert!(budget.build_ms < 1_000);
+    assert_eq!(budget.safety_margin_ms, 250);
+    assert!(client.swap(&quote).is_ok());
+}
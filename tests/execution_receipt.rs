@@ -0,0 +1,195 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{pubkey, pubkey::Pubkey, signature::Signature};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{QuoteRequest, QuoteResponse},
+    receipt::{EncodedConfirmedTransactionWithStatusMeta, ExecutionReceipt, TokenBalanceChange},
+    test_utils::SwapRouteBuilder,
+    ClientError,
+};
+use wiremock::{
+    matchers::{body_partial_json, method},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const CONFIRMED_SWAP: &str = include_str!("fixtures/confirmed_swap_transaction.json");
+const SIGNATURE: &str =
+    "5EGJVJvUt89N4WsQ9Z8arVeZsoNjk3d5ZnSxSCHdv978W7trTEwnUmEPEhnNXuAw2FCJQScmr86MPun2sHWMq4iT";
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn quote() -> QuoteResponse {
+    QuoteResponse::from_route(
+        &QuoteRequest {
+            input_mint: SOL_MINT,
+            output_mint: USDC_MINT,
+            amount: 1_000_000_000,
+            user_pubkey: Some(USER),
+            ..Default::default()
+        },
+        SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+            .amounts(1_000_000_000, 186_000_000)
+            .build(),
+    )
+}
+
+fn transaction() -> EncodedConfirmedTransactionWithStatusMeta {
+    serde_json::from_str(CONFIRMED_SWAP).unwrap()
+}
+
+#[test]
+fn reads_the_signers_balances_from_the_transaction() {
+    let receipt = ExecutionReceipt::from_transaction(&transaction(), &quote()).unwrap();
+
+    assert_eq!(receipt.signature.to_string(), SIGNATURE);
+    assert_eq!(receipt.slot, 312_345_678);
+    assert_eq!(receipt.error, None);
+    assert_eq!(receipt.quote, quote().summary());
+    // The pool vault's USDC is not the signer's, and SOL is the signer's
+    // lamports less the 5000 lamport fee.
+    assert_eq!(
+        receipt.token_balances,
+        vec![
+            TokenBalanceChange {
+                mint: SOL_MINT,
+                decimals: 9,
+                pre_amount: 5_000_000_000,
+                post_amount: 4_000_000_000,
+            },
+            TokenBalanceChange {
+                mint: USDC_MINT,
+                decimals: 6,
+                pre_amount: 10_000_000,
+                post_amount: 195_500_000,
+            },
+        ]
+    );
+    assert_eq!(receipt.in_amount, 1_000_000_000);
+    assert_eq!(receipt.out_amount, 185_500_000);
+    assert_eq!(receipt.realized_price, Some(0.1855));
+    assert!((receipt.slippage_bps.unwrap() - 26.88).abs() < 0.01);
+}
+
+#[test]
+fn failed_transactions_keep_their_error() {
+    let mut json: serde_json::Value = serde_json::from_str(CONFIRMED_SWAP).unwrap();
+    let error = serde_json::json!({ "InstructionError": [0, { "Custom": 6001 }] });
+    json["meta"]["err"] = error.clone();
+    json["meta"]["status"] = serde_json::json!({ "Err": error });
+    // Only the fee was paid.
+    json["meta"]["postBalances"][0] = serde_json::json!(4_999_995_000u64);
+    json["meta"]["postTokenBalances"] = json["meta"]["preTokenBalances"].clone();
+    let transaction = serde_json::from_value(json).unwrap();
+
+    let receipt = ExecutionReceipt::from_transaction(&transaction, &quote()).unwrap();
+    assert!(receipt.error.as_ref().unwrap().contains("6001"));
+    assert_eq!(receipt.in_amount, 0);
+    assert_eq!(receipt.realized_price, None);
+    assert_eq!(receipt.slippage_bps, None);
+
+    let json = serde_json::to_value(&receipt).unwrap();
+    assert!(json.get("realizedPrice").is_none());
+    assert_eq!(
+        serde_json::from_value::<ExecutionReceipt>(json).unwrap(),
+        receipt
+    );
+}
+
+#[test]
+fn json_encoded_transactions_are_rejected() {
+    let mut json: serde_json::Value = serde_json::from_str(CONFIRMED_SWAP).unwrap();
+    json["transaction"] = serde_json::json!({
+        "signatures": [SIGNATURE],
+        "message": {
+            "header": {
+                "numRequiredSignatures": 1,
+                "numReadonlySignedAccounts": 0,
+                "numReadonlyUnsignedAccounts": 1
+            },
+            "accountKeys": [USER.to_string()],
+            "recentBlockhash": Pubkey::default().to_string(),
+            "instructions": []
+        }
+    });
+    let transaction = serde_json::from_value(json).unwrap();
+
+    assert!(matches!(
+        ExecutionReceipt::from_transaction(&transaction, &quote()),
+        Err(ClientError::InvalidResponse {
+            field: "transaction",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn manual_receipts_price_the_fill() {
+    let signature = Signature::default();
+    let balances = |out_post| {
+        vec![
+            TokenBalanceChange {
+                mint: SOL_MINT,
+                decimals: 9,
+                pre_amount: 2_000_000_000,
+                post_amount: 1_000_000_000,
+            },
+            TokenBalanceChange {
+                mint: USDC_MINT,
+                decimals: 6,
+                pre_amount: 0,
+                post_amount: out_post,
+            },
+        ]
+    };
+
+    let better = ExecutionReceipt::new(&quote(), signature, 1, balances(186_930_000));
+    assert_eq!(better.out_amount, 186_930_000);
+    assert!((better.slippage_bps.unwrap() + 50.0).abs() < 1e-9);
+
+    let nothing_spent = ExecutionReceipt::new(&quote(), signature, 1, Vec::new());
+    assert_eq!(nothing_spent.in_amount, 0);
+    assert_eq!(nothing_spent.realized_price, None);
+    assert_eq!(nothing_spent.slippage_bps, None);
+}
+
+#[test]
+fn round_trips_through_json() {
+    let receipt = ExecutionReceipt::from_transaction(&transaction(), &quote()).unwrap();
+    let json = serde_json::to_value(&receipt).unwrap();
+
+    assert_eq!(json["signature"], SIGNATURE);
+    assert_eq!(json["outAmount"], "185500000");
+    assert!(json.get("error").is_none());
+    assert_eq!(
+        serde_json::from_value::<ExecutionReceipt>(json).unwrap(),
+        receipt
+    );
+}
+
+#[tokio::test]
+async fn fetch_reads_the_transaction_over_rpc() {
+    let rpc_server = MockServer::start().await;
+    let transaction: serde_json::Value = serde_json::from_str(CONFIRMED_SWAP).unwrap();
+    Mock::given(method("POST"))
+        .and(body_partial_json(
+            serde_json::json!({ "method": "getTransaction" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": transaction,
+            "id": 1
+        })))
+        .mount(&rpc_server)
+        .await;
+
+    let receipt = ExecutionReceipt::fetch(
+        &RpcClient::new(rpc_server.uri()),
+        &SIGNATURE.parse().unwrap(),
+        &quote(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(receipt.out_amount, 185_500_000);
+    assert_eq!(receipt.slot, 312_345_678);
+}
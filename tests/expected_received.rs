@@ -0,0 +1,217 @@
+use base64::Engine;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use titan_swap_api_client::{
+    constants::TOKEN_2022_PROGRAM_ID,
+    quote::{QuoteRequest, QuoteResponse, SwapMode},
+    test_utils::SwapRouteBuilder,
+    token_2022::{fetch_received_adjustments, MintExtensions, ReceivedAdjustments},
+};
+use wiremock::{
+    matchers::{body_partial_json, method},
+    Mock, MockServer, ResponseTemplate,
+};
+
+// 1% (capped at 5 tokens) until epoch 600, then 2.5% (capped at 1,000).
+const TRANSFER_FEE_MINT: &[u8] = include_bytes!("fixtures/token2022_transfer_fee_mint.bin");
+
+fn quote(swap_mode: SwapMode, out_amount: u64, slippage_bps: u16) -> QuoteResponse {
+    QuoteResponse::from_route(
+        &QuoteRequest {
+            swap_mode: Some(swap_mode),
+            ..Default::default()
+        },
+        SwapRouteBuilder::default()
+            .amounts(1_000_000_000, out_amount)
+            .slippage_bps(slippage_bps)
+            .build(),
+    )
+}
+
+fn received(out_amount: u64, adjustments: ReceivedAdjustments) -> u64 {
+    quote(SwapMode::ExactIn, out_amount, 0).expected_received(&adjustments)
+}
+
+#[test]
+fn no_adjustments_leave_the_net_output() {
+    let quote = quote(SwapMode::ExactIn, 100_000_000, 50);
+    let none = ReceivedAdjustments::default();
+
+    assert_eq!(quote.expected_received(&none), 100_000_000);
+    assert_eq!(quote.min_out_amount_with(&none), quote.min_out_amount());
+}
+
+#[test]
+fn transfer_fees_round_the_received_amount_down() {
+    let bps = ReceivedAdjustments::transfer_fee_bps;
+
+    assert_eq!(received(100_000_000, bps(0)), 100_000_000);
+    assert_eq!(received(0, bps(1)), 0);
+    assert_eq!(received(1, bps(1)), 0);
+    assert_eq!(received(9_999, bps(1)), 9_998);
+    assert_eq!(received(10_000, bps(1)), 9_999);
+    assert_eq!(received(10_001, bps(1)), 9_999);
+    assert_eq!(received(100_000_000, bps(9_999)), 10_000);
+    assert_eq!(received(100_000_000, bps(10_000)), 0);
+    assert_eq!(received(100_000_000, bps(u16::MAX)), 0);
+    assert_eq!(received(u64::MAX, bps(1)), 18_444_899_399_302_180_659);
+    assert_eq!(received(u64::MAX, bps(10_000)), 0);
+}
+
+#[test]
+fn transfer_fees_are_capped_at_the_mint_maximum() {
+    let extensions = MintExtensions::parse(TRANSFER_FEE_MINT).unwrap();
+
+    assert_eq!(
+        received(100_000_000, extensions.received_adjustments(10)),
+        99_000_000
+    );
+    assert_eq!(
+        received(100_000_000, extensions.received_adjustments(600)),
+        97_500_000
+    );
+    assert_eq!(
+        received(u64::MAX, extensions.received_adjustments(10)),
+        u64::MAX - 5_000_000
+    );
+    assert_eq!(
+        MintExtensions::default().received_adjustments(10),
+        ReceivedAdjustments::default()
+    );
+}
+
+#[test]
+fn platform_fees_are_taken_before_the_transfer_fee() {
+    let platform = ReceivedAdjustments::default().with_platform_fee_bps(50);
+    assert_eq!(received(100_000_000, platform), 99_500_000);
+    assert_eq!(received(10_001, platform), 9_950);
+    assert_eq!(
+        received(
+            100_000_000,
+            ReceivedAdjustments::default().with_platform_fee_bps(10_000)
+        ),
+        0
+    );
+
+    // 99_500_000 after the platform fee, less a 995_000 transfer fee.
+    let both = ReceivedAdjustments::transfer_fee_bps(100).with_platform_fee_bps(50);
+    assert_eq!(received(100_000_000, both), 98_505_000);
+    // 9_999 after a 2 unit platform fee, less a 1 unit transfer fee.
+    let both = ReceivedAdjustments::transfer_fee_bps(1).with_platform_fee_bps(1);
+    assert_eq!(received(10_001, both), 9_998);
+}
+
+#[test]
+fn a_platform_fee_paid_by_the_final_hop_is_netted_out() {
+    let mut quote = QuoteResponse::from_route(
+        &QuoteRequest::default(),
+        SwapRouteBuilder::default()
+            .amounts(1_000_000_000, 97_000_000)
+            .platform_fee(3_000_000, 300)
+            .step(
+                "Whirlpool",
+                Pubkey::new_unique(),
+                Pubkey::default(),
+                Pubkey::default(),
+                1_000_000_000,
+                100_000_000,
+                1_000_000_000,
+            )
+            .build(),
+    );
+    // As if `out_amount` were reported gross of the fee.
+    quote.out_amount = 100_000_000;
+
+    assert_eq!(
+        quote.expected_received(&ReceivedAdjustments::default()),
+        97_000_000
+    );
+    assert_eq!(
+        quote.expected_received(&ReceivedAdjustments::transfer_fee_bps(100)),
+        96_030_000
+    );
+}
+
+#[test]
+fn min_out_applies_slippage_to_the_expected_amount() {
+    let adjustments = ReceivedAdjustments::transfer_fee_bps(100);
+
+    // 99_000_000 expected, less 0.5%.
+    let exact_in = quote(SwapMode::ExactIn, 100_000_000, 50);
+    assert_eq!(exact_in.min_out_amount_with(&adjustments), 98_505_000);
+    // 9_999 expected, less 0.01% rounded down.
+    let exact_in = quote(SwapMode::ExactIn, 10_001, 1);
+    assert_eq!(
+        exact_in.min_out_amount_with(&ReceivedAdjustments::transfer_fee_bps(1)),
+        9_998
+    );
+    assert_eq!(
+        quote(SwapMode::ExactIn, 100_000_000, 10_000).min_out_amount_with(&adjustments),
+        0
+    );
+
+    // The output of ExactOut is fixed, so only the fees apply.
+    let exact_out = quote(SwapMode::ExactOut, 100_000_000, 50);
+    assert_eq!(exact_out.min_out_amount_with(&adjustments), 99_000_000);
+}
+
+async fn rpc_with_mint_in_epoch(epoch: u64) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(
+            serde_json::json!({ "method": "getAccountInfo" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": {
+                "context": { "slot": 1 },
+                "value": {
+                    "data": [
+                        base64::engine::general_purpose::STANDARD.encode(TRANSFER_FEE_MINT),
+                        "base64"
+                    ],
+                    "executable": false,
+                    "lamports": 1_461_600,
+                    "owner": TOKEN_2022_PROGRAM_ID.to_string(),
+                    "rentEpoch": 0,
+                    "space": TRANSFER_FEE_MINT.len()
+                }
+            },
+            "id": 1
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(
+            serde_json::json!({ "method": "getEpochInfo" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": {
+                "absoluteSlot": 259_200_000u64,
+                "blockHeight": 240_000_000u64,
+                "epoch": epoch,
+                "slotIndex": 0,
+                "slotsInEpoch": 432_000,
+                "transactionCount": null
+            },
+            "id": 1
+        })))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn fetches_the_fee_in_the_current_epoch() {
+    let server = rpc_with_mint_in_epoch(600).await;
+    let rpc = RpcClient::new(server.uri());
+
+    let adjustments = fetch_received_adjustments(&Pubkey::new_unique(), &rpc)
+        .await
+        .unwrap();
+
+    let extensions = MintExtensions::parse(TRANSFER_FEE_MINT).unwrap();
+    assert_eq!(adjustments, extensions.received_adjustments(600));
+    assert_eq!(received(100_000_000, adjustments), 97_500_000);
+}
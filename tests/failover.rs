@@ -0,0 +1,53 @@
+use titan_swap_api_client::{
+    quote::QuoteRequest,
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    TitanClient,
+};
+
+#[tokio::test]
+async fn fails_over_to_healthy_endpoint_and_stays() {
+    let primary = MockTitanServer::start().await;
+    primary.respond_with_status(503).mount().await;
+    let secondary = MockTitanServer::start().await;
+    secondary
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+
+    let client = TitanClient::builder("test-token")
+        .base_urls(vec![primary.uri(), secondary.uri()])
+        .build()
+        .unwrap();
+    assert_eq!(client.current_endpoint(), primary.uri());
+
+    for _ in 0..3 {
+        client.quote(&QuoteRequest::default()).await.unwrap();
+    }
+
+    assert_eq!(client.current_endpoint(), secondary.uri());
+    assert_eq!(primary.received_request_count().await, 1);
+    assert_eq!(secondary.received_request_count().await, 3);
+}
+
+#[tokio::test]
+async fn does_not_fail_over_on_client_errors() {
+    let primary = MockTitanServer::start().await;
+    primary
+        .respond_with_error(404, "No routes found")
+        .mount()
+        .await;
+    let secondary = MockTitanServer::start().await;
+    secondary
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+
+    let client = TitanClient::builder("test-token")
+        .base_urls(vec![primary.uri(), secondary.uri()])
+        .build()
+        .unwrap();
+
+    assert!(client.quote(&QuoteRequest::default()).await.is_err());
+    assert_eq!(client.current_endpoint(), primary.uri());
+    assert_eq!(secondary.received_request_count().await, 0);
+}
@@ -0,0 +1,39 @@
+use titan_swap_api_client::swap::SwapResponse;
+
+fn swap(compute_unit_limit: Option<u32>, compute_units_safe: Option<u64>) -> SwapResponse {
+    SwapResponse {
+        instructions: vec![],
+        address_lookup_table_addresses: vec![],
+        compute_unit_limit,
+        compute_units_safe,
+        context_slot: None,
+        expires_at_ms: None,
+        expires_after_slot: None,
+    }
+}
+
+#[test]
+fn combines_signature_and_priority_fees() {
+    // 300_000 CUs at 1_000 micro-lamports is 300 lamports.
+    let swap = swap(Some(200_000), Some(300_000));
+    assert_eq!(swap.estimated_fee_lamports(1_000, 1), Some(5_300));
+    assert_eq!(swap.estimated_fee_lamports(0, 2), Some(10_000));
+}
+
+#[test]
+fn priority_fee_rounds_up() {
+    let swap = swap(Some(1), None);
+    assert_eq!(swap.estimated_fee_lamports(1, 1), Some(5_001));
+}
+
+#[test]
+fn refuses_to_guess_without_compute_units() {
+    assert_eq!(swap(None, None).estimated_fee_lamports(1_000, 1), None);
+}
+
+#[test]
+fn overflow_is_none() {
+    let swap = swap(None, Some(u64::MAX));
+    assert_eq!(swap.estimated_fee_lamports(u64::MAX, 1), None);
+    assert_eq!(swap.estimated_fee_lamports(1, usize::MAX), None);
+}
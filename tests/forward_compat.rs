@@ -0,0 +1,102 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    quote::{QuoteRequest, SwapMode},
+    test_utils::{sample_request, MockTitanServer},
+    ClientError,
+};
+
+const FUTURE_RESPONSE: &[u8] = include_bytes!("fixtures/future_response.msgpack");
+const SINGLE_HOP: &[u8] = include_bytes!("fixtures/single_hop.msgpack");
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey: Some(USER),
+        slippage_bps: 50,
+        ..sample_request()
+    }
+}
+
+#[test]
+fn unknown_swap_modes_are_captured() {
+    let mode: SwapMode = serde_json::from_str("\"Hybrid\"").unwrap();
+    assert_eq!(mode, SwapMode::Other("Hybrid".to_string()));
+    assert_eq!(serde_json::to_string(&mode).unwrap(), "\"Hybrid\"");
+
+    let bytes = rmp_serde::to_vec(&SwapMode::ExactOut).unwrap();
+    assert_eq!(
+        rmp_serde::from_slice::<SwapMode>(&bytes).unwrap(),
+        SwapMode::ExactOut
+    );
+}
+
+#[tokio::test]
+async fn strict_decoding_rejects_changed_field_types() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_body(FUTURE_RESPONSE).mount().await;
+
+    let err = server.client().quote(&request()).await.unwrap_err();
+    assert!(matches!(err, ClientError::MsgpackError(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn lenient_decoding_keeps_a_usable_quote() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_body(FUTURE_RESPONSE).mount().await;
+
+    let client = server
+        .client_builder()
+        .lenient_decode(true)
+        .build()
+        .unwrap();
+    // The fixture answers in a mode this version doesn't know.
+    let request = QuoteRequest {
+        swap_mode: Some(SwapMode::Other("ExactInPartial".to_string())),
+        ..request()
+    };
+    let quote = client.quote(&request).await.unwrap();
+
+    assert_eq!(quote.out_amount, 187_654_321);
+    assert_eq!(quote.route_plan.len(), 1);
+    assert_eq!(quote.route_plan[0].swap_info.label, "Whirlpool");
+    assert_eq!(quote.route_data().unwrap().compute_units, None);
+    assert_eq!(
+        quote.route_data().unwrap().compute_units_safe,
+        Some(260_000)
+    );
+    assert_eq!(
+        quote.route_data().unwrap().expires_after_slot,
+        Some(312_000_150)
+    );
+    assert_eq!(quote.route_data().unwrap().reference_id, None);
+
+    let fields: Vec<_> = quote
+        .warnings
+        .iter()
+        .filter(|w| w.step.is_none())
+        .map(|w| w.field.as_str())
+        .collect();
+    assert_eq!(fields, vec!["computeUnits", "referenceId"]);
+
+    let swap = client.swap(&quote).unwrap();
+    assert_eq!(swap.instructions.len(), 1);
+    assert_eq!(swap.compute_unit_limit, None);
+}
+
+#[tokio::test]
+async fn lenient_decoding_of_a_current_response_has_no_warnings() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_body(SINGLE_HOP).mount().await;
+
+    let client = server
+        .client_builder()
+        .lenient_decode(true)
+        .build()
+        .unwrap();
+    let quote = client.quote(&request()).await.unwrap();
+
+    assert_eq!(quote.out_amount, 187_654_321);
+    // Only the step the fixture leaves without a fee mint.
+    assert!(quote.warnings.iter().all(|w| w.step.is_some()));
+}
@@ -0,0 +1,173 @@
+use reqwest::header::{HeaderName, HeaderValue};
+use titan_swap_api_client::{
+    quote::QuoteRequest,
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    ClientError, TitanClient, CLIENT_ID_HEADER, DEFAULT_USER_AGENT,
+};
+
+async fn server() -> MockTitanServer {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn default_headers_are_sent_alongside_authorization() {
+    let server = server().await;
+    let client = server
+        .client_builder()
+        .default_header(
+            HeaderName::from_static("x-org-id"),
+            HeaderValue::from_static("org-42"),
+        )
+        .build()
+        .unwrap();
+
+    client.quote(&QuoteRequest::default()).await.unwrap();
+
+    assert_eq!(
+        server.received_quote_headers("x-org-id").await,
+        vec![Some("org-42".to_string())]
+    );
+    assert_eq!(
+        server.received_quote_headers("authorization").await,
+        vec![Some("Bearer test-token".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn default_authorization_replaces_the_bearer_token() {
+    let server = server().await;
+    let client = server
+        .client_builder()
+        .default_header(
+            reqwest::header::AUTHORIZATION,
+            HeaderValue::from_static("Gateway abc"),
+        )
+        .build()
+        .unwrap();
+
+    client.quote(&QuoteRequest::default()).await.unwrap();
+
+    assert_eq!(
+        server.received_quote_headers("authorization").await,
+        vec![Some("Gateway abc".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn middleware_sees_the_final_request() {
+    let server = server().await;
+    let client = server
+        .client_builder()
+        .request_middleware(|request| {
+            assert!(request.headers().contains_key("authorization"));
+            let signature = format!("{} {}", request.method(), request.url().path());
+            request.headers_mut().insert(
+                HeaderName::from_static("x-signature"),
+                HeaderValue::from_str(&signature).unwrap(),
+            );
+        })
+        .build()
+        .unwrap();
+
+    client.quote(&QuoteRequest::default()).await.unwrap();
+
+    assert_eq!(
+        server.received_quote_headers("x-signature").await,
+        vec![Some("GET /api/v1/quote/swap".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn default_user_agent_names_the_crate_version() {
+    let server = server().await;
+
+    server
+        .client()
+        .quote(&QuoteRequest::default())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        DEFAULT_USER_AGENT,
+        format!("titan-swap-api-client/{}", env!("CARGO_PKG_VERSION"))
+    );
+    assert_eq!(
+        server.received_quote_headers("user-agent").await,
+        vec![Some(DEFAULT_USER_AGENT.to_string())]
+    );
+    assert_eq!(
+        server.received_quote_headers(CLIENT_ID_HEADER).await,
+        vec![None]
+    );
+}
+
+#[tokio::test]
+async fn user_agent_and_client_id_are_sent_on_every_request() {
+    let server = server().await;
+    let client = server
+        .client_builder()
+        .user_agent("acme-router/2.1")
+        .client_id("acme")
+        .build()
+        .unwrap();
+
+    client.quote(&QuoteRequest::default()).await.unwrap();
+    client.quote(&QuoteRequest::default()).await.unwrap();
+
+    assert_eq!(
+        server.received_quote_headers("user-agent").await,
+        vec![Some("acme-router/2.1".to_string()); 2]
+    );
+    assert_eq!(
+        server.received_quote_headers("x-client-id").await,
+        vec![Some("acme".to_string()); 2]
+    );
+}
+
+#[tokio::test]
+async fn default_header_user_agent_replaces_the_default_one() {
+    let server = server().await;
+    let client = server
+        .client_builder()
+        .default_header(
+            HeaderName::from_static("user-agent"),
+            HeaderValue::from_static("custom/1"),
+        )
+        .build()
+        .unwrap();
+
+    client.quote(&QuoteRequest::default()).await.unwrap();
+
+    assert_eq!(
+        server.received_quote_headers("user-agent").await,
+        vec![Some("custom/1".to_string())]
+    );
+}
+
+#[test]
+fn invalid_user_agent_or_client_id_fails_the_build() {
+    let err = TitanClient::builder("token")
+        .user_agent("acme\nrouter")
+        .build()
+        .err()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ClientError::InvalidHeader { name: "user-agent", ref value } if value == "acme\nrouter"
+    ));
+
+    let err = TitanClient::builder("token")
+        .client_id("acme\r")
+        .build()
+        .err()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ClientError::InvalidHeader { name: CLIENT_ID_HEADER, ref value } if value == "acme\r"
+    ));
+}
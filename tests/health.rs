@@ -0,0 +1,68 @@
+use titan_swap_api_client::{
+    health::HealthState,
+    quote::QuoteRequest,
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    TitanClient,
+};
+
+#[tokio::test]
+async fn classifies_health_responses() {
+    for (status, body, expected) in [
+        (200, "ok", HealthState::Healthy),
+        (401, "bad token", HealthState::Unauthorized),
+        (403, "", HealthState::Unauthorized),
+        (
+            503,
+            "maintenance\n",
+            HealthState::Degraded("503 Service Unavailable: maintenance".to_string()),
+        ),
+    ] {
+        let server = MockTitanServer::start().await;
+        server.respond_with_health(status, body).mount().await;
+
+        let health = server.client().health().await.unwrap();
+        assert_eq!(health.state, expected);
+    }
+}
+
+#[tokio::test]
+async fn unreachable_endpoint_is_an_error() {
+    let client = TitanClient::new(
+        "test-token".to_string(),
+        Some("http://127.0.0.1:1".to_string()),
+    );
+
+    let err = client.health().await.unwrap_err();
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn probing_moves_failover_off_unhealthy_endpoints() {
+    let primary = MockTitanServer::start().await;
+    primary.respond_with_health(503, "").mount().await;
+    primary
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+    let secondary = MockTitanServer::start().await;
+    secondary.respond_with_health(200, "").mount().await;
+    secondary
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+
+    let client = TitanClient::builder("test-token")
+        .base_urls(vec![primary.uri(), secondary.uri()])
+        .build()
+        .unwrap();
+
+    let results = client.probe_endpoints().await;
+    assert_eq!(results.len(), 2);
+    assert!(!results[0].1.as_ref().unwrap().is_healthy());
+    assert!(results[1].1.as_ref().unwrap().is_healthy());
+    assert_eq!(client.current_endpoint(), secondary.uri());
+
+    client.quote(&QuoteRequest::default()).await.unwrap();
+    assert_eq!(primary.received_request_count().await, 0);
+    assert_eq!(secondary.received_request_count().await, 1);
+}
@@ -0,0 +1,75 @@
+use std::time::{Duration, Instant};
+use titan_swap_api_client::{
+    quote::QuoteRequest,
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+};
+
+#[tokio::test]
+async fn hedged_request_beats_slow_primary() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_delay(
+            SwapRouteBuilder::default().amounts(1, 1),
+            Duration::from_secs(2),
+        )
+        .times(1)
+        .mount()
+        .await;
+    server
+        .respond_with_route(SwapRouteBuilder::default().amounts(1, 2))
+        .mount()
+        .await;
+
+    let client = server
+        .client_builder()
+        .hedge_after(Duration::from_millis(50))
+        .build()
+        .unwrap();
+
+    let started = Instant::now();
+    let quote = client.quote(&QuoteRequest::default()).await.unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(1));
+    assert_eq!(quote.out_amount, 2);
+    assert_eq!(server.received_request_count().await, 2);
+}
+
+#[tokio::test]
+async fn fast_response_is_not_hedged() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+
+    let client = server
+        .client_builder()
+        .hedge_after(Duration::from_millis(500))
+        .build()
+        .unwrap();
+    client.quote(&QuoteRequest::default()).await.unwrap();
+
+    assert_eq!(server.received_request_count().await, 1);
+}
+
+#[tokio::test]
+async fn hedge_budget_caps_extra_load() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_delay(SwapRouteBuilder::default(), Duration::from_millis(100))
+        .mount()
+        .await;
+
+    let client = server
+        .client_builder()
+        .hedge_after(Duration::from_millis(10))
+        .max_hedge_ratio(0.1)
+        .build()
+        .unwrap();
+    for _ in 0..10 {
+        client.quote(&QuoteRequest::default()).await.unwrap();
+    }
+
+    // Ten requests at a 10% ratio allow a single hedge.
+    assert_eq!(server.received_request_count().await, 11);
+}
@@ -0,0 +1,90 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{QuoteRequest, QuoteResponse},
+    swap::SwapInstructionsOptions,
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn request(user_pubkey: Option<Pubkey>) -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey,
+        ..sample_request()
+    }
+}
+
+async fn server() -> MockTitanServer {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, 186_000_000),
+        )
+        .mount()
+        .await;
+    server
+}
+
+#[test]
+fn user_public_key_is_omitted_when_unset() {
+    assert!(!request(None)
+        .query_params()
+        .iter()
+        .any(|(name, _)| *name == "userPublicKey"));
+    assert!(request(Some(USER))
+        .query_params()
+        .contains(&("userPublicKey", USER.to_string())));
+}
+
+#[tokio::test]
+async fn quotes_without_a_user_are_indicative() {
+    let server = server().await;
+    let client = server.client();
+
+    let quote = client.quote(&request(None)).await.unwrap();
+    assert!(quote.indicative);
+    assert_eq!(quote.out_amount, 186_000_000);
+    assert!(!server.received_query_params().await[0].contains_key("userPublicKey"));
+
+    let executable = client.quote(&request(Some(USER))).await.unwrap();
+    assert!(!executable.indicative);
+    assert!(client.swap(&executable).is_ok());
+}
+
+#[tokio::test]
+async fn indicative_quotes_cannot_be_swapped() {
+    let server = server().await;
+    let client = server.client();
+    let quote = client.quote(&request(None)).await.unwrap();
+
+    assert!(matches!(
+        client.swap(&quote),
+        Err(ClientError::MissingUserContext)
+    ));
+    assert!(matches!(
+        client
+            .swap_instructions(&quote, SwapInstructionsOptions::default())
+            .await,
+        Err(ClientError::MissingUserContext)
+    ));
+    assert!(server.received_swap_instructions_bodies().await.is_empty());
+}
+
+#[test]
+fn marker_round_trips_through_json() {
+    let route = SwapRouteBuilder::new(SOL_MINT, USDC_MINT).build();
+    let quote = QuoteResponse::from_route(&request(None), route.clone());
+    let json = serde_json::to_value(&quote).unwrap();
+    assert_eq!(json["indicative"], true);
+    assert!(
+        serde_json::from_value::<QuoteResponse>(json)
+            .unwrap()
+            .indicative
+    );
+
+    let executable = QuoteResponse::from_route(&request(Some(USER)), route);
+    let json = serde_json::to_value(&executable).unwrap();
+    assert!(json.get("indicative").is_none());
+}
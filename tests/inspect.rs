@@ -0,0 +1,152 @@
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, pubkey, pubkey::Pubkey,
+};
+use solana_system_interface::instruction as system_instruction;
+use titan_swap_api_client::{
+    constants::{TOKEN_PROGRAM_ID, USDC_MINT},
+    inspect::{classify_instruction, classify_instructions, DexOperation, InstructionKind, Router},
+    swap::SwapResponse,
+    venues::Dex,
+};
+
+const TITAN_ROUTE_WITH_TIP: &str = include_str!("fixtures/swap_titan_route_with_tip.json");
+const DIRECT_DEX: &str = include_str!("fixtures/swap_direct_dex.json");
+const SWAP_INSTRUCTIONS: &[u8] = include_bytes!("fixtures/swap_instructions_response.msgpack");
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+const WSOL_ATA: Pubkey = pubkey!("3emsAVdmGKERbHjmGfQ6oZ1e35dkf5iYcS6U4CPKFVaa");
+const JITO_TIP: Pubkey = pubkey!("96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5");
+
+#[test]
+fn titan_route_with_tip() {
+    let swap = SwapResponse::from_json(TITAN_ROUTE_WITH_TIP).unwrap();
+    assert_eq!(
+        classify_instructions(&swap),
+        [
+            InstructionKind::SetComputeUnitLimit { units: 240_000 },
+            InstructionKind::SetComputeUnitPrice {
+                micro_lamports: 50_000
+            },
+            InstructionKind::CreateAssociatedTokenAccount { idempotent: true },
+            InstructionKind::Transfer {
+                lamports: 1_000_000_000,
+                to: WSOL_ATA
+            },
+            InstructionKind::SyncNative {
+                program_id: TOKEN_PROGRAM_ID
+            },
+            InstructionKind::Route {
+                router: Router::Titan
+            },
+            InstructionKind::CloseAccount {
+                program_id: TOKEN_PROGRAM_ID
+            },
+            InstructionKind::Tip {
+                lamports: 10_000,
+                to: JITO_TIP
+            },
+        ]
+    );
+}
+
+#[test]
+fn msgpack_swap_instructions() {
+    let swap = SwapResponse::from_bytes(SWAP_INSTRUCTIONS).unwrap();
+    let descriptions: Vec<String> = classify_instructions(&swap)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(descriptions, ["create ATA (idempotent)", "Titan route"]);
+}
+
+#[test]
+fn direct_dex_swaps() {
+    let swap = SwapResponse::from_json(DIRECT_DEX).unwrap();
+    let kinds = classify_instructions(&swap);
+    assert_eq!(
+        kinds[1..],
+        [
+            InstructionKind::Dex {
+                dex: Dex::Whirlpool,
+                operation: DexOperation::Swap
+            },
+            InstructionKind::Dex {
+                dex: Dex::RaydiumCpmm,
+                operation: DexOperation::Swap
+            },
+            InstructionKind::TokenTransfer {
+                program_id: TOKEN_PROGRAM_ID,
+                amount: 186_000
+            },
+            InstructionKind::Dex {
+                dex: Dex::Whirlpool,
+                operation: DexOperation::Other
+            },
+            InstructionKind::Memo {
+                text: "titan:direct".to_string()
+            },
+            InstructionKind::Unknown {
+                program_id: pubkey!("57dtdPpVpuVrNkhARs4DPwYuDDTYQBWCFrkKN12U2xcY")
+            },
+        ]
+    );
+
+    let descriptions: Vec<String> = kinds.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        descriptions,
+        [
+            "set compute unit limit 180000",
+            "Whirlpool swap",
+            "Raydium CPMM swap",
+            "token transfer 186000",
+            "Whirlpool instruction",
+            "memo \"titan:direct\"",
+            "unknown program 57dtdPpVpuVrNkhARs4DPwYuDDTYQBWCFrkKN12U2xcY",
+        ]
+    );
+}
+
+#[test]
+fn matches_sdk_encodings() {
+    assert_eq!(
+        classify_instruction(&ComputeBudgetInstruction::set_compute_unit_limit(1_400_000)),
+        InstructionKind::SetComputeUnitLimit { units: 1_400_000 }
+    );
+    assert_eq!(
+        classify_instruction(&ComputeBudgetInstruction::set_compute_unit_price(7)),
+        InstructionKind::SetComputeUnitPrice { micro_lamports: 7 }
+    );
+    assert_eq!(
+        classify_instruction(&system_instruction::transfer(&USER, &JITO_TIP, 5_000)),
+        InstructionKind::Tip {
+            lamports: 5_000,
+            to: JITO_TIP
+        }
+    );
+}
+
+#[test]
+fn unrecognized_instructions_of_known_programs() {
+    let ix = system_instruction::create_account(&USER, &WSOL_ATA, 1, 165, &TOKEN_PROGRAM_ID);
+    assert_eq!(
+        classify_instruction(&ix).to_string(),
+        "System Program instruction"
+    );
+
+    // Truncated data never panics.
+    let truncated = Instruction::new_with_bytes(TOKEN_PROGRAM_ID, &[3, 1, 2], vec![]);
+    assert!(matches!(
+        classify_instruction(&truncated),
+        InstructionKind::Program {
+            name: "Token Program",
+            ..
+        }
+    ));
+    let mint = Instruction::new_with_bytes(USDC_MINT, &[], vec![]);
+    assert_eq!(
+        classify_instruction(&mint),
+        InstructionKind::Unknown {
+            program_id: USDC_MINT
+        }
+    );
+}
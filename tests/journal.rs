@@ -0,0 +1,213 @@
+use solana_sdk::{instruction::Instruction, pubkey, pubkey::Pubkey, signature::Signature};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    journal::{read_journal, ExecutionStatus, JournalRecord, JsonlFileJournal},
+    quote::QuoteRequest,
+    swap::SwapInstructionsOptions,
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    TitanClient,
+};
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey: Some(USER),
+        slippage_bps: 50,
+        ..sample_request()
+    }
+}
+
+fn route() -> SwapRouteBuilder {
+    SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+        .amounts(1_000_000_000, 187_000_000)
+        .instruction(&Instruction::new_with_bytes(
+            pubkey!("T1TANpTeScyeqVzzgNViGDNrkQ6qHz9KrSBS4aNXvGT"),
+            &[7],
+            vec![],
+        ))
+}
+
+fn journal_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "titan-journal-{}-{}.jsonl",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+/// Drops the client, then writes out and closes its journal.
+fn close(client: TitanClient, journal: Arc<JsonlFileJournal>) {
+    drop(client);
+    Arc::into_inner(journal).unwrap().close().unwrap();
+}
+
+#[tokio::test]
+async fn records_round_trip() {
+    let path = journal_path("round-trip");
+    let server = MockTitanServer::start().await;
+    server.respond_with_route(route()).mount().await;
+    let journal = Arc::new(JsonlFileJournal::open(&path).unwrap());
+    let client = server
+        .client_builder()
+        .journal(journal.clone())
+        .build()
+        .unwrap();
+
+    let quote = client.quote(&request()).await.unwrap();
+    let swap = client.swap(&quote).unwrap();
+    let signature = Signature::new_unique();
+    client.record_execution(&signature, &ExecutionStatus::Landed);
+    close(client, journal);
+
+    let records = read_journal(&path).unwrap();
+    assert_eq!(records.len(), 3);
+    match &records[0] {
+        JournalRecord::Quote {
+            request: recorded,
+            quote: Some(recorded_quote),
+            error: None,
+            ..
+        } => {
+            assert_eq!(*recorded, request());
+            assert_eq!(recorded_quote.out_amount, 187_000_000);
+            assert_eq!(
+                recorded_quote.route_data().unwrap().in_amount,
+                1_000_000_000
+            );
+        }
+        other => panic!("expected a quote record, got {:?}", other),
+    }
+    match &records[1] {
+        JournalRecord::Swap {
+            swap: Some(recorded),
+            error: None,
+            ..
+        } => assert_eq!(*recorded, swap),
+        other => panic!("expected a swap record, got {:?}", other),
+    }
+    match &records[2] {
+        JournalRecord::Execution {
+            signature: recorded,
+            status: ExecutionStatus::Landed,
+            ..
+        } => assert_eq!(*recorded, signature),
+        other => panic!("expected an execution record, got {:?}", other),
+    }
+    assert!(records
+        .windows(2)
+        .all(|pair| pair[0].timestamp_ms() <= pair[1].timestamp_ms()));
+    fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn failed_quotes_are_recorded() {
+    let path = journal_path("failed");
+    let server = MockTitanServer::start().await;
+    server.respond_with_error(400, "bad mint").mount().await;
+    let journal = Arc::new(JsonlFileJournal::open(&path).unwrap());
+    let client = server
+        .client_builder()
+        .journal(journal.clone())
+        .build()
+        .unwrap();
+
+    assert!(client.quote(&request()).await.is_err());
+    close(client, journal);
+
+    match read_journal(&path).unwrap().as_slice() {
+        [JournalRecord::Quote {
+            quote: None,
+            error: Some(error),
+            ..
+        }] => assert!(error.contains("400"), "{}", error),
+        other => panic!("expected one failed quote record, got {:?}", other),
+    }
+    fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn rejected_swaps_are_recorded() {
+    let path = journal_path("rejected-swap");
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route().referral_fee(500, 5))
+        .mount()
+        .await;
+    let journal = Arc::new(JsonlFileJournal::open(&path).unwrap());
+    let client = server
+        .client_builder()
+        .journal(journal.clone())
+        .build()
+        .unwrap();
+
+    let quote = client.quote(&request()).await.unwrap();
+    let options = SwapInstructionsOptions {
+        fee_account: Some(Pubkey::new_unique()),
+        ..Default::default()
+    };
+    assert!(client.swap_instructions(&quote, options).await.is_err());
+    close(client, journal);
+
+    match read_journal(&path).unwrap().as_slice() {
+        [JournalRecord::Quote { .. }, JournalRecord::Swap {
+            swap: None,
+            error: Some(error),
+            ..
+        }] => assert!(error.contains("fee account"), "{}", error),
+        other => panic!("expected a rejected swap record, got {:?}", other),
+    }
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn crash_mid_write_keeps_complete_records() {
+    let path = journal_path("crash");
+    // A buffer smaller than one record, so records reach the file in pieces.
+    let journal = JsonlFileJournal::with_buffer_size(&path, 64).unwrap();
+    for status in [
+        ExecutionStatus::Landed,
+        ExecutionStatus::Failed {
+            reason: "blockhash expired".to_string(),
+        },
+        ExecutionStatus::Landed,
+    ] {
+        journal.append(JournalRecord::Execution {
+            timestamp_ms: 1_760_000_000_000,
+            signature: Signature::new_unique(),
+            status,
+        });
+    }
+    drop(journal);
+
+    let written = fs::read(&path).unwrap();
+    let last_line_start = written[..written.len() - 1]
+        .iter()
+        .rposition(|&byte| byte == b'\n')
+        .unwrap()
+        + 1;
+    // Cut the file anywhere inside the last record, as a crash between
+    // buffer flushes would.
+    for cut in last_line_start + 1..written.len() - 1 {
+        fs::write(&path, &written[..cut]).unwrap();
+        assert_eq!(read_journal(&path).unwrap().len(), 2, "cut at {}", cut);
+    }
+    fs::write(&path, &written).unwrap();
+    assert_eq!(read_journal(&path).unwrap().len(), 3);
+
+    // Damage before the last line isn't a crash and is reported.
+    let mut damaged = written.clone();
+    damaged[1] = b'#';
+    fs::write(&path, &damaged).unwrap();
+    assert_eq!(
+        read_journal(&path).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+    fs::remove_file(&path).unwrap();
+}
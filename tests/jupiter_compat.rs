@@ -0,0 +1,83 @@
+use serde_json::Value;
+use titan_swap_api_client::{
+    jupiter::JupiterQuote,
+    quote::{QuoteResponse, SwapMode},
+};
+
+const QUOTE_V1: &str = include_str!("fixtures/quote_response_v1.json");
+const JUPITER_QUOTE: &str = include_str!("fixtures/jupiter_quote_v6.json");
+
+/// Same keys and value types at every level; arrays are compared by their
+/// first element.
+fn assert_same_schema(actual: &Value, expected: &Value, path: &str) {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => {
+            let actual_keys: Vec<_> = actual.keys().collect();
+            let expected_keys: Vec<_> = expected.keys().collect();
+            assert_eq!(actual_keys, expected_keys, "keys differ at {path}");
+            for (key, value) in expected {
+                assert_same_schema(&actual[key], value, &format!("{path}.{key}"));
+            }
+        }
+        (Value::Array(actual), Value::Array(expected)) => {
+            if let (Some(actual), Some(expected)) = (actual.first(), expected.first()) {
+                assert_same_schema(actual, expected, &format!("{path}[0]"));
+            }
+        }
+        (Value::String(_), Value::String(_))
+        | (Value::Number(_), Value::Number(_))
+        | (Value::Bool(_), Value::Bool(_))
+        | (Value::Null, Value::Null) => {}
+        _ => panic!("type differs at {path}: {actual} vs {expected}"),
+    }
+}
+
+#[test]
+fn jupiter_fixture_round_trips_byte_for_byte() {
+    let quote: JupiterQuote = serde_json::from_str(JUPITER_QUOTE).unwrap();
+
+    assert_eq!(quote.route_plan.len(), 2);
+    assert_eq!(quote.route_plan[0].percent, 60);
+    assert_eq!(serde_json::to_string_pretty(&quote).unwrap(), JUPITER_QUOTE);
+}
+
+#[test]
+fn titan_quote_matches_jupiter_schema() {
+    let quote = QuoteResponse::from_json_str(QUOTE_V1).unwrap();
+
+    let actual: Value = serde_json::from_str(&quote.to_jupiter_json().unwrap()).unwrap();
+    let expected: Value = serde_json::from_str(JUPITER_QUOTE).unwrap();
+    assert_same_schema(&actual, &expected, "$");
+}
+
+#[test]
+fn titan_quote_converts_to_jupiter_values() {
+    let quote = QuoteResponse::from_json_str(QUOTE_V1).unwrap();
+    let jupiter = JupiterQuote::from(quote.clone());
+
+    assert_eq!(jupiter.input_mint, quote.input_mint);
+    assert_eq!(jupiter.in_amount, 1_000_000_000);
+    assert_eq!(jupiter.out_amount, 187_654_321);
+    assert_eq!(jupiter.other_amount_threshold, 186_716_049);
+    assert_eq!(jupiter.swap_mode, SwapMode::ExactIn);
+    assert_eq!(jupiter.platform_fee, None);
+    assert_eq!(jupiter.price_impact_pct, "0");
+    assert_eq!(jupiter.route_plan[0].percent, 100);
+    assert_eq!(jupiter.route_plan[0].swap_info.label, "Whirlpool");
+    assert_eq!(jupiter.context_slot, Some(312_000_000));
+    assert_eq!(jupiter.time_taken, Some(0.0042));
+}
+
+#[test]
+fn missing_context_slot_stays_unset() {
+    let mut json: Value = serde_json::from_str(JUPITER_QUOTE).unwrap();
+    let object = json.as_object_mut().unwrap();
+    object.remove("contextSlot");
+    object.remove("timeTaken");
+    let quote: JupiterQuote = serde_json::from_value(json).unwrap();
+    assert_eq!(quote.context_slot, None);
+
+    let route = quote.into_swap_route();
+    assert_eq!(route.context_slot, None);
+    assert_eq!(route.time_taken_ns, None);
+}
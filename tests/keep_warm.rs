@@ -0,0 +1,51 @@
+use std::time::Duration;
+use titan_swap_api_client::test_utils::MockTitanServer;
+
+async fn health_pings(server: &MockTitanServer) -> usize {
+    server
+        .received_paths()
+        .await
+        .iter()
+        .filter(|path| path.ends_with("/health"))
+        .count()
+}
+
+#[tokio::test]
+async fn keep_warm_pings_until_the_last_clone_is_dropped() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_health(200, "ok").mount().await;
+
+    let client = server
+        .client_builder()
+        .keep_warm(Duration::from_millis(20))
+        .build()
+        .unwrap();
+    let clone = client.clone();
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(health_pings(&server).await >= 3);
+
+    drop(client);
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    assert!(
+        health_pings(&server).await >= 4,
+        "a live clone keeps the task running"
+    );
+
+    drop(clone);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let stopped_at = health_pings(&server).await;
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(health_pings(&server).await, stopped_at);
+}
+
+#[tokio::test]
+async fn no_pings_without_keep_warm() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_health(200, "ok").mount().await;
+
+    let _client = server.client();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(health_pings(&server).await, 0);
+}
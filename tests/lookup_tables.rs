@@ -0,0 +1,171 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    format::ResponseFormat,
+    observer::Observer,
+    quote::{QuoteRequest, QuoteResponse},
+    swap::SwapWarning,
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    TitanClient,
+};
+
+const DUPLICATE_LOOKUP_TABLES_JSON: &[u8] = include_bytes!("fixtures/duplicate_lookup_tables.json");
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+#[derive(Default)]
+struct RecordingObserver {
+    warnings: Mutex<Vec<SwapWarning>>,
+}
+
+impl Observer for RecordingObserver {
+    fn on_swap_warning(&self, warning: SwapWarning) {
+        self.warnings.lock().unwrap().push(warning);
+    }
+}
+
+impl RecordingObserver {
+    // Observer calls are fire-and-forget, so give the spawned task a moment to land.
+    async fn wait_for_warning(&self) -> SwapWarning {
+        for _ in 0..100 {
+            if let Some(warning) = self.warnings.lock().unwrap().first() {
+                return *warning;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("observer was not notified");
+    }
+}
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey: Some(USER),
+        ..sample_request()
+    }
+}
+
+fn observed_client(
+    server: &MockTitanServer,
+    format: ResponseFormat,
+) -> (TitanClient, Arc<RecordingObserver>) {
+    let observer = Arc::new(RecordingObserver::default());
+    let client = server
+        .client_builder()
+        .response_format(format)
+        .observer(observer.clone())
+        .build()
+        .unwrap();
+    (client, observer)
+}
+
+/// A route whose one instruction touches `accounts` distinct accounts.
+fn wide_route(accounts: u8) -> SwapRouteBuilder {
+    let metas = (0..accounts)
+        .map(|n| AccountMeta::new(Pubkey::new_from_array([n + 10; 32]), false))
+        .collect();
+    SwapRouteBuilder::new(SOL_MINT, USDC_MINT).instruction(&Instruction::new_with_bytes(
+        Pubkey::new_from_array([4; 32]),
+        &[],
+        metas,
+    ))
+}
+
+#[tokio::test]
+async fn repeated_lookup_tables_are_fetched_once() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_json_body(DUPLICATE_LOOKUP_TABLES_JSON)
+        .mount()
+        .await;
+    let (client, observer) = observed_client(&server, ResponseFormat::Json);
+    let quote = client.quote(&request()).await.unwrap();
+
+    let swap = client.swap(&quote).unwrap();
+    assert_eq!(swap.address_lookup_table_addresses.len(), 5);
+    assert_eq!(
+        swap.lookup_table_addresses(),
+        [
+            pubkey!("7rpqGdBpp77CctWoM6okSNwBzLedZDrmzWdGy2kzaeTh"),
+            pubkey!("B6o93voSQUjUNAN79ZcSeydqW9gVYjXaVMSQrfiw9Y9k"),
+            pubkey!("996KieH1yfBpZDCjib3iJVXLyyczJX8aKYD6UzB7rPAW"),
+        ]
+    );
+    assert_eq!(
+        observer.wait_for_warning().await,
+        SwapWarning::DuplicateLookupTables {
+            listed: 5,
+            unique: 3
+        }
+    );
+}
+
+#[test]
+fn lookup_table_addresses_are_unique() {
+    let table = |n| Pubkey::new_from_array([n; 32]);
+    let route = [2, 1, 2, 3, 1]
+        .into_iter()
+        .fold(SwapRouteBuilder::new(SOL_MINT, USDC_MINT), |route, n| {
+            route.address_lookup_table(table(n))
+        })
+        .build();
+    let swap = titan_swap_api_client::transform::route_to_swap_response(&route).unwrap();
+
+    assert_eq!(
+        swap.address_lookup_table_addresses,
+        [2, 1, 2, 3, 1].map(table)
+    );
+    assert_eq!(
+        swap.lookup_table_addresses(),
+        [table(2), table(1), table(3)]
+    );
+}
+
+#[tokio::test]
+async fn warns_when_a_large_route_has_no_lookup_tables() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_route(wide_route(40)).mount().await;
+    let (client, observer) = observed_client(&server, ResponseFormat::Msgpack);
+    let quote = client.quote(&request()).await.unwrap();
+
+    let swap = client.swap(&quote).unwrap();
+    let warning = swap.lookup_table_warning();
+    assert!(matches!(
+        warning,
+        Some(SwapWarning::MissingLookupTables { estimated_size }) if estimated_size > 1_232
+    ));
+    assert_eq!(observer.wait_for_warning().await, warning.unwrap());
+}
+
+#[test]
+fn small_routes_and_routes_with_tables_need_no_warning() {
+    let swap = |route: SwapRouteBuilder| {
+        titan_swap_api_client::transform::route_to_swap_response(&route.build()).unwrap()
+    };
+    assert_eq!(swap(wide_route(10)).lookup_table_warning(), None);
+    assert_eq!(
+        swap(wide_route(40).address_lookup_table(Pubkey::new_unique())).lookup_table_warning(),
+        None
+    );
+}
+
+#[test]
+fn warns_inline_without_a_runtime() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let (client, observer, quote) = runtime.block_on(async {
+        let server = MockTitanServer::start().await;
+        server.respond_with_route(wide_route(40)).mount().await;
+        let (client, observer) = observed_client(&server, ResponseFormat::Msgpack);
+        let quote: QuoteResponse = client.quote(&request()).await.unwrap();
+        (client, observer, quote)
+    });
+    drop(runtime);
+
+    client.swap(&quote).unwrap();
+    assert_eq!(observer.warnings.lock().unwrap().len(), 1);
+}
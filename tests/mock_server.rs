@@ -0,0 +1,47 @@
+use titan_swap_api_client::{
+    account_limits::AccountLimits,
+    quote::QuoteRequest,
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        slippage_bps: 75,
+        account_limits: AccountLimits::with_total(40),
+        ..sample_request()
+    }
+}
+
+#[tokio::test]
+async fn serves_built_route_and_records_params() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default().amounts(1_000_000, 990_000))
+        .mount()
+        .await;
+
+    let quote = server.client().quote(&request()).await.unwrap();
+    assert_eq!(quote.out_amount, 990_000);
+    assert_eq!(quote.route_plan.len(), 1);
+
+    server.assert_query_param("slippageBps", "75").await;
+    server.assert_query_param("accountsLimitTotal", "40").await;
+    server.assert_query_param("amount", "1000000000").await;
+}
+
+#[tokio::test]
+async fn scripted_failure_then_success() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_status(503).times(1).mount().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+
+    let client = server.client();
+    let err = client.quote(&request()).await.unwrap_err();
+    assert!(matches!(err, ClientError::RequestFailed { status, .. } if status == 503));
+    assert!(client.quote(&request()).await.is_ok());
+    assert_eq!(server.received_request_count().await, 2);
+}
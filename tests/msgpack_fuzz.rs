@@ -0,0 +1,88 @@
+use proptest::prelude::*;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::OnceLock;
+use titan_swap_api_client::{
+    quote::{QuoteRequest, QuoteResponse, SwapQuotes},
+    test_utils::SwapRouteBuilder,
+    ClientError, TitanClient,
+};
+
+const FIXTURES: &[&[u8]] = &[
+    include_bytes!("fixtures/single_hop.msgpack"),
+    include_bytes!("fixtures/multi_hop_split.msgpack"),
+    include_bytes!("fixtures/platform_fee.msgpack"),
+    include_bytes!("fixtures/prebuilt_transaction.msgpack"),
+];
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey: Some(Pubkey::new_unique()),
+        ..Default::default()
+    }
+}
+
+/// Shared by every case: building a client sets up a new HTTP client and
+/// TLS configuration, which would dominate the run.
+fn client() -> &'static TitanClient {
+    static CLIENT: OnceLock<TitanClient> = OnceLock::new();
+    CLIENT.get_or_init(|| TitanClient::new(String::new(), None))
+}
+
+// Decode and transform exactly as `quote()` and `swap()` do; must never panic.
+fn decode_and_transform(bytes: &[u8]) {
+    let Ok(quotes) = rmp_serde::from_slice::<SwapQuotes>(bytes) else {
+        return;
+    };
+    for route in quotes.quotes.into_values() {
+        let quote = QuoteResponse::from_route(&request(), route);
+        let _ = client().swap(&quote);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn arbitrary_bytes(bytes in prop::collection::vec(any::<u8>(), 0..4096)) {
+        decode_and_transform(&bytes);
+    }
+
+    #[test]
+    fn truncated_fixture(fixture in 0..FIXTURES.len(), cut in any::<prop::sample::Index>()) {
+        let bytes = FIXTURES[fixture];
+        decode_and_transform(&bytes[..cut.index(bytes.len())]);
+    }
+
+    #[test]
+    fn mutated_fixture(
+        fixture in 0..FIXTURES.len(),
+        mutations in prop::collection::vec((any::<prop::sample::Index>(), any::<u8>()), 1..16),
+    ) {
+        let mut bytes = FIXTURES[fixture].to_vec();
+        for (index, value) in mutations {
+            let i = index.index(bytes.len());
+            bytes[i] = value;
+        }
+        decode_and_transform(&bytes);
+    }
+
+    #[test]
+    fn compute_units_never_truncate(compute_units in any::<u64>()) {
+        let route = SwapRouteBuilder::default()
+            .compute_units(compute_units, compute_units)
+            .build();
+        let quote = QuoteResponse::from_route(&request(), route);
+        let result = client().swap(&quote);
+
+        match u32::try_from(compute_units) {
+            Ok(limit) => prop_assert_eq!(result.unwrap().compute_unit_limit, Some(limit)),
+            Err(_) => {
+                let rejected = matches!(
+                    result,
+                    Err(ClientError::InvalidResponse { field: "computeUnits", .. })
+                );
+                prop_assert!(rejected);
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use titan_swap_api_client::{
+    observer::{Observer, QuoteResultKind},
+    quote::QuoteRequest,
+    solana_sdk::pubkey::Pubkey,
+    swap::SwapWarning,
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+};
+
+#[derive(Default)]
+struct RecordingObserver {
+    quotes: Mutex<Vec<(QuoteResultKind, Option<String>, usize)>>,
+    http: Mutex<Vec<(Option<u16>, usize)>>,
+}
+
+impl Observer for RecordingObserver {
+    fn on_quote(
+        &self,
+        kind: QuoteResultKind,
+        _latency: Duration,
+        provider: Option<&str>,
+        route_hops: usize,
+    ) {
+        self.quotes
+            .lock()
+            .unwrap()
+            .push((kind, provider.map(str::to_string), route_hops));
+    }
+
+    fn on_http(&self, status: Option<u16>, _latency: Duration, bytes: usize) {
+        self.http.lock().unwrap().push((status, bytes));
+    }
+}
+
+impl RecordingObserver {
+    // Observer calls are fire-and-forget, so give the spawned tasks a moment to land.
+    async fn wait_for_events(&self) {
+        for _ in 0..100 {
+            if !self.quotes.lock().unwrap().is_empty() && !self.http.lock().unwrap().is_empty() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("observer was not notified");
+    }
+}
+
+async fn observe(server: &MockTitanServer) -> Arc<RecordingObserver> {
+    let observer = Arc::new(RecordingObserver::default());
+    let client = server
+        .client_builder()
+        .observer(observer.clone())
+        .build()
+        .unwrap();
+    let _ = client.quote(&QuoteRequest::default()).await;
+    observer.wait_for_events().await;
+    observer
+}
+
+#[tokio::test]
+async fn records_success() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default().provider("Titan"))
+        .mount()
+        .await;
+
+    let observer = observe(&server).await;
+    assert_eq!(
+        *observer.quotes.lock().unwrap(),
+        vec![(QuoteResultKind::Success, Some("Titan".to_string()), 1)]
+    );
+    let http = observer.http.lock().unwrap();
+    assert_eq!(http.len(), 1);
+    assert_eq!(http[0].0, Some(200));
+    assert!(http[0].1 > 0);
+}
+
+#[tokio::test]
+async fn records_no_routes() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_error(404, "No routes found")
+        .mount()
+        .await;
+
+    let observer = observe(&server).await;
+    assert_eq!(
+        *observer.quotes.lock().unwrap(),
+        vec![(QuoteResultKind::NoRoutes, None, 0)]
+    );
+    assert_eq!(*observer.http.lock().unwrap(), vec![(Some(404), 0)]);
+}
+
+#[tokio::test]
+async fn records_server_error() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_status(500).mount().await;
+
+    let observer = observe(&server).await;
+    assert_eq!(
+        *observer.quotes.lock().unwrap(),
+        vec![(QuoteResultKind::Error, None, 0)]
+    );
+    assert_eq!(*observer.http.lock().unwrap(), vec![(Some(500), 0)]);
+}
+
+#[test]
+fn attempts_dropped_without_a_runtime_are_reported_inline() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let observer = Arc::new(RecordingObserver::default());
+    let (_server, quote) = runtime.block_on(async {
+        let server = MockTitanServer::start().await;
+        server
+            .respond_with_delay(SwapRouteBuilder::default(), Duration::from_secs(5))
+            .mount()
+            .await;
+        let client = server
+            .client_builder()
+            .observer(observer.clone())
+            .build()
+            .unwrap();
+        let mut quote = Box::pin(async move { client.quote(&QuoteRequest::default()).await });
+        // Start the request, then leave it in flight.
+        tokio::select! {
+            _ = &mut quote => panic!("quote finished before its delay"),
+            () = tokio::time::sleep(Duration::from_millis(100)) => {}
+        }
+        (server, quote)
+    });
+    drop(runtime);
+
+    drop(quote);
+    assert_eq!(*observer.http.lock().unwrap(), vec![(None, 0)]);
+}
+
+struct PanickingObserver;
+
+impl Observer for PanickingObserver {
+    fn on_swap_warning(&self, _warning: SwapWarning) {
+        panic!("observer bug");
+    }
+}
+
+#[test]
+fn panics_in_inline_calls_do_not_reach_the_caller() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let table = Pubkey::new_unique();
+    let (_server, client, quote) = runtime.block_on(async {
+        let server = MockTitanServer::start().await;
+        server
+            .respond_with_route(
+                SwapRouteBuilder::default()
+                    .address_lookup_table(table)
+                    .address_lookup_table(table),
+            )
+            .mount()
+            .await;
+        let client = server
+            .client_builder()
+            .observer(Arc::new(PanickingObserver))
+            .build()
+            .unwrap();
+        let request = QuoteRequest {
+            user_pubkey: Some(Pubkey::new_unique()),
+            ..sample_request()
+        };
+        let quote = client.quote(&request).await.unwrap();
+        (server, client, quote)
+    });
+    drop(runtime);
+
+    // Outside a runtime the duplicate table warning is reported inline.
+    let swap = client.swap(&quote).unwrap();
+    assert_eq!(swap.address_lookup_table_addresses, [table, table]);
+}
@@ -0,0 +1,138 @@
+use solana_sdk::{instruction::Instruction, pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    inspect::onchain_min_out,
+    quote::QuoteRequest,
+    swap::SwapResponse,
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    venues::Dex,
+    ClientError,
+};
+
+const JUPITER_ROUTE: &str = include_str!("fixtures/swap_jupiter_route.json");
+const DIRECT_DEX: &str = include_str!("fixtures/swap_direct_dex.json");
+const TITAN_ROUTE_WITH_TIP: &str = include_str!("fixtures/swap_titan_route_with_tip.json");
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+const JUPITER_EXACT_OUT_ROUTE: [u8; 8] = [0xd0, 0x33, 0xef, 0x97, 0x7b, 0x2b, 0xed, 0x5c];
+
+fn fixture(json: &str) -> SwapResponse {
+    SwapResponse::from_json(json).unwrap()
+}
+
+/// The Jupiter fixture with its route instruction's trailing parameters
+/// replaced.
+fn jupiter_route(quoted_out_amount: u64, slippage_bps: u16) -> SwapResponse {
+    let mut swap = fixture(JUPITER_ROUTE);
+    let data = &mut swap.instructions[1].data;
+    let tail = data.len() - 11;
+    data[tail..tail + 8].copy_from_slice(&quoted_out_amount.to_le_bytes());
+    data[tail + 8..tail + 10].copy_from_slice(&slippage_bps.to_le_bytes());
+    swap
+}
+
+fn raydium_swap_base_in(minimum_amount_out: u64) -> Instruction {
+    let mut data = vec![9];
+    data.extend_from_slice(&1_000_000_000u64.to_le_bytes());
+    data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+    Instruction {
+        program_id: Dex::Raydium.program_id(),
+        accounts: Vec::new(),
+        data,
+    }
+}
+
+#[test]
+fn jupiter_route_layout() {
+    assert_eq!(fixture(JUPITER_ROUTE).has_onchain_min_out(), Some(true));
+    assert_eq!(jupiter_route(0, 50).has_onchain_min_out(), Some(false));
+    assert_eq!(
+        jupiter_route(186_000_000, 10_000).has_onchain_min_out(),
+        Some(false)
+    );
+
+    // ExactOut routes fix the output; their threshold bounds the input.
+    let mut exact_out = jupiter_route(0, 0);
+    exact_out.instructions[1].data[..8].copy_from_slice(&JUPITER_EXACT_OUT_ROUTE);
+    assert_eq!(exact_out.has_onchain_min_out(), Some(true));
+
+    let mut unknown = fixture(JUPITER_ROUTE);
+    unknown.instructions[1].data[..8].copy_from_slice(&[0; 8]);
+    assert_eq!(unknown.has_onchain_min_out(), None);
+}
+
+#[test]
+fn direct_dex_layouts() {
+    // The last swap is a Raydium CPMM swap_base_input with a minimum out.
+    let mut swap = fixture(DIRECT_DEX);
+    assert_eq!(swap.has_onchain_min_out(), Some(true));
+
+    swap.instructions[2].data[16..24].copy_from_slice(&0u64.to_le_bytes());
+    assert_eq!(swap.has_onchain_min_out(), Some(false));
+
+    assert_eq!(onchain_min_out(&[raydium_swap_base_in(1)]), Some(true));
+    assert_eq!(onchain_min_out(&[raydium_swap_base_in(0)]), Some(false));
+}
+
+#[test]
+fn only_the_final_swap_counts() {
+    // An unchecked hop followed by a checked one is still protected.
+    assert_eq!(
+        onchain_min_out(&[raydium_swap_base_in(0), raydium_swap_base_in(1)]),
+        Some(true)
+    );
+    assert_eq!(
+        onchain_min_out(&[raydium_swap_base_in(1), raydium_swap_base_in(0)]),
+        Some(false)
+    );
+}
+
+#[test]
+fn unknown_layouts_are_none() {
+    assert_eq!(fixture(TITAN_ROUTE_WITH_TIP).has_onchain_min_out(), None);
+    assert_eq!(onchain_min_out(&[]), None);
+}
+
+async fn strict_swap(instruction: &Instruction) -> Result<SwapResponse, ClientError> {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+                .amounts(1_000_000_000, 186_000_000)
+                .instruction(instruction),
+        )
+        .mount()
+        .await;
+    let client = server
+        .client_builder()
+        .require_onchain_min_out(true)
+        .build()
+        .unwrap();
+    let request = QuoteRequest {
+        input_mint: SOL_MINT,
+        output_mint: USDC_MINT,
+        amount: 1_000_000_000,
+        user_pubkey: Some(USER),
+        ..Default::default()
+    };
+
+    let quote = client.quote(&request).await.unwrap();
+    client.swap(&quote)
+}
+
+#[tokio::test]
+async fn strict_mode_refuses_unchecked_swaps() {
+    assert!(strict_swap(&raydium_swap_base_in(185_000_000))
+        .await
+        .is_ok());
+    assert!(matches!(
+        strict_swap(&raydium_swap_base_in(0)).await,
+        Err(ClientError::NoOnchainMinOut)
+    ));
+
+    let titan_route = fixture(TITAN_ROUTE_WITH_TIP).instructions[5].clone();
+    assert!(matches!(
+        strict_swap(&titan_route).await,
+        Err(ClientError::NoOnchainMinOut)
+    ));
+}
@@ -0,0 +1,137 @@
+use solana_sdk::{instruction::Instruction, pubkey, pubkey::Pubkey};
+use solana_system_interface::instruction as system_instruction;
+use titan_swap_api_client::{
+    inspect::{classify_instruction, InstructionKind},
+    swap::{PartitionedInstructions, SwapResponse},
+};
+
+const TITAN_ROUTE_WITH_TIP: &str = include_str!("fixtures/swap_titan_route_with_tip.json");
+const DIRECT_DEX: &str = include_str!("fixtures/swap_direct_dex.json");
+const JUPITER_ROUTE: &str = include_str!("fixtures/swap_jupiter_route.json");
+const SWAP_INSTRUCTIONS: &[u8] = include_bytes!("fixtures/swap_instructions_response.msgpack");
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn kinds(instructions: &[Instruction]) -> Vec<String> {
+    instructions
+        .iter()
+        .map(|ix| classify_instruction(ix).to_string())
+        .collect()
+}
+
+/// The parts as descriptions, after checking they hold every instruction.
+fn partition(swap: &SwapResponse) -> [Vec<String>; 3] {
+    let PartitionedInstructions {
+        setup,
+        core,
+        cleanup,
+    } = swap.partition_instructions();
+    assert_eq!(
+        setup.len() + core.len() + cleanup.len(),
+        swap.instructions.len()
+    );
+    [kinds(&setup), kinds(&core), kinds(&cleanup)]
+}
+
+#[test]
+fn titan_route_with_wrapping_and_tip() {
+    let swap = SwapResponse::from_json(TITAN_ROUTE_WITH_TIP).unwrap();
+    let [setup, core, cleanup] = partition(&swap);
+
+    assert_eq!(
+        setup,
+        [
+            "create ATA (idempotent)",
+            "transfer 1000000000 lamports to 3emsAVdmGKERbHjmGfQ6oZ1e35dkf5iYcS6U4CPKFVaa",
+            "sync native SOL",
+        ]
+    );
+    assert_eq!(
+        core,
+        [
+            "set compute unit limit 240000",
+            "set compute unit price 50000 micro-lamports",
+            "Titan route",
+            "transfer tip 10000 lamports to 96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+        ]
+    );
+    assert_eq!(cleanup, ["close token account"]);
+}
+
+#[test]
+fn routes_without_wrapping() {
+    let swap = SwapResponse::from_bytes(SWAP_INSTRUCTIONS).unwrap();
+    assert_eq!(
+        partition(&swap),
+        [
+            vec!["create ATA (idempotent)".to_string()],
+            vec!["Titan route".to_string()],
+            vec![],
+        ]
+    );
+
+    let swap = SwapResponse::from_json(JUPITER_ROUTE).unwrap();
+    let [setup, core, cleanup] = partition(&swap);
+    assert!(setup.is_empty() && cleanup.is_empty());
+    assert_eq!(core.len(), swap.instructions.len());
+}
+
+#[test]
+fn direct_dex_swaps_are_all_core() {
+    let swap = SwapResponse::from_json(DIRECT_DEX).unwrap();
+    let [setup, core, cleanup] = partition(&swap);
+
+    assert!(setup.is_empty() && cleanup.is_empty());
+    assert_eq!(core, kinds(&swap.instructions));
+}
+
+#[test]
+fn transfers_are_setup_only_when_synced() {
+    let mut swap = SwapResponse::from_json(TITAN_ROUTE_WITH_TIP).unwrap();
+    let payment = system_instruction::transfer(&USER, &Pubkey::new_unique(), 1_000);
+    swap.instructions.insert(2, payment.clone());
+
+    let PartitionedInstructions { setup, core, .. } = swap.partition_instructions();
+    assert_eq!(setup.len(), 3);
+    assert!(core.contains(&payment));
+}
+
+#[test]
+fn instructions_after_the_close_keep_it_core() {
+    let mut swap = SwapResponse::from_json(TITAN_ROUTE_WITH_TIP).unwrap();
+    swap.instructions.push(Instruction::new_with_bytes(
+        Pubkey::new_unique(),
+        &[],
+        vec![],
+    ));
+
+    assert!(swap.partition_instructions().cleanup.is_empty());
+}
+
+#[test]
+fn routes_of_only_setup_instructions_are_all_core() {
+    let mut swap = SwapResponse::from_json(TITAN_ROUTE_WITH_TIP).unwrap();
+    swap.instructions.truncate(5);
+
+    let partitioned = swap.partition_instructions();
+    assert!(partitioned.setup.is_empty());
+    assert_eq!(partitioned.core, swap.instructions);
+}
+
+#[test]
+fn without_setup_and_cleanup() {
+    let swap = SwapResponse::from_json(TITAN_ROUTE_WITH_TIP).unwrap();
+    let PartitionedInstructions { core, cleanup, .. } = swap.partition_instructions();
+
+    let without_setup = swap.clone().without_setup();
+    assert_eq!(without_setup.instructions[..2], core[..2]);
+    assert_eq!(without_setup.instructions.len(), core.len() + cleanup.len());
+    assert_eq!(without_setup.compute_unit_limit, swap.compute_unit_limit);
+
+    let core_only = without_setup.without_cleanup();
+    assert_eq!(core_only.instructions, core);
+    assert!(!core_only.instructions.iter().any(|ix| matches!(
+        classify_instruction(ix),
+        InstructionKind::CloseAccount { .. }
+    )));
+}
@@ -0,0 +1,110 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    composite::CompositeQuote,
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{PlatformFee, QuoteRequest, QuoteResponse, SwapMode},
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+};
+
+const PLATFORM_FEE_300BPS: &[u8] = include_bytes!("fixtures/platform_fee_300bps.msgpack");
+
+const BONK: Pubkey = pubkey!("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263");
+
+fn request(input_mint: Pubkey, output_mint: Pubkey, swap_mode: SwapMode) -> QuoteRequest {
+    QuoteRequest {
+        input_mint,
+        output_mint,
+        swap_mode: Some(swap_mode),
+        ..sample_request()
+    }
+}
+
+/// A single-step quote whose step outputs `gross`, with `fee` taken from it.
+fn quote(input_mint: Pubkey, output_mint: Pubkey, gross: u64, fee: u64) -> QuoteResponse {
+    let route = SwapRouteBuilder::new(input_mint, output_mint)
+        .amounts(1_000_000_000, gross - fee)
+        .platform_fee(fee, 300)
+        .step(
+            "Whirlpool",
+            Pubkey::new_unique(),
+            input_mint,
+            output_mint,
+            1_000_000_000,
+            gross,
+            1_000_000_000,
+        );
+    QuoteResponse::from_route(
+        &request(input_mint, output_mint, SwapMode::ExactIn),
+        route.build(),
+    )
+}
+
+#[tokio::test]
+async fn decodes_fees_above_255_bps() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_body(PLATFORM_FEE_300BPS).mount().await;
+
+    let quote = server
+        .client()
+        .quote(&request(SOL_MINT, USDC_MINT, SwapMode::ExactIn))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        quote.platform_fee,
+        Some(PlatformFee {
+            amount: 5_629_629,
+            fee_bps: 300
+        })
+    );
+    assert_eq!(quote.out_amount, 182_024_692);
+    assert_eq!(quote.net_out_amount(), quote.out_amount);
+    assert_eq!(quote.platform_fee_mint(), Some(USDC_MINT));
+
+    let json = quote.to_json_string().unwrap();
+    let restored = QuoteResponse::from_json_str(&json).unwrap();
+    assert_eq!(restored.platform_fee.unwrap().fee_bps, 300);
+}
+
+#[test]
+fn net_out_amount_takes_the_fee_from_the_gross_output() {
+    let quote = quote(SOL_MINT, USDC_MINT, 100_000_000, 3_000_000);
+    assert_eq!(quote.net_out_amount(), 97_000_000);
+
+    let mut without_plan = quote.clone();
+    without_plan.route_plan.clear();
+    without_plan.out_amount = 100_000_000;
+    assert_eq!(without_plan.net_out_amount(), 97_000_000);
+
+    let mut without_fee = quote;
+    without_fee.platform_fee = None;
+    assert_eq!(without_fee.net_out_amount(), without_fee.out_amount);
+    assert_eq!(without_fee.platform_fee_mint(), None);
+}
+
+#[test]
+fn exact_out_fees_are_charged_in_the_input_mint() {
+    let route = SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+        .swap_mode(SwapMode::ExactOut)
+        .amounts(1_030_000_000, 1_000_000_000)
+        .platform_fee(30_000_000, 300);
+    let quote = QuoteResponse::from_route(
+        &request(SOL_MINT, USDC_MINT, SwapMode::ExactOut),
+        route.build(),
+    );
+
+    assert_eq!(quote.platform_fee_mint(), Some(SOL_MINT));
+    assert_eq!(quote.net_out_amount(), 1_000_000_000);
+}
+
+#[test]
+fn composite_fees_include_platform_fees() {
+    let composite = CompositeQuote {
+        first: quote(SOL_MINT, USDC_MINT, 100_000_000, 3_000_000),
+        second: quote(USDC_MINT, BONK, 50_000_000, 1_500_000),
+    };
+
+    let fees = composite.fees_by_mint().unwrap();
+    assert_eq!(fees.get(&USDC_MINT), Some(&3_000_000));
+    assert_eq!(fees.get(&BONK), Some(&1_500_000));
+}
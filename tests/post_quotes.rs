@@ -0,0 +1,78 @@
+use titan_swap_api_client::{
+    quote::QuoteRequest,
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+};
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        slippage_bps: 50,
+        excluded_dexes: Some("Phoenix".to_string()),
+        ..sample_request()
+    }
+}
+
+#[tokio::test]
+async fn get_and_post_decode_identically() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::default()
+                .amounts(1_000_000, 990_000)
+                .context_slot(42),
+        )
+        .mount()
+        .await;
+
+    let via_get = server.client().quote(&request()).await.unwrap();
+    let via_post = server
+        .client_builder()
+        .prefer_post(true)
+        .build()
+        .unwrap()
+        .quote(&request())
+        .await
+        .unwrap();
+
+    // Client-side timings naturally differ between the two requests, so
+    // compare the decoded fields.
+    assert_eq!(via_get.input_mint, via_post.input_mint);
+    assert_eq!(via_get.output_mint, via_post.output_mint);
+    assert_eq!(via_get.in_amount, via_post.in_amount);
+    assert_eq!(via_get.out_amount, via_post.out_amount);
+    assert_eq!(via_get.swap_mode, via_post.swap_mode);
+    assert_eq!(via_get.slippage_bps, via_post.slippage_bps);
+    assert!(via_get.platform_fee.is_none() && via_post.platform_fee.is_none());
+    assert_eq!(via_get.route_plan, via_post.route_plan);
+    assert_eq!(via_get.context_slot, Some(42));
+    assert_eq!(via_get.context_slot, via_post.context_slot);
+    assert_eq!(server.received_quote_methods().await, vec!["GET", "POST"]);
+
+    let params = server.received_query_params().await;
+    assert_eq!(params[0], params[1]);
+    assert_eq!(params[1]["excludeDexes"], "phoenix");
+    assert_eq!(params[1]["slippageBps"], "50");
+}
+
+#[tokio::test]
+async fn long_queries_switch_to_post() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+
+    let excluded = (0..40)
+        .map(|i| format!("SomeVenueWithALongName{i}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    let request = QuoteRequest {
+        excluded_dexes: Some(excluded),
+        ..request()
+    };
+
+    let client = server.client_builder().post_threshold(512).build().unwrap();
+    client.quote(&self::request()).await.unwrap();
+    client.quote(&request).await.unwrap();
+
+    assert_eq!(server.received_quote_methods().await, vec!["GET", "POST"]);
+}
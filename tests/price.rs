@@ -0,0 +1,111 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    price::PriceResponse,
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+};
+
+const USDT: Pubkey = pubkey!("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB");
+
+fn price(base_mint: Pubkey, price: f64) -> PriceResponse {
+    PriceResponse {
+        base_mint,
+        quote_mint: USDC_MINT,
+        price,
+        timestamp_ms: 1_760_000_000_000,
+    }
+}
+
+async fn server_with_reference(reference: f64) -> MockTitanServer {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, 187_654_321),
+        )
+        .mount()
+        .await;
+    server
+        .respond_with_price(&price(SOL_MINT, reference))
+        .mount()
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn fetches_single_and_batch_prices() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_price(&price(SOL_MINT, 0.1876))
+        .mount()
+        .await;
+    server
+        .respond_with_prices(&[price(SOL_MINT, 0.1876), price(USDT, 1.0001)])
+        .mount()
+        .await;
+    let client = server.client();
+
+    assert_eq!(
+        client.price(SOL_MINT, USDC_MINT).await.unwrap(),
+        price(SOL_MINT, 0.1876)
+    );
+    let prices = client.prices(&[SOL_MINT, USDT], USDC_MINT).await.unwrap();
+    assert_eq!(prices.len(), 2);
+    assert_eq!(prices[1].base_mint, USDT);
+}
+
+#[test]
+fn json_form_uses_base58_mints() {
+    let json = serde_json::to_value(price(SOL_MINT, 0.1876)).unwrap();
+    assert_eq!(json["baseMint"], SOL_MINT.to_string());
+    assert_eq!(json["quoteMint"], USDC_MINT.to_string());
+
+    let decoded: PriceResponse = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded, price(SOL_MINT, 0.1876));
+    assert!((decoded.ui_price(9, 6) - 187.6).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn sanity_check_is_off_by_default() {
+    let server = server_with_reference(0.1876).await;
+
+    let quote = server.client().quote(&sample_request()).await.unwrap();
+    assert!(quote.price_check.is_none());
+}
+
+#[tokio::test]
+async fn quote_close_to_reference_is_not_flagged() {
+    let server = server_with_reference(0.1876).await;
+    let client = server
+        .client_builder()
+        .price_sanity_check(10)
+        .build()
+        .unwrap();
+
+    let check = client
+        .quote(&sample_request())
+        .await
+        .unwrap()
+        .price_check
+        .unwrap();
+    assert!(!check.flagged);
+    assert!((check.deviation_bps - 2.9).abs() < 0.1);
+}
+
+#[tokio::test]
+async fn quote_far_from_reference_is_flagged() {
+    let server = server_with_reference(0.2).await;
+    let client = server
+        .client_builder()
+        .price_sanity_check(100)
+        .build()
+        .unwrap();
+
+    let check = client
+        .quote(&sample_request())
+        .await
+        .unwrap()
+        .price_check
+        .unwrap();
+    assert!(check.flagged);
+    assert!(check.deviation_bps < -600.0);
+}
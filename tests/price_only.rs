@@ -0,0 +1,127 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    options::{QuoteDetail, QuoteRequestOptions},
+    quote::{QuoteRequest, QuoteResponse},
+    swap::SwapInstructionsOptions,
+    test_utils::{sample_request, MockTitanServer},
+    transform::{decode_price_only_quotes, decode_swap_quotes},
+    ClientError,
+};
+
+const SINGLE_HOP: &[u8] = include_bytes!("fixtures/single_hop.msgpack");
+const SINGLE_HOP_JSON: &[u8] = include_bytes!("fixtures/single_hop.json");
+const SWAP_QUOTES_LARGE: &[u8] = include_bytes!("fixtures/swap_quotes_large.msgpack");
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey: Some(USER),
+        slippage_bps: 50,
+        ..sample_request()
+    }
+}
+
+fn price_only() -> QuoteRequestOptions {
+    QuoteRequestOptions::with_detail(QuoteDetail::PriceOnly)
+}
+
+#[test]
+fn decode_skips_execution_data_only() {
+    for fixture in [SINGLE_HOP, SWAP_QUOTES_LARGE] {
+        let full = decode_swap_quotes(fixture).unwrap();
+        let slim = decode_price_only_quotes(fixture).unwrap();
+
+        assert_eq!(slim.id, full.id);
+        assert_eq!(
+            slim.quotes.keys().collect::<Vec<_>>(),
+            full.quotes.keys().collect::<Vec<_>>()
+        );
+        for (slim, full) in slim.quotes.values().zip(full.quotes.values()) {
+            assert_eq!(slim.out_amount, full.out_amount);
+            assert_eq!(slim.expires_at_ms, full.expires_at_ms);
+            assert!(slim.address_lookup_tables.is_empty());
+            assert!(slim.transaction.is_none());
+        }
+    }
+}
+
+#[tokio::test]
+async fn price_only_quotes_match_full_quotes() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_body(SINGLE_HOP).mount().await;
+    let client = server.client();
+
+    let full = client.quote(&request()).await.unwrap();
+    let slim = client
+        .quote_with_options(&request(), &price_only())
+        .await
+        .unwrap();
+
+    assert_eq!(full.detail, QuoteDetail::Full);
+    assert_eq!(slim.detail, QuoteDetail::PriceOnly);
+    assert_eq!(slim.out_amount, full.out_amount);
+    assert_eq!(slim.route_plan, full.route_plan);
+    assert!(full.is_executable());
+    assert!(!slim.is_executable());
+}
+
+#[tokio::test]
+async fn price_only_quotes_cannot_be_swapped() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_body(SINGLE_HOP).mount().await;
+    let client = server.client();
+    let quote = client
+        .quote_with_options(&request(), &price_only())
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        client.swap(&quote),
+        Err(ClientError::PriceOnlyQuote)
+    ));
+    assert!(matches!(
+        client
+            .swap_instructions(&quote, SwapInstructionsOptions::default())
+            .await,
+        Err(ClientError::PriceOnlyQuote)
+    ));
+}
+
+#[tokio::test]
+async fn json_responses_are_stripped() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_json_body(SINGLE_HOP_JSON).mount().await;
+
+    let quote = server
+        .client()
+        .quote_with_options(&request(), &price_only())
+        .await
+        .unwrap();
+
+    assert_eq!(quote.out_amount, 187_654_321);
+    assert_eq!(quote.detail, QuoteDetail::PriceOnly);
+    assert!(quote.route_data().unwrap().address_lookup_tables.is_empty());
+}
+
+#[test]
+fn offline_conversion_keeps_the_detail() {
+    let full = decode_swap_quotes(SINGLE_HOP).unwrap();
+    let route = full.quotes.into_values().next().unwrap();
+    let quote = QuoteResponse::from_route(&request(), route);
+    assert_eq!(quote.detail, QuoteDetail::Full);
+    assert!(serde_json::to_value(&quote)
+        .unwrap()
+        .get("detail")
+        .is_none());
+
+    let slim = decode_price_only_quotes(SINGLE_HOP).unwrap();
+    let route = slim.quotes.into_values().next().unwrap();
+    let quote = QuoteResponse::from_route(&request(), route);
+    assert_eq!(quote.detail, QuoteDetail::PriceOnly);
+
+    let json = serde_json::to_value(&quote).unwrap();
+    assert_eq!(json["detail"], "PriceOnly");
+    let restored: QuoteResponse = serde_json::from_value(json).unwrap();
+    assert_eq!(restored.detail, QuoteDetail::PriceOnly);
+}
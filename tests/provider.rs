@@ -0,0 +1,98 @@
+use titan_swap_api_client::{
+    quote::QuoteRequest,
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+async fn server_with_providers() -> MockTitanServer {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_routes([
+            SwapRouteBuilder::default()
+                .provider("Titan")
+                .amounts(1_000_000, 990_000),
+            SwapRouteBuilder::default()
+                .provider("Hashflow")
+                .amounts(1_000_000, 995_000),
+        ])
+        .mount()
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn quote_records_the_provider_it_came_from() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default().provider("Titan"))
+        .mount()
+        .await;
+
+    let quote = server
+        .client()
+        .quote(&QuoteRequest::default())
+        .await
+        .unwrap();
+
+    assert_eq!(quote.provider(), Some("Titan"));
+}
+
+#[tokio::test]
+async fn quote_from_picks_the_requested_provider() {
+    let server = server_with_providers().await;
+    let client = server.client();
+
+    let quote = client
+        .quote_from(&QuoteRequest::default(), "hashflow")
+        .await
+        .unwrap();
+    assert_eq!(quote.provider(), Some("Hashflow"));
+    assert_eq!(quote.out_amount, 995_000);
+
+    let quote = client
+        .quote_from(&QuoteRequest::default(), "Titan")
+        .await
+        .unwrap();
+    assert_eq!(quote.provider(), Some("Titan"));
+    assert_eq!(quote.out_amount, 990_000);
+}
+
+#[tokio::test]
+async fn quote_from_a_missing_provider_is_unavailable() {
+    let server = server_with_providers().await;
+
+    let err = server
+        .client()
+        .quote_from(&QuoteRequest::default(), "Jupiter")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, ClientError::ProviderUnavailable(provider) if provider == "Jupiter"));
+}
+
+#[tokio::test]
+async fn quote_all_attributes_each_quote() {
+    let server = server_with_providers().await;
+
+    let quotes = server
+        .client()
+        .quote_all(&QuoteRequest::default())
+        .await
+        .unwrap();
+
+    for (provider, quote) in &quotes {
+        assert_eq!(quote.provider(), Some(provider.as_str()));
+    }
+}
+
+#[test]
+fn provider_round_trips_through_json() {
+    let quote = titan_swap_api_client::quote::QuoteResponse::from_route(
+        &QuoteRequest::default(),
+        SwapRouteBuilder::default().build(),
+    )
+    .with_provider("Titan");
+
+    let json = quote.to_json_string().unwrap();
+    assert!(json.contains(r#""provider":"Titan""#));
+}
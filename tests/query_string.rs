@@ -0,0 +1,205 @@
+use proptest::prelude::*;
+use solana_sdk::pubkey::Pubkey;
+use titan_swap_api_client::{
+    account_limits::{AccountLimits, ACCOUNT_LOCK_LIMIT},
+    constants::{SOL_MINT, USDC_MINT},
+    query::{ParseError, QuoteRequestTemplate},
+    quote::{Provider, QuoteRequest, SizeConstraint, SwapMode, MAX_TRANSACTION_BYTES},
+};
+
+fn arb_request() -> impl Strategy<Value = QuoteRequest> {
+    (
+        (
+            any::<[u8; 32]>(),
+            any::<[u8; 32]>(),
+            any::<u64>(),
+            proptest::option::of(any::<[u8; 32]>()),
+            proptest::option::of(1..=ACCOUNT_LOCK_LIMIT),
+            prop_oneof![
+                Just(None),
+                Just(Some(SwapMode::ExactIn)),
+                Just(Some(SwapMode::ExactOut)),
+                "[A-Za-z]{1,12}".prop_map(|mode| Some(SwapMode::Other(mode))),
+            ],
+        ),
+        (
+            any::<u16>(),
+            any::<Option<bool>>(),
+            proptest::option::of("[a-z0-9-]{1,12}(,[a-z0-9-]{1,12}){0,3}"),
+            proptest::option::of(
+                (1..=MAX_TRANSACTION_BYTES)
+                    .prop_map(|bytes| SizeConstraint::max_transaction_bytes(bytes).unwrap()),
+            ),
+            proptest::option::of(1..=ACCOUNT_LOCK_LIMIT),
+            any::<bool>(),
+        ),
+    )
+        .prop_map(
+            |(
+                (input, output, amount, user, total, swap_mode),
+                (slippage_bps, only_direct_routes, excluded_dexes, size, writable, titan),
+            )| QuoteRequest {
+                input_mint: Pubkey::from(input),
+                output_mint: Pubkey::from(output),
+                amount,
+                user_pubkey: user.map(Pubkey::from),
+                account_limits: AccountLimits {
+                    total,
+                    // At most the total, as validation requires.
+                    writable: writable
+                        .map(|writable| total.map_or(writable, |total| writable.min(total))),
+                },
+                swap_mode: swap_mode.map(|mode| match mode {
+                    // Spellings of known modes parse back to the known mode.
+                    SwapMode::Other(mode) => mode.parse().unwrap_or(SwapMode::Other(mode)),
+                    mode => mode,
+                }),
+                slippage_bps,
+                only_direct_routes,
+                excluded_dexes,
+                size_constraint: size,
+                providers: titan.then_some(Provider::Titan),
+                ..Default::default()
+            },
+        )
+}
+
+fn sorted(mut params: Vec<(&'static str, String)>) -> Vec<(&'static str, String)> {
+    params.sort();
+    params
+}
+
+proptest! {
+    #[test]
+    fn request_round_trips(request in arb_request()) {
+        let parsed = QuoteRequest::from_query_str(&request.to_query_string()).unwrap();
+        // Dex labels are sent canonicalized, so that is what comes back.
+        let mut expected = request.clone();
+        expected.excluded_dexes = request
+            .query_params()
+            .into_iter()
+            .find(|(name, _)| *name == "excludeDexes")
+            .map(|(_, dexes)| dexes);
+        prop_assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn params_round_trip_modulo_order(request in arb_request()) {
+        let mut params = request.query_params();
+        params.reverse();
+        let query = params
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("&");
+        let parsed = QuoteRequest::from_query_str(&query).unwrap();
+        prop_assert_eq!(sorted(parsed.query_params()), sorted(request.query_params()));
+    }
+}
+
+#[test]
+fn parses_a_logged_url() {
+    let url = format!(
+        "https://api.titan.exchange/api/v1/quote/swap?inputMint={SOL_MINT}&outputMint={USDC_MINT}\
+         &amount=1000000&userPublicKey=11111111111111111111111111111111\
+         &swapMode=ExactOut&slippageBps=50&excludeDexes=raydium-clmm%2Cphoenix"
+    );
+    let request = QuoteRequest::from_query_str(&url).unwrap();
+    assert_eq!(request.input_mint, SOL_MINT);
+    assert_eq!(request.output_mint, USDC_MINT);
+    assert_eq!(request.amount, 1_000_000);
+    assert_eq!(request.swap_mode, Some(SwapMode::ExactOut));
+    assert_eq!(request.slippage_bps, 50);
+    assert_eq!(
+        request.excluded_dexes.as_deref(),
+        Some("raydium-clmm,phoenix")
+    );
+}
+
+#[test]
+fn canonicalizes_dex_labels() {
+    let request = QuoteRequest {
+        input_mint: SOL_MINT,
+        output_mint: USDC_MINT,
+        amount: 1,
+        excluded_dexes: Some("Raydium CLMM, Phoenix".to_string()),
+        ..Default::default()
+    };
+    let parsed = QuoteRequest::from_query_str(&request.to_query_string()).unwrap();
+    assert_eq!(
+        parsed.excluded_dexes.as_deref(),
+        Some("raydium-clmm,phoenix")
+    );
+}
+
+#[test]
+fn errors_name_the_parameter() {
+    let base = format!("inputMint={SOL_MINT}&outputMint={USDC_MINT}&amount=1");
+    let parse = |extra: &str| QuoteRequest::from_query_str(&format!("{base}&{extra}"));
+
+    assert_eq!(
+        parse("slipageBps=50"),
+        Err(ParseError::UnknownParameter("slipageBps".to_string()))
+    );
+    assert_eq!(
+        parse("amount=2"),
+        Err(ParseError::DuplicateParameter("amount".to_string()))
+    );
+    assert!(matches!(
+        parse("slippageBps=lots"),
+        Err(ParseError::InvalidValue { name: "slippageBps", ref value, .. }) if value == "lots"
+    ));
+    assert!(matches!(
+        parse("onlyDirectRoutes=yes"),
+        Err(ParseError::InvalidValue {
+            name: "onlyDirectRoutes",
+            ..
+        })
+    ));
+    assert_eq!(
+        parse("excludeDexes=%G1"),
+        Err(ParseError::InvalidEncoding("%G1".to_string()))
+    );
+    assert_eq!(
+        QuoteRequest::from_query_str(&format!("inputMint={SOL_MINT}&amount=1")),
+        Err(ParseError::MissingParameter("outputMint"))
+    );
+    assert!(QuoteRequest::from_query_str(&format!(
+        "inputMint=nope&outputMint={USDC_MINT}&amount=1"
+    ))
+    .unwrap_err()
+    .to_string()
+    .contains("inputMint"));
+}
+
+#[test]
+fn cache_key_covers_client_side_filters() {
+    let request = QuoteRequest {
+        input_mint: SOL_MINT,
+        output_mint: USDC_MINT,
+        amount: 1_000,
+        ..Default::default()
+    };
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let excluding = |keys: Vec<Pubkey>| QuoteRequest {
+        excluded_amm_keys: Some(keys),
+        ..request.clone()
+    };
+
+    assert_eq!(request.cache_key(), request.to_query_string());
+    assert_ne!(excluding(vec![a]).cache_key(), request.cache_key());
+    assert_ne!(
+        excluding(vec![a]).cache_key(),
+        excluding(vec![b]).cache_key()
+    );
+    assert_eq!(
+        excluding(vec![a, b]).cache_key(),
+        excluding(vec![b, a, b]).cache_key()
+    );
+
+    let template = QuoteRequestTemplate::new(excluding(vec![a]));
+    assert_eq!(
+        template.cache_key_with_amount(5),
+        template.request_with_amount(5).cache_key()
+    );
+}
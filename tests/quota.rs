@@ -0,0 +1,171 @@
+use reqwest::header::HeaderName;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quota::{QuotaHeaders, QuotaInfo},
+    rate_limit::RateLimitStrategy,
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+fn route() -> SwapRouteBuilder {
+    SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, 186_000_000)
+}
+
+#[tokio::test]
+async fn quotes_carry_the_reported_quota() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route())
+        .header("x-ratelimit-remaining", 41)
+        .header("x-ratelimit-limit", 60)
+        .header("x-ratelimit-reset", 1_900_000_000)
+        .mount()
+        .await;
+    let client = server.client();
+    assert_eq!(client.last_quota(), None);
+
+    let quote = client.quote(&sample_request()).await.unwrap();
+    let expected = QuotaInfo {
+        remaining: Some(41),
+        limit: Some(60),
+        reset_at: Some(UNIX_EPOCH + Duration::from_secs(1_900_000_000)),
+    };
+    assert_eq!(quote.quota, Some(expected));
+    assert_eq!(client.clone().last_quota(), Some(expected));
+}
+
+#[tokio::test]
+async fn small_resets_are_seconds_from_now() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route())
+        .header("x-ratelimit-reset", 30)
+        .mount()
+        .await;
+
+    let before = SystemTime::now();
+    let quota = server
+        .client()
+        .quote(&sample_request())
+        .await
+        .unwrap()
+        .quota
+        .unwrap();
+    let reset_at = quota.reset_at.unwrap();
+
+    assert_eq!((quota.remaining, quota.limit), (None, None));
+    assert!(reset_at >= before + Duration::from_secs(30));
+    assert!(reset_at <= SystemTime::now() + Duration::from_secs(30));
+}
+
+#[tokio::test]
+async fn responses_without_quota_headers_leave_it_unset() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_route(route()).mount().await;
+    let client = server.client();
+
+    let quote = client.quote(&sample_request()).await.unwrap();
+    assert_eq!(quote.quota, None);
+    assert_eq!(client.last_quota(), None);
+    assert!(serde_json::to_value(&quote).unwrap().get("quota").is_none());
+}
+
+#[tokio::test]
+async fn header_names_are_configurable() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route())
+        .header("x-ratelimit-remaining", 1)
+        .header("ratelimit-remaining", 7)
+        .header("ratelimit-limit", "not a number")
+        .mount()
+        .await;
+    let client = server
+        .client_builder()
+        .quota_headers(QuotaHeaders {
+            remaining: HeaderName::from_static("ratelimit-remaining"),
+            limit: HeaderName::from_static("ratelimit-limit"),
+            reset: HeaderName::from_static("ratelimit-reset"),
+        })
+        .build()
+        .unwrap();
+
+    let quota = client
+        .quote(&sample_request())
+        .await
+        .unwrap()
+        .quota
+        .unwrap();
+    assert_eq!(quota.remaining, Some(7));
+    assert_eq!(quota.limit, None);
+}
+
+#[tokio::test]
+async fn errors_carry_the_reported_quota() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_error(429, "slow down")
+        .header("x-ratelimit-remaining", 0)
+        .header("x-ratelimit-limit", 60)
+        .mount()
+        .await;
+    let client = server.client();
+
+    let err = client.quote(&sample_request()).await.unwrap_err();
+    assert!(matches!(err, ClientError::RequestFailed { status, .. } if status == 429));
+    let quota = err.quota().unwrap();
+    assert!(quota.is_exhausted());
+    assert_eq!(client.last_quota().as_ref(), Some(quota));
+    assert_eq!(ClientError::NoRoutesAvailable.quota(), None);
+}
+
+#[tokio::test]
+async fn the_rate_limiter_pauses_until_an_exhausted_quota_resets() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route())
+        .header("x-ratelimit-remaining", 0)
+        .header("x-ratelimit-reset", 60)
+        .mount()
+        .await;
+    let client = server
+        .client_builder()
+        .rate_limit(100, Duration::from_secs(1))
+        .rate_limit_strategy(RateLimitStrategy::Reject)
+        .build()
+        .unwrap();
+
+    client.quote(&sample_request()).await.unwrap();
+    match client.quote(&sample_request()).await {
+        Err(ClientError::RateLimited { retry_after }) => {
+            assert!(retry_after > Duration::from_secs(55));
+            assert!(retry_after <= Duration::from_secs(60));
+        }
+        other => panic!("expected RateLimited, got {other:?}"),
+    }
+    assert_eq!(server.received_request_count().await, 1);
+}
+
+#[tokio::test]
+async fn the_rate_limiter_budget_is_capped_at_the_remaining_quota() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route())
+        .header("x-ratelimit-remaining", 1)
+        .mount()
+        .await;
+    let client = server
+        .client_builder()
+        .rate_limit(100, Duration::from_secs(3600))
+        .rate_limit_strategy(RateLimitStrategy::Reject)
+        .build()
+        .unwrap();
+
+    client.quote(&sample_request()).await.unwrap();
+    client.quote(&sample_request()).await.unwrap();
+    assert!(matches!(
+        client.quote(&sample_request()).await,
+        Err(ClientError::RateLimited { .. })
+    ));
+}
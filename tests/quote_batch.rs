@@ -0,0 +1,200 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use std::time::{Duration, Instant};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    options::QuoteRequestOptions,
+    quote::QuoteRequest,
+    rate_limit::RateLimitStrategy,
+    test_utils::{sample_request, SwapRouteBuilder},
+    ClientError, TitanClient,
+};
+use wiremock::{
+    matchers::{path_regex, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const BONK: Pubkey = pubkey!("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263");
+const JUP: Pubkey = pubkey!("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN");
+const WIF: Pubkey = pubkey!("EKpQGSJtjMFqKZ9KQanSqYXRcF8fBopzLHYxdM65zcjm");
+
+fn request(output_mint: Pubkey) -> QuoteRequest {
+    QuoteRequest {
+        output_mint,
+        ..sample_request()
+    }
+}
+
+/// Quotes USDC; rate limits BONK; rejects JUP; fails WIF with a 502.
+async fn server() -> MockServer {
+    let server = MockServer::start().await;
+    let respond = |output_mint: Pubkey, template: ResponseTemplate| {
+        Mock::given(path_regex("/quote/swap$"))
+            .and(query_param("outputMint", output_mint.to_string()))
+            .respond_with(template)
+    };
+    respond(
+        USDC_MINT,
+        ResponseTemplate::new(200)
+            .insert_header("content-type", "application/vnd.msgpack")
+            .set_body_bytes(
+                SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+                    .amounts(1_000_000_000, 187_000_000)
+                    .to_msgpack(),
+            ),
+    )
+    .mount(&server)
+    .await;
+    respond(
+        BONK,
+        ResponseTemplate::new(429).set_body_string("slow down"),
+    )
+    .mount(&server)
+    .await;
+    respond(
+        JUP,
+        ResponseTemplate::new(400).set_body_string("unknown mint"),
+    )
+    .mount(&server)
+    .await;
+    respond(
+        WIF,
+        ResponseTemplate::new(502).set_body_string("bad gateway"),
+    )
+    .mount(&server)
+    .await;
+    server
+}
+
+#[tokio::test]
+async fn reports_mixed_outcomes_in_request_order() {
+    let server = server().await;
+    let client = TitanClient::builder("test-token")
+        .base_path(server.uri())
+        .build()
+        .unwrap();
+
+    let report = client
+        .quote_batch(vec![
+            request(JUP),
+            request(USDC_MINT),
+            request(BONK),
+            request(WIF),
+            request(USDC_MINT),
+        ])
+        .await;
+
+    assert_eq!(report.total(), 5);
+    assert_eq!(report.successes.len(), 2);
+    assert!(report.successes.iter().all(
+        |(request, quote)| request.output_mint == USDC_MINT && quote.out_amount == 187_000_000
+    ));
+
+    let failed: Vec<Pubkey> = report
+        .failures
+        .iter()
+        .map(|(request, _)| request.output_mint)
+        .collect();
+    assert_eq!(failed, [JUP, BONK, WIF]);
+    assert!(matches!(
+        report.failures[0].1,
+        ClientError::RequestFailed { status, .. } if status == 400
+    ));
+    assert_eq!(report.rate_limited, 1);
+    assert!((report.failure_rate() - 0.6).abs() < 1e-9);
+
+    let retryable: Vec<Pubkey> = report
+        .retryable_failures()
+        .map(|(request, _)| request.output_mint)
+        .collect();
+    assert_eq!(retryable, [BONK, WIF]);
+    assert_eq!(report.retry_requests(), [request(BONK), request(WIF)]);
+}
+
+#[tokio::test]
+async fn client_rate_limit_is_counted() {
+    let server = server().await;
+    let client = TitanClient::builder("test-token")
+        .base_path(server.uri())
+        .rate_limit(1, Duration::from_secs(60))
+        .rate_limit_strategy(RateLimitStrategy::Reject)
+        .build()
+        .unwrap();
+
+    let report = client
+        .quote_batch(vec![
+            request(USDC_MINT),
+            request(USDC_MINT),
+            request(USDC_MINT),
+        ])
+        .await;
+
+    assert_eq!(report.successes.len(), 1);
+    assert_eq!(report.rate_limited, 2);
+    assert!(report
+        .failures
+        .iter()
+        .all(|(_, err)| matches!(err, ClientError::RateLimited { .. })));
+    assert_eq!(report.retryable_failures().count(), 2);
+}
+
+#[tokio::test]
+async fn one_timeout_bounds_the_whole_batch() {
+    let server = server().await;
+    let client = TitanClient::builder("test-token")
+        .base_path(server.uri())
+        .rate_limit(1, Duration::from_secs(60))
+        .build()
+        .unwrap();
+
+    // The first quote takes the only token; the others wait on the limiter
+    // until the batch's timeout passes.
+    let started = Instant::now();
+    let report = client
+        .quote_batch_with_options(
+            vec![
+                request(USDC_MINT),
+                request(USDC_MINT),
+                request(USDC_MINT),
+            ],
+            &QuoteRequestOptions::with_timeout(Duration::from_millis(200)),
+        )
+        .await;
+
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(report.successes.len(), 1);
+    assert_eq!(report.failures.len(), 2);
+    assert!(report
+        .failures
+        .iter()
+        .all(|(_, err)| matches!(err, ClientError::DeadlineExceeded)));
+}
+
+#[tokio::test]
+async fn empty_batch() {
+    let client = TitanClient::new("test-token".to_string(), None);
+    let report = client.quote_batch(Vec::new()).await;
+    assert_eq!(report.total(), 0);
+    assert_eq!(report.failure_rate(), 0.0);
+}
+
+#[tokio::test]
+async fn report_serializes_for_dashboards() {
+    let server = server().await;
+    let client = TitanClient::builder("test-token")
+        .base_path(server.uri())
+        .build()
+        .unwrap();
+    let report = client
+        .quote_batch(vec![request(USDC_MINT), request(BONK)])
+        .await;
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["successes"].as_array().unwrap().len(), 1);
+    assert_eq!(json["rateLimited"], 1);
+    assert!(json["elapsedMs"].is_u64());
+    let failure = &json["failures"][0];
+    assert_eq!(failure["request"]["outputMint"], BONK.to_string());
+    assert_eq!(failure["retryable"], true);
+    assert_eq!(failure["rateLimited"], true);
+    assert!(failure["error"].as_str().unwrap().contains("429"));
+}
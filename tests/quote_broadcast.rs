@@ -0,0 +1,105 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use titan_swap_api_client::constants::{SOL_MINT, USDC_MINT};
+use titan_swap_api_client::test_utils::{sample_request, MockTitanServer, SwapRouteBuilder};
+use tokio::time::timeout;
+
+const INTERVAL: Duration = Duration::from_millis(50);
+
+fn route(out_amount: u64) -> SwapRouteBuilder {
+    SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, out_amount)
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+#[tokio::test]
+async fn publishes_each_refresh() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route(187_000_000))
+        .times(1)
+        .mount()
+        .await;
+    server.respond_with_route(route(188_000_000)).mount().await;
+
+    let (mut quotes, handle) = server.client().quote_broadcast(&sample_request(), INTERVAL);
+    assert!(quotes.borrow().is_none());
+
+    quotes.changed().await.unwrap();
+    let first = quotes.borrow_and_update().clone().unwrap();
+    assert_eq!(first.out_amount, 187_000_000);
+
+    quotes.changed().await.unwrap();
+    let second = quotes.borrow_and_update().clone().unwrap();
+    assert_eq!(second.out_amount, 188_000_000);
+
+    // Subscribers share the published quote.
+    let subscriber = quotes.clone();
+    assert!(Arc::ptr_eq(
+        subscriber.borrow().as_ref().unwrap(),
+        quotes.borrow().as_ref().unwrap()
+    ));
+    handle.abort();
+}
+
+#[tokio::test]
+async fn failed_refresh_keeps_a_live_quote() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route(187_000_000).expires(now_ms() + 60_000, 0))
+        .times(1)
+        .mount()
+        .await;
+    server.respond_with_error(400, "bad request").mount().await;
+
+    let (mut quotes, handle) = server.client().quote_broadcast(&sample_request(), INTERVAL);
+    quotes.changed().await.unwrap();
+    quotes.borrow_and_update();
+
+    assert!(timeout(INTERVAL * 4, quotes.changed()).await.is_err());
+    assert!(quotes.borrow().is_some());
+    assert!(server.received_request_count().await > 1);
+    handle.abort();
+}
+
+#[tokio::test]
+async fn expired_quote_is_withdrawn_when_refresh_fails() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(route(187_000_000).expires(now_ms() + 100, 0))
+        .times(1)
+        .mount()
+        .await;
+    server.respond_with_error(400, "bad request").mount().await;
+
+    let (mut quotes, handle) = server.client().quote_broadcast(&sample_request(), INTERVAL);
+    quotes.changed().await.unwrap();
+    assert!(quotes.borrow_and_update().is_some());
+
+    timeout(Duration::from_secs(2), quotes.changed())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(quotes.borrow().is_none());
+    handle.abort();
+}
+
+#[tokio::test]
+async fn stops_when_receivers_are_dropped() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_route(route(187_000_000)).mount().await;
+
+    let (mut quotes, handle) = server.client().quote_broadcast(&sample_request(), INTERVAL);
+    quotes.changed().await.unwrap();
+    drop(quotes);
+
+    timeout(Duration::from_secs(2), handle)
+        .await
+        .unwrap()
+        .unwrap();
+}
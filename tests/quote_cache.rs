@@ -0,0 +1,205 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use titan_swap_api_client::{
+    cache::QuoteCache,
+    constants::{SOL_MINT, USDC_MINT},
+    quote::QuoteRequest,
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+};
+
+const TTL: Duration = Duration::from_millis(50);
+
+fn expiring_in(out_amount: u64, after: Duration) -> SwapRouteBuilder {
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+        .amounts(1_000_000_000, out_amount)
+        .expires(now_ms + after.as_millis() as u64, 0)
+}
+
+#[tokio::test]
+async fn serves_from_cache_within_the_ttl() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, 100))
+        .mount()
+        .await;
+    let cache = QuoteCache::new(server.client(), TTL);
+
+    let first = cache.quote(&sample_request()).await.unwrap();
+    let second = cache.quote(&sample_request()).await.unwrap();
+
+    assert!(!first.stale && !second.stale);
+    assert_eq!(first.age, Duration::ZERO);
+    assert_eq!(second.quote.out_amount, 100);
+    assert_eq!(server.received_request_count().await, 1);
+
+    let other = QuoteRequest {
+        amount: 2_000_000_000,
+        ..sample_request()
+    };
+    cache.quote(&other).await.unwrap();
+    assert_eq!(server.received_request_count().await, 2);
+}
+
+#[tokio::test]
+async fn refetches_past_the_ttl_by_default() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(expiring_in(100, Duration::from_secs(60)))
+        .times(1)
+        .mount()
+        .await;
+    server
+        .respond_with_route(expiring_in(200, Duration::from_secs(60)))
+        .mount()
+        .await;
+    let cache = QuoteCache::new(server.client(), TTL);
+
+    cache.quote(&sample_request()).await.unwrap();
+    tokio::time::sleep(TTL * 2).await;
+    let refetched = cache.quote(&sample_request()).await.unwrap();
+
+    assert!(!refetched.stale);
+    assert_eq!(refetched.quote.out_amount, 200);
+    assert_eq!(server.received_request_count().await, 2);
+}
+
+#[tokio::test]
+async fn stale_entries_are_served_while_refreshing() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(expiring_in(100, Duration::from_secs(60)))
+        .times(1)
+        .mount()
+        .await;
+    server
+        .respond_with_route(expiring_in(200, Duration::from_secs(60)))
+        .delay(Duration::from_millis(100))
+        .mount()
+        .await;
+    // Long enough for the refreshed quote to still be fresh once it lands.
+    let ttl = Duration::from_millis(300);
+    let cache = QuoteCache::new(server.client(), ttl).stale_while_revalidate(true);
+
+    cache.quote(&sample_request()).await.unwrap();
+    tokio::time::sleep(ttl * 2).await;
+
+    let stale = cache.quote(&sample_request()).await.unwrap();
+    assert!(stale.stale);
+    assert!(stale.age > ttl);
+    assert_eq!(stale.quote.out_amount, 100);
+
+    // Served again while the refresh is in flight, without a second refresh.
+    let again = cache.quote(&sample_request()).await.unwrap();
+    assert!(again.stale);
+    assert_eq!(again.quote.out_amount, 100);
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(server.received_request_count().await, 2);
+
+    let refreshed = cache.quote(&sample_request()).await.unwrap();
+    assert!(!refreshed.stale);
+    assert_eq!(refreshed.quote.out_amount, 200);
+    assert_eq!(server.received_request_count().await, 2);
+}
+
+#[tokio::test]
+async fn never_served_past_expiry() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(expiring_in(100, Duration::from_millis(100)))
+        .times(1)
+        .mount()
+        .await;
+    server
+        .respond_with_route(expiring_in(200, Duration::from_secs(60)))
+        .mount()
+        .await;
+    let cache =
+        QuoteCache::new(server.client(), Duration::from_secs(60)).stale_while_revalidate(true);
+
+    cache.quote(&sample_request()).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    let refetched = cache.quote(&sample_request()).await.unwrap();
+
+    // Expired within the TTL, so fetched again rather than served.
+    assert!(!refetched.stale);
+    assert_eq!(refetched.quote.out_amount, 200);
+    assert_eq!(server.received_request_count().await, 2);
+}
+
+#[tokio::test]
+async fn quotes_without_an_expiry_are_not_served_stale() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, 100))
+        .mount()
+        .await;
+    let cache = QuoteCache::new(server.client(), TTL).stale_while_revalidate(true);
+
+    cache.quote(&sample_request()).await.unwrap();
+    tokio::time::sleep(TTL * 2).await;
+
+    assert!(!cache.quote(&sample_request()).await.unwrap().stale);
+    assert_eq!(server.received_request_count().await, 2);
+}
+
+#[tokio::test]
+async fn invalidate_forces_a_fetch() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, 100))
+        .mount()
+        .await;
+    let cache = QuoteCache::new(server.client(), Duration::from_secs(60));
+
+    cache.quote(&sample_request()).await.unwrap();
+    cache.invalidate(&sample_request());
+    cache.quote(&sample_request()).await.unwrap();
+
+    assert_eq!(server.received_request_count().await, 2);
+}
+
+#[tokio::test]
+async fn entries_that_cannot_be_served_are_dropped() {
+    let server = MockTitanServer::start().await;
+    for route in [
+        expiring_in(100, Duration::from_secs(60)),
+        SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, 100),
+        expiring_in(100, Duration::from_millis(250)),
+    ] {
+        server.respond_with_route(route).times(1).mount().await;
+    }
+    server
+        .respond_with_route(expiring_in(100, Duration::from_secs(60)))
+        .mount()
+        .await;
+    let with_amount = |amount| QuoteRequest {
+        amount,
+        ..sample_request()
+    };
+    // Long enough for all three to be stored before any goes stale.
+    let ttl = Duration::from_millis(200);
+
+    let cache = QuoteCache::new(server.client(), ttl).stale_while_revalidate(true);
+    assert!(cache.is_empty());
+    for amount in 1..=3 {
+        cache.quote(&with_amount(amount)).await.unwrap();
+    }
+    assert_eq!(cache.len(), 3);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    // The stale quote that hasn't expired stays, to be served while it
+    // refreshes. The one without an expiry and the expired one go.
+    cache.quote(&with_amount(4)).await.unwrap();
+    assert_eq!(cache.len(), 2);
+    assert!(cache.quote(&with_amount(1)).await.unwrap().stale);
+
+    let cache = QuoteCache::new(server.client(), TTL);
+    cache.quote(&with_amount(1)).await.unwrap();
+    tokio::time::sleep(TTL * 2).await;
+    cache.quote(&with_amount(2)).await.unwrap();
+    assert_eq!(cache.len(), 1);
+}
@@ -0,0 +1,90 @@
+use solana_sdk::pubkey;
+use titan_swap_api_client::{
+    compare::CompareError,
+    quote::{PlatformFee, QuoteResponse, SwapMode},
+};
+
+const QUOTE_V1: &str = include_str!("fixtures/quote_response_v1.json");
+
+fn golden() -> QuoteResponse {
+    QuoteResponse::from_json_str(QUOTE_V1).unwrap()
+}
+
+#[test]
+fn identical_quotes_have_no_delta() {
+    let quote = golden();
+    let delta = quote.compare(&quote).unwrap();
+
+    assert_eq!(delta.amount, 0);
+    assert_eq!(delta.bps, 0.0);
+    assert_eq!(delta.compute_units, Some(0));
+    assert_eq!(delta.to_string(), "+0.0 bps, +0 hops, +0 CU");
+}
+
+#[test]
+fn reports_amount_hops_fee_and_compute_units() {
+    let original = golden();
+    let mut other = golden();
+    other.out_amount = 187_654_321 + 232_704;
+    other.route_plan.clear();
+    other.platform_fee = Some(PlatformFee {
+        amount: 1_000,
+        fee_bps: 5,
+    });
+    other.route_data_mut().unwrap().compute_units = Some(198_000);
+
+    let delta = original.compare(&other).unwrap();
+
+    assert_eq!(delta.amount, 232_704);
+    assert!((delta.bps - 12.4).abs() < 0.01);
+    assert_eq!(delta.fee, 1_000);
+    assert_eq!(delta.hops, -1);
+    assert_eq!(delta.compute_units, Some(18_000));
+    assert_eq!(delta.to_string(), "+12.4 bps, -1 hop, +18k CU");
+}
+
+#[test]
+fn exact_out_compares_input_cost() {
+    let mut original = golden();
+    original.swap_mode = SwapMode::ExactOut;
+    let mut other = original.clone();
+    other.route_data_mut().unwrap().in_amount -= 1_000_000;
+
+    let delta = original.compare(&other).unwrap();
+    assert_eq!(delta.amount, 1_000_000);
+    assert!((delta.bps - 10.0).abs() < 1e-9);
+}
+
+#[test]
+fn within_bps_only_tolerates_worse_quotes_up_to_the_limit() {
+    let original = golden();
+    let mut refreshed = golden();
+    refreshed.out_amount -= 187_654; // ~10 bps worse
+
+    let delta = original.compare(&refreshed).unwrap();
+    assert!(delta.within_bps(15.0));
+    assert!(!delta.within_bps(5.0));
+    assert!(refreshed.compare(&original).unwrap().within_bps(0.0));
+}
+
+#[test]
+fn rejects_different_pairs_and_modes() {
+    let original = golden();
+
+    let mut other_pair = golden();
+    other_pair.output_mint = pubkey!("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB");
+    assert!(matches!(
+        original.compare(&other_pair),
+        Err(CompareError::MintMismatch(..))
+    ));
+
+    let mut other_mode = golden();
+    other_mode.swap_mode = SwapMode::ExactOut;
+    assert_eq!(
+        original.compare(&other_mode),
+        Err(CompareError::SwapModeMismatch(
+            SwapMode::ExactIn,
+            SwapMode::ExactOut
+        ))
+    );
+}
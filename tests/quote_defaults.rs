@@ -0,0 +1,391 @@
+use proptest::prelude::*;
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use std::time::Duration;
+use titan_swap_api_client::{
+    account_limits::AccountLimits,
+    cache::QuoteCache,
+    constants::{SOL_MINT, USDC_MINT},
+    fallback::QuoteRelaxation,
+    quote::{Provider, QuoteRequest, SizeConstraint, SwapMode},
+    quote_defaults::{DexExclusion, QuoteDefaults},
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+};
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn size(bytes: u16) -> SizeConstraint {
+    SizeConstraint::max_transaction_bytes(bytes).unwrap()
+}
+
+fn defaults() -> QuoteDefaults {
+    QuoteDefaults {
+        slippage_bps: Some(50),
+        account_limits: AccountLimits {
+            total: Some(40),
+            writable: Some(20),
+        },
+        only_direct_routes: Some(true),
+        excluded_dexes: Some("Phoenix, Raydium CLMM".to_string()),
+        dex_exclusion: DexExclusion::Union,
+        size_constraint: Some(size(1_000)),
+    }
+}
+
+fn replacing() -> QuoteDefaults {
+    QuoteDefaults {
+        dex_exclusion: DexExclusion::Replace,
+        ..defaults()
+    }
+}
+
+#[test]
+fn unset_request_fields_take_the_defaults() {
+    let merged = defaults().merge(&sample_request());
+    assert_eq!(
+        merged,
+        QuoteRequest {
+            slippage_bps: 50,
+            account_limits: AccountLimits {
+                total: Some(40),
+                writable: Some(20),
+            },
+            only_direct_routes: Some(true),
+            excluded_dexes: Some("phoenix,raydium-clmm".to_string()),
+            size_constraint: Some(size(1_000)),
+            ..sample_request()
+        }
+    );
+}
+
+#[test]
+fn set_request_fields_win() {
+    let own = QuoteRequest {
+        slippage_bps: 10,
+        account_limits: AccountLimits::with_total(64),
+        only_direct_routes: Some(false),
+        size_constraint: Some(size(800)),
+        ..sample_request()
+    };
+    let merged = defaults().merge(&own);
+
+    assert_eq!(merged.slippage_bps, 10);
+    assert_eq!(merged.only_direct_routes, Some(false));
+    assert_eq!(merged.size_constraint, Some(size(800)));
+    // The defaults' writable limit isn't mixed into the request's total.
+    assert_eq!(merged.account_limits, AccountLimits::with_total(64));
+}
+
+#[test]
+fn account_limits_are_merged_as_a_pair() {
+    let writable_only = QuoteRequest {
+        account_limits: AccountLimits {
+            total: None,
+            writable: Some(50),
+        },
+        ..sample_request()
+    };
+    let merged = defaults().merge(&writable_only);
+
+    assert_eq!(merged.account_limits, writable_only.account_limits);
+    assert!(merged.account_limits.validate().is_ok());
+}
+
+#[test]
+#[allow(deprecated)]
+fn the_deprecated_size_constraint_keeps_the_default_out() {
+    let own = QuoteRequest {
+        size_constraints: Some(5_000),
+        ..sample_request()
+    };
+    let merged = defaults().merge(&own);
+
+    assert_eq!(merged.size_constraint, None);
+    assert_eq!(merged.size_constraints, Some(5_000));
+}
+
+#[test]
+fn empty_defaults_change_nothing() {
+    let own = QuoteRequest {
+        user_pubkey: Some(USER),
+        swap_mode: Some(SwapMode::ExactOut),
+        excluded_dexes: Some("whirlpool".to_string()),
+        excluded_amm_keys: Some(vec![USER]),
+        providers: Some(Provider::Titan),
+        ..sample_request()
+    };
+
+    for defaults in [
+        QuoteDefaults::default(),
+        QuoteDefaults {
+            dex_exclusion: DexExclusion::Replace,
+            ..Default::default()
+        },
+    ] {
+        assert_eq!(defaults.merge(&sample_request()), sample_request());
+        assert_eq!(defaults.merge(&own), own);
+    }
+}
+
+#[test]
+fn fields_without_defaults_are_untouched() {
+    let own = QuoteRequest {
+        user_pubkey: Some(USER),
+        swap_mode: Some(SwapMode::ExactOut),
+        excluded_amm_keys: Some(vec![USER]),
+        providers: Some(Provider::Titan),
+        ..sample_request()
+    };
+    let merged = defaults().merge(&own);
+
+    assert_eq!(merged.user_pubkey, own.user_pubkey);
+    assert_eq!(merged.swap_mode, own.swap_mode);
+    assert_eq!(merged.excluded_amm_keys, own.excluded_amm_keys);
+    assert_eq!(merged.providers, own.providers);
+}
+
+#[test]
+fn dex_exclusions_are_unioned_by_default() {
+    let with_dexes = |dexes: &str| QuoteRequest {
+        excluded_dexes: Some(dexes.to_string()),
+        ..sample_request()
+    };
+    let dexes =
+        |defaults: QuoteDefaults, own: &str| defaults.merge(&with_dexes(own)).excluded_dexes;
+
+    assert_eq!(
+        dexes(defaults(), "Whirlpool, raydium_clmm"),
+        Some("phoenix,raydium-clmm,whirlpool".to_string())
+    );
+    assert_eq!(
+        dexes(defaults(), "Some New DEX"),
+        Some("phoenix,raydium-clmm,Some New DEX".to_string())
+    );
+    assert_eq!(
+        dexes(defaults(), ""),
+        Some("phoenix,raydium-clmm".to_string())
+    );
+}
+
+#[test]
+fn dex_exclusions_can_be_replaced() {
+    let with_dexes = |dexes: Option<&str>| QuoteRequest {
+        excluded_dexes: dexes.map(str::to_string),
+        ..sample_request()
+    };
+    let dexes = |own| replacing().merge(&with_dexes(own)).excluded_dexes;
+
+    assert_eq!(dexes(Some("Whirlpool")), Some("Whirlpool".to_string()));
+    // An empty list clears the defaults.
+    assert_eq!(dexes(Some("")), Some(String::new()));
+    assert_eq!(dexes(None), Some("Phoenix, Raydium CLMM".to_string()));
+}
+
+#[test]
+fn requests_without_defaults_are_sent_as_is() {
+    let own = sample_request().without_defaults();
+    assert!(own.skip_defaults);
+    assert_eq!(defaults().merge(&own), own);
+    assert_eq!(own.query_params(), sample_request().query_params());
+}
+
+#[test]
+fn skipping_defaults_round_trips_through_json() {
+    let json = serde_json::to_value(sample_request()).unwrap();
+    assert!(json.get("skipDefaults").is_none());
+
+    let json = serde_json::to_value(sample_request().without_defaults()).unwrap();
+    assert_eq!(json["skipDefaults"], true);
+    let parsed: QuoteRequest = serde_json::from_value(json).unwrap();
+    assert!(parsed.skip_defaults);
+}
+
+fn arbitrary_request() -> impl Strategy<Value = QuoteRequest> {
+    (
+        prop_oneof![Just(0u16), 1..500u16],
+        prop::option::of(1..64usize),
+        prop::option::of(any::<bool>()),
+        prop::option::of(prop::sample::select(vec![
+            "",
+            "Whirlpool",
+            "phoenix, Whirlpool",
+            "Some New DEX",
+        ])),
+        prop::option::of(100..1_232u16),
+    )
+        .prop_map(|(slippage_bps, total, direct, dexes, bytes)| QuoteRequest {
+            slippage_bps,
+            account_limits: AccountLimits {
+                total,
+                writable: None,
+            },
+            only_direct_routes: direct,
+            excluded_dexes: dexes.map(str::to_string),
+            size_constraint: bytes.map(size),
+            ..sample_request()
+        })
+}
+
+proptest! {
+    #[test]
+    fn merging_is_idempotent_and_keeps_set_fields(
+        own in arbitrary_request(),
+        replace in any::<bool>(),
+    ) {
+        let defaults = if replace { replacing() } else { defaults() };
+        let merged = defaults.merge(&own);
+
+        prop_assert_eq!(defaults.merge(&merged), merged.clone());
+        if own.slippage_bps > 0 {
+            prop_assert_eq!(merged.slippage_bps, own.slippage_bps);
+        }
+        if own.account_limits.total.is_some() {
+            prop_assert_eq!(merged.account_limits, own.account_limits);
+        }
+        if own.only_direct_routes.is_some() {
+            prop_assert_eq!(merged.only_direct_routes, own.only_direct_routes);
+        }
+        if own.size_constraint.is_some() {
+            prop_assert_eq!(merged.size_constraint, own.size_constraint);
+        }
+        if replace && own.excluded_dexes.is_some() {
+            prop_assert_eq!(merged.excluded_dexes, own.excluded_dexes);
+        }
+    }
+}
+
+async fn server() -> MockTitanServer {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, 186_000_000),
+        )
+        .mount()
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn defaults_are_sent_with_every_quote() {
+    let server = server().await;
+    let client = server
+        .client_builder()
+        .default_quote_options(defaults())
+        .build()
+        .unwrap();
+
+    client.quote(&sample_request()).await.unwrap();
+    client
+        .quote(&QuoteRequest {
+            slippage_bps: 10,
+            excluded_dexes: Some("Whirlpool".to_string()),
+            ..sample_request()
+        })
+        .await
+        .unwrap();
+    client
+        .quote(&sample_request().without_defaults())
+        .await
+        .unwrap();
+
+    let params = server.received_query_params().await;
+    assert_eq!(params[0]["slippageBps"], "50");
+    assert_eq!(params[0]["accountsLimitTotal"], "40");
+    assert_eq!(params[0]["accountsLimitWritable"], "20");
+    assert_eq!(params[0]["onlyDirectRoutes"], "true");
+    assert_eq!(params[0]["excludeDexes"], "phoenix,raydium-clmm");
+    assert_eq!(params[0]["sizeConstraint"], "1000");
+
+    assert_eq!(params[1]["slippageBps"], "10");
+    assert_eq!(params[1]["excludeDexes"], "phoenix,raydium-clmm,whirlpool");
+
+    for name in ["slippageBps", "accountsLimitTotal", "excludeDexes"] {
+        assert!(!params[2].contains_key(name), "{name} was sent");
+    }
+}
+
+#[tokio::test]
+async fn request_with_defaults_shows_what_is_sent() {
+    let server = server().await;
+    let client = server
+        .client_builder()
+        .default_quote_options(defaults())
+        .build()
+        .unwrap();
+    let own = QuoteRequest {
+        slippage_bps: 10,
+        account_limits: AccountLimits::with_total(40),
+        only_direct_routes: Some(true),
+        excluded_dexes: Some("phoenix,raydium-clmm".to_string()),
+        size_constraint: Some(size(1_000)),
+        ..sample_request()
+    };
+
+    assert_eq!(
+        *client.request_with_defaults(&sample_request()),
+        defaults().merge(&sample_request())
+    );
+    assert!(matches!(
+        client.request_with_defaults(&own),
+        std::borrow::Cow::Borrowed(_)
+    ));
+    assert!(matches!(
+        server.client().request_with_defaults(&sample_request()),
+        std::borrow::Cow::Borrowed(_)
+    ));
+}
+
+#[tokio::test]
+async fn fallback_relaxes_the_defaults() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_error(404, "No routes found")
+        .times(1)
+        .mount()
+        .await;
+    server
+        .respond_with_route(SwapRouteBuilder::new(SOL_MINT, USDC_MINT))
+        .mount()
+        .await;
+    let client = server
+        .client_builder()
+        .default_quote_options(defaults())
+        .build()
+        .unwrap();
+
+    client
+        .quote_with_fallback(
+            &sample_request(),
+            vec![QuoteRelaxation::RemoveDexExclusions],
+        )
+        .await
+        .unwrap();
+
+    let params = server.received_query_params().await;
+    assert_eq!(params[0]["excludeDexes"], "phoenix,raydium-clmm");
+    assert!(!params[1].contains_key("excludeDexes"));
+    assert_eq!(params[1]["slippageBps"], "50");
+}
+
+#[tokio::test]
+async fn cache_entries_are_keyed_by_what_is_sent() {
+    let server = server().await;
+    let client = server
+        .client_builder()
+        .default_quote_options(defaults())
+        .build()
+        .unwrap();
+    let cache = QuoteCache::new(client, Duration::from_secs(60));
+
+    cache.quote(&sample_request()).await.unwrap();
+    cache
+        .quote(&defaults().merge(&sample_request()))
+        .await
+        .unwrap();
+    assert_eq!(server.received_request_count().await, 1);
+
+    cache
+        .quote(&sample_request().without_defaults())
+        .await
+        .unwrap();
+    assert_eq!(server.received_request_count().await, 2);
+}
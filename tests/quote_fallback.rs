@@ -0,0 +1,118 @@
+use solana_sdk::pubkey::Pubkey;
+use titan_swap_api_client::{
+    account_limits::AccountLimits,
+    fallback::QuoteRelaxation,
+    quote::QuoteRequest,
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        input_mint: Pubkey::new_from_array([1; 32]),
+        output_mint: Pubkey::new_from_array([2; 32]),
+        amount: 1_000_000,
+        account_limits: AccountLimits::with_total(20),
+        only_direct_routes: Some(true),
+        excluded_dexes: Some("Phoenix".to_string()),
+        ..Default::default()
+    }
+}
+
+fn chain() -> Vec<QuoteRelaxation> {
+    vec![
+        QuoteRelaxation::IncreaseMaxAccounts(20),
+        QuoteRelaxation::AllowIndirectRoutes,
+        QuoteRelaxation::RemoveDexExclusions,
+    ]
+}
+
+#[tokio::test]
+async fn relaxes_until_a_route_is_found() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_error(404, "No routes found")
+        .times(2)
+        .mount()
+        .await;
+    server
+        .respond_with_route(SwapRouteBuilder::default().amounts(1_000_000, 990_000))
+        .mount()
+        .await;
+
+    let result = server
+        .client()
+        .quote_with_fallback(&request(), chain())
+        .await
+        .unwrap();
+
+    assert_eq!(result.quote.out_amount, 990_000);
+    assert_eq!(
+        result.applied,
+        vec![
+            QuoteRelaxation::IncreaseMaxAccounts(20),
+            QuoteRelaxation::AllowIndirectRoutes,
+        ]
+    );
+
+    let params = server.received_query_params().await;
+    assert_eq!(params.len(), 3);
+    assert_eq!(params[0]["accountsLimitTotal"], "20");
+    assert_eq!(params[0]["onlyDirectRoutes"], "true");
+    assert_eq!(params[1]["accountsLimitTotal"], "40");
+    assert_eq!(params[1]["onlyDirectRoutes"], "true");
+    assert_eq!(params[2]["accountsLimitTotal"], "40");
+    assert_eq!(params[2]["onlyDirectRoutes"], "false");
+    assert_eq!(params[2]["excludeDexes"], "phoenix");
+}
+
+#[tokio::test]
+async fn original_request_success_applies_nothing() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+
+    let result = server
+        .client()
+        .quote_with_fallback(&request(), chain())
+        .await
+        .unwrap();
+
+    assert!(result.applied.is_empty());
+    assert_eq!(server.received_request_count().await, 1);
+}
+
+#[tokio::test]
+async fn exhausted_chain_returns_no_routes() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_error(404, "No routes found")
+        .mount()
+        .await;
+
+    let err = server
+        .client()
+        .quote_with_fallback(&request(), chain())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, ClientError::NoRoutesAvailable));
+    assert_eq!(server.received_request_count().await, 4);
+}
+
+#[tokio::test]
+async fn other_errors_stop_the_chain() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_status(401).mount().await;
+
+    let err = server
+        .client()
+        .quote_with_fallback(&request(), chain())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, ClientError::RequestFailed { status, .. } if status == 401));
+    assert_eq!(server.received_request_count().await, 1);
+}
@@ -0,0 +1,250 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    options::QuoteRequestOptions,
+    price::{PriceResponse, QuoteGuard},
+    quote::{QuoteRequest, QuoteResponse, SwapMode},
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+const BONK: Pubkey = pubkey!("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263");
+
+// 150 USDC per SOL.
+const SOL_USDC: QuoteGuard = QuoteGuard {
+    reference_price: 150.0,
+    max_deviation_bps: 50,
+    in_decimals: 9,
+    out_decimals: 6,
+};
+
+fn quote(
+    input_mint: Pubkey,
+    output_mint: Pubkey,
+    swap_mode: SwapMode,
+    in_amount: u64,
+    out_amount: u64,
+) -> QuoteResponse {
+    let request = QuoteRequest {
+        input_mint,
+        output_mint,
+        amount: match swap_mode {
+            SwapMode::ExactOut => out_amount,
+            _ => in_amount,
+        },
+        swap_mode: Some(swap_mode.clone()),
+        ..Default::default()
+    };
+    let route = SwapRouteBuilder::new(input_mint, output_mint)
+        .swap_mode(swap_mode)
+        .amounts(in_amount, out_amount)
+        .build();
+    QuoteResponse::from_route(&request, route)
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 0.01, "{actual} != {expected}");
+}
+
+#[test]
+fn exact_in_deviation_in_both_directions() {
+    let at_reference = quote(
+        SOL_MINT,
+        USDC_MINT,
+        SwapMode::ExactIn,
+        1_000_000_000,
+        150_000_000,
+    );
+    assert_close(SOL_USDC.deviation_bps(&at_reference), 0.0);
+    assert!(SOL_USDC.check(&at_reference).is_ok());
+
+    // 1% fewer USDC out.
+    let worse = quote(
+        SOL_MINT,
+        USDC_MINT,
+        SwapMode::ExactIn,
+        1_000_000_000,
+        148_500_000,
+    );
+    assert_close(SOL_USDC.deviation_bps(&worse), -100.0);
+    match SOL_USDC.check(&worse) {
+        Err(ClientError::QuoteRejected { deviation_bps }) => assert_close(deviation_bps, -100.0),
+        other => panic!("expected QuoteRejected, got {other:?}"),
+    }
+
+    // 1% more USDC out is never rejected.
+    let better = quote(
+        SOL_MINT,
+        USDC_MINT,
+        SwapMode::ExactIn,
+        1_000_000_000,
+        151_500_000,
+    );
+    assert_close(SOL_USDC.deviation_bps(&better), 100.0);
+    assert!(SOL_USDC.check(&better).is_ok());
+
+    // Within tolerance.
+    let slightly_worse = quote(
+        SOL_MINT,
+        USDC_MINT,
+        SwapMode::ExactIn,
+        1_000_000_000,
+        149_400_000,
+    );
+    assert_close(SOL_USDC.deviation_bps(&slightly_worse), -40.0);
+    assert!(SOL_USDC.check(&slightly_worse).is_ok());
+}
+
+#[test]
+fn exact_out_prices_on_the_route_input() {
+    // 150 USDC out for 2% more SOL than the reference: a worse price.
+    let worse = quote(
+        SOL_MINT,
+        USDC_MINT,
+        SwapMode::ExactOut,
+        1_020_000_000,
+        150_000_000,
+    );
+    assert!(SOL_USDC.deviation_bps(&worse) < -190.0);
+    assert!(matches!(
+        SOL_USDC.check(&worse),
+        Err(ClientError::QuoteRejected { .. })
+    ));
+
+    // The same output for less SOL is better.
+    let better = quote(
+        SOL_MINT,
+        USDC_MINT,
+        SwapMode::ExactOut,
+        980_000_000,
+        150_000_000,
+    );
+    assert!(SOL_USDC.deviation_bps(&better) > 200.0);
+    assert!(SOL_USDC.check(&better).is_ok());
+}
+
+#[test]
+fn inverted_pair_uses_the_inverse_reference() {
+    let usdc_sol = QuoteGuard {
+        reference_price: 1.0 / 150.0,
+        in_decimals: 6,
+        out_decimals: 9,
+        ..SOL_USDC
+    };
+
+    let at_reference = quote(
+        USDC_MINT,
+        SOL_MINT,
+        SwapMode::ExactIn,
+        150_000_000,
+        1_000_000_000,
+    );
+    assert_close(usdc_sol.deviation_bps(&at_reference), 0.0);
+
+    let worse = quote(
+        USDC_MINT,
+        SOL_MINT,
+        SwapMode::ExactIn,
+        150_000_000,
+        990_000_000,
+    );
+    assert_close(usdc_sol.deviation_bps(&worse), -100.0);
+    assert!(usdc_sol.check(&worse).is_err());
+
+    let better = quote(
+        USDC_MINT,
+        SOL_MINT,
+        SwapMode::ExactIn,
+        150_000_000,
+        1_010_000_000,
+    );
+    assert_close(usdc_sol.deviation_bps(&better), 100.0);
+    assert!(usdc_sol.check(&better).is_ok());
+}
+
+#[test]
+fn from_price_handles_either_orientation() {
+    // Raw USDC units per lamport.
+    let price = PriceResponse {
+        base_mint: SOL_MINT,
+        quote_mint: USDC_MINT,
+        price: 0.15,
+        timestamp_ms: 0,
+    };
+
+    let sol_usdc = QuoteGuard::from_price(&price, SOL_MINT, USDC_MINT, 50, 9, 6).unwrap();
+    assert_close(sol_usdc.reference_price, 150.0);
+
+    let usdc_sol = QuoteGuard::from_price(&price, USDC_MINT, SOL_MINT, 50, 6, 9).unwrap();
+    assert_close(usdc_sol.reference_price * 150.0, 1.0);
+    let at_reference = quote(
+        USDC_MINT,
+        SOL_MINT,
+        SwapMode::ExactIn,
+        150_000_000,
+        1_000_000_000,
+    );
+    assert!(usdc_sol.check(&at_reference).is_ok());
+
+    assert_eq!(
+        QuoteGuard::from_price(&price, SOL_MINT, BONK, 50, 9, 5),
+        None
+    );
+}
+
+#[test]
+fn unpriceable_quotes_are_rejected() {
+    let no_input = quote(SOL_MINT, USDC_MINT, SwapMode::ExactIn, 0, 150_000_000);
+    assert!(SOL_USDC.deviation_bps(&no_input).is_nan());
+    assert!(SOL_USDC.check(&no_input).is_err());
+
+    let no_reference = QuoteGuard {
+        reference_price: 0.0,
+        ..SOL_USDC
+    };
+    let at_reference = quote(
+        SOL_MINT,
+        USDC_MINT,
+        SwapMode::ExactIn,
+        1_000_000_000,
+        150_000_000,
+    );
+    assert!(no_reference.check(&at_reference).is_err());
+}
+
+#[tokio::test]
+async fn quote_with_options_applies_the_guard() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, 140_000_000),
+        )
+        .mount()
+        .await;
+    let client = server.client();
+    let request = QuoteRequest {
+        input_mint: SOL_MINT,
+        output_mint: USDC_MINT,
+        amount: 1_000_000_000,
+        ..Default::default()
+    };
+
+    let err = client
+        .quote_with_options(&request, &QuoteRequestOptions::with_guard(SOL_USDC))
+        .await
+        .unwrap_err();
+    match err {
+        ClientError::QuoteRejected { deviation_bps } => assert_close(deviation_bps, -666.67),
+        other => panic!("expected QuoteRejected, got {other:?}"),
+    }
+
+    let loose = QuoteGuard {
+        max_deviation_bps: 1_000,
+        ..SOL_USDC
+    };
+    let quote = client
+        .quote_with_options(&request, &QuoteRequestOptions::with_guard(loose))
+        .await
+        .unwrap();
+    assert_eq!(quote.out_amount, 140_000_000);
+}
@@ -0,0 +1,123 @@
+use titan_swap_api_client::{
+    decode_swap_quotes,
+    quote::{QuoteKey, QuoteRequest},
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+const SINGLE_HOP: &[u8] = include_bytes!("fixtures/single_hop.msgpack");
+const MULTI_HOP_SPLIT: &[u8] = include_bytes!("fixtures/multi_hop_split.msgpack");
+const SWAP_QUOTES_LARGE: &[u8] = include_bytes!("fixtures/swap_quotes_large.msgpack");
+
+fn provider(provider: &str, variant: Option<&str>, params: &[&str]) -> QuoteKey {
+    QuoteKey::Provider {
+        provider: provider.to_string(),
+        variant: variant.map(str::to_string),
+        params: params.iter().map(|param| param.to_string()).collect(),
+    }
+}
+
+#[test]
+fn fixture_keys_are_provider_names() {
+    for fixture in [SINGLE_HOP, MULTI_HOP_SPLIT, SWAP_QUOTES_LARGE] {
+        let quotes = decode_swap_quotes(fixture).unwrap();
+        for key in quotes.quotes.keys() {
+            let name = key.provider().unwrap();
+            assert!(["Titan", "Other"].contains(&name));
+            assert_eq!(key, &provider(name, None, &[]));
+            assert_eq!(key.to_string(), name);
+        }
+    }
+}
+
+#[test]
+fn parses_segments() {
+    assert_eq!(QuoteKey::parse("Hashflow"), provider("Hashflow", None, &[]));
+
+    let key = QuoteKey::parse("Titan:split:v2:fast");
+    assert_eq!(key.provider(), Some("Titan"));
+    assert_eq!(key.variant(), Some("split"));
+    assert_eq!(key.params(), ["v2", "fast"]);
+    assert_eq!(key.attribution(), "Titan");
+}
+
+#[test]
+fn unknown_formats_are_raw() {
+    for raw in ["", ":split", "Titan:", "Titan::v2", " Titan", "Titan\n"] {
+        let key = QuoteKey::parse(raw);
+        assert_eq!(key, QuoteKey::Raw(raw.to_string()), "{raw:?}");
+        assert_eq!(key.provider(), None);
+        assert_eq!(key.variant(), None);
+        assert!(key.params().is_empty());
+        assert_eq!(key.attribution(), raw);
+    }
+}
+
+#[test]
+fn displays_and_serializes_as_parsed() {
+    for raw in ["Titan", "Titan:split:v2", "Titan::v2"] {
+        let key = QuoteKey::parse(raw);
+        assert_eq!(key.to_string(), raw);
+        assert_eq!(serde_json::to_value(&key).unwrap(), raw);
+        assert_eq!(serde_json::from_value::<QuoteKey>(raw.into()).unwrap(), key);
+    }
+}
+
+#[test]
+fn provider_matching_ignores_case_and_segments() {
+    let key = QuoteKey::parse("Titan:split");
+    assert!(key.matches_provider("Titan"));
+    assert!(key.matches_provider("titan"));
+    assert!(!key.matches_provider("Titan:split"));
+    assert!(!key.matches_provider("Hashflow"));
+
+    assert!(QuoteKey::parse("Titan::v2").matches_provider("titan::v2"));
+}
+
+#[test]
+fn ordered_by_provider_name() {
+    let mut keys = ["Titan", "Hashflow:rfq", "Hashflow", "Titan:split"].map(QuoteKey::parse);
+    keys.sort();
+    assert_eq!(
+        keys.map(|key| key.to_string()),
+        ["Hashflow", "Hashflow:rfq", "Titan", "Titan:split"]
+    );
+}
+
+#[tokio::test]
+async fn selection_and_attribution_use_the_parsed_provider() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_routes([
+            SwapRouteBuilder::default()
+                .provider("Titan:split")
+                .amounts(1_000_000, 990_000),
+            SwapRouteBuilder::default()
+                .provider("Hashflow:rfq")
+                .amounts(1_000_000, 995_000),
+        ])
+        .mount()
+        .await;
+    let client = server.client();
+
+    let quote = client
+        .quote_from(&QuoteRequest::default(), "titan")
+        .await
+        .unwrap();
+    assert_eq!(quote.provider(), Some("Titan"));
+    assert_eq!(quote.out_amount, 990_000);
+
+    assert!(matches!(
+        client
+            .quote_from(&QuoteRequest::default(), "Titan:split")
+            .await,
+        Err(ClientError::ProviderUnavailable(_))
+    ));
+
+    let quotes = client.quote_all(&QuoteRequest::default()).await.unwrap();
+    assert_eq!(
+        quotes.keys().collect::<Vec<_>>(),
+        ["Hashflow:rfq", "Titan:split"]
+    );
+    assert_eq!(quotes["Hashflow:rfq"].provider(), Some("Hashflow"));
+}
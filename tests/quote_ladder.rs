@@ -0,0 +1,209 @@
+use std::time::Duration;
+use titan_swap_api_client::{
+    account_limits::AccountLimits,
+    constants::{SOL_MINT, USDC_MINT},
+    ladder::{MarginalPrice, QuoteLadder},
+    quote::{QuoteRequest, QuoteResponse},
+    rate_limit::RateLimitStrategy,
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+fn request(amount: u64) -> QuoteRequest {
+    QuoteRequest {
+        amount,
+        ..sample_request()
+    }
+}
+
+fn quote(in_amount: u64, out_amount: u64) -> Result<QuoteResponse, ClientError> {
+    Ok(QuoteResponse::from_route(
+        &request(in_amount),
+        SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+            .amounts(in_amount, out_amount)
+            .build(),
+    ))
+}
+
+/// Prices 0.186, 0.1855, 0.1845 and 0.1825, given out of order.
+fn ladder() -> QuoteLadder {
+    QuoteLadder::from_results([
+        (4_000_000_000, quote(4_000_000_000, 738_000_000)),
+        (1_000_000_000, quote(1_000_000_000, 186_000_000)),
+        (3_000_000_000, Err(ClientError::NoRoutesAvailable)),
+        (8_000_000_000, quote(8_000_000_000, 1_460_000_000)),
+        (2_000_000_000, quote(2_000_000_000, 371_000_000)),
+    ])
+}
+
+fn assert_close(actual: Option<f64>, expected: f64) {
+    let actual = actual.unwrap();
+    assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+}
+
+#[test]
+fn levels_are_priced_in_amount_order() {
+    let ladder = ladder();
+    let amounts: Vec<u64> = ladder.levels.iter().map(|level| level.amount).collect();
+    assert_eq!(
+        amounts,
+        [
+            1_000_000_000,
+            2_000_000_000,
+            3_000_000_000,
+            4_000_000_000,
+            8_000_000_000
+        ]
+    );
+
+    let prices = [0.186, 0.1855, 0.1845, 0.1825];
+    let impacts = [0.0, 26.881_720_430, 80.645_161_290, 188.172_043_011];
+    let quoted = ladder.levels.iter().filter(|level| level.quote.is_some());
+    for ((level, price), impact) in quoted.zip(prices).zip(impacts) {
+        assert_close(level.effective_price, price);
+        assert!((level.impact_bps.unwrap() - impact).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn marginal_prices_skip_failed_levels() {
+    let marginal = ladder().marginal_prices;
+    let spans: Vec<(u64, u64)> = marginal
+        .iter()
+        .map(|price| (price.from_amount, price.to_amount))
+        .collect();
+    assert_eq!(
+        spans,
+        [
+            (1_000_000_000, 2_000_000_000),
+            (2_000_000_000, 4_000_000_000),
+            (4_000_000_000, 8_000_000_000),
+        ]
+    );
+    // Each extra unit is priced below the average, and falls with size.
+    assert_close(marginal[0].price, 0.185);
+    assert_close(marginal[1].price, 0.1835);
+    assert_close(marginal[2].price, 0.1805);
+}
+
+#[test]
+fn marginal_price_is_unset_when_the_input_does_not_grow() {
+    let ladder =
+        QuoteLadder::from_results([(1_000, quote(1_000, 186)), (2_000, quote(1_000, 186))]);
+    assert_eq!(
+        ladder.marginal_prices,
+        [MarginalPrice {
+            from_amount: 1_000,
+            to_amount: 2_000,
+            price: None
+        }]
+    );
+}
+
+#[test]
+fn worst_level_within_an_impact() {
+    let ladder = ladder();
+    let amount = |impact_bps| {
+        ladder
+            .worst_level_within(impact_bps)
+            .map(|level| level.amount)
+    };
+
+    assert_eq!(amount(10.0), Some(1_000_000_000));
+    assert_eq!(amount(100.0), Some(4_000_000_000));
+    assert_eq!(amount(500.0), Some(8_000_000_000));
+    assert_eq!(amount(-1.0), None);
+}
+
+#[test]
+fn failed_levels_are_recorded() {
+    let ladder = ladder();
+    let failed: Vec<_> = ladder.failed_levels().collect();
+
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].amount, 3_000_000_000);
+    assert_eq!(failed[0].error.as_deref(), Some("No routes available"));
+    assert_eq!(failed[0].impact_bps, None);
+}
+
+#[test]
+fn round_trips_through_json() {
+    let ladder = ladder();
+    let json = serde_json::to_value(&ladder).unwrap();
+
+    assert_eq!(json["levels"][0]["amount"], "1000000000");
+    assert!(json["levels"][2].get("quote").is_none());
+    assert_eq!(json["marginalPrices"][1]["toAmount"], "4000000000");
+    assert_eq!(serde_json::from_value::<QuoteLadder>(json).unwrap(), ladder);
+}
+
+#[tokio::test]
+async fn fans_out_each_distinct_amount() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, 186_000_000),
+        )
+        .mount()
+        .await;
+
+    let ladder = server
+        .client()
+        .quote_ladder(&request(0), &[3_000, 1_000, 2_000, 1_000])
+        .await
+        .unwrap();
+
+    assert_eq!(ladder.levels.len(), 3);
+    assert_eq!(ladder.failed_levels().count(), 0);
+    let mut amounts: Vec<String> = server
+        .received_query_params()
+        .await
+        .into_iter()
+        .map(|params| params["amount"].clone())
+        .collect();
+    amounts.sort();
+    assert_eq!(amounts, ["1000", "2000", "3000"]);
+}
+
+#[tokio::test]
+async fn rate_limited_levels_fail_alone() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, 186_000_000),
+        )
+        .mount()
+        .await;
+    let client = server
+        .client_builder()
+        .rate_limit(2, Duration::from_secs(60))
+        .rate_limit_strategy(RateLimitStrategy::Reject)
+        .build()
+        .unwrap();
+
+    let ladder = client
+        .quote_ladder(&request(0), &[1, 2, 3, 4])
+        .await
+        .unwrap();
+
+    assert_eq!(ladder.levels.len(), 4);
+    assert_eq!(ladder.failed_levels().count(), 2);
+    assert_eq!(server.received_request_count().await, 2);
+}
+
+#[tokio::test]
+async fn invalid_base_requests_fail() {
+    let server = MockTitanServer::start().await;
+    let base = QuoteRequest {
+        account_limits: AccountLimits {
+            total: Some(10),
+            writable: Some(20),
+        },
+        ..request(0)
+    };
+
+    assert!(matches!(
+        server.client().quote_ladder(&base, &[1, 2]).await,
+        Err(ClientError::InvalidAccountLimits(_))
+    ));
+}
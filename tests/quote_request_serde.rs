@@ -0,0 +1,152 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    account_limits::AccountLimits,
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{Provider, QuoteRequest, SwapMode},
+    quote_builder::{QuoteRequestConfig, QuoteRequestError},
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    tokens::{TokenInfo, TokenRegistry},
+};
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+const CONFIG: &str = r#"{
+    "inputMint": "So11111111111111111111111111111111111111112",
+    "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    "amount": "1000000000",
+    "userPubkey": "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM",
+    "swapMode": "ExactOut",
+    "slippageBps": 75,
+    "maxAccounts": 40,
+    "onlyDirectRoutes": true,
+    "excludedDexes": "Phoenix",
+    "providers": "Titan"
+}"#;
+
+fn registry() -> TokenRegistry {
+    let token = |mint, symbol: &str, decimals| TokenInfo {
+        mint,
+        symbol: symbol.to_string(),
+        name: symbol.to_string(),
+        decimals,
+        tags: vec![],
+    };
+    TokenRegistry::from_tokens(vec![token(SOL_MINT, "SOL", 9), token(USDC_MINT, "USDC", 6)])
+}
+
+#[test]
+fn deserializes_every_field() {
+    let request: QuoteRequest = serde_json::from_str(CONFIG).unwrap();
+    assert_eq!(
+        request,
+        QuoteRequest {
+            input_mint: SOL_MINT,
+            output_mint: USDC_MINT,
+            amount: 1_000_000_000,
+            user_pubkey: Some(USER),
+            account_limits: AccountLimits::with_total(40),
+            swap_mode: Some(SwapMode::ExactOut),
+            slippage_bps: 75,
+            only_direct_routes: Some(true),
+            excluded_dexes: Some("Phoenix".to_string()),
+            size_constraint: None,
+            providers: Some(Provider::Titan),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn optional_fields_default() {
+    let request: QuoteRequest = serde_json::from_str(
+        r#"{
+            "inputMint": "So11111111111111111111111111111111111111112",
+            "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            "amount": "5"
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        request,
+        QuoteRequest {
+            input_mint: SOL_MINT,
+            output_mint: USDC_MINT,
+            amount: 5,
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn missing_mint_is_an_error() {
+    assert!(serde_json::from_str::<QuoteRequest>(r#"{"amount": "5"}"#).is_err());
+}
+
+#[test]
+fn serialize_round_trips() {
+    let request: QuoteRequest = serde_json::from_str(CONFIG).unwrap();
+    let json = serde_json::to_string(&request).unwrap();
+    assert_eq!(
+        serde_json::from_str::<QuoteRequest>(&json).unwrap(),
+        request
+    );
+}
+
+#[tokio::test]
+async fn config_becomes_query_params() {
+    let request: QuoteRequest = serde_json::from_str(CONFIG).unwrap();
+    let server = MockTitanServer::start().await;
+    // ExactOut, so the route must deliver the requested amount.
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+                .swap_mode(SwapMode::ExactOut)
+                .amounts(6_000_000, 1_000_000_000),
+        )
+        .mount()
+        .await;
+    server.client().quote(&request).await.unwrap();
+
+    let params = &server.received_query_params().await[0];
+    let param = |name: &str| params.get(name).map(String::as_str);
+    assert_eq!(param("inputMint"), Some(SOL_MINT.to_string().as_str()));
+    assert_eq!(param("outputMint"), Some(USDC_MINT.to_string().as_str()));
+    assert_eq!(param("amount"), Some("1000000000"));
+    assert_eq!(param("userPublicKey"), Some(USER.to_string().as_str()));
+    assert_eq!(param("swapMode"), Some("ExactOut"));
+    assert_eq!(param("slippageBps"), Some("75"));
+    assert_eq!(param("accountsLimitTotal"), Some("40"));
+    assert_eq!(param("onlyDirectRoutes"), Some("true"));
+    assert_eq!(param("excludeDexes"), Some("phoenix"));
+    assert_eq!(param("providers"), Some("Titan"));
+    assert_eq!(param("sizeConstraint"), None);
+}
+
+#[test]
+fn config_resolves_symbols() {
+    let config: QuoteRequestConfig = serde_json::from_str(
+        r#"{"inputMint": "sol", "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "amount": "5", "slippageBps": 50}"#,
+    )
+    .unwrap();
+    let request = config.finalize(&registry()).unwrap();
+    assert_eq!(request.input_mint, SOL_MINT);
+    assert_eq!(request.output_mint, USDC_MINT);
+    assert_eq!(request.slippage_bps, 50);
+
+    let config: QuoteRequestConfig = serde_json::from_str(CONFIG).unwrap();
+    assert_eq!(
+        config.finalize(&registry()).unwrap(),
+        serde_json::from_str::<QuoteRequest>(CONFIG).unwrap()
+    );
+}
+
+#[test]
+fn config_rejects_unknown_symbols() {
+    let config: QuoteRequestConfig =
+        serde_json::from_str(r#"{"inputMint": "SOL", "outputMint": "WIF", "amount": "5"}"#)
+            .unwrap();
+    assert_eq!(
+        config.finalize(&registry()),
+        Err(QuoteRequestError::UnknownSymbol("WIF".to_string()))
+    );
+}
@@ -0,0 +1,64 @@
+use solana_sdk::pubkey;
+use titan_swap_api_client::{constants::SOL_MINT, quote::QuoteResponse, TitanClient};
+
+const QUOTE_V1: &str = include_str!("fixtures/quote_response_v1.json");
+
+#[test]
+fn loads_golden_quote() {
+    let quote = QuoteResponse::from_json_str(QUOTE_V1).unwrap();
+
+    assert_eq!(quote.input_mint, SOL_MINT);
+    assert_eq!(quote.out_amount, 187_654_321);
+    assert_eq!(quote.route_plan.len(), 1);
+    assert_eq!(quote.route_data().unwrap().address_lookup_tables.len(), 1);
+    assert_eq!(
+        quote.route_data().unwrap().expires_after_slot,
+        Some(312_000_150)
+    );
+}
+
+#[test]
+fn golden_quote_round_trips() {
+    let quote = QuoteResponse::from_json_str(QUOTE_V1).unwrap();
+    let json = quote.to_json_string().unwrap();
+
+    let expected: serde_json::Value = serde_json::from_str(QUOTE_V1).unwrap();
+    let actual: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn golden_quote_raw_round_trips() {
+    let quote = QuoteResponse::from_json_str(QUOTE_V1).unwrap();
+    let raw = quote.to_raw();
+
+    assert_eq!(raw.input_mint, quote.input_mint.to_bytes());
+    assert_eq!(
+        raw.route_plan[0].swap_info.amm_key,
+        quote.route_plan[0].swap_info.amm_key.to_bytes()
+    );
+
+    let restored = QuoteResponse::from_raw(raw);
+    let expected: serde_json::Value = serde_json::from_str(QUOTE_V1).unwrap();
+    let actual: serde_json::Value =
+        serde_json::from_str(&restored.to_json_string().unwrap()).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn golden_quote_replays_swap() {
+    let quote = QuoteResponse::from_json_str(QUOTE_V1).unwrap();
+    let client = TitanClient::new(String::new(), None);
+    let swap = client.swap(&quote).unwrap();
+
+    assert_eq!(swap.instructions.len(), 1);
+    assert_eq!(
+        swap.instructions[0].program_id,
+        pubkey!("T1TANpTeScyeqVzzgNViGDNrkQ6qHz9KrSBS4aNXvGT")
+    );
+    assert_eq!(
+        swap.address_lookup_table_addresses,
+        vec![pubkey!("AxjwU2LzNrbXYTJhDcmVJHXJ3tvSEfrMhC3rwRMcn5eh")]
+    );
+    assert_eq!(swap.compute_unit_limit, Some(180_000));
+}
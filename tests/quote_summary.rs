@@ -0,0 +1,105 @@
+use solana_sdk::{instruction::Instruction, pubkey, pubkey::Pubkey};
+use std::sync::Arc;
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{QuoteRequest, QuoteResponse, SwapMode},
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        amount: 150_000_000,
+        user_pubkey: Some(USER),
+        swap_mode: Some(SwapMode::ExactOut),
+        ..sample_request()
+    }
+}
+
+fn route() -> SwapRouteBuilder {
+    SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+        .swap_mode(SwapMode::ExactOut)
+        .amounts(1_000_000_000, 150_000_000)
+        .platform_fee(150_000, 10)
+        .step(
+            "Whirlpool",
+            Pubkey::new_unique(),
+            SOL_MINT,
+            USDC_MINT,
+            600_000_000,
+            90_000_000,
+            600_000_000,
+        )
+        .step(
+            "Raydium",
+            Pubkey::new_unique(),
+            SOL_MINT,
+            USDC_MINT,
+            400_000_000,
+            60_000_000,
+            400_000_000,
+        )
+        .instruction(&Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1, 2, 3],
+            vec![],
+        ))
+        .expires(1_760_000_000_000, 312_000_150)
+}
+
+fn quote() -> QuoteResponse {
+    QuoteResponse::from_route(&request(), route().build())
+}
+
+#[test]
+fn clones_share_route_data() {
+    let quote = quote();
+    let clone = quote.clone();
+    assert!(Arc::ptr_eq(
+        quote.route_data().unwrap(),
+        clone.route_data().unwrap()
+    ));
+
+    let mut edited = quote.clone();
+    edited.route_data_mut().unwrap().compute_units = Some(1);
+    assert_eq!(quote.route_data().unwrap().compute_units, None);
+}
+
+#[test]
+fn summary_takes_actual_input() {
+    let summary = quote().summary();
+    assert_eq!(summary.in_amount, 1_000_000_000);
+    assert_eq!(summary.out_amount, 150_000_000);
+    assert_eq!(summary.platform_fee.map(|fee| fee.amount), Some(150_000));
+    assert_eq!(summary.expires_at_ms, Some(1_760_000_000_000));
+    assert_eq!(&*summary.labels, ["Whirlpool", "Raydium"]);
+
+    let json = serde_json::to_string(&summary).unwrap();
+    assert_eq!(serde_json::from_str::<_>(&json).ok(), Some(summary));
+}
+
+#[test]
+fn quote_without_route_data_round_trips() {
+    let quote = quote().without_route_data();
+    assert!(quote.route_data().is_none());
+
+    let json = quote.to_json_string().unwrap();
+    assert!(!json.contains("rawRoute"));
+    let decoded = QuoteResponse::from_json_str(&json).unwrap();
+    assert!(decoded.route_data().is_none());
+    assert_eq!(decoded.route_plan.len(), 2);
+}
+
+#[tokio::test]
+async fn swap_needs_route_data() {
+    let server = MockTitanServer::start().await;
+    let client = server.client();
+
+    assert!(client.swap(&quote()).is_ok());
+    assert!(matches!(
+        client.swap(&quote().without_route_data()),
+        Err(ClientError::MissingRouteData)
+    ));
+}
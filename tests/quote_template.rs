@@ -0,0 +1,103 @@
+use proptest::prelude::*;
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use std::time::Duration;
+use titan_swap_api_client::{
+    account_limits::AccountLimits,
+    cache::QuoteCache,
+    constants::{SOL_MINT, USDC_MINT},
+    query::QuoteRequestTemplate,
+    quote::{Provider, QuoteRequest, SwapMode},
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+};
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn full() -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey: Some(USER),
+        account_limits: AccountLimits {
+            total: Some(40),
+            writable: Some(20),
+        },
+        swap_mode: Some(SwapMode::ExactIn),
+        slippage_bps: 50,
+        only_direct_routes: Some(false),
+        excluded_dexes: Some("Raydium CLMM, Phoenix".to_string()),
+        providers: Some(Provider::Titan),
+        ..sample_request()
+    }
+}
+
+proptest! {
+    #[test]
+    fn encodes_like_the_request(amount in any::<u64>()) {
+        for request in [sample_request(), full()] {
+            let template = QuoteRequestTemplate::new(request.clone());
+            let expected = QuoteRequest { amount, ..request };
+
+            prop_assert_eq!(template.request_with_amount(amount), expected.clone());
+            prop_assert_eq!(template.query_params_with_amount(amount), expected.query_params());
+            prop_assert_eq!(template.query_string_with_amount(amount), expected.to_query_string());
+        }
+    }
+}
+
+#[test]
+fn keeps_the_original_request() {
+    let template = QuoteRequestTemplate::from(full());
+    assert_eq!(template.request(), &full());
+    assert_eq!(
+        template.query_params_with_amount(7)[2],
+        ("amount", "7".to_string())
+    );
+}
+
+#[tokio::test]
+async fn quotes_with_each_amount() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(2_000_000_000, 372_000_000),
+        )
+        .mount()
+        .await;
+    let client = server.client();
+    let template = QuoteRequestTemplate::new(full());
+
+    let quote = template
+        .quote_with_amount(&client, 2_000_000_000)
+        .await
+        .unwrap();
+    assert_eq!(quote.out_amount, 372_000_000);
+    assert!(!quote.indicative);
+
+    let params = server.received_query_params().await;
+    assert_eq!(params[0]["amount"], "2000000000");
+    assert_eq!(params[0]["userPublicKey"], USER.to_string());
+    assert_eq!(params[0]["slippageBps"], "50");
+}
+
+#[tokio::test]
+async fn shares_cache_entries_with_plain_requests() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, 186_000_000),
+        )
+        .mount()
+        .await;
+    let cache = QuoteCache::new(server.client(), Duration::from_secs(60));
+    let template = QuoteRequestTemplate::new(full());
+
+    let first = cache
+        .quote_with_amount(&template, 1_000_000_000)
+        .await
+        .unwrap();
+    let second = cache.quote(&full()).await.unwrap();
+
+    assert_eq!(second.quote.out_amount, first.quote.out_amount);
+    assert_eq!(server.received_request_count().await, 1);
+
+    cache.quote_with_amount(&template, 5).await.unwrap();
+    assert_eq!(server.received_request_count().await, 2);
+}
@@ -0,0 +1,169 @@
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use titan_swap_api_client::{
+    compose::{compose_swaps_with, ComposeOptions},
+    composite::CompositeQuote,
+    quote::{QuoteRequest, QuoteResponse, SwapMode},
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+const INPUT: Pubkey = Pubkey::new_from_array([1; 32]);
+const VIA: Pubkey = Pubkey::new_from_array([5; 32]);
+const OUTPUT: Pubkey = Pubkey::new_from_array([6; 32]);
+const USER: Pubkey = Pubkey::new_from_array([9; 32]);
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        input_mint: INPUT,
+        output_mint: OUTPUT,
+        amount: 1_000_000,
+        user_pubkey: Some(USER),
+        swap_mode: Some(SwapMode::ExactIn),
+        slippage_bps: 100,
+        ..Default::default()
+    }
+}
+
+async fn two_leg_server() -> MockTitanServer {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(INPUT, VIA)
+                .amounts(1_000_000, 2_000_000)
+                .slippage_bps(100),
+        )
+        .times(1)
+        .mount()
+        .await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(VIA, OUTPUT)
+                .amounts(1_980_000, 495_000)
+                .slippage_bps(50),
+        )
+        .mount()
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn second_leg_spends_the_first_legs_minimum_output() {
+    let server = two_leg_server().await;
+
+    let composite = server.client().quote_via(&request(), VIA).await.unwrap();
+
+    let params = server.received_query_params().await;
+    assert_eq!(params[0]["outputMint"], VIA.to_string());
+    assert_eq!(params[1]["inputMint"], VIA.to_string());
+    assert_eq!(params[1]["outputMint"], OUTPUT.to_string());
+    // 2_000_000 less the first leg's 100 bps.
+    assert_eq!(params[1]["amount"], "1980000");
+
+    assert_eq!(composite.via(), VIA);
+    assert_eq!(composite.in_amount(), 1_000_000);
+    assert_eq!(composite.out_amount(), 495_000);
+}
+
+#[tokio::test]
+async fn only_the_second_legs_slippage_is_left() {
+    let server = two_leg_server().await;
+
+    let composite = server.client().quote_via(&request(), VIA).await.unwrap();
+
+    // The first leg's is already in the amount the second leg spends.
+    assert_eq!(composite.slippage_bps(), 50);
+    assert_eq!(composite.min_out_amount(), 492_525);
+}
+
+#[test]
+fn legs_that_dont_chain_are_not_swapped() {
+    let leg = |input, output, in_amount, out_amount| {
+        let request = QuoteRequest {
+            input_mint: input,
+            output_mint: output,
+            amount: in_amount,
+            ..request()
+        };
+        let route = SwapRouteBuilder::new(input, output)
+            .amounts(in_amount, out_amount)
+            .slippage_bps(100)
+            .build();
+        QuoteResponse::from_route(&request, route)
+    };
+    let first = leg(INPUT, VIA, 1_000_000, 2_000_000);
+
+    // Sized from the first leg's out_amount, more than its minimum.
+    let composite = CompositeQuote {
+        first: first.clone(),
+        second: leg(VIA, OUTPUT, 2_000_000, 500_000),
+    };
+    assert!(matches!(
+        composite.swap(),
+        Err(ClientError::UnchainedLegs {
+            via: VIA,
+            guaranteed: 1_980_000,
+            spent_mint: VIA,
+            spent: 2_000_000,
+        })
+    ));
+
+    let composite = CompositeQuote {
+        first: first.clone(),
+        second: leg(INPUT, OUTPUT, 1_000_000, 500_000),
+    };
+    assert!(matches!(
+        composite.swap(),
+        Err(ClientError::UnchainedLegs {
+            spent_mint: INPUT,
+            ..
+        })
+    ));
+
+    let composite = CompositeQuote {
+        first,
+        second: leg(VIA, OUTPUT, 1_980_000, 495_000),
+    };
+    assert!(composite.swap().is_ok());
+}
+
+#[tokio::test]
+async fn legs_compose_into_one_swap() {
+    let server = two_leg_server().await;
+    let composite = server.client().quote_via(&request(), VIA).await.unwrap();
+
+    let [first, second] = composite.swap().unwrap();
+    assert_eq!(first.instructions.len(), 1);
+    assert_eq!(second.instructions.len(), 1);
+
+    let shared: HashSet<Pubkey> = first
+        .instructions
+        .iter()
+        .chain(&second.instructions)
+        .flat_map(|ix| &ix.accounts)
+        .map(|meta| meta.pubkey)
+        .collect();
+    let options = ComposeOptions {
+        shared_writable: shared,
+        ..Default::default()
+    };
+    let composed = compose_swaps_with(&[first, second], &options).unwrap();
+    assert_eq!(composed.instruction_ranges, vec![0..1, 1..2]);
+}
+
+#[tokio::test]
+async fn exact_out_is_unsupported() {
+    let server = two_leg_server().await;
+    let request = QuoteRequest {
+        swap_mode: Some(SwapMode::ExactOut),
+        ..request()
+    };
+
+    let err = server.client().quote_via(&request, VIA).await.unwrap_err();
+
+    assert!(matches!(
+        err,
+        ClientError::UnsupportedSwapMode(SwapMode::ExactOut)
+    ));
+    assert!(server.received_query_params().await.is_empty());
+}
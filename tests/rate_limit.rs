@@ -0,0 +1,62 @@
+use std::time::{Duration, Instant};
+use titan_swap_api_client::{
+    quote::QuoteRequest,
+    rate_limit::RateLimitStrategy,
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+#[tokio::test]
+async fn paces_concurrent_quotes() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+
+    let client = server
+        .client_builder()
+        .rate_limit(10, Duration::from_secs(1))
+        .build()
+        .unwrap();
+
+    let started = Instant::now();
+    let tasks: Vec<_> = (0..30)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.quote(&QuoteRequest::default()).await })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+
+    // A burst of 10, then the remaining 20 at 10 per second.
+    assert!(started.elapsed() >= Duration::from_millis(1900));
+    assert_eq!(server.received_request_count().await, 30);
+}
+
+#[tokio::test]
+async fn reject_strategy_fails_fast() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+
+    let client = server
+        .client_builder()
+        .rate_limit(2, Duration::from_secs(60))
+        .rate_limit_strategy(RateLimitStrategy::Reject)
+        .build()
+        .unwrap();
+
+    client.quote(&QuoteRequest::default()).await.unwrap();
+    client.quote(&QuoteRequest::default()).await.unwrap();
+    let err = client.quote(&QuoteRequest::default()).await.unwrap_err();
+
+    assert!(
+        matches!(err, ClientError::RateLimited { retry_after } if retry_after > Duration::ZERO)
+    );
+    assert_eq!(server.received_request_count().await, 2);
+}
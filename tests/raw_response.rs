@@ -0,0 +1,110 @@
+use solana_sdk::pubkey::Pubkey;
+use std::sync::{Arc, Mutex};
+use titan_swap_api_client::{
+    decode_swap_quotes,
+    quote::{QuoteKey, QuoteRequest},
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    ClientError, TitanClient,
+};
+
+const SINGLE_HOP: &[u8] = include_bytes!("fixtures/single_hop.msgpack");
+
+type Captured = Arc<Mutex<Vec<(String, u16, Vec<u8>)>>>;
+
+fn capturing_client(server: &MockTitanServer) -> (TitanClient, Captured) {
+    let captured = Captured::default();
+    let sink = captured.clone();
+    let client = server
+        .client_builder()
+        .on_raw_response(move |url, status, body| {
+            sink.lock()
+                .unwrap()
+                .push((url.to_string(), status, body.to_vec()));
+        })
+        .build()
+        .unwrap();
+    (client, captured)
+}
+
+#[tokio::test]
+async fn raw_bytes_decode_to_the_same_quotes() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_body(SINGLE_HOP).mount().await;
+    let client = server.client();
+
+    let raw = client
+        .fetch_swap_quotes_raw(&QuoteRequest::default())
+        .await
+        .unwrap();
+    assert_eq!(raw.as_ref(), SINGLE_HOP);
+
+    let decoded = decode_swap_quotes(&raw).unwrap();
+    let fetched = client
+        .fetch_swap_quotes(&QuoteRequest::default())
+        .await
+        .unwrap();
+    assert_eq!(decoded.id, fetched.id);
+    assert_eq!(
+        decoded.quotes[&QuoteKey::from("Titan")].out_amount,
+        fetched.quotes[&QuoteKey::from("Titan")].out_amount
+    );
+}
+
+#[tokio::test]
+async fn hook_sees_successful_responses() {
+    let server = MockTitanServer::start().await;
+    let route = SwapRouteBuilder::default().amounts(1_000_000, 990_000);
+    let body = route.clone().to_msgpack();
+    server.respond_with_body(body.clone()).mount().await;
+    let (client, captured) = capturing_client(&server);
+
+    let request = QuoteRequest {
+        input_mint: Pubkey::new_from_array([1; 32]),
+        output_mint: Pubkey::new_from_array([2; 32]),
+        amount: 1_000_000,
+        ..Default::default()
+    };
+    client.quote(&request).await.unwrap();
+
+    let captured = captured.lock().unwrap();
+    assert_eq!(captured.len(), 1);
+    let (url, status, bytes) = &captured[0];
+    assert_eq!(url, &format!("{}/api/v1/quote/swap", server.uri()));
+    assert_eq!(*status, 200);
+    assert_eq!(bytes, &body);
+}
+
+#[tokio::test]
+async fn hook_sees_bodies_that_fail_to_decode() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_body(b"not msgpack".to_vec())
+        .mount()
+        .await;
+    let (client, captured) = capturing_client(&server);
+
+    let err = client.quote(&QuoteRequest::default()).await.unwrap_err();
+    assert!(matches!(err, ClientError::MsgpackError(_)), "{err:?}");
+
+    let captured = captured.lock().unwrap();
+    assert_eq!(captured.len(), 1);
+    assert_eq!(captured[0].1, 200);
+    assert_eq!(captured[0].2, b"not msgpack");
+}
+
+#[tokio::test]
+async fn hook_sees_error_responses() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_error(404, "No routes found")
+        .mount()
+        .await;
+    let (client, captured) = capturing_client(&server);
+
+    let err = client.quote(&QuoteRequest::default()).await.unwrap_err();
+    assert!(matches!(err, ClientError::NoRoutesAvailable), "{err:?}");
+
+    let captured = captured.lock().unwrap();
+    assert_eq!(captured[0].1, 404);
+    assert_eq!(captured[0].2, b"No routes found");
+}
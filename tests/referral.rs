@@ -0,0 +1,132 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    format::ResponseFormat,
+    query::ParseError,
+    quote::{PlatformFee, QuoteRequest, QuoteResponse, ReferralError},
+    swap::SwapInstructionsOptions,
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+const REFERRAL_FEE_JSON: &[u8] = include_bytes!("fixtures/referral_fee.json");
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+const REFERRAL: Pubkey = Pubkey::new_from_array([7; 32]);
+
+fn request(referral_fee_bps: Option<u16>) -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey: Some(USER),
+        slippage_bps: 50,
+        referral_account: Some(REFERRAL),
+        referral_fee_bps,
+        ..sample_request()
+    }
+}
+
+#[test]
+fn sends_referral_params() {
+    let params = request(Some(5)).query_params();
+    assert!(params.contains(&("referralAccount", REFERRAL.to_string())));
+    assert!(params.contains(&("referralFeeBps", "5".to_string())));
+
+    let without_fee = request(None).query_params();
+    assert!(without_fee
+        .iter()
+        .all(|(name, _)| *name != "referralFeeBps"));
+
+    let parsed = QuoteRequest::from_query_str(&request(Some(5)).to_query_string()).unwrap();
+    assert_eq!(parsed, request(Some(5)));
+}
+
+#[test]
+fn rejects_a_fee_without_an_account() {
+    let request = QuoteRequest {
+        referral_account: None,
+        ..request(Some(5))
+    };
+    assert_eq!(
+        request.validate_referral(),
+        Err(ReferralError::FeeWithoutAccount(5))
+    );
+    assert!(matches!(
+        QuoteRequest::from_query_str(&request.to_query_string()),
+        Err(ParseError::InvalidValue {
+            name: "referralFeeBps",
+            ..
+        })
+    ));
+}
+
+#[tokio::test]
+async fn invalid_referral_is_rejected_before_sending() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::new(SOL_MINT, USDC_MINT))
+        .mount()
+        .await;
+
+    let err = server
+        .client()
+        .quote(&request(Some(10_001)))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::InvalidReferral(ReferralError::FeeOverMax(10_001))
+    ));
+    assert_eq!(server.received_request_count().await, 0);
+}
+
+#[tokio::test]
+async fn decodes_the_referral_fee_apart_from_the_platform_fee() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_json_body(REFERRAL_FEE_JSON)
+        .mount()
+        .await;
+    let client = server
+        .client_builder()
+        .response_format(ResponseFormat::Json)
+        .build()
+        .unwrap();
+
+    let quote = client.quote(&request(Some(5))).await.unwrap();
+    assert_eq!(
+        quote.referral_fee,
+        Some(PlatformFee {
+            amount: 93_827,
+            fee_bps: 5
+        })
+    );
+    assert_eq!(quote.platform_fee, None);
+    server
+        .assert_query_param("referralAccount", &REFERRAL.to_string())
+        .await;
+    server.assert_query_param("referralFeeBps", "5").await;
+
+    let json = quote.to_json_string().unwrap();
+    let restored = QuoteResponse::from_json_str(&json).unwrap();
+    assert_eq!(restored.referral_fee, quote.referral_fee);
+}
+
+#[tokio::test]
+async fn referral_quote_cannot_name_a_fee_account() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::new(SOL_MINT, USDC_MINT).referral_fee(500, 5))
+        .mount()
+        .await;
+    let client = server.client();
+    let quote = client.quote(&request(Some(5))).await.unwrap();
+
+    let options = SwapInstructionsOptions {
+        fee_account: Some(Pubkey::new_unique()),
+        ..Default::default()
+    };
+    let err = client.swap_instructions(&quote, options).await.unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::InvalidReferral(ReferralError::FeeAccountConflict)
+    ));
+}
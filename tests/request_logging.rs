@@ -0,0 +1,205 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::QuoteRequest,
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    TitanClient,
+};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+const TOKEN: &str = "very-secret-token";
+
+type Fields = BTreeMap<String, String>;
+
+/// Collects the fields of every "quote request" event.
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<Fields>>>);
+
+impl Capture {
+    fn events(&self) -> Vec<Fields> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+struct Visitor(Fields);
+
+impl Visit for Visitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = Visitor(Fields::new());
+        event.record(&mut visitor);
+        let fields = visitor.0;
+        if fields.get("message").map(String::as_str) == Some("quote request") {
+            self.0.lock().unwrap().push(fields);
+        }
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey: Some(USER),
+        ..sample_request()
+    }
+}
+
+async fn server() -> MockTitanServer {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, 187_000_000),
+        )
+        .mount()
+        .await;
+    server
+}
+
+async fn logged_quote(client: &TitanClient) -> Vec<Fields> {
+    let capture = Capture::default();
+    let _guard = tracing::subscriber::set_default(capture.clone());
+    client.quote(&request()).await.unwrap();
+    capture.events()
+}
+
+fn assert_no_token(events: &[Fields]) {
+    for fields in events {
+        for value in fields.values() {
+            assert!(!value.contains(TOKEN), "token logged in {:?}", fields);
+            assert!(
+                !value.contains("Bearer"),
+                "auth header logged in {:?}",
+                fields
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn logs_nothing_by_default() {
+    let server = server().await;
+    let client = TitanClient::builder(TOKEN)
+        .base_path(server.uri())
+        .build()
+        .unwrap();
+    assert!(logged_quote(&client).await.is_empty());
+}
+
+#[tokio::test]
+async fn logs_parameters_without_the_token() {
+    let server = server().await;
+    let client = TitanClient::builder(TOKEN)
+        .base_path(server.uri())
+        .log_requests(true)
+        .build()
+        .unwrap();
+
+    let events = logged_quote(&client).await;
+    assert_eq!(events.len(), 1);
+    let fields = &events[0];
+    assert_eq!(fields["method"], "GET");
+    assert!(fields["url"].starts_with(&server.uri()));
+    assert!(!fields["url"].contains('?'));
+    assert!(fields["query"].contains(&format!("userPublicKey={}", USER)));
+    assert!(fields["query"].contains("amount=1000000000"));
+    assert_eq!(fields["attempt"], "1");
+    assert_eq!(fields["hedge"], "false");
+    assert_eq!(fields["status"], "200");
+    assert!(fields.contains_key("latency_ms"));
+    assert_no_token(&events);
+}
+
+#[tokio::test]
+async fn redacts_the_user_pubkey() {
+    let server = server().await;
+    let client = TitanClient::builder(TOKEN)
+        .base_path(server.uri())
+        .log_requests(true)
+        .redact_user_pubkey(true)
+        .build()
+        .unwrap();
+
+    let first = logged_quote(&client).await;
+    let second = logged_quote(&client).await;
+    for events in [&first, &second] {
+        let query = &events[0]["query"];
+        assert!(!query.contains(&USER.to_string()), "{}", query);
+        assert!(query.contains("userPublicKey=sha256:"), "{}", query);
+        assert_no_token(events);
+    }
+    // The digest is stable, so a user's requests can still be correlated.
+    assert_eq!(first[0]["query"], second[0]["query"]);
+}
+
+#[tokio::test]
+async fn redacts_post_bodies_too() {
+    let server = server().await;
+    let client = TitanClient::builder(TOKEN)
+        .base_path(server.uri())
+        .prefer_post(true)
+        .log_requests(true)
+        .redact_user_pubkey(true)
+        .build()
+        .unwrap();
+
+    let events = logged_quote(&client).await;
+    assert_eq!(events[0]["method"], "POST");
+    assert!(!events[0]["query"].contains(&USER.to_string()));
+    assert_no_token(&events);
+}
+
+#[tokio::test]
+async fn numbers_failover_attempts() {
+    let failing = MockTitanServer::start().await;
+    failing.respond_with_status(503).mount().await;
+    let healthy = server().await;
+    let client = TitanClient::builder(TOKEN)
+        .base_urls(vec![failing.uri(), healthy.uri()])
+        .log_requests(true)
+        .build()
+        .unwrap();
+
+    let events = logged_quote(&client).await;
+    let attempts: Vec<_> = events
+        .iter()
+        .map(|fields| {
+            (
+                fields["attempt"].as_str(),
+                fields["status"].as_str(),
+                fields["url"].starts_with(&failing.uri()),
+            )
+        })
+        .collect();
+    assert_eq!(attempts, [("1", "503", true), ("2", "200", false)]);
+    assert_no_token(&events);
+}
@@ -0,0 +1,88 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{QuoteRequest, RouteField},
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey: Some(USER),
+        ..sample_request()
+    }
+}
+
+fn bare(provider: &str) -> SwapRouteBuilder {
+    SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+        .provider(provider)
+        .amounts(1_000_000_000, 187_000_000)
+}
+
+#[tokio::test]
+async fn missing_fields_stay_unset_by_default() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_route(bare("Alpha")).mount().await;
+    let client = server.client();
+
+    let quote = client.quote(&request()).await.unwrap();
+    let route = quote.route_data().unwrap();
+    assert_eq!(quote.context_slot, None);
+    assert_eq!(route.compute_units, None);
+    assert_eq!(route.expires_at_ms, None);
+
+    let swap = client.swap(&quote).unwrap();
+    assert_eq!(swap.compute_unit_limit, None);
+    assert_eq!(swap.context_slot, None);
+}
+
+#[tokio::test]
+async fn incomplete_routes_are_discarded() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_routes([
+            bare("Alpha"),
+            bare("Beta")
+                .compute_units(180_000, 240_000)
+                .context_slot(312_000_000),
+        ])
+        .mount()
+        .await;
+    let client = server
+        .client_builder()
+        .required_route_fields([RouteField::ComputeUnits, RouteField::ContextSlot])
+        .build()
+        .unwrap();
+
+    let quote = client.quote(&request()).await.unwrap();
+    assert_eq!(quote.provider(), Some("Beta"));
+
+    let quotes = client.quote_all(&request()).await.unwrap();
+    assert_eq!(quotes.keys().collect::<Vec<_>>(), ["Beta"]);
+}
+
+#[tokio::test]
+async fn no_complete_route_names_the_missing_field() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(bare("Alpha").compute_units(180_000, 240_000))
+        .mount()
+        .await;
+    let client = server
+        .client_builder()
+        .required_route_fields([RouteField::ComputeUnits, RouteField::ExpiresAtMs])
+        .build()
+        .unwrap();
+
+    let err = client.quote(&request()).await.unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::MissingRouteField(RouteField::ExpiresAtMs)
+    ));
+    assert_eq!(
+        err.to_string(),
+        "Route is missing required field expiresAtMs"
+    );
+}
@@ -0,0 +1,98 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    format::ResponseFormat,
+    quote::{QuoteRequest, QuoteResponse},
+    test_utils::{sample_request, MockTitanServer},
+};
+
+const SINGLE_HOP: &[u8] = include_bytes!("fixtures/single_hop.msgpack");
+const SINGLE_HOP_JSON: &[u8] = include_bytes!("fixtures/single_hop.json");
+const MULTI_HOP_SPLIT: &[u8] = include_bytes!("fixtures/multi_hop_split.msgpack");
+const MULTI_HOP_SPLIT_JSON: &[u8] = include_bytes!("fixtures/multi_hop_split.json");
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn request(amount: u64) -> QuoteRequest {
+    QuoteRequest {
+        amount,
+        user_pubkey: Some(USER),
+        slippage_bps: 50,
+        ..sample_request()
+    }
+}
+
+// Client-side timings differ between any two requests.
+fn without_timing(mut quote: QuoteResponse) -> serde_json::Value {
+    quote.timing = None;
+    serde_json::from_str(&quote.to_json_string().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn both_formats_decode_identically() {
+    for (msgpack, json, amount) in [
+        (SINGLE_HOP, SINGLE_HOP_JSON, 1_000_000_000),
+        (MULTI_HOP_SPLIT, MULTI_HOP_SPLIT_JSON, 5_000_000_000),
+    ] {
+        let msgpack_server = MockTitanServer::start().await;
+        msgpack_server.respond_with_body(msgpack).mount().await;
+        let json_server = MockTitanServer::start().await;
+        json_server.respond_with_json_body(json).mount().await;
+
+        let from_msgpack = msgpack_server
+            .client()
+            .quote(&request(amount))
+            .await
+            .unwrap();
+        let client = json_server
+            .client_builder()
+            .response_format(ResponseFormat::Json)
+            .build()
+            .unwrap();
+        let from_json = client.quote(&request(amount)).await.unwrap();
+
+        assert_eq!(without_timing(from_msgpack), without_timing(from_json));
+        assert_eq!(
+            json_server.received_quote_headers("accept").await,
+            vec![Some("application/json".to_string())]
+        );
+
+        let swap = client.swap(&client.quote(&request(amount)).await.unwrap());
+        assert!(!swap.unwrap().instructions.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn format_can_be_chosen_per_call() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_json_body(SINGLE_HOP_JSON).mount().await;
+
+    let quote = server
+        .client()
+        .quote_with_format(&request(1_000_000_000), ResponseFormat::Json)
+        .await
+        .unwrap();
+
+    assert_eq!(quote.out_amount, 187_654_321);
+    assert_eq!(
+        server.received_quote_headers("accept").await,
+        vec![Some("application/json".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn served_content_type_wins_over_the_requested_format() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_json_body(SINGLE_HOP_JSON).mount().await;
+
+    let quote = server
+        .client()
+        .quote(&request(1_000_000_000))
+        .await
+        .unwrap();
+
+    assert_eq!(quote.out_amount, 187_654_321);
+    assert_eq!(
+        server.received_quote_headers("accept").await,
+        vec![Some("application/vnd.msgpack".to_string())]
+    );
+}
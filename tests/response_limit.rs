@@ -0,0 +1,105 @@
+use std::time::Duration;
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    transport::{ReqwestTransport, TransportClient},
+    ClientError, TitanClient,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+#[tokio::test]
+async fn default_limit_accepts_normal_responses() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::new(SOL_MINT, USDC_MINT))
+        .mount()
+        .await;
+    server.client().quote(&sample_request()).await.unwrap();
+}
+
+#[tokio::test]
+async fn declared_length_over_the_limit_is_rejected() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_body(vec![0u8; 64 * 1024]).mount().await;
+    let client = server
+        .client_builder()
+        .max_response_bytes(1024)
+        .build()
+        .unwrap();
+
+    let err = client.quote(&sample_request()).await.unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::ResponseTooLarge {
+            limit: 1024,
+            received_at_least: 65_536
+        }
+    ));
+}
+
+/// Streams chunks forever, so a client that buffered the whole body before
+/// checking its size would never return.
+async fn endless_chunked_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = [0u8; 4096];
+        let _ = socket.read(&mut request).await;
+        let headers = "HTTP/1.1 200 OK\r\n\
+                       Content-Type: application/vnd.msgpack\r\n\
+                       Transfer-Encoding: chunked\r\n\r\n";
+        if socket.write_all(headers.as_bytes()).await.is_err() {
+            return;
+        }
+        let chunk = format!("{:x}\r\n{}\r\n", 4096, "a".repeat(4096));
+        while socket.write_all(chunk.as_bytes()).await.is_ok() {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    });
+    format!("http://{address}")
+}
+
+#[tokio::test]
+async fn streamed_body_is_abandoned_once_over_the_limit() {
+    let client = TitanClient::builder("token")
+        .base_path(endless_chunked_server().await)
+        .max_response_bytes(64 * 1024)
+        .build()
+        .unwrap();
+
+    let err = tokio::time::timeout(Duration::from_secs(5), client.quote(&sample_request()))
+        .await
+        .expect("the download is interrupted")
+        .unwrap_err();
+    match err {
+        ClientError::ResponseTooLarge {
+            limit,
+            received_at_least,
+        } => {
+            assert_eq!(limit, 64 * 1024);
+            assert!(received_at_least > limit);
+        }
+        err => panic!("unexpected error {err:?}"),
+    }
+}
+
+#[tokio::test]
+async fn reqwest_transport_abandons_oversized_bodies() {
+    let transport = ReqwestTransport::default().with_max_response_bytes(64 * 1024);
+    let client = TransportClient::new(
+        transport,
+        "token".to_string(),
+        Some(endless_chunked_server().await),
+    );
+
+    let err = tokio::time::timeout(Duration::from_secs(5), client.quote(&sample_request()))
+        .await
+        .expect("the download is interrupted")
+        .unwrap_err();
+    assert!(
+        matches!(err, ClientError::ResponseTooLarge { limit, .. } if limit == 64 * 1024),
+        "unexpected error {err:?}"
+    );
+}
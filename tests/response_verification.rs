@@ -0,0 +1,177 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{QuoteRequest, SwapMode},
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    transform::verify_swap_quotes,
+    ClientError,
+};
+
+const BONK: Pubkey = pubkey!("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263");
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        swap_mode: Some(SwapMode::ExactIn),
+        ..sample_request()
+    }
+}
+
+fn route() -> SwapRouteBuilder {
+    SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, 187_000_000)
+}
+
+/// Serves `route` as built, whatever was requested.
+async fn server_serving(route: SwapRouteBuilder) -> MockTitanServer {
+    let server = MockTitanServer::start().await;
+    server.respond_with_body(route.to_msgpack()).mount().await;
+    server
+}
+
+fn mismatch(err: ClientError) -> (&'static str, String, String) {
+    match err {
+        ClientError::ResponseMismatch {
+            field,
+            expected,
+            actual,
+        } => (field, expected, actual),
+        other => panic!("expected ResponseMismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn matching_response_passes() {
+    assert!(verify_swap_quotes(&request(), &route().to_swap_quotes()).is_ok());
+}
+
+#[test]
+fn each_field_is_checked() {
+    let cases = [
+        (
+            SwapRouteBuilder::new(BONK, USDC_MINT).amounts(1_000_000_000, 1),
+            ("inputMint", SOL_MINT.to_string(), BONK.to_string()),
+        ),
+        (
+            SwapRouteBuilder::new(SOL_MINT, BONK).amounts(1_000_000_000, 1),
+            ("outputMint", USDC_MINT.to_string(), BONK.to_string()),
+        ),
+        (
+            route().swap_mode(SwapMode::ExactOut),
+            ("swapMode", "ExactIn".to_string(), "ExactOut".to_string()),
+        ),
+        (
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(2_000_000_000, 1),
+            ("amount", "1000000000".to_string(), "2000000000".to_string()),
+        ),
+    ];
+    for (route, expected) in cases {
+        let err = verify_swap_quotes(&request(), &route.to_swap_quotes()).unwrap_err();
+        assert_eq!(mismatch(err), expected);
+    }
+}
+
+#[test]
+fn unset_swap_mode_expects_exact_in() {
+    let request = QuoteRequest {
+        swap_mode: None,
+        ..request()
+    };
+    assert!(verify_swap_quotes(&request, &route().to_swap_quotes()).is_ok());
+
+    let quotes = route().swap_mode(SwapMode::ExactOut).to_swap_quotes();
+    let err = verify_swap_quotes(&request, &quotes).unwrap_err();
+    assert_eq!(
+        mismatch(err),
+        ("swapMode", "ExactIn".to_string(), "ExactOut".to_string())
+    );
+}
+
+#[test]
+fn error_names_the_field() {
+    let quotes = SwapRouteBuilder::new(SOL_MINT, BONK)
+        .amounts(1_000_000_000, 1)
+        .to_swap_quotes();
+    let err = verify_swap_quotes(&request(), &quotes).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("Response outputMint is {BONK}, but {USDC_MINT} was requested")
+    );
+}
+
+#[tokio::test]
+async fn quote_for_another_pair_is_rejected() {
+    let server =
+        server_serving(SwapRouteBuilder::new(SOL_MINT, BONK).amounts(1_000_000_000, 1)).await;
+
+    let err = server.client().quote(&request()).await.unwrap_err();
+
+    assert_eq!(mismatch(err).0, "outputMint");
+}
+
+#[tokio::test]
+async fn quote_for_another_amount_is_rejected() {
+    let server = server_serving(route()).await;
+    let request = QuoteRequest {
+        amount: 5_000_000_000,
+        ..request()
+    };
+
+    let err = server.client().quote(&request).await.unwrap_err();
+
+    assert_eq!(
+        mismatch(err),
+        ("amount", "5000000000".to_string(), "1000000000".to_string())
+    );
+}
+
+#[tokio::test]
+async fn mode_is_verified_when_the_request_leaves_it_unset() {
+    let server = server_serving(route().swap_mode(SwapMode::ExactOut)).await;
+    let request = QuoteRequest {
+        swap_mode: None,
+        ..request()
+    };
+
+    let err = server.client().quote(&request).await.unwrap_err();
+
+    assert_eq!(
+        mismatch(err),
+        ("swapMode", "ExactIn".to_string(), "ExactOut".to_string())
+    );
+}
+
+#[tokio::test]
+async fn quote_all_is_verified_too() {
+    let server = server_serving(route().swap_mode(SwapMode::ExactOut)).await;
+
+    let err = server.client().quote_all(&request()).await.unwrap_err();
+
+    assert_eq!(mismatch(err).0, "swapMode");
+}
+
+#[tokio::test]
+async fn verification_can_be_turned_off() {
+    let server =
+        server_serving(SwapRouteBuilder::new(SOL_MINT, BONK).amounts(1_000_000_000, 1)).await;
+    let client = server
+        .client_builder()
+        .verify_responses(false)
+        .build()
+        .unwrap();
+
+    let quote = client.quote(&request()).await.unwrap();
+
+    assert_eq!(quote.out_amount, 1);
+}
+
+#[tokio::test]
+async fn mock_routes_answer_the_request() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_route(route()).mount().await;
+    let request = QuoteRequest {
+        output_mint: BONK,
+        amount: 3_000_000_000,
+        ..request()
+    };
+
+    assert!(server.client().quote(&request).await.is_ok());
+}
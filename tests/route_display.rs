@@ -0,0 +1,99 @@
+//! Pinned renderings of the fixture routes. If a formatting change is
+//! intended, update the files under `tests/snapshots/` to match.
+
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{QuoteRequest, QuoteResponse, SwapMode},
+    test_utils::MockTitanServer,
+    tokens::{TokenInfo, TokenRegistry},
+};
+
+const SINGLE_HOP: &[u8] = include_bytes!("fixtures/single_hop.msgpack");
+const MULTI_HOP_SPLIT: &[u8] = include_bytes!("fixtures/multi_hop_split.msgpack");
+const PLATFORM_FEE: &[u8] = include_bytes!("fixtures/platform_fee.msgpack");
+
+const BONK: Pubkey = pubkey!("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263");
+
+async fn quote_fixture(body: &[u8], amount: u64) -> QuoteResponse {
+    let server = MockTitanServer::start().await;
+    server.respond_with_body(body).mount().await;
+    let request = QuoteRequest {
+        input_mint: SOL_MINT,
+        output_mint: USDC_MINT,
+        amount,
+        swap_mode: Some(SwapMode::ExactIn),
+        ..Default::default()
+    };
+    server.client().quote(&request).await.unwrap()
+}
+
+fn registry() -> TokenRegistry {
+    let token = |mint, symbol: &str, decimals| TokenInfo {
+        mint,
+        symbol: symbol.to_string(),
+        name: symbol.to_string(),
+        decimals,
+        tags: vec![],
+    };
+    TokenRegistry::from_tokens(vec![
+        token(SOL_MINT, "SOL", 9),
+        token(USDC_MINT, "USDC", 6),
+        token(BONK, "BONK", 5),
+    ])
+}
+
+fn assert_snapshot(rendered: String, snapshot: &str) {
+    assert_eq!(rendered, snapshot.trim_end(), "\n{rendered}\n");
+}
+
+#[tokio::test]
+async fn single_hop() {
+    let quote = quote_fixture(SINGLE_HOP, 1_000_000_000).await;
+    assert_snapshot(
+        quote.display().registry(&registry()).to_string(),
+        include_str!("snapshots/route_display_single_hop.txt"),
+    );
+}
+
+#[tokio::test]
+async fn multi_hop_split() {
+    let quote = quote_fixture(MULTI_HOP_SPLIT, 5_000_000_000).await;
+    let display = quote.display().registry(&registry());
+    assert_snapshot(
+        display.to_string(),
+        include_str!("snapshots/route_display_multi_hop_split.txt"),
+    );
+    assert_eq!(
+        display.compact(),
+        "5 SOL → 938.1 USDC: SOL ─(Whirlpool 60%, Raydium CLMM 40%)→ BONK ─(Phoenix 100%)→ USDC"
+    );
+}
+
+#[tokio::test]
+async fn platform_fee() {
+    let quote = quote_fixture(PLATFORM_FEE, 1_000_000_000).await;
+    assert_snapshot(
+        quote.display().registry(&registry()).to_string(),
+        include_str!("snapshots/route_display_platform_fee.txt"),
+    );
+}
+
+#[tokio::test]
+async fn unknown_mints_are_abbreviated_in_atoms() {
+    let quote = quote_fixture(SINGLE_HOP, 1_000_000_000).await;
+    assert_eq!(
+        quote.display().compact(),
+        "1,000,000,000 So11…1112 → 187,654,321 EPjF…Dt1v: So11…1112 ─(Whirlpool 100%)→ EPjF…Dt1v"
+    );
+    // Individually supplied tokens override the address.
+    assert_eq!(
+        quote
+            .display()
+            .token(SOL_MINT, "SOL", 9)
+            .token(USDC_MINT, "USDC", 6)
+            .compact(),
+        "1 SOL → 187.654321 USDC: SOL ─(Whirlpool 100%)→ USDC"
+    );
+    assert_eq!(quote.to_string(), quote.display().to_string());
+}
@@ -0,0 +1,143 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use std::collections::HashSet;
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{QuoteRequest, QuoteResponse},
+    route_shape::{shape_diff, ShapeChange},
+    test_utils::SwapRouteBuilder,
+};
+
+const BONK: Pubkey = pubkey!("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263");
+
+fn quote(route: SwapRouteBuilder) -> QuoteResponse {
+    QuoteResponse::from_route(
+        &QuoteRequest {
+            input_mint: SOL_MINT,
+            output_mint: USDC_MINT,
+            amount: 1_000_000,
+            ..Default::default()
+        },
+        route.build(),
+    )
+}
+
+fn split(whirlpool_ppb: u32, raydium_ppb: u32) -> QuoteResponse {
+    quote(
+        SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+            .step(
+                "Whirlpool",
+                Pubkey::new_unique(),
+                SOL_MINT,
+                USDC_MINT,
+                0,
+                0,
+                whirlpool_ppb,
+            )
+            .step(
+                "Raydium",
+                Pubkey::new_unique(),
+                SOL_MINT,
+                USDC_MINT,
+                0,
+                0,
+                raydium_ppb,
+            ),
+    )
+}
+
+#[test]
+fn splits_are_bucketed_to_five_percent() {
+    let shape = split(612_000_000, 388_000_000).route_shape();
+    let splits: Vec<u8> = shape.steps.iter().map(|s| s.split_percent).collect();
+    assert_eq!(splits, [60, 40]);
+    assert_eq!(shape.hop_count(), 1);
+
+    // A small reallocation within a bucket is the same shape, even from
+    // different pools of the same venues.
+    assert_eq!(shape, split(590_000_000, 410_000_000).route_shape());
+    let shapes: HashSet<_> = [
+        shape.clone(),
+        split(599_000_000, 401_000_000).route_shape(),
+        split(700_000_000, 300_000_000).route_shape(),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(shapes.len(), 2);
+}
+
+#[test]
+fn identical_shapes_have_no_diff() {
+    let shape = split(600_000_000, 400_000_000).route_shape();
+    assert!(shape_diff(&shape, &shape).is_empty());
+}
+
+#[test]
+fn diff_reports_split_changes() {
+    let old = split(600_000_000, 400_000_000).route_shape();
+    let new = split(250_000_000, 750_000_000).route_shape();
+    let changes = shape_diff(&old, &new);
+    assert_eq!(changes.len(), 2);
+    assert!(matches!(
+        &changes[0],
+        ShapeChange::SplitChanged { venue, from: 40, to: 75 } if venue.label == "Raydium"
+    ));
+    assert_eq!(
+        changes[1].to_string(),
+        format!("Whirlpool ({SOL_MINT} -> {USDC_MINT}) split 60% -> 25%")
+    );
+}
+
+#[test]
+fn diff_reports_venue_and_hop_changes() {
+    let old = split(600_000_000, 400_000_000).route_shape();
+    let new = quote(
+        SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+            .step(
+                "Whirlpool",
+                Pubkey::new_unique(),
+                SOL_MINT,
+                BONK,
+                0,
+                0,
+                1_000_000_000,
+            )
+            .step(
+                "Meteora",
+                Pubkey::new_unique(),
+                BONK,
+                USDC_MINT,
+                0,
+                0,
+                1_000_000_000,
+            ),
+    )
+    .route_shape();
+    assert_eq!(new.hop_count(), 2);
+
+    let changes: Vec<String> = old.diff(&new).iter().map(ToString::to_string).collect();
+    assert_eq!(
+        changes,
+        [
+            "hops 1 -> 2".to_string(),
+            format!("removed Raydium ({SOL_MINT} -> {USDC_MINT}) at 40%"),
+            format!("removed Whirlpool ({SOL_MINT} -> {USDC_MINT}) at 60%"),
+            format!("added Meteora ({BONK} -> {USDC_MINT}) at 100%"),
+            format!("added Whirlpool ({SOL_MINT} -> {BONK}) at 100%"),
+        ]
+    );
+}
+
+#[test]
+fn missing_allocation_is_the_whole_input() {
+    let shape = quote(SwapRouteBuilder::new(SOL_MINT, USDC_MINT).step(
+        "Whirlpool",
+        Pubkey::new_unique(),
+        SOL_MINT,
+        USDC_MINT,
+        0,
+        0,
+        0,
+    ))
+    .route_shape();
+    assert_eq!(shape.steps[0].split_percent, 100);
+}
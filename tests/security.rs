@@ -0,0 +1,257 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_system_interface::instruction as system_instruction;
+use titan_swap_api_client::{
+    constants::{
+        JITO_TIP_ACCOUNTS, SYSTEM_PROGRAM_ID, TITAN_PROGRAM_ID, TOKEN_PROGRAM_ID, USDC_MINT,
+        WSOL_MINT,
+    },
+    security::{default_program_allowlist, SecurityError},
+    swap::SwapResponse,
+    token_2022::associated_token_address,
+};
+
+const USER: Pubkey = Pubkey::new_from_array([7; 32]);
+const MAX_TIP: u64 = 100_000;
+
+fn swap(instructions: Vec<Instruction>) -> SwapResponse {
+    SwapResponse {
+        instructions,
+        address_lookup_table_addresses: vec![],
+        compute_unit_limit: None,
+        compute_units_safe: None,
+        context_slot: None,
+        expires_at_ms: None,
+        expires_after_slot: None,
+    }
+}
+
+fn titan_swap() -> Instruction {
+    Instruction::new_with_bytes(
+        TITAN_PROGRAM_ID,
+        &[1],
+        vec![
+            AccountMeta::new(USER, true),
+            AccountMeta::new(Pubkey::new_unique(), false),
+        ],
+    )
+}
+
+fn verify(instructions: Vec<Instruction>) -> Result<(), SecurityError> {
+    swap(instructions).verify_programs(&default_program_allowlist(), USER, MAX_TIP)
+}
+
+#[test]
+fn allowlisted_routes_pass() {
+    let wrapped_sol = associated_token_address(&USER, &WSOL_MINT, &TOKEN_PROGRAM_ID);
+    assert_eq!(
+        verify(vec![
+            system_instruction::transfer(&USER, &wrapped_sol, 1_000_000),
+            titan_swap(),
+            system_instruction::transfer(&USER, &JITO_TIP_ACCOUNTS[0], 10_000),
+        ]),
+        Ok(())
+    );
+}
+
+#[test]
+fn unknown_programs_the_user_signs_for_are_rejected() {
+    let program_id = Pubkey::new_unique();
+    let drain = Instruction::new_with_bytes(
+        program_id,
+        &[],
+        vec![
+            AccountMeta::new(Pubkey::new_unique(), true),
+            AccountMeta::new(USER, true),
+        ],
+    );
+    assert_eq!(
+        verify(vec![titan_swap(), drain]),
+        Err(SecurityError::UnexpectedSigner {
+            index: 1,
+            program_id,
+            signer: USER,
+        })
+    );
+
+    // Another signer alone doesn't make it the user's.
+    let other = Instruction::new_with_bytes(
+        program_id,
+        &[],
+        vec![AccountMeta::new(Pubkey::new_unique(), true)],
+    );
+    assert_eq!(
+        verify(vec![other]),
+        Err(SecurityError::UnexpectedProgram {
+            index: 0,
+            program_id,
+        })
+    );
+}
+
+#[test]
+fn system_transfers_elsewhere_are_rejected() {
+    let transfer = system_instruction::transfer(&USER, &Pubkey::new_unique(), 1_000_000);
+    let unexpected = Err(SecurityError::UnexpectedSigner {
+        index: 1,
+        program_id: SYSTEM_PROGRAM_ID,
+        signer: USER,
+    });
+    assert_eq!(verify(vec![titan_swap(), transfer.clone()]), unexpected);
+
+    // Even when the allowlist names the System program.
+    let mut allowlist = default_program_allowlist();
+    allowlist.insert(SYSTEM_PROGRAM_ID);
+    assert_eq!(
+        swap(vec![titan_swap(), transfer]).verify_programs(&allowlist, USER, MAX_TIP),
+        unexpected
+    );
+}
+
+#[test]
+fn tips_over_the_limit_are_rejected() {
+    let drain = system_instruction::transfer(&USER, &JITO_TIP_ACCOUNTS[0], 5_000_000_000);
+    assert_eq!(
+        verify(vec![titan_swap(), drain.clone()]),
+        Err(SecurityError::TipTooLarge {
+            index: 1,
+            lamports: 5_000_000_000,
+            max: MAX_TIP,
+        })
+    );
+    assert_eq!(
+        swap(vec![drain]).verify_programs(&default_program_allowlist(), USER, 0),
+        Err(SecurityError::TipTooLarge {
+            index: 0,
+            lamports: 5_000_000_000,
+            max: 0,
+        })
+    );
+}
+
+#[test]
+fn tips_are_capped_in_total() {
+    let tip = |to| system_instruction::transfer(&USER, to, MAX_TIP);
+    assert_eq!(
+        verify(vec![titan_swap(), tip(&JITO_TIP_ACCOUNTS[0])]),
+        Ok(())
+    );
+    assert_eq!(
+        verify(vec![
+            titan_swap(),
+            tip(&JITO_TIP_ACCOUNTS[0]),
+            tip(&JITO_TIP_ACCOUNTS[1]),
+            tip(&JITO_TIP_ACCOUNTS[0]),
+        ]),
+        Err(SecurityError::TipTooLarge {
+            index: 2,
+            lamports: 2 * MAX_TIP,
+            max: MAX_TIP,
+        })
+    );
+}
+
+/// A token instruction with `data`, signed by `USER` as its last account.
+fn token_instruction(data: &[u8], accounts: &[Pubkey]) -> Instruction {
+    let mut metas: Vec<_> = accounts
+        .iter()
+        .map(|&account| AccountMeta::new(account, false))
+        .collect();
+    metas.push(AccountMeta::new_readonly(USER, true));
+    Instruction::new_with_bytes(TOKEN_PROGRAM_ID, data, metas)
+}
+
+const TRANSFER: &[u8] = &[3, 0, 0, 0, 0, 0, 0, 0, 1];
+const APPROVE: &[u8] = &[4, 0, 0, 0, 0, 0, 0, 0, 1];
+const TRANSFER_CHECKED: &[u8] = &[12, 0, 0, 0, 0, 0, 0, 0, 1, 6];
+const CLOSE_ACCOUNT: &[u8] = &[9];
+
+#[test]
+fn user_signed_token_transfers_to_other_accounts_are_rejected() {
+    let source = associated_token_address(&USER, &USDC_MINT, &TOKEN_PROGRAM_ID);
+    let attacker = Pubkey::new_unique();
+    for ix in [
+        token_instruction(TRANSFER, &[source, attacker]),
+        token_instruction(APPROVE, &[source, attacker]),
+        token_instruction(TRANSFER_CHECKED, &[source, USDC_MINT, attacker]),
+    ] {
+        assert_eq!(
+            verify(vec![titan_swap(), ix]),
+            Err(SecurityError::UnexpectedTokenRecipient {
+                index: 1,
+                recipient: attacker,
+            })
+        );
+    }
+}
+
+#[test]
+fn token_transfers_to_own_accounts_pass() {
+    let source = associated_token_address(&USER, &USDC_MINT, &TOKEN_PROGRAM_ID);
+    let wrapped_sol = associated_token_address(&USER, &WSOL_MINT, &TOKEN_PROGRAM_ID);
+    assert_eq!(
+        verify(vec![
+            token_instruction(TRANSFER, &[source, wrapped_sol]),
+            token_instruction(TRANSFER_CHECKED, &[wrapped_sol, USDC_MINT, source]),
+            token_instruction(CLOSE_ACCOUNT, &[wrapped_sol, USER]),
+            titan_swap(),
+        ]),
+        Ok(())
+    );
+
+    // Nobody's transfer but the user's is checked.
+    let unsigned = Instruction::new_with_bytes(
+        TOKEN_PROGRAM_ID,
+        TRANSFER,
+        vec![
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), true),
+        ],
+    );
+    assert_eq!(verify(vec![titan_swap(), unsigned]), Ok(()));
+}
+
+#[test]
+fn route_accounts_are_not_the_users() {
+    let source = associated_token_address(&USER, &USDC_MINT, &TOKEN_PROGRAM_ID);
+    let route = titan_swap();
+    let vault = route.accounts[1].pubkey;
+    for ix in [
+        token_instruction(TRANSFER, &[source, vault]),
+        token_instruction(APPROVE, &[source, vault]),
+        token_instruction(CLOSE_ACCOUNT, &[source, vault]),
+    ] {
+        assert_eq!(
+            verify(vec![route.clone(), ix]),
+            Err(SecurityError::UnexpectedTokenRecipient {
+                index: 1,
+                recipient: vault,
+            })
+        );
+    }
+}
+
+#[test]
+fn other_user_signed_token_instructions_are_rejected() {
+    let source = associated_token_address(&USER, &USDC_MINT, &TOKEN_PROGRAM_ID);
+    let attacker = Pubkey::new_unique();
+    // SetAuthority to AccountOwner, handing the account to `attacker`.
+    let mut set_authority = vec![6, 2, 1];
+    set_authority.extend_from_slice(attacker.as_ref());
+    for (data, accounts) in [
+        (set_authority, vec![source]),
+        (vec![8, 0, 0, 0, 0, 0, 0, 0, 1], vec![source, USDC_MINT]),
+    ] {
+        let tag = data[0];
+        assert_eq!(
+            verify(vec![titan_swap(), token_instruction(&data, &accounts)]),
+            Err(SecurityError::UnexpectedTokenInstruction {
+                index: 1,
+                tag: Some(tag),
+            })
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    query::ParseError,
+    quote::{QuoteRequest, SizeConstraint, SizeConstraintError, MAX_TRANSACTION_BYTES},
+    test_utils::sample_request,
+};
+
+fn size_param(request: &QuoteRequest) -> Option<String> {
+    request
+        .query_params()
+        .into_iter()
+        .find(|(name, _)| *name == "sizeConstraint")
+        .map(|(_, value)| value)
+}
+
+#[test]
+fn constructors_validate_the_range() {
+    assert_eq!(
+        SizeConstraint::max_transaction_bytes(1000).unwrap().bytes(),
+        1000
+    );
+    assert_eq!(
+        SizeConstraint::max_transaction_bytes(MAX_TRANSACTION_BYTES)
+            .unwrap()
+            .bytes(),
+        1232
+    );
+    assert_eq!(
+        SizeConstraint::max_transaction_bytes(0),
+        Err(SizeConstraintError(0))
+    );
+    assert_eq!(
+        SizeConstraint::max_transaction_bytes(1233),
+        Err(SizeConstraintError(1233))
+    );
+    assert_eq!(
+        SizeConstraint::try_from(u64::MAX),
+        Err(SizeConstraintError(u64::MAX))
+    );
+
+    assert_eq!(SizeConstraint::reserving_bytes(200).unwrap().bytes(), 1032);
+    assert!(SizeConstraint::reserving_bytes(MAX_TRANSACTION_BYTES).is_err());
+    assert!(SizeConstraint::reserving_bytes(u16::MAX).is_err());
+}
+
+#[test]
+fn wire_format_is_bytes_as_a_decimal_integer() {
+    let request = QuoteRequest {
+        size_constraint: Some(SizeConstraint::max_transaction_bytes(1032).unwrap()),
+        ..sample_request()
+    };
+    assert_eq!(size_param(&request).as_deref(), Some("1032"));
+    assert!(request.to_query_string().contains("&sizeConstraint=1032"));
+
+    assert_eq!(size_param(&QuoteRequest::default()), None);
+}
+
+#[test]
+#[allow(deprecated)]
+fn raw_value_passes_through_unchecked() {
+    let raw = QuoteRequest {
+        size_constraints: Some(5000),
+        ..sample_request()
+    };
+    assert_eq!(size_param(&raw).as_deref(), Some("5000"));
+
+    let both = QuoteRequest {
+        size_constraint: Some(SizeConstraint::max_transaction_bytes(900).unwrap()),
+        size_constraints: Some(5000),
+        ..sample_request()
+    };
+    assert_eq!(size_param(&both).as_deref(), Some("900"));
+}
+
+#[test]
+fn parsing_validates_the_range() {
+    let query = format!("inputMint={SOL_MINT}&outputMint={USDC_MINT}&amount=1&sizeConstraint=1000");
+    assert_eq!(
+        QuoteRequest::from_query_str(&query)
+            .unwrap()
+            .size_constraint,
+        Some(SizeConstraint::max_transaction_bytes(1000).unwrap())
+    );
+
+    for value in ["0", "1233", "-1"] {
+        let query =
+            format!("inputMint={SOL_MINT}&outputMint={USDC_MINT}&amount=1&sizeConstraint={value}");
+        assert!(
+            matches!(
+                QuoteRequest::from_query_str(&query),
+                Err(ParseError::InvalidValue {
+                    name: "sizeConstraint",
+                    ..
+                })
+            ),
+            "{value}"
+        );
+    }
+}
+
+#[test]
+fn serializes_as_a_number() {
+    let request = QuoteRequest {
+        size_constraint: Some(SizeConstraint::max_transaction_bytes(1100).unwrap()),
+        ..sample_request()
+    };
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["sizeConstraint"], 1100);
+
+    let mut json = json;
+    json["sizeConstraint"] = 2000.into();
+    assert!(serde_json::from_value::<QuoteRequest>(json).is_err());
+}
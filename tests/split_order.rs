@@ -0,0 +1,251 @@
+use proptest::prelude::*;
+use solana_sdk::{
+    hash::Hash,
+    message::AddressLookupTableAccount,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::VersionedTransaction,
+};
+use std::sync::Mutex;
+use titan_swap_api_client::{
+    execute::{ExecuteOptions, RpcError, Simulation, SwapRpc},
+    ladder::QuoteLadder,
+    quote::{QuoteRequest, QuoteResponse},
+    split::{split_order, SplitError, SplitStrategy, MAX_PARTS},
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    ClientError,
+};
+
+const INPUT: Pubkey = Pubkey::new_from_array([1; 32]);
+const OUTPUT: Pubkey = Pubkey::new_from_array([2; 32]);
+
+fn request(amount: u64) -> QuoteRequest {
+    QuoteRequest {
+        input_mint: INPUT,
+        output_mint: OUTPUT,
+        amount,
+        slippage_bps: 30,
+        ..Default::default()
+    }
+}
+
+fn amounts(strategy: SplitStrategy<'_>, amount: u64) -> Result<Vec<u64>, SplitError> {
+    let parts = split_order(&request(amount), strategy)?;
+    for part in &parts {
+        assert_eq!(
+            QuoteRequest {
+                amount,
+                ..part.clone()
+            },
+            request(amount)
+        );
+    }
+    Ok(parts.into_iter().map(|part| part.amount).collect())
+}
+
+/// Levels where 100 is the reference price, 1_000 is 100 bps worse and
+/// 10_000 is 1_000 bps worse.
+fn ladder() -> QuoteLadder {
+    let level = |amount, out_amount| {
+        let route = SwapRouteBuilder::new(INPUT, OUTPUT)
+            .amounts(amount, out_amount)
+            .build();
+        (
+            amount,
+            Ok(QuoteResponse::from_route(&request(amount), route)),
+        )
+    };
+    QuoteLadder::from_results([level(100, 100), level(1_000, 990), level(10_000, 9_000)])
+}
+
+#[test]
+fn even_parts_spread_the_remainder_over_the_first_parts() {
+    assert_eq!(
+        amounts(SplitStrategy::EvenParts(4), 1_000),
+        Ok(vec![250; 4])
+    );
+    assert_eq!(
+        amounts(SplitStrategy::EvenParts(3), 1_000),
+        Ok(vec![334, 333, 333])
+    );
+    assert_eq!(
+        amounts(SplitStrategy::EvenParts(4), 1_003),
+        Ok(vec![251, 251, 251, 250])
+    );
+    assert_eq!(amounts(SplitStrategy::EvenParts(1), 7), Ok(vec![7]));
+}
+
+#[test]
+fn even_parts_never_include_zero() {
+    assert_eq!(amounts(SplitStrategy::EvenParts(5), 3), Ok(vec![1, 1, 1]));
+    assert_eq!(amounts(SplitStrategy::EvenParts(3), 0), Ok(vec![]));
+    // More parts than allowed, but never more than there are units.
+    assert_eq!(
+        amounts(SplitStrategy::EvenParts(u32::MAX), 2),
+        Ok(vec![1, 1])
+    );
+}
+
+#[test]
+fn fixed_clips_end_with_the_remainder() {
+    assert_eq!(
+        amounts(SplitStrategy::FixedClip(300), 1_000),
+        Ok(vec![300, 300, 300, 100])
+    );
+    assert_eq!(
+        amounts(SplitStrategy::FixedClip(250), 1_000),
+        Ok(vec![250; 4])
+    );
+    assert_eq!(
+        amounts(SplitStrategy::FixedClip(5_000), 1_000),
+        Ok(vec![1_000])
+    );
+}
+
+#[test]
+fn max_impact_clips_at_the_largest_level_within_it() {
+    let ladder = ladder();
+    assert_eq!(
+        amounts(SplitStrategy::MaxImpactBps(150.0, &ladder), 2_500),
+        Ok(vec![1_000, 1_000, 500])
+    );
+    assert_eq!(
+        amounts(SplitStrategy::MaxImpactBps(5_000.0, &ladder), 2_500),
+        Ok(vec![2_500])
+    );
+    assert_eq!(
+        amounts(SplitStrategy::MaxImpactBps(0.0, &ladder), 250),
+        Ok(vec![100, 100, 50])
+    );
+}
+
+#[test]
+fn rejects_degenerate_strategies() {
+    assert_eq!(
+        amounts(SplitStrategy::EvenParts(0), 1_000),
+        Err(SplitError::ZeroParts)
+    );
+    assert_eq!(
+        amounts(SplitStrategy::FixedClip(0), 1_000),
+        Err(SplitError::ZeroClip)
+    );
+    assert_eq!(
+        amounts(SplitStrategy::FixedClip(1), u64::MAX),
+        Err(SplitError::TooManyParts(u64::MAX))
+    );
+    assert_eq!(
+        amounts(SplitStrategy::EvenParts(MAX_PARTS as u32 + 1), 1_000_000),
+        Err(SplitError::TooManyParts(MAX_PARTS + 1))
+    );
+    let empty = QuoteLadder::from_results([]);
+    assert_eq!(
+        amounts(SplitStrategy::MaxImpactBps(100.0, &empty), 1_000),
+        Err(SplitError::NoLevelWithin(100.0))
+    );
+}
+
+proptest! {
+    #[test]
+    fn even_parts_add_up(amount in any::<u64>(), parts in 1..=MAX_PARTS as u32) {
+        let split = amounts(SplitStrategy::EvenParts(parts), amount).unwrap();
+        let total: u128 = split.iter().map(|part| u128::from(*part)).sum();
+        prop_assert_eq!(total, u128::from(amount));
+        prop_assert!(split.iter().all(|part| *part > 0));
+        prop_assert!(split.windows(2).all(|pair| pair[0] - pair[1] <= 1));
+    }
+
+    #[test]
+    fn fixed_clips_add_up(amount in 0..1_000_000u64, clip in 1_000..2_000_000u64) {
+        let split = amounts(SplitStrategy::FixedClip(clip), amount).unwrap();
+        prop_assert_eq!(split.iter().sum::<u64>(), amount);
+        prop_assert!(split.iter().all(|part| *part > 0 && *part <= clip));
+        prop_assert!(split.iter().rev().skip(1).all(|part| *part == clip));
+    }
+}
+
+/// Sends succeed until `failing_send`, counted from zero, which fails.
+struct MockRpc {
+    failing_send: usize,
+    sent: Mutex<Vec<VersionedTransaction>>,
+}
+
+impl SwapRpc for MockRpc {
+    async fn latest_blockhash(&self) -> Result<Hash, RpcError> {
+        Ok(Hash::new_from_array([9; 32]))
+    }
+
+    async fn lookup_tables(
+        &self,
+        _addresses: &[Pubkey],
+    ) -> Result<Vec<AddressLookupTableAccount>, RpcError> {
+        Ok(Vec::new())
+    }
+
+    async fn simulate(&self, _transaction: &VersionedTransaction) -> Result<Simulation, RpcError> {
+        Ok(Simulation::default())
+    }
+
+    async fn send(&self, transaction: &VersionedTransaction) -> Result<Signature, RpcError> {
+        let mut sent = self.sent.lock().unwrap();
+        if sent.len() == self.failing_send {
+            return Err(RpcError::Other("node unavailable".into()));
+        }
+        sent.push(transaction.clone());
+        Ok(transaction.signatures[0])
+    }
+}
+
+#[tokio::test]
+async fn execute_split_reports_filled_and_remaining_parts() {
+    let signer = Keypair::new();
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::new(INPUT, OUTPUT))
+        .mount()
+        .await;
+    let client = server.client();
+    let order = QuoteRequest {
+        user_pubkey: Some(signer.pubkey()),
+        ..request(1_000)
+    };
+    let parts = split_order(&order, SplitStrategy::FixedClip(300)).unwrap();
+
+    let rpc = MockRpc {
+        failing_send: 2,
+        sent: Mutex::default(),
+    };
+    let report = client
+        .execute_split(parts.clone(), &rpc, &signer, &ExecuteOptions::default())
+        .await;
+    assert!(!report.is_complete());
+    assert_eq!(report.fills.len(), 2);
+    assert_eq!(report.filled_amount(), 600);
+    assert_eq!(report.remaining_amount(), 400);
+    assert_eq!(report.remaining, parts[2..]);
+    assert!(matches!(report.error, Some(ClientError::Execution(_))));
+    // Parts after the failure were never quoted.
+    assert_eq!(server.received_request_count().await, 3);
+    assert_eq!(
+        report.fills[1].swap.signature,
+        rpc.sent.lock().unwrap()[1].signatures[0]
+    );
+
+    let rpc = MockRpc {
+        failing_send: usize::MAX,
+        sent: Mutex::default(),
+    };
+    let report = client
+        .execute_split(parts, &rpc, &signer, &ExecuteOptions::default())
+        .await;
+    assert!(report.is_complete());
+    assert!(report.error.is_none());
+    assert_eq!(report.filled_amount(), 1_000);
+    assert_eq!(
+        report
+            .fills
+            .iter()
+            .map(|fill| fill.request.amount)
+            .collect::<Vec<_>>(),
+        [300, 300, 300, 100]
+    );
+}
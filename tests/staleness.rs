@@ -0,0 +1,96 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use titan_swap_api_client::{
+    quote::{QuoteRequest, QuoteResponse},
+    staleness::StalenessReport,
+    test_utils::SwapRouteBuilder,
+    ClientError,
+};
+use wiremock::{
+    matchers::{body_partial_json, method},
+    Mock, MockServer, ResponseTemplate,
+};
+
+fn quote() -> QuoteResponse {
+    let (input, output) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut quote = QuoteResponse::from_route(
+        &QuoteRequest::default(),
+        SwapRouteBuilder::default()
+            .context_slot(1_000)
+            .step("A", Pubkey::new_unique(), input, output, 1, 1, 500_000_000)
+            .step("B", Pubkey::new_unique(), input, output, 1, 1, 500_000_000)
+            .step("C", Pubkey::new_unique(), input, output, 1, 1, 0)
+            .build(),
+    );
+    quote.route_plan[0].swap_info.context_slot = Some(990);
+    quote.route_plan[1].swap_info.context_slot = Some(998);
+    quote
+}
+
+#[test]
+fn oldest_and_newest_context_slots() {
+    let quote = quote();
+
+    assert_eq!(quote.oldest_context_slot(), Some(990));
+    assert_eq!(quote.newest_context_slot(), Some(998));
+}
+
+#[test]
+fn context_slots_fall_back_to_the_route() {
+    let mut quote = quote();
+    for step in &mut quote.route_plan {
+        step.swap_info.context_slot = None;
+    }
+
+    assert_eq!(quote.oldest_context_slot(), Some(1_000));
+    assert_eq!(quote.newest_context_slot(), Some(1_000));
+}
+
+#[test]
+fn report_lags_each_step() {
+    let report = StalenessReport::new(&quote(), 1_010);
+
+    assert_eq!(report.route_lag, Some(10));
+    assert_eq!(report.step_lags, vec![Some(20), Some(12), None]);
+    assert_eq!(report.max_lag(), Some(20));
+}
+
+#[tokio::test]
+async fn staleness_compares_against_the_rpc_slot() {
+    let rpc_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(
+            serde_json::json!({ "method": "getSlot" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": 1_005,
+            "id": 1
+        })))
+        .mount(&rpc_server)
+        .await;
+
+    let report = quote()
+        .staleness(&RpcClient::new(rpc_server.uri()))
+        .await
+        .unwrap();
+
+    assert_eq!(report.current_slot, 1_005);
+    assert_eq!(report.max_lag(), Some(15));
+}
+
+#[tokio::test]
+async fn rpc_failures_are_reported() {
+    let rpc_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&rpc_server)
+        .await;
+
+    let err = quote()
+        .staleness(&RpcClient::new(rpc_server.uri()))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, ClientError::Rpc(_)));
+}
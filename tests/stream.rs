@@ -0,0 +1,347 @@
+use serde::Serialize;
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+use std::time::Duration;
+use titan_swap_api_client::{
+    quote::{QuoteRequest, RouteField, SwapQuotes},
+    stream::{
+        Frame, StreamConnection, StreamConnector, StreamError, StreamOptions, SubscriptionHandle,
+        TitanStreamClient,
+    },
+    test_utils::SwapRouteBuilder,
+    transform::RouteChecks,
+    transport::TransportError,
+    ClientError,
+};
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+const SOL: Pubkey = Pubkey::new_from_array([1; 32]);
+const USDC: Pubkey = Pubkey::new_from_array([2; 32]);
+const BONK: Pubkey = Pubkey::new_from_array([3; 32]);
+
+/// Hands the server end of each new connection to the test.
+struct FakeConnector {
+    accepted: mpsc::UnboundedSender<FakeServer>,
+}
+
+struct FakeConnection {
+    to_server: mpsc::UnboundedSender<Frame>,
+    from_server: mpsc::UnboundedReceiver<Frame>,
+}
+
+/// The server end of one connection. Dropping it closes the connection.
+struct FakeServer {
+    received: mpsc::UnboundedReceiver<Frame>,
+    to_client: mpsc::UnboundedSender<Frame>,
+}
+
+impl StreamConnector for FakeConnector {
+    type Connection = FakeConnection;
+
+    async fn connect(&self) -> Result<FakeConnection, TransportError> {
+        let (to_server, received) = mpsc::unbounded_channel();
+        let (to_client, from_server) = mpsc::unbounded_channel();
+        self.accepted
+            .send(FakeServer {
+                received,
+                to_client,
+            })
+            .map_err(|_| "test finished")?;
+        Ok(FakeConnection {
+            to_server,
+            from_server,
+        })
+    }
+}
+
+impl StreamConnection for FakeConnection {
+    async fn send(&mut self, frame: Frame) -> Result<(), TransportError> {
+        Ok(self.to_server.send(frame).map_err(|_| "server closed")?)
+    }
+
+    async fn recv(&mut self) -> Option<Result<Frame, TransportError>> {
+        self.from_server.recv().await.map(Ok)
+    }
+}
+
+impl FakeServer {
+    /// The next frame the client sent, as JSON.
+    async fn next_frame(&mut self) -> Value {
+        let frame = timeout(Duration::from_secs(1), self.received.recv())
+            .await
+            .expect("client sent a frame")
+            .expect("connection is open");
+        match frame {
+            Frame::Text(text) => serde_json::from_str(&text).unwrap(),
+            Frame::Binary(_) => panic!("client frames are text"),
+        }
+    }
+
+    async fn expect_subscribe(&mut self) -> (u64, String) {
+        let frame = self.next_frame().await;
+        assert_eq!(frame["op"], "subscribe");
+        (
+            frame["id"].as_u64().unwrap(),
+            frame["query"].as_str().unwrap().to_string(),
+        )
+    }
+
+    async fn assert_quiet(&mut self) {
+        let frame = timeout(Duration::from_millis(50), self.received.recv()).await;
+        assert!(frame.is_err(), "unexpected frame {frame:?}");
+    }
+
+    fn send(&self, frame: &ServerFrame) {
+        let body = rmp_serde::to_vec_named(frame).unwrap();
+        self.to_client.send(Frame::Binary(body)).unwrap();
+    }
+
+    fn send_quote(&self, id: u64, input: Pubkey, output: Pubkey, out_amount: u64) {
+        let quotes = SwapRouteBuilder::new(input, output)
+            .amounts(AMOUNT, out_amount)
+            .to_swap_quotes();
+        self.send(&ServerFrame {
+            id,
+            quotes: Some(&quotes),
+            error: None,
+        });
+    }
+}
+
+#[derive(Serialize)]
+struct ServerFrame<'a> {
+    id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quotes: Option<&'a SwapQuotes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+const AMOUNT: u64 = 1_000;
+
+fn request(input: Pubkey, output: Pubkey) -> QuoteRequest {
+    QuoteRequest {
+        input_mint: input,
+        output_mint: output,
+        amount: AMOUNT,
+        ..Default::default()
+    }
+}
+
+fn start(buffer_capacity: usize) -> (TitanStreamClient, mpsc::UnboundedReceiver<FakeServer>) {
+    start_with(StreamOptions {
+        buffer_capacity,
+        reconnect_delay: Duration::from_millis(10),
+        ..Default::default()
+    })
+}
+
+fn start_with(options: StreamOptions) -> (TitanStreamClient, mpsc::UnboundedReceiver<FakeServer>) {
+    let (accepted, connections) = mpsc::unbounded_channel();
+    let client = TitanStreamClient::new(FakeConnector { accepted }, options);
+    (client, connections)
+}
+
+async fn accept(connections: &mut mpsc::UnboundedReceiver<FakeServer>) -> FakeServer {
+    timeout(Duration::from_secs(1), connections.recv())
+        .await
+        .expect("client connected")
+        .unwrap()
+}
+
+async fn next_out_amount(handle: &mut SubscriptionHandle) -> u64 {
+    timeout(Duration::from_secs(1), handle.next())
+        .await
+        .expect("an update arrived")
+        .expect("stream is open")
+        .expect("update is a quote")
+        .out_amount
+}
+
+async fn assert_no_update(handle: &mut SubscriptionHandle) {
+    assert!(timeout(Duration::from_millis(50), handle.next())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn pairs_share_one_connection() {
+    let (client, mut connections) = start(16);
+    let mut sol_usdc = client.subscribe(request(SOL, USDC));
+    let mut bonk_usdc = client.subscribe(request(BONK, USDC));
+
+    let mut server = accept(&mut connections).await;
+    let (first, first_query) = server.expect_subscribe().await;
+    let (second, second_query) = server.expect_subscribe().await;
+    assert_ne!(first, second);
+    assert_eq!(first_query, request(SOL, USDC).to_query_string());
+    assert_eq!(second_query, request(BONK, USDC).to_query_string());
+
+    server.send_quote(second, BONK, USDC, 7);
+    server.send_quote(first, SOL, USDC, 3);
+    assert_eq!(next_out_amount(&mut bonk_usdc).await, 7);
+    assert_eq!(next_out_amount(&mut sol_usdc).await, 3);
+    assert_no_update(&mut bonk_usdc).await;
+
+    // Still the one connection.
+    assert!(connections.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn updates_are_checked_against_the_request() {
+    let (client, mut connections) = start(16);
+    let mut handle = client.subscribe(request(SOL, USDC));
+    let mut server = accept(&mut connections).await;
+    let (id, _) = server.expect_subscribe().await;
+
+    server.send_quote(id, BONK, USDC, 1);
+    let err = handle.next().await.unwrap().unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::ResponseMismatch {
+            field: "inputMint",
+            ..
+        }
+    ));
+
+    server.send(&ServerFrame {
+        id,
+        quotes: None,
+        error: Some("pair not supported"),
+    });
+    let err = handle.next().await.unwrap().unwrap_err();
+    assert!(
+        matches!(err, ClientError::Stream(StreamError::Server(message)) if message == "pair not supported")
+    );
+}
+
+#[tokio::test]
+async fn updates_get_the_clients_route_checks() {
+    let pool = Pubkey::new_unique();
+    let (client, mut connections) = start_with(StreamOptions {
+        route_checks: RouteChecks {
+            required_route_fields: vec![RouteField::ContextSlot],
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let mut handle = client.subscribe(QuoteRequest {
+        excluded_amm_keys: Some(vec![pool]),
+        ..request(SOL, USDC)
+    });
+    let mut server = accept(&mut connections).await;
+    let (id, _) = server.expect_subscribe().await;
+
+    let through_pool = SwapRouteBuilder::new(SOL, USDC)
+        .amounts(AMOUNT, 5)
+        .step("Pool", pool, SOL, USDC, AMOUNT, 5, 1_000_000_000)
+        .context_slot(1)
+        .to_swap_quotes();
+    server.send(&ServerFrame {
+        id,
+        quotes: Some(&through_pool),
+        error: None,
+    });
+    let err = handle.next().await.unwrap().unwrap_err();
+    assert!(matches!(err, ClientError::NoRoutesAvailable));
+
+    server.send_quote(id, SOL, USDC, 5);
+    let err = handle.next().await.unwrap().unwrap_err();
+    assert!(matches!(
+        err,
+        ClientError::MissingRouteField(RouteField::ContextSlot)
+    ));
+}
+
+#[tokio::test]
+async fn last_handle_dropped_unsubscribes() {
+    let (client, mut connections) = start(16);
+    let mut first = client.subscribe(request(SOL, USDC));
+    let mut second = client.subscribe(request(SOL, USDC));
+    let mut server = accept(&mut connections).await;
+    let (id, _) = server.expect_subscribe().await;
+    server.assert_quiet().await;
+    assert_eq!(client.subscription_count(), 1);
+
+    server.send_quote(id, SOL, USDC, 5);
+    assert_eq!(next_out_amount(&mut first).await, 5);
+    assert_eq!(next_out_amount(&mut second).await, 5);
+
+    drop(first);
+    server.assert_quiet().await;
+    drop(second);
+    let frame = server.next_frame().await;
+    assert_eq!(frame["op"], "unsubscribe");
+    assert_eq!(frame["id"], id);
+    assert_eq!(client.subscription_count(), 0);
+}
+
+#[tokio::test]
+async fn slow_handles_drop_their_oldest_updates() {
+    let (client, mut connections) = start(2);
+    let mut slow = client.subscribe(request(SOL, USDC));
+    let mut fast = client.subscribe(request(BONK, USDC));
+    let mut server = accept(&mut connections).await;
+    let (slow_id, _) = server.expect_subscribe().await;
+    let (fast_id, _) = server.expect_subscribe().await;
+
+    for out_amount in 1..=5 {
+        server.send_quote(slow_id, SOL, USDC, out_amount);
+    }
+    // The full buffer doesn't hold up the other subscription.
+    server.send_quote(fast_id, BONK, USDC, 9);
+    assert_eq!(next_out_amount(&mut fast).await, 9);
+
+    assert_eq!(next_out_amount(&mut slow).await, 4);
+    assert_eq!(next_out_amount(&mut slow).await, 5);
+    assert_eq!(slow.dropped(), 3);
+    assert_no_update(&mut slow).await;
+}
+
+#[tokio::test]
+async fn reconnects_and_resubscribes() {
+    let (client, mut connections) = start(16);
+    let mut sol_usdc = client.subscribe(request(SOL, USDC));
+    let mut bonk_usdc = client.subscribe(request(BONK, USDC));
+    let mut server = accept(&mut connections).await;
+    let mut ids = vec![
+        server.expect_subscribe().await.0,
+        server.expect_subscribe().await.0,
+    ];
+    drop(server);
+
+    assert!(matches!(
+        sol_usdc.next().await,
+        Some(Err(ClientError::Stream(StreamError::Disconnected)))
+    ));
+    assert!(matches!(
+        bonk_usdc.next().await,
+        Some(Err(ClientError::Stream(StreamError::Disconnected)))
+    ));
+
+    let mut server = accept(&mut connections).await;
+    let mut resubscribed = vec![
+        server.expect_subscribe().await.0,
+        server.expect_subscribe().await.0,
+    ];
+    ids.sort();
+    resubscribed.sort();
+    assert_eq!(resubscribed, ids);
+
+    server.send_quote(ids[0], SOL, USDC, 11);
+    assert_eq!(next_out_amount(&mut sol_usdc).await, 11);
+}
+
+#[tokio::test]
+async fn dropping_the_client_ends_every_stream() {
+    let (client, mut connections) = start(16);
+    let mut handle = client.subscribe(request(SOL, USDC));
+    let _server = accept(&mut connections).await;
+
+    drop(client);
+    let next = timeout(Duration::from_secs(1), handle.next())
+        .await
+        .unwrap();
+    assert!(next.is_none());
+}
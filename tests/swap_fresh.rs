@@ -0,0 +1,170 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    execution_budget::ExecutionBudget,
+    quote::{QuoteRequest, QuoteResponse, SwapMode},
+    test_utils::{sample_request, MockClock, MockTitanServer, SwapRouteBuilder},
+    ClientError, TitanClient,
+};
+
+const QUOTE_V1: &str = include_str!("fixtures/quote_response_v1.json");
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey: Some(USER),
+        swap_mode: Some(SwapMode::ExactIn),
+        slippage_bps: 50,
+        ..sample_request()
+    }
+}
+
+/// The golden quote expired long ago.
+fn expired_quote() -> QuoteResponse {
+    QuoteResponse::from_json_str(QUOTE_V1).unwrap()
+}
+
+const NOW_MS: u64 = 1_700_000_000_000;
+
+/// A quote from the golden fixture, valid for `valid_for` from `NOW_MS`.
+fn quote_valid_for(valid_for: Duration) -> QuoteResponse {
+    let mut quote = expired_quote();
+    quote.route_data_mut().unwrap().expires_at_ms = Some(NOW_MS + valid_for.as_millis() as u64);
+    quote
+}
+
+fn client_at(server: &MockTitanServer, clock: &MockClock) -> TitanClient {
+    server
+        .client_builder()
+        .clock(Arc::new(clock.clone()))
+        .build()
+        .unwrap()
+}
+
+async fn server_quoting(out_amount: u64) -> MockTitanServer {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+                .amounts(1_000_000_000, out_amount)
+                .instruction(&Instruction::new_with_bytes(
+                    pubkey!("T1TANpTeScyeqVzzgNViGDNrkQ6qHz9KrSBS4aNXvGT"),
+                    &[7],
+                    vec![AccountMeta::new(Pubkey::new_unique(), true)],
+                )),
+        )
+        .mount()
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn live_quote_is_used_as_is() {
+    let server = server_quoting(187_000_000).await;
+    let quote = quote_valid_for(Duration::from_secs(60));
+
+    let fresh = client_at(&server, &MockClock::new(NOW_MS))
+        .swap_fresh(&request(), &quote, 10)
+        .await
+        .unwrap();
+
+    assert!(!fresh.requoted);
+    assert_eq!(fresh.quote.out_amount, quote.out_amount);
+    assert_eq!(server.received_request_count().await, 0);
+}
+
+#[tokio::test]
+async fn quote_about_to_expire_is_refreshed() {
+    let server = server_quoting(187_654_321).await;
+    let quote = quote_valid_for(Duration::from_millis(500));
+
+    let fresh = client_at(&server, &MockClock::new(NOW_MS))
+        .swap_fresh(&request(), &quote, 10)
+        .await
+        .unwrap();
+
+    assert!(fresh.requoted);
+    assert_eq!(server.received_request_count().await, 1);
+}
+
+#[tokio::test]
+async fn execution_budget_is_checked_against_the_client_clock() {
+    let server = server_quoting(187_654_321).await;
+    let clock = MockClock::new(NOW_MS);
+    let client = server
+        .client_builder()
+        .clock(Arc::new(clock.clone()))
+        .execution_budget(ExecutionBudget {
+            build_ms: 500,
+            send_ms: 1_000,
+            safety_margin_ms: 500,
+        })
+        .build()
+        .unwrap();
+    let quote = quote_valid_for(Duration::from_secs(3));
+
+    let fresh = client.swap_fresh(&request(), &quote, 10).await.unwrap();
+    assert!(!fresh.requoted);
+    assert_eq!(server.received_request_count().await, 0);
+
+    clock.advance(Duration::from_millis(1_001));
+    let fresh = client.swap_fresh(&request(), &quote, 10).await.unwrap();
+    assert!(fresh.requoted);
+    assert_eq!(server.received_request_count().await, 1);
+}
+
+#[tokio::test]
+async fn expired_quote_within_tolerance_is_replaced() {
+    // ~2.9 bps worse than the original.
+    let server = server_quoting(187_600_000).await;
+
+    let fresh = server
+        .client()
+        .swap_fresh(&request(), &expired_quote(), 10)
+        .await
+        .unwrap();
+
+    assert!(fresh.requoted);
+    assert_eq!(fresh.quote.out_amount, 187_600_000);
+    assert_eq!(fresh.swap.instructions.len(), 1);
+}
+
+#[tokio::test]
+async fn expired_quote_beyond_tolerance_errors() {
+    // ~408 bps worse than the original.
+    let server = server_quoting(180_000_000).await;
+
+    let err = server
+        .client()
+        .swap_fresh(&request(), &expired_quote(), 10)
+        .await
+        .unwrap_err();
+
+    match err {
+        ClientError::PriceMoved { divergence_bps } => {
+            assert!((divergence_bps - 407.9).abs() < 0.1, "{divergence_bps}")
+        }
+        other => panic!("expected PriceMoved, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn better_fresh_quote_is_always_accepted() {
+    let server = server_quoting(190_000_000).await;
+
+    let fresh = server
+        .client()
+        .swap_fresh(&request(), &expired_quote(), 0)
+        .await
+        .unwrap();
+
+    assert!(fresh.requoted);
+    assert_eq!(fresh.quote.out_amount, 190_000_000);
+}
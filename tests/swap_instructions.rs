@@ -0,0 +1,103 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    quote::QuoteResponse,
+    swap::{SwapInstructionsOptions, SwapResponse},
+    test_utils::MockTitanServer,
+};
+
+const QUOTE_V1: &str = include_str!("fixtures/quote_response_v1.json");
+const OPTIONS: &str = include_str!("fixtures/swap_instructions_options.json");
+const RESPONSE: &[u8] = include_bytes!("fixtures/swap_instructions_response.msgpack");
+
+const FEE_ACCOUNT: Pubkey = pubkey!("3emsAVdmGKERbHjmGfQ6oZ1e35dkf5iYcS6U4CPKFVaa");
+
+fn options() -> SwapInstructionsOptions {
+    SwapInstructionsOptions {
+        wrap_and_unwrap_sol: Some(true),
+        fee_account: Some(FEE_ACCOUNT),
+        compute_unit_price_micro_lamports: Some(50_000),
+    }
+}
+
+#[test]
+fn options_round_trip_through_json() {
+    let parsed: SwapInstructionsOptions = serde_json::from_str(OPTIONS).unwrap();
+    assert_eq!(parsed, options());
+
+    let expected: serde_json::Value = serde_json::from_str(OPTIONS).unwrap();
+    assert_eq!(serde_json::to_value(&parsed).unwrap(), expected);
+    assert_eq!(
+        serde_json::to_value(SwapInstructionsOptions::default()).unwrap(),
+        serde_json::json!({})
+    );
+}
+
+#[test]
+fn response_fixture_round_trips_through_msgpack() {
+    let swap = SwapResponse::from_bytes(RESPONSE).unwrap();
+
+    assert_eq!(swap.instructions.len(), 2);
+    assert_eq!(swap.instructions[0].accounts.len(), 6);
+    assert_eq!(
+        swap.instructions[1].program_id,
+        pubkey!("T1TANpTeScyeqVzzgNViGDNrkQ6qHz9KrSBS4aNXvGT")
+    );
+    assert_eq!(swap.compute_unit_limit, Some(200_000));
+    assert_eq!(swap.expires_after_slot, Some(312_000_160));
+
+    let bytes = swap.to_bytes().unwrap();
+    assert_eq!(SwapResponse::from_bytes(&bytes).unwrap(), swap);
+}
+
+#[tokio::test]
+async fn posts_route_and_options() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_swap_instructions(RESPONSE)
+        .mount()
+        .await;
+    let quote = QuoteResponse::from_json_str(QUOTE_V1).unwrap();
+
+    let swap = server
+        .client()
+        .swap_instructions(&quote, options())
+        .await
+        .unwrap();
+    assert_eq!(swap, SwapResponse::from_bytes(RESPONSE).unwrap());
+
+    let bodies = server.received_swap_instructions_bodies().await;
+    assert_eq!(bodies.len(), 1);
+    assert_eq!(bodies[0]["wrapAndUnwrapSol"], true);
+    assert_eq!(bodies[0]["feeAccount"], FEE_ACCOUNT.to_string());
+    assert_eq!(bodies[0]["computeUnitPriceMicroLamports"], 50_000);
+    assert_eq!(bodies[0]["route"]["outAmount"], 187_654_321);
+    assert!(bodies[0].get("referenceId").is_none());
+}
+
+#[tokio::test]
+async fn prefers_reference_id_over_route() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_swap_instructions(RESPONSE)
+        .mount()
+        .await;
+    let mut quote = QuoteResponse::from_json_str(QUOTE_V1).unwrap();
+    quote.route_data_mut().unwrap().reference_id = Some("ref-123".to_string());
+
+    server
+        .client()
+        .swap_instructions(&quote, SwapInstructionsOptions::default())
+        .await
+        .unwrap();
+
+    let bodies = server.received_swap_instructions_bodies().await;
+    assert_eq!(bodies[0], serde_json::json!({ "referenceId": "ref-123" }));
+}
+
+#[test]
+fn local_swap_remains_available_offline() {
+    let quote = QuoteResponse::from_json_str(QUOTE_V1).unwrap();
+    let client = titan_swap_api_client::TitanClient::new(String::new(), None);
+
+    assert_eq!(client.swap(&quote).unwrap().instructions.len(), 1);
+}
@@ -0,0 +1,35 @@
+use titan_swap_api_client::quote::{ParseSwapModeError, SwapMode};
+
+#[test]
+fn parses_common_spellings() {
+    for s in [
+        "ExactIn", "exactIn", "exactin", "EXACT_IN", "exact_in", "exact-in",
+    ] {
+        assert_eq!(s.parse::<SwapMode>(), Ok(SwapMode::ExactIn), "{s}");
+    }
+    for s in ["ExactOut", "exactOut", "EXACT_OUT", "exact_out"] {
+        assert_eq!(s.parse::<SwapMode>(), Ok(SwapMode::ExactOut), "{s}");
+    }
+}
+
+#[test]
+fn rejects_unknown_modes() {
+    assert_eq!(
+        "Exact".parse::<SwapMode>(),
+        Err(ParseSwapModeError("Exact".to_string()))
+    );
+}
+
+#[test]
+fn displays_the_wire_spelling() {
+    assert_eq!(SwapMode::ExactIn.to_string(), "ExactIn");
+    assert_eq!(SwapMode::ExactOut.as_str(), "ExactOut");
+    assert_eq!(SwapMode::Other("Hybrid".to_string()).to_string(), "Hybrid");
+}
+
+#[test]
+fn deserializes_any_spelling() {
+    let mode: SwapMode = serde_json::from_str("\"EXACT_OUT\"").unwrap();
+    assert_eq!(mode, SwapMode::ExactOut);
+    assert_eq!(serde_json::to_string(&mode).unwrap(), "\"ExactOut\"");
+}
@@ -0,0 +1,82 @@
+use proptest::prelude::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use titan_swap_api_client::swap::SwapResponse;
+
+fn arb_pubkey() -> impl Strategy<Value = Pubkey> {
+    any::<[u8; 32]>().prop_map(Pubkey::from)
+}
+
+fn arb_instruction() -> impl Strategy<Value = Instruction> {
+    (
+        arb_pubkey(),
+        prop::collection::vec((arb_pubkey(), any::<bool>(), any::<bool>()), 0..8),
+        prop::collection::vec(any::<u8>(), 0..256),
+    )
+        .prop_map(|(program_id, accounts, data)| Instruction {
+            program_id,
+            accounts: accounts
+                .into_iter()
+                .map(|(pubkey, is_signer, is_writable)| AccountMeta {
+                    pubkey,
+                    is_signer,
+                    is_writable,
+                })
+                .collect(),
+            data,
+        })
+}
+
+fn arb_swap_response() -> impl Strategy<Value = SwapResponse> {
+    (
+        prop::collection::vec(arb_instruction(), 0..6),
+        prop::collection::vec(arb_pubkey(), 0..4),
+        any::<Option<u32>>(),
+        any::<Option<u64>>(),
+        any::<Option<u64>>(),
+        any::<Option<u64>>(),
+        any::<Option<u64>>(),
+    )
+        .prop_map(
+            |(
+                instructions,
+                address_lookup_table_addresses,
+                compute_unit_limit,
+                compute_units_safe,
+                context_slot,
+                expires_at_ms,
+                expires_after_slot,
+            )| SwapResponse {
+                instructions,
+                address_lookup_table_addresses,
+                compute_unit_limit,
+                compute_units_safe,
+                context_slot,
+                expires_at_ms,
+                expires_after_slot,
+            },
+        )
+}
+
+proptest! {
+    #[test]
+    fn json_round_trip(swap in arb_swap_response()) {
+        let json = swap.to_json().unwrap();
+        prop_assert_eq!(SwapResponse::from_json(&json).unwrap(), swap);
+    }
+
+    #[test]
+    fn bytes_round_trip(swap in arb_swap_response()) {
+        let bytes = swap.to_bytes().unwrap();
+        prop_assert_eq!(SwapResponse::from_bytes(&bytes).unwrap(), swap);
+    }
+
+    #[test]
+    fn raw_round_trip(swap in arb_swap_response()) {
+        let raw = swap.to_raw();
+        prop_assert_eq!(raw.instructions.len(), swap.instructions.len());
+        prop_assert_eq!(SwapResponse::from_raw(raw), swap);
+    }
+}
@@ -0,0 +1,125 @@
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+use titan_swap_api_client::{
+    quote::QuoteRequest, test_utils::SwapRouteBuilder, ClientError, TitanClient, TitanClientBuilder,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio_rustls::rustls::{self, pki_types::PrivateKeyDer};
+use tokio_rustls::TlsAcceptor;
+
+const CERT: &[u8] = include_bytes!("fixtures/tls/localhost.pem");
+const KEY: &[u8] = include_bytes!("fixtures/tls/localhost.key");
+
+/// The request the server's route answers.
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        input_mint: Pubkey::new_from_array([1; 32]),
+        output_mint: Pubkey::new_from_array([2; 32]),
+        amount: 1_000,
+        ..Default::default()
+    }
+}
+
+/// Serves a single-route quote over TLS with a self-signed certificate for
+/// `localhost`, one request per connection.
+async fn start_tls_server() -> String {
+    let certs = rustls_pemfile::certs(&mut &CERT[..])
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let key = PrivateKeyDer::from(
+        rustls_pemfile::pkcs8_private_keys(&mut &KEY[..])
+            .next()
+            .unwrap()
+            .unwrap(),
+    );
+    let config = rustls::ServerConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .unwrap()
+    .with_no_client_auth()
+    .with_single_cert(certs, key)
+    .unwrap();
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let body = SwapRouteBuilder::default().amounts(1_000, 990).to_msgpack();
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                return;
+            };
+            let acceptor = acceptor.clone();
+            let body = body.clone();
+            tokio::spawn(async move {
+                // Clients that reject the certificate fail the handshake.
+                let Ok(mut stream) = acceptor.accept(stream).await else {
+                    return;
+                };
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let head = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/vnd.msgpack\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                    body.len()
+                );
+                let _ = stream.write_all(head.as_bytes()).await;
+                let _ = stream.write_all(&body).await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    });
+
+    format!("https://localhost:{}", port)
+}
+
+fn client(
+    base_url: String,
+    configure: impl FnOnce(TitanClientBuilder) -> TitanClientBuilder,
+) -> TitanClient {
+    configure(TitanClient::builder("test-token").base_path(base_url))
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn untrusted_certificate_is_rejected() {
+    let url = start_tls_server().await;
+    let err = client(url, |builder| builder)
+        .quote(&request())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, ClientError::HttpError(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn added_root_certificate_is_trusted() {
+    let url = start_tls_server().await;
+    let certificate = reqwest::Certificate::from_pem(CERT).unwrap();
+    let quote = client(url, |builder| builder.add_root_certificate(certificate))
+        .quote(&request())
+        .await
+        .unwrap();
+
+    assert_eq!(quote.out_amount, 990);
+}
+
+#[tokio::test]
+async fn invalid_certificates_can_be_accepted_explicitly() {
+    let url = start_tls_server().await;
+    let quote = client(url, |builder| builder.danger_accept_invalid_certs(true))
+        .quote(&request())
+        .await
+        .unwrap();
+
+    assert_eq!(quote.out_amount, 990);
+}
@@ -0,0 +1,193 @@
+use base64::Engine;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use titan_swap_api_client::{
+    constants::{TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID},
+    quote::{QuoteRequest, QuoteResponse, SwapMode},
+    test_utils::SwapRouteBuilder,
+    token_2022::{
+        associated_token_address, associated_token_address_for, MintExtensions, Token2022Error,
+        TransferFee,
+    },
+};
+use wiremock::{
+    matchers::{body_partial_json, method},
+    Mock, MockServer, ResponseTemplate,
+};
+
+// A 6-decimal Token-2022 mint charging 1% (capped at 5 tokens) until epoch
+// 600 and 2.5% (capped at 1,000) after, with a metadata pointer and a
+// transfer hook.
+const TRANSFER_FEE_MINT: &[u8] = include_bytes!("fixtures/token2022_transfer_fee_mint.bin");
+
+fn extensions() -> MintExtensions {
+    MintExtensions::parse(TRANSFER_FEE_MINT).unwrap()
+}
+
+fn quote(out_amount: u64, slippage_bps: u16) -> QuoteResponse {
+    QuoteResponse::from_route(
+        &QuoteRequest {
+            swap_mode: Some(SwapMode::ExactIn),
+            ..Default::default()
+        },
+        SwapRouteBuilder::default()
+            .amounts(1_000_000_000, out_amount)
+            .slippage_bps(slippage_bps)
+            .build(),
+    )
+}
+
+async fn rpc_with_mint(owner: Pubkey, data: &[u8]) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(
+            serde_json::json!({ "method": "getAccountInfo" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": {
+                "context": { "slot": 1 },
+                "value": {
+                    "data": [base64::engine::general_purpose::STANDARD.encode(data), "base64"],
+                    "executable": false,
+                    "lamports": 1_461_600,
+                    "owner": owner.to_string(),
+                    "rentEpoch": 0,
+                    "space": data.len()
+                }
+            },
+            "id": 1
+        })))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[test]
+fn parses_transfer_fee_and_hook() {
+    let extensions = extensions();
+    let config = extensions.transfer_fee.unwrap();
+
+    assert_eq!(
+        config.older_transfer_fee,
+        TransferFee {
+            epoch: 0,
+            maximum_fee: 5_000_000,
+            transfer_fee_basis_points: 100,
+        }
+    );
+    assert_eq!(config.fee_for(599), &config.older_transfer_fee);
+    assert_eq!(config.fee_for(600).transfer_fee_basis_points, 250);
+    assert_eq!(
+        extensions.transfer_hook_program,
+        Some(Pubkey::new_from_array([11; 32]))
+    );
+}
+
+#[test]
+fn legacy_mint_has_no_extensions() {
+    assert_eq!(
+        MintExtensions::parse(&TRANSFER_FEE_MINT[..82]),
+        Ok(MintExtensions::default())
+    );
+}
+
+#[test]
+fn malformed_mint_data() {
+    assert_eq!(
+        MintExtensions::parse(&TRANSFER_FEE_MINT[..40]),
+        Err(Token2022Error::Truncated)
+    );
+    assert_eq!(
+        MintExtensions::parse(&TRANSFER_FEE_MINT[..200]),
+        Err(Token2022Error::Truncated)
+    );
+
+    let mut account = TRANSFER_FEE_MINT.to_vec();
+    account[165] = 2;
+    assert_eq!(
+        MintExtensions::parse(&account),
+        Err(Token2022Error::NotAMint)
+    );
+}
+
+#[test]
+fn fees_round_up_and_cap() {
+    let fee = extensions().transfer_fee.unwrap().older_transfer_fee;
+
+    assert_eq!(fee.calculate_fee(0), 0);
+    assert_eq!(fee.calculate_fee(1), 1);
+    assert_eq!(fee.calculate_fee(150), 2);
+    assert_eq!(fee.calculate_fee(100_000_000), 1_000_000);
+    assert_eq!(fee.calculate_fee(u64::MAX), 5_000_000);
+}
+
+#[test]
+fn received_amounts_account_for_the_fee() {
+    let extensions = extensions();
+    let quote = quote(100_000_000, 50);
+
+    assert_eq!(
+        quote.out_amount_after_transfer_fee(&extensions, 10),
+        99_000_000
+    );
+    assert_eq!(
+        quote.out_amount_after_transfer_fee(&extensions, 600),
+        97_500_000
+    );
+    // 99_500_000 after slippage, less a 995_000 fee.
+    assert_eq!(quote.min_received_amount(&extensions, 10), 98_505_000);
+    assert_eq!(
+        quote.min_received_amount(&MintExtensions::default(), 10),
+        99_500_000
+    );
+}
+
+#[test]
+fn associated_token_address_depends_on_the_program() {
+    let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    assert_ne!(
+        associated_token_address(&wallet, &mint, &TOKEN_PROGRAM_ID),
+        associated_token_address(&wallet, &mint, &TOKEN_2022_PROGRAM_ID)
+    );
+}
+
+#[tokio::test]
+async fn resolves_token_2022_output() {
+    let server = rpc_with_mint(TOKEN_2022_PROGRAM_ID, TRANSFER_FEE_MINT).await;
+    let rpc = RpcClient::new(server.uri());
+    let mut quote = quote(100_000_000, 50);
+    assert_eq!(quote.output_is_token2022, None);
+
+    let extensions = quote.resolve_output_mint(&rpc).await.unwrap();
+
+    assert_eq!(quote.output_is_token2022, Some(true));
+    assert_eq!(extensions, self::extensions());
+}
+
+#[tokio::test]
+async fn resolves_legacy_output() {
+    let server = rpc_with_mint(TOKEN_PROGRAM_ID, &TRANSFER_FEE_MINT[..82]).await;
+    let rpc = RpcClient::new(server.uri());
+    let mut quote = quote(100_000_000, 50);
+
+    let extensions = quote.resolve_output_mint(&rpc).await.unwrap();
+
+    assert_eq!(quote.output_is_token2022, Some(false));
+    assert_eq!(extensions, MintExtensions::default());
+}
+
+#[tokio::test]
+async fn associated_token_address_uses_the_mint_owner() {
+    let server = rpc_with_mint(TOKEN_2022_PROGRAM_ID, TRANSFER_FEE_MINT).await;
+    let rpc = RpcClient::new(server.uri());
+    let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    assert_eq!(
+        associated_token_address_for(&wallet, &mint, &rpc)
+            .await
+            .unwrap(),
+        associated_token_address(&wallet, &mint, &TOKEN_2022_PROGRAM_ID)
+    );
+}
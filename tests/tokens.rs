@@ -0,0 +1,133 @@
+use solana_sdk::pubkey::Pubkey;
+use std::time::Duration;
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{QuoteRequest, QuoteResponse, SwapMode},
+    quote_builder::QuoteRequestError,
+    test_utils::MockTitanServer,
+    tokens::{TokenInfo, TokenRegistry},
+};
+
+const QUOTE_V1: &str = include_str!("fixtures/quote_response_v1.json");
+
+fn token(mint: Pubkey, symbol: &str, decimals: u8) -> TokenInfo {
+    TokenInfo {
+        mint,
+        symbol: symbol.to_string(),
+        name: format!("{symbol} token"),
+        decimals,
+        tags: vec!["verified".to_string()],
+    }
+}
+
+fn tokens() -> Vec<TokenInfo> {
+    vec![token(SOL_MINT, "SOL", 9), token(USDC_MINT, "USDC", 6)]
+}
+
+#[tokio::test]
+async fn decodes_msgpack_token_list() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_tokens(&tokens()).mount().await;
+
+    assert_eq!(server.client().tokens().await.unwrap(), tokens());
+}
+
+#[tokio::test]
+async fn decodes_json_token_list() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_tokens_json(&tokens()).mount().await;
+
+    assert_eq!(server.client().tokens().await.unwrap(), tokens());
+}
+
+#[tokio::test]
+async fn registry_refreshes_only_when_stale() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_tokens(&tokens()).mount().await;
+    let registry = TokenRegistry::new(server.client(), Duration::from_secs(60));
+
+    assert_eq!(registry.resolve("USDC"), None);
+    registry.refresh_if_stale().await.unwrap();
+    registry.refresh_if_stale().await.unwrap();
+
+    assert_eq!(registry.resolve("usdc"), Some(USDC_MINT));
+    assert_eq!(registry.decimals(&SOL_MINT), Some(9));
+    assert_eq!(registry.resolve("BONK"), None);
+}
+
+#[test]
+fn ui_amounts_and_effective_price_use_registry_decimals() {
+    let registry = TokenRegistry::from_tokens(tokens());
+
+    assert_eq!(
+        registry.ui_amount(&USDC_MINT, 187_654_321),
+        Some(187.654321)
+    );
+    assert_eq!(registry.raw_amount(&SOL_MINT, 1.5), Some(1_500_000_000));
+    assert_eq!(registry.raw_amount(&SOL_MINT, -1.0), None);
+
+    let quote = QuoteResponse::from_json_str(QUOTE_V1).unwrap();
+    let price = registry.effective_price(&quote).unwrap();
+    assert!((price - 187.654321).abs() < 1e-9);
+}
+
+#[test]
+fn builder_resolves_symbols_and_ui_amounts() {
+    let registry = TokenRegistry::from_tokens(tokens());
+
+    let request = QuoteRequest::builder()
+        .registry(&registry)
+        .input_symbol("SOL")
+        .output_symbol("usdc")
+        .ui_amount(0.25)
+        .slippage_bps(50)
+        .build()
+        .unwrap();
+    assert_eq!(request.input_mint, SOL_MINT);
+    assert_eq!(request.output_mint, USDC_MINT);
+    assert_eq!(request.amount, 250_000_000);
+    assert_eq!(request.slippage_bps, 50);
+
+    let exact_out = QuoteRequest::builder()
+        .registry(&registry)
+        .input_symbol("SOL")
+        .output_symbol("USDC")
+        .swap_mode(SwapMode::ExactOut)
+        .ui_amount(10.0)
+        .build()
+        .unwrap();
+    assert_eq!(exact_out.amount, 10_000_000);
+}
+
+#[test]
+fn builder_reports_missing_and_unknown_tokens() {
+    let registry = TokenRegistry::from_tokens(tokens());
+
+    assert_eq!(
+        QuoteRequest::builder()
+            .input_symbol("SOL")
+            .output_mint(USDC_MINT)
+            .amount(1)
+            .build()
+            .unwrap_err(),
+        QuoteRequestError::NoRegistry
+    );
+    assert_eq!(
+        QuoteRequest::builder()
+            .registry(&registry)
+            .input_symbol("BONK")
+            .output_mint(USDC_MINT)
+            .amount(1)
+            .build()
+            .unwrap_err(),
+        QuoteRequestError::UnknownSymbol("BONK".to_string())
+    );
+    assert_eq!(
+        QuoteRequest::builder()
+            .input_mint(SOL_MINT)
+            .amount(1)
+            .build()
+            .unwrap_err(),
+        QuoteRequestError::Missing("output mint")
+    );
+}
@@ -0,0 +1,115 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{QuoteRequest, SwapMode},
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    transform::{
+        decode_swap_quotes, route_to_quote_response, route_to_swap_response, select_route,
+        RouteSelection,
+    },
+    ClientError,
+};
+
+const SINGLE_HOP: &[u8] = include_bytes!("fixtures/single_hop.msgpack");
+const SWAP_QUOTES_LARGE: &[u8] = include_bytes!("fixtures/swap_quotes_large.msgpack");
+
+const USER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        user_pubkey: Some(USER),
+        swap_mode: Some(SwapMode::ExactIn),
+        slippage_bps: 50,
+        ..sample_request()
+    }
+}
+
+#[test]
+fn archived_response_to_swap_offline() {
+    let quotes = decode_swap_quotes(SINGLE_HOP).unwrap();
+    let (_, route) = select_route(&quotes, RouteSelection::First).unwrap();
+
+    let quote = route_to_quote_response(route, &request());
+    assert_eq!(quote.out_amount, 187_654_321);
+    assert_eq!(quote.route_plan.len(), 1);
+
+    let swap = route_to_swap_response(route).unwrap();
+    assert!(!swap.instructions.is_empty());
+    assert_eq!(swap.address_lookup_table_addresses.len(), 1);
+}
+
+#[test]
+fn selection() {
+    let quotes = SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+        .provider("Titan")
+        .to_swap_quotes();
+
+    let (key, _) = select_route(&quotes, RouteSelection::First).unwrap();
+    assert_eq!(key.provider(), Some("Titan"));
+    let (key, _) = select_route(&quotes, RouteSelection::Provider("titan".to_string())).unwrap();
+    assert_eq!(key.provider(), Some("Titan"));
+    assert!(matches!(
+        select_route(&quotes, RouteSelection::Provider("Other".to_string())),
+        Err(ClientError::ProviderUnavailable(provider)) if provider == "Other"
+    ));
+
+    let mut empty = quotes;
+    empty.quotes.clear();
+    for selection in [
+        RouteSelection::First,
+        RouteSelection::Provider("Titan".to_string()),
+    ] {
+        assert!(matches!(
+            select_route(&empty, selection),
+            Err(ClientError::NoRoutesAvailable)
+        ));
+    }
+}
+
+#[test]
+fn first_route_is_by_provider_name() {
+    let quotes = decode_swap_quotes(SWAP_QUOTES_LARGE).unwrap();
+    let (key, _) = select_route(&quotes, RouteSelection::First).unwrap();
+    assert_eq!(Some(key), quotes.quotes.keys().next());
+}
+
+#[test]
+fn best_route_by_mode() {
+    let route = |provider, in_amount, out_amount| {
+        SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+            .provider(provider)
+            .amounts(in_amount, out_amount)
+    };
+    let mut quotes = route("Alpha", 1_000, 900).to_swap_quotes();
+    for (provider, in_amount, out_amount) in [("Beta", 990, 950), ("Gamma", 980, 950)] {
+        let extra = route(provider, in_amount, out_amount).to_swap_quotes();
+        quotes.quotes.extend(extra.quotes);
+    }
+
+    let (key, _) = select_route(&quotes, RouteSelection::Best).unwrap();
+    assert_eq!(key.provider(), Some("Beta"));
+
+    quotes.swap_mode = SwapMode::ExactOut;
+    let (key, _) = select_route(&quotes, RouteSelection::Best).unwrap();
+    assert_eq!(key.provider(), Some("Gamma"));
+}
+
+#[tokio::test]
+async fn matches_the_client() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_body(SINGLE_HOP).mount().await;
+    let client = server.client();
+
+    let from_client = client.quote(&request()).await.unwrap();
+    let quotes = decode_swap_quotes(SINGLE_HOP).unwrap();
+    let (_, route) = select_route(&quotes, RouteSelection::First).unwrap();
+    let offline = route_to_quote_response(route, &request());
+
+    assert_eq!(offline.in_amount, from_client.in_amount);
+    assert_eq!(offline.out_amount, from_client.out_amount);
+    assert_eq!(offline.route_plan, from_client.route_plan);
+    assert_eq!(
+        route_to_swap_response(route).unwrap(),
+        client.swap(&from_client).unwrap()
+    );
+}
@@ -0,0 +1,323 @@
+use bytes::Bytes;
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use reqwest::StatusCode;
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use std::sync::Mutex;
+use titan_swap_api_client::{
+    account_limits::{AccountLimits, AccountLimitsError},
+    api_version::ApiVersion,
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{QuoteRequest, SwapMode},
+    test_utils::{sample_request, MockTitanServer, SwapRouteBuilder},
+    transport::{
+        decode_quote_response, quote_headers, quote_url, HttpResponse, HttpTransport,
+        ReqwestTransport, TransportClient, TransportError,
+    },
+    ClientError,
+};
+
+const SINGLE_HOP: &[u8] = include_bytes!("fixtures/single_hop.msgpack");
+
+type Sent = (String, Vec<(String, String)>, Vec<(String, String)>);
+
+/// Answers every request with a canned response and keeps what was sent.
+struct CannedTransport {
+    response: Result<HttpResponse, String>,
+    sent: Mutex<Vec<Sent>>,
+}
+
+impl CannedTransport {
+    fn new(status: StatusCode, content_type: &'static str, body: &'static [u8]) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+        Self {
+            response: Ok(HttpResponse {
+                status,
+                headers,
+                body: Bytes::from_static(body),
+            }),
+            sent: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn failing(message: &str) -> Self {
+        Self {
+            response: Err(message.to_string()),
+            sent: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl HttpTransport for CannedTransport {
+    async fn get(
+        &self,
+        url: &str,
+        headers: &[(&str, String)],
+        query: &[(&str, String)],
+    ) -> Result<HttpResponse, TransportError> {
+        let owned = |pairs: &[(&str, String)]| {
+            pairs
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect()
+        };
+        self.sent
+            .lock()
+            .unwrap()
+            .push((url.to_string(), owned(headers), owned(query)));
+        self.response.clone().map_err(TransportError::from)
+    }
+}
+
+#[test]
+fn builds_the_quote_request() {
+    assert_eq!(
+        quote_url("https://api.example.com", ApiVersion::V1),
+        "https://api.example.com/api/v1/quote/swap"
+    );
+    assert_eq!(
+        quote_url("https://api.example.com", ApiVersion::V2),
+        "https://api.example.com/api/v2/quote/swap"
+    );
+    assert_eq!(
+        quote_headers("secret"),
+        [
+            ("Accept", "application/vnd.msgpack".to_string()),
+            ("Authorization", "Bearer secret".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn decodes_a_successful_response() {
+    let response = HttpResponse {
+        status: StatusCode::OK,
+        headers: HeaderMap::new(),
+        body: Bytes::from_static(SINGLE_HOP),
+    };
+
+    let quotes = decode_quote_response(&response).unwrap();
+    assert_eq!(quotes.quotes.len(), 1);
+    assert_eq!(Pubkey::from(quotes.input_mint), SOL_MINT);
+}
+
+#[test]
+fn decodes_error_responses() {
+    let no_routes = HttpResponse {
+        status: StatusCode::NOT_FOUND,
+        headers: HeaderMap::new(),
+        body: Bytes::from_static(b"No routes found"),
+    };
+    assert!(matches!(
+        decode_quote_response(&no_routes),
+        Err(ClientError::NoRoutesAvailable)
+    ));
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static("text/plain; charset=utf-8"),
+    );
+    let unavailable = HttpResponse {
+        status: StatusCode::SERVICE_UNAVAILABLE,
+        headers,
+        body: Bytes::from_static(b"overloaded"),
+    };
+    match decode_quote_response(&unavailable) {
+        Err(ClientError::RequestFailed {
+            status,
+            body,
+            content_type,
+            ..
+        }) => {
+            assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+            assert_eq!(body, "overloaded");
+            assert_eq!(content_type.as_deref(), Some("text/plain"));
+        }
+        other => panic!("expected RequestFailed, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn quotes_through_a_custom_transport() {
+    let transport = CannedTransport::new(StatusCode::OK, "application/vnd.msgpack", SINGLE_HOP);
+    let client = TransportClient::new(
+        transport,
+        "secret".to_string(),
+        Some("https://api.example.com".to_string()),
+    );
+
+    let quote = client.quote(&sample_request()).await.unwrap();
+    assert_eq!(quote.out_amount, 187_654_321);
+    assert_eq!(client.quote_all(&sample_request()).await.unwrap().len(), 1);
+
+    let sent = client.transport().sent.lock().unwrap();
+    let (url, headers, query) = &sent[0];
+    assert_eq!(url, "https://api.example.com/api/v1/quote/swap");
+    assert!(headers.contains(&("Authorization".to_string(), "Bearer secret".to_string())));
+    assert!(query.contains(&("inputMint".to_string(), SOL_MINT.to_string())));
+    assert!(query.contains(&("amount".to_string(), "1000000000".to_string())));
+}
+
+#[tokio::test]
+async fn requests_go_to_the_configured_api_version() {
+    let transport = CannedTransport::new(StatusCode::OK, "application/vnd.msgpack", SINGLE_HOP);
+    let client = TransportClient::new(
+        transport,
+        String::new(),
+        Some("https://api.example.com".to_string()),
+    )
+    .with_api_version(ApiVersion::V2);
+
+    client.quote(&sample_request()).await.unwrap();
+    let sent = client.transport().sent.lock().unwrap();
+    assert_eq!(sent[0].0, "https://api.example.com/api/v2/quote/swap");
+}
+
+#[tokio::test]
+async fn responses_for_another_request_are_rejected() {
+    let transport = CannedTransport::new(StatusCode::OK, "application/vnd.msgpack", SINGLE_HOP);
+    let client = TransportClient::new(transport, "secret".to_string(), None);
+    let exact_out = QuoteRequest {
+        swap_mode: Some(SwapMode::ExactOut),
+        ..sample_request()
+    };
+
+    assert!(matches!(
+        client.quote(&exact_out).await,
+        Err(ClientError::ResponseMismatch {
+            field: "swapMode",
+            ..
+        })
+    ));
+    assert!(matches!(
+        client.quote_all(&exact_out).await,
+        Err(ClientError::ResponseMismatch {
+            field: "swapMode",
+            ..
+        })
+    ));
+}
+
+#[tokio::test]
+async fn transport_failures_are_reported() {
+    let client = TransportClient::new(
+        CannedTransport::failing("connection reset"),
+        String::new(),
+        None,
+    );
+
+    match client.quote(&sample_request()).await {
+        Err(ClientError::Transport(err)) => assert_eq!(err.to_string(), "connection reset"),
+        other => panic!("expected Transport, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn reqwest_transport_against_the_mock_server() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT).amounts(1_000_000_000, 186_000_000),
+        )
+        .mount()
+        .await;
+    let client = TransportClient::new(
+        ReqwestTransport::default(),
+        String::new(),
+        Some(server.uri()),
+    );
+
+    let quote = client.quote(&sample_request()).await.unwrap();
+    assert_eq!(quote.out_amount, 186_000_000);
+}
+
+#[tokio::test]
+async fn invalid_requests_are_never_sent() {
+    let transport = CannedTransport::new(StatusCode::OK, "application/vnd.msgpack", SINGLE_HOP);
+    let client = TransportClient::new(transport, "secret".to_string(), None);
+    let request = QuoteRequest {
+        account_limits: AccountLimits::with_total(0),
+        ..sample_request()
+    };
+
+    assert!(matches!(
+        client.quote(&request).await,
+        Err(ClientError::InvalidAccountLimits(AccountLimitsError::Zero))
+    ));
+    assert!(matches!(
+        client.quote_all(&request).await,
+        Err(ClientError::InvalidAccountLimits(AccountLimitsError::Zero))
+    ));
+    assert!(client.transport().sent.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn routes_are_checked_against_the_request() {
+    const BONK: Pubkey = pubkey!("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263");
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+                .amounts(1_000_000_000, 187_000_000)
+                .step(
+                    "Whirlpool",
+                    Pubkey::new_unique(),
+                    SOL_MINT,
+                    BONK,
+                    1_000_000_000,
+                    9_000_000_000,
+                    1_000_000_000,
+                )
+                .step(
+                    "Phoenix",
+                    Pubkey::new_unique(),
+                    BONK,
+                    USDC_MINT,
+                    9_000_000_000,
+                    187_000_000,
+                    1_000_000_000,
+                ),
+        )
+        .times(2)
+        .mount()
+        .await;
+    server
+        .respond_with_route(
+            SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+                .swap_mode(SwapMode::ExactOut)
+                .amounts(5_400_000_000, 999_999_999),
+        )
+        .mount()
+        .await;
+    let client = TransportClient::new(
+        ReqwestTransport::default(),
+        String::new(),
+        Some(server.uri()),
+    );
+
+    let direct = QuoteRequest {
+        only_direct_routes: Some(true),
+        ..sample_request()
+    };
+    assert!(matches!(
+        client.quote(&direct).await,
+        Err(ClientError::NoRoutesAvailable)
+    ));
+    assert!(matches!(
+        client.quote_all(&direct).await,
+        Err(ClientError::NoRoutesAvailable)
+    ));
+
+    let exact_out = QuoteRequest {
+        swap_mode: Some(SwapMode::ExactOut),
+        ..sample_request()
+    };
+    assert!(matches!(
+        client.quote(&exact_out).await,
+        Err(ClientError::QuoteMismatch {
+            requested: 1_000_000_000,
+            out_amount: 999_999_999,
+        })
+    ));
+}
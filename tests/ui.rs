@@ -0,0 +1,89 @@
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use titan_swap_api_client::{
+    constants::{SOL_MINT, USDC_MINT},
+    quote::{QuoteRequest, QuoteResponse},
+    test_utils::SwapRouteBuilder,
+    tokens::{TokenInfo, TokenRegistry},
+    ui::UiAmount,
+};
+
+#[test]
+fn display_groups_thousands_and_trims_zeros() {
+    assert_eq!(
+        UiAmount::new(1_234_567_890_000, 9).to_string(),
+        "1,234.56789"
+    );
+    assert_eq!(UiAmount::new(1_000_000, 6).to_string(), "1");
+    assert_eq!(UiAmount::new(5, 6).to_string(), "0.000005");
+    assert_eq!(UiAmount::new(0, 9).to_string(), "0");
+    assert_eq!(UiAmount::new(123_456_789, 0).to_string(), "123,456,789");
+    assert_eq!(
+        UiAmount::new(u64::MAX, 0).to_string(),
+        "18,446,744,073,709,551,615"
+    );
+}
+
+#[test]
+fn display_precision_rounds_half_up() {
+    let amount = UiAmount::new(1_234_565_000, 6);
+    assert_eq!(format!("{amount:.2}"), "1,234.57");
+    assert_eq!(format!("{amount:.0}"), "1,235");
+    assert_eq!(format!("{amount:.8}"), "1,234.56500000");
+    assert_eq!(format!("{:.2}", UiAmount::new(999_999_999, 9)), "1.00");
+    assert_eq!(format!("{:.3}", UiAmount::new(7, 0)), "7.000");
+    assert_eq!(format!("{:.0}", UiAmount::new(u64::MAX, 40)), "0");
+}
+
+#[test]
+fn to_f64_and_decimal() {
+    let amount = UiAmount::new(1_500_000_000, 9);
+
+    assert_eq!(amount.to_f64(), 1.5);
+    assert_eq!(amount.to_decimal(), Some(Decimal::from_str("1.5").unwrap()));
+    assert_eq!(UiAmount::new(1, 29).to_decimal(), None);
+}
+
+#[test]
+fn quote_and_step_amounts() {
+    let route = SwapRouteBuilder::new(SOL_MINT, USDC_MINT)
+        .amounts(2_500_000_000, 412_345_678)
+        .build();
+    let quote = QuoteResponse::from_route(
+        &QuoteRequest {
+            input_mint: SOL_MINT,
+            output_mint: USDC_MINT,
+            amount: 2_500_000_000,
+            ..Default::default()
+        },
+        route,
+    );
+
+    assert_eq!(quote.ui_in_amount(9).to_string(), "2.5");
+    assert_eq!(quote.ui_out_amount(6).to_string(), "412.345678");
+
+    let step = &quote.route_plan[0].swap_info;
+    assert_eq!(step.ui_in_amount(9).to_string(), "2.5");
+    assert_eq!(step.ui_fee_amount(9), None);
+
+    let registry =
+        TokenRegistry::from_tokens(vec![token(SOL_MINT, "SOL", 9), token(USDC_MINT, "USDC", 6)]);
+    let amounts = quote.ui_amounts(&registry).unwrap();
+    assert_eq!(amounts.in_amount, UiAmount::new(2_500_000_000, 9));
+    assert_eq!(amounts.out_amount, UiAmount::new(412_345_678, 6));
+    assert_eq!(step.ui_amounts(&registry), Some(amounts));
+
+    let partial = TokenRegistry::from_tokens(vec![token(SOL_MINT, "SOL", 9)]);
+    assert_eq!(quote.ui_amounts(&partial), None);
+}
+
+fn token(mint: Pubkey, symbol: &str, decimals: u8) -> TokenInfo {
+    TokenInfo {
+        mint,
+        symbol: symbol.to_string(),
+        name: symbol.to_string(),
+        decimals,
+        tags: vec![],
+    }
+}
@@ -0,0 +1,100 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use titan_swap_api_client::{
+    quote::QuoteRequest,
+    test_utils::{MockTitanServer, SwapRouteBuilder},
+    venues::{normalize_label, Dex, VenueInfo, Venues},
+};
+
+fn venue(id: &str, name: &str, program_id: Pubkey) -> VenueInfo {
+    VenueInfo {
+        id: id.to_string(),
+        name: name.to_string(),
+        program_id,
+    }
+}
+
+fn venue_list() -> Vec<VenueInfo> {
+    vec![
+        venue(
+            "raydium-clmm",
+            "Raydium CLMM",
+            Dex::RaydiumClmm.program_id(),
+        ),
+        venue("orca-whirlpool", "Whirlpool", Dex::Whirlpool.program_id()),
+        venue(
+            "fresh-amm",
+            "Fresh AMM",
+            pubkey!("T1TANpTeScyeqVzzgNViGDNrkQ6qHz9KrSBS4aNXvGT"),
+        ),
+    ]
+}
+
+#[test]
+fn normalizes_labels_fuzzily() {
+    assert_eq!(normalize_label("Whirlpool"), Some(Dex::Whirlpool));
+    assert_eq!(normalize_label("orca"), Some(Dex::Whirlpool));
+    assert_eq!(normalize_label("Raydium"), Some(Dex::Raydium));
+    assert_eq!(normalize_label("raydium_clmm"), Some(Dex::RaydiumClmm));
+    assert_eq!(normalize_label("Raydium CLMM (v2)"), Some(Dex::RaydiumClmm));
+    assert_eq!(normalize_label("Meteora DLMM"), Some(Dex::MeteoraDlmm));
+    assert_eq!(normalize_label("Meteora"), Some(Dex::MeteoraDamm));
+    assert_eq!(normalize_label("Pump.fun Amm"), Some(Dex::PumpSwap));
+    assert_eq!(normalize_label("Unknown Venue"), None);
+    assert_eq!(normalize_label(" - "), None);
+}
+
+#[test]
+fn every_dex_round_trips_through_its_id_and_program() {
+    for dex in Dex::ALL {
+        assert_eq!(normalize_label(dex.id()), Some(*dex));
+        assert_eq!(Dex::from_program_id(&dex.program_id()), Some(*dex));
+    }
+}
+
+#[test]
+fn venue_list_takes_precedence() {
+    let venues = Venues::new(venue_list());
+
+    assert_eq!(
+        venues.canonical_id("Orca"),
+        Some("orca-whirlpool".to_string())
+    );
+    assert_eq!(
+        venues.canonical_id("Fresh AMM"),
+        Some("fresh-amm".to_string())
+    );
+    assert_eq!(venues.canonical_id("Phoenix"), Some("phoenix".to_string()));
+    assert_eq!(venues.canonical_id("Nowhere"), None);
+    assert_eq!(venues.normalize("Raydium CLMM"), Some(Dex::RaydiumClmm));
+    assert_eq!(venues.normalize("Fresh AMM"), None);
+}
+
+#[tokio::test]
+async fn fetches_venues() {
+    let server = MockTitanServer::start().await;
+    server.respond_with_venues(&venue_list()).mount().await;
+
+    assert_eq!(server.client().venues().await.unwrap(), venue_list());
+}
+
+#[tokio::test]
+async fn excluded_dexes_are_sent_as_canonical_ids() {
+    let server = MockTitanServer::start().await;
+    server
+        .respond_with_route(SwapRouteBuilder::default())
+        .mount()
+        .await;
+
+    let request = QuoteRequest {
+        input_mint: Pubkey::new_from_array([1; 32]),
+        output_mint: Pubkey::new_from_array([2; 32]),
+        amount: 1_000_000,
+        excluded_dexes: Some("Raydium CLMM, Orca,,SomeNewDex".to_string()),
+        ..Default::default()
+    };
+    server.client().quote(&request).await.unwrap();
+
+    server
+        .assert_query_param("excludeDexes", "raydium-clmm,whirlpool,SomeNewDex")
+        .await;
+}
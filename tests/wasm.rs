@@ -0,0 +1,118 @@
+//! Run with `wasm-pack test --node -- --no-default-features --features wasm`.
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use titan_swap_api_client::{
+    pubkey::Pubkey,
+    quote::{QuoteRequest, SwapMode},
+    wasm::TitanClient,
+    ClientError,
+};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const SINGLE_HOP: &[u8] = include_bytes!("fixtures/single_hop.msgpack");
+const ERROR_NO_ROUTES: &str = include_str!("fixtures/error_no_routes.txt");
+
+#[wasm_bindgen(inline_js = r#"
+export function mock_fetch(body, status) {
+    const bytes = body.slice();
+    globalThis.__titanRequests = [];
+    globalThis.fetch = async (input) => {
+        globalThis.__titanRequests.push(typeof input === "string" ? input : input.url);
+        return new Response(bytes, { status });
+    };
+}
+
+export function last_request_url() {
+    const requests = globalThis.__titanRequests || [];
+    return requests[requests.length - 1];
+}
+"#)]
+extern "C" {
+    fn mock_fetch(body: &[u8], status: u16);
+    fn last_request_url() -> String;
+}
+
+fn sol() -> Pubkey {
+    "So11111111111111111111111111111111111111112"
+        .parse()
+        .unwrap()
+}
+
+fn usdc() -> Pubkey {
+    "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+        .parse()
+        .unwrap()
+}
+
+fn request() -> QuoteRequest {
+    QuoteRequest {
+        input_mint: sol(),
+        output_mint: usdc(),
+        amount: 1_000_000_000,
+        swap_mode: Some(SwapMode::ExactIn),
+        slippage_bps: 50,
+        ..Default::default()
+    }
+}
+
+fn client() -> TitanClient {
+    TitanClient::new(
+        "test-token".to_string(),
+        Some("http://titan.test".to_string()),
+    )
+}
+
+#[wasm_bindgen_test]
+async fn quote_decodes_msgpack_from_fetch() {
+    mock_fetch(SINGLE_HOP, 200);
+
+    let quote = client().quote(&request()).await.unwrap();
+
+    assert_eq!(quote.input_mint, sol());
+    assert_eq!(quote.output_mint, usdc());
+    assert_eq!(quote.in_amount, 1_000_000_000);
+    assert_eq!(quote.out_amount, 187_654_321);
+    assert_eq!(quote.route_plan.len(), 1);
+    assert_eq!(quote.route_plan[0].swap_info.label, "Whirlpool");
+
+    let url = last_request_url();
+    assert!(url.starts_with("http://titan.test/api/v1/quote/swap?"));
+    assert!(url.contains(&format!("inputMint={}", sol())));
+    assert!(url.contains("slippageBps=50"));
+}
+
+#[wasm_bindgen_test]
+async fn quote_maps_no_routes_error() {
+    mock_fetch(ERROR_NO_ROUTES.as_bytes(), 404);
+
+    let err = client().quote(&request()).await.unwrap_err();
+
+    assert!(matches!(err, ClientError::NoRoutesAvailable));
+}
+
+#[wasm_bindgen_test]
+async fn quote_rejects_a_response_for_another_request() {
+    mock_fetch(SINGLE_HOP, 200);
+
+    let exact_out = QuoteRequest {
+        swap_mode: Some(SwapMode::ExactOut),
+        ..request()
+    };
+    let err = client().quote(&exact_out).await.unwrap_err();
+
+    assert!(matches!(
+        err,
+        ClientError::ResponseMismatch {
+            field: "swapMode",
+            ..
+        }
+    ));
+}
+
+#[wasm_bindgen_test]
+fn pubkey_round_trips_through_base58() {
+    let mint = usdc();
+    assert_eq!(mint.to_string().parse::<Pubkey>().unwrap(), mint);
+    assert!("not-a-pubkey".parse::<Pubkey>().is_err());
+}